@@ -0,0 +1,57 @@
+//! Wall time of passing assertions
+//!
+//! Run with and without the `fast-messages` feature to compare:
+//!
+//! ```text
+//! cargo bench --bench assertions
+//! cargo bench --bench assertions --features fast-messages
+//! ```
+
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::measure;
+use rest::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Number of assertions timed per scenario
+const ITERATIONS: usize = 100_000;
+
+fn main() {
+    // Printing every passing assertion would dominate the measure
+    config().show_success_details(false).apply();
+
+    let mode = if cfg!(feature = "fast-messages") { "fast-messages" } else { "default" };
+    println!("passing assertions, {} iterations, {} build\n", ITERATIONS, mode);
+
+    let words = (0..100).map(|index| format!("word-{}", index)).collect::<Vec<_>>();
+    let scores = (0..100).map(|index| (format!("player-{}", index), index)).collect::<HashMap<_, _>>();
+    let expected_scores = scores.clone();
+
+    let total = [
+        measure("numeric comparison", ITERATIONS, |index| {
+            expect!(index).to_be_greater_than_or_equal(0);
+        }),
+        measure("string contains", ITERATIONS, |index| {
+            expect!(words[index % 100].as_str()).to_contain("word");
+        }),
+        measure("collection contains", ITERATIONS, |index| {
+            expect!(&words).to_contain(format!("word-{}", index % 100));
+        }),
+        measure("collection equality", ITERATIONS, |_| {
+            expect!(&words).to_equal_collection(&words);
+        }),
+        measure("map equality", ITERATIONS, |_| {
+            expect!(&scores).to_equal_map(&expected_scores);
+        }),
+        measure("chained option", ITERATIONS, |index| {
+            expect!(Some(index)).to_be_some().and().to_contain(&index);
+        }),
+    ]
+    .iter()
+    .sum::<Duration>();
+
+    println!("\n{:<28} {:>10.2?}", "total", total);
+}
@@ -0,0 +1,24 @@
+//! Timing shared by the benchmarks
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Time a scenario over a number of iterations, printing and returning the average duration of one iteration
+///
+/// A tenth of the iterations warms up caches first. The reporter's deduplication is then reset, so that the timed
+/// iterations grow it from empty as a test binary does.
+pub fn measure(name: &str, iterations: usize, scenario: impl Fn(usize)) -> Duration {
+    for index in 0..iterations / 10 {
+        scenario(index);
+    }
+    rest::Reporter::reset_message_cache();
+
+    let start = Instant::now();
+    for index in 0..iterations {
+        scenario(black_box(index));
+    }
+    let average = start.elapsed() / iterations as u32;
+
+    println!("{:<28} {:>10.2?} per assertion", name, average);
+    return average;
+}
@@ -0,0 +1,44 @@
+//! Wall time of reporting assertions in a large suite
+//!
+//! Every assertion goes through the reporter's deduplication, which keeps one key per distinct report until the
+//! session ends. The suites below grow that set like a large test binary does:
+//!
+//! ```text
+//! cargo bench --bench reporting
+//! ```
+
+#![allow(clippy::needless_return)]
+
+mod common;
+
+use common::measure;
+use rest::prelude::*;
+use std::time::Duration;
+
+/// Number of distinct assertions reported per suite
+const ASSERTIONS: usize = 100_000;
+
+fn main() {
+    // Printing every passing assertion would dominate the measure
+    config().show_success_details(false).apply();
+
+    println!("distinct passing assertions, {} per suite\n", ASSERTIONS);
+
+    let words = (0..20).map(|index| format!("word-{}", index)).collect::<Vec<_>>();
+
+    let total = [
+        measure("single step", ASSERTIONS, |index| {
+            expect!(index).to_be_greater_than_or_equal(0);
+        }),
+        measure("chain of three steps", ASSERTIONS, |index| {
+            expect!(index).to_be_greater_than_or_equal(0).and().to_be_less_than(ASSERTIONS).and().not().to_equal(ASSERTIONS);
+        }),
+        measure("large actual value", ASSERTIONS, |index| {
+            expect!(&words).to_contain(format!("word-{}", index % 20)).and().not().to_contain(format!("other-{}", index));
+        }),
+    ]
+    .iter()
+    .sum::<Duration>();
+
+    println!("\n{:<28} {:>10.2?}", "total", total);
+}
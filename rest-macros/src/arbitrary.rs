@@ -0,0 +1,225 @@
+//! Expansion of `#[derive(RestArbitrary)]`
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{Data, DeriveInput, Expr, Field, Fields, GenericParam, Ident, parse_quote, spanned::Spanned};
+
+/// Constraint declared on a field with `#[arbitrary(...)]`
+enum FieldConstraint {
+    /// Sample the field uniformly from a range
+    Range(Expr),
+    /// Generate a collection whose length is sampled from a range
+    Len(Expr),
+}
+
+pub(crate) fn expand(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = input.ident.clone();
+
+    // Every type parameter must itself be generatable
+    for param in input.generics.params.iter_mut() {
+        if let GenericParam::Type(type_param) = param {
+            type_param.bounds.push(parse_quote!(rest::backend::property::Arbitrary));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (body, shrink_arms) = match &input.data {
+        Data::Struct(data) => (construct(quote!(#name), &data.fields)?, vec![shrink_arm(quote!(#name), &data.fields)?]),
+        Data::Enum(data) => {
+            if data.variants.is_empty() {
+                return Err(syn::Error::new_spanned(&input.ident, "RestArbitrary cannot be derived for an enum without variants"));
+            }
+
+            let count = data.variants.len();
+            let arms = data
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(index, variant)| {
+                    let variant_name = &variant.ident;
+                    let constructor = construct(quote!(#name::#variant_name), &variant.fields)?;
+                    Ok(quote!(#index => #constructor,))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let shrink_arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let variant_name = &variant.ident;
+                    shrink_arm(quote!(#name::#variant_name), &variant.fields)
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+
+            let body = quote! {
+                match __rest_gen.gen_index(#count) {
+                    #(#arms)*
+                    _ => unreachable!(),
+                }
+            };
+            (body, shrink_arms)
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(data.union_token, "RestArbitrary cannot be derived for unions"));
+        }
+    };
+
+    let version_check = crate::version_check();
+    Ok(quote! {
+        #version_check
+
+        impl #impl_generics rest::backend::property::Arbitrary for #name #ty_generics #where_clause {
+            fn arbitrary(__rest_gen: &mut rest::backend::property::Gen) -> Self {
+                #body
+            }
+
+            fn shrink(&self) -> Vec<Self> {
+                let mut __rest_candidates = Vec::new();
+                match self {
+                    #(#shrink_arms)*
+                }
+                __rest_candidates
+            }
+        }
+    })
+}
+
+/// Build the expression constructing a struct or enum variant from generated fields
+fn construct(path: TokenStream, fields: &Fields) -> syn::Result<TokenStream> {
+    match fields {
+        Fields::Named(named) => {
+            let values = named
+                .named
+                .iter()
+                .map(|field| {
+                    let field_name = &field.ident;
+                    let value = field_value(field)?;
+                    Ok(quote!(#field_name: #value))
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote!(#path { #(#values),* }))
+        }
+        Fields::Unnamed(unnamed) => {
+            let values = unnamed.unnamed.iter().map(field_value).collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote!(#path(#(#values),*)))
+        }
+        Fields::Unit => Ok(path),
+    }
+}
+
+/// Build the match arm shrinking one field at a time, keeping the others and the variant
+fn shrink_arm(path: TokenStream, fields: &Fields) -> syn::Result<TokenStream> {
+    let bindings = (0..fields.len()).map(|index| format_ident!("__rest_field_{}", index)).collect::<Vec<_>>();
+    let build = |replaced: usize| match fields {
+        Fields::Named(named) => {
+            let values = named.named.iter().zip(&bindings).enumerate().map(|(index, (field, binding))| {
+                let field_name = &field.ident;
+                if index == replaced {
+                    quote!(#field_name: __rest_simpler)
+                } else {
+                    let value = clone(field, binding);
+                    quote!(#field_name: #value)
+                }
+            });
+            quote!(#path { #(#values),* })
+        }
+        Fields::Unnamed(unnamed) => {
+            let values = unnamed
+                .unnamed
+                .iter()
+                .zip(&bindings)
+                .enumerate()
+                .map(|(index, (field, binding))| if index == replaced { quote!(__rest_simpler) } else { clone(field, binding) });
+            quote!(#path(#(#values),*))
+        }
+        Fields::Unit => path.clone(),
+    };
+
+    let pattern = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote!(#path { #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(#path(#(#bindings),*)),
+        Fields::Unit => path.clone(),
+    };
+
+    let shrinks = fields
+        .iter()
+        .zip(&bindings)
+        .enumerate()
+        .map(|(index, (field, binding))| {
+            let ty = &field.ty;
+            // Constrained fields only shrink to values that still satisfy their constraint
+            let keep = match field_constraint(field)? {
+                Some(FieldConstraint::Range(range)) => quote!((#range).contains(&__rest_simpler)),
+                Some(FieldConstraint::Len(len)) => quote!((#len).contains(&__rest_simpler.len())),
+                None => quote!(true),
+            };
+            let candidate = build(index);
+            Ok(quote! {
+                for __rest_simpler in <#ty as rest::backend::property::Arbitrary>::shrink(#binding) {
+                    if #keep {
+                        __rest_candidates.push(#candidate);
+                    }
+                }
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    Ok(quote! {
+        #pattern => {
+            #(#shrinks)*
+        }
+    })
+}
+
+/// Clone of a field kept by a shrink candidate, blaming the field type when it isn't `Clone`
+fn clone(field: &Field, binding: &Ident) -> TokenStream {
+    let mut binding = binding.clone();
+    binding.set_span(field.ty.span());
+    quote_spanned!(field.ty.span()=> ::core::clone::Clone::clone(#binding))
+}
+
+/// Build the expression generating a single field
+fn field_value(field: &Field) -> syn::Result<TokenStream> {
+    let ty = &field.ty;
+
+    // Errors about the field, such as a type that can't be generated, point at its type
+    Ok(match field_constraint(field)? {
+        Some(FieldConstraint::Range(range)) => quote_spanned! {ty.span()=>
+            __rest_gen.gen_range::<#ty, _>(#range)
+        },
+        Some(FieldConstraint::Len(len)) => quote_spanned! {ty.span()=> {
+            let __rest_len = __rest_gen.gen_range::<usize, _>(#len);
+            <#ty as rest::backend::property::ArbitraryLen>::arbitrary_with_len(__rest_gen, __rest_len)
+        }},
+        None => quote_spanned! {ty.span()=>
+            <#ty as rest::backend::property::Arbitrary>::arbitrary(__rest_gen)
+        },
+    })
+}
+
+/// Parse the `#[arbitrary(range = ...)]` or `#[arbitrary(len = ...)]` attribute of a field
+fn field_constraint(field: &Field) -> syn::Result<Option<FieldConstraint>> {
+    let mut constraint = None;
+
+    for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("arbitrary")) {
+        attr.parse_nested_meta(|meta| {
+            let parsed = if meta.path.is_ident("range") {
+                FieldConstraint::Range(meta.value()?.parse()?)
+            } else if meta.path.is_ident("len") {
+                FieldConstraint::Len(meta.value()?.parse()?)
+            } else {
+                return Err(meta.error("unsupported arbitrary constraint, expected `range = ..` or `len = ..`"));
+            };
+
+            if constraint.is_some() {
+                return Err(meta.error("a field accepts a single arbitrary constraint"));
+            }
+            constraint = Some(parsed);
+            Ok(())
+        })?;
+    }
+
+    Ok(constraint)
+}
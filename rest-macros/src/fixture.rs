@@ -0,0 +1,162 @@
+//! Expansion of the fixture attributes: `#[before_all]`, `#[setup]`, `#[tear_down]` and `#[after_all]`
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::Parser;
+use syn::punctuated::Punctuated;
+use syn::{AngleBracketedGenericArguments, GenericParam, ItemFn, ReturnType, Stmt, Token, Type, parse_quote};
+
+/// Kind of fixture declared by an attribute
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Fixture {
+    BeforeAll,
+    AfterAll,
+    Setup,
+    TearDown,
+}
+
+impl Fixture {
+    /// Name of the attribute, for error messages
+    fn attribute(self) -> &'static str {
+        match self {
+            Fixture::BeforeAll => "before_all",
+            Fixture::AfterAll => "after_all",
+            Fixture::Setup => "setup",
+            Fixture::TearDown => "tear_down",
+        }
+    }
+
+    /// Name of the fixture in the generated registration function
+    fn registration_name(self) -> &'static str {
+        match self {
+            Fixture::BeforeAll => "before_all",
+            Fixture::AfterAll => "after_all",
+            Fixture::Setup => "setup",
+            Fixture::TearDown => "teardown",
+        }
+    }
+
+    /// Function of `rest::backend::fixtures` registering the fixture
+    fn register_fn(self) -> TokenStream {
+        match self {
+            Fixture::BeforeAll => quote!(register_before_all),
+            Fixture::AfterAll => quote!(register_after_all),
+            Fixture::Setup => quote!(register_setup),
+            Fixture::TearDown => quote!(register_teardown),
+        }
+    }
+
+    /// Variant of `rest::backend::discovery::FixtureKind` listing the fixture
+    fn discovery_kind(self) -> TokenStream {
+        match self {
+            Fixture::BeforeAll => quote!(BeforeAll),
+            Fixture::AfterAll => quote!(AfterAll),
+            Fixture::Setup => quote!(Setup),
+            Fixture::TearDown => quote!(TearDown),
+        }
+    }
+}
+
+/// Generic arguments a generic fixture is registered with, as in `#[setup(::<u32>, ::<String>)]`
+type Instances = Punctuated<AngleBracketedGenericArguments, Token![,]>;
+
+pub(crate) fn expand(attr: TokenStream, mut input: ItemFn, fixture: Fixture) -> syn::Result<TokenStream> {
+    let instances = Parser::parse2(Instances::parse_terminated, attr)?;
+    check_signature(&input, fixture, &instances)?;
+
+    let fn_name = input.sig.ident.clone();
+
+    // Create a unique registration function name based on the function name
+    let register_fn_name = format_ident!("__register_{}_fixture_{}", fixture.registration_name(), fn_name, span = fn_name.span());
+    let register_fn = fixture.register_fn();
+    let discovery_kind = fixture.discovery_kind();
+    let version_check = crate::version_check();
+
+    // One registration per instance of a generic fixture
+    let calls = if instances.is_empty() {
+        vec![quote!(#fn_name())]
+    } else {
+        instances
+            .iter()
+            .map(|arguments| {
+                let generics = &arguments.args;
+                quote!(#fn_name::<#generics>())
+            })
+            .collect()
+    };
+
+    // The registration function is nested in the fixture, so that a fixture removed by its `cfg` attributes isn't
+    // registered either, and that it only finds the fixture by name when the fixture is a free function
+    let registration: Stmt = parse_quote! {
+        // We use ctor to register the function at runtime
+        #[rest::__ctor::ctor]
+        fn #register_fn_name() {
+            #version_check
+            #(
+                rest::backend::fixtures::#register_fn(
+                    module_path!(),
+                    Box::new(|| #calls)
+                );
+            )*
+            rest::backend::discovery::register_fixture(
+                module_path!(),
+                rest::backend::discovery::FixtureKind::#discovery_kind,
+                stringify!(#fn_name)
+            );
+        }
+    };
+    input.block.stmts.insert(0, registration);
+
+    Ok(quote!(#input))
+}
+
+/// Reject the functions that can't be called as `name()`, or `name::<...>()` for generic fixtures, by their registration function
+fn check_signature(input: &ItemFn, fixture: Fixture, instances: &Instances) -> syn::Result<()> {
+    let sig = &input.sig;
+    let attribute = fixture.attribute();
+
+    if let Some(receiver) = sig.receiver() {
+        return Err(syn::Error::new_spanned(
+            receiver,
+            format!("#[{}] can't be used on a method, fixtures are free functions: move it out of the `impl` block", attribute),
+        ));
+    }
+    if let Some(argument) = sig.inputs.first() {
+        return Err(syn::Error::new_spanned(argument, format!("#[{}] functions can't take arguments", attribute)));
+    }
+    if let Some(asyncness) = sig.asyncness {
+        return Err(syn::Error::new_spanned(asyncness, format!("#[{}] functions can't be async", attribute)));
+    }
+    if let Some(unsafety) = sig.unsafety {
+        return Err(syn::Error::new_spanned(unsafety, format!("#[{}] functions can't be unsafe", attribute)));
+    }
+
+    // Lifetimes are inferred at the call, type and const parameters are given by the attribute
+    let generic_param = sig.generics.params.iter().find(|param| !matches!(param, GenericParam::Lifetime(_)));
+    match (generic_param, instances.first()) {
+        (Some(param), None) => {
+            return Err(syn::Error::new_spanned(
+                param,
+                format!(
+                    "#[{}] functions generic over types or constants need the arguments to register them with, as in `#[{}(::<...>)]`",
+                    attribute, attribute
+                ),
+            ));
+        }
+        (None, Some(arguments)) => {
+            return Err(syn::Error::new_spanned(
+                arguments,
+                format!("#[{}] only takes generic arguments, and `{}` has no type or const parameters", attribute, sig.ident),
+            ));
+        }
+        _ => {}
+    }
+
+    if let ReturnType::Type(_, output) = &sig.output
+        && !matches!(&**output, Type::Tuple(tuple) if tuple.elems.is_empty())
+    {
+        return Err(syn::Error::new_spanned(output, format!("#[{}] functions must return `()`", attribute)));
+    }
+
+    Ok(())
+}
@@ -1,14 +1,38 @@
+mod arbitrary;
+mod fixture;
+
+use fixture::Fixture;
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{
-    Attribute, Item, ItemFn, ItemMod, parse_macro_input,
+    Attribute, DeriveInput, Expr, ExprArray, ExprLit, ItemFn, ItemMod, Lit, LitStr, ReturnType, parse_macro_input,
+    spanned::Spanned,
     visit_mut::{self, VisitMut},
 };
 
+/// Constant failing the compilation unless `rest` is the version these macros were built for
+fn version_check() -> proc_macro2::TokenStream {
+    let version = env!("CARGO_PKG_VERSION");
+    quote! {
+        const _: () = rest::backend::macros_version::check_macros_version(#version);
+    }
+}
+
+/// Name of the function holding the body of a `#[with_fixtures]` test
+fn impl_ident(fn_name: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("__{}_impl", fn_name), fn_name.span())
+}
+
+/// Name of the test a function belongs to, the test itself or the implementation of a `#[with_fixtures]` test
+fn test_name(fn_name: &syn::Ident) -> String {
+    let name = fn_name.to_string();
+    name.strip_prefix("__").and_then(|name| name.strip_suffix("_impl")).unwrap_or(&name).to_string()
+}
+
 /// Registers a function to be run once before any test in the current module
 ///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[before_all]
@@ -17,33 +41,19 @@ use syn::{
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn before_all(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn before_all(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
 
-    // Create a unique registration function name based on the function name
-    let register_fn_name = syn::Ident::new(&format!("__register_before_all_fixture_{}", fn_name), fn_name.span());
-
-    let output = quote! {
-        #input_fn
-
-        // We use ctor to register the function at runtime
-        #[ctor::ctor]
-        fn #register_fn_name() {
-            rest::backend::fixtures::register_before_all(
-                module_path!(),
-                Box::new(|| #fn_name())
-            );
-        }
-    };
-
-    TokenStream::from(output)
+    match fixture::expand(attr.into(), input_fn, Fixture::BeforeAll) {
+        Ok(output) => TokenStream::from(output),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
 }
 
 /// Registers a function to be run once after all tests in the current module
 ///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[after_all]
@@ -52,33 +62,19 @@ pub fn before_all(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn after_all(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn after_all(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
-
-    // Create a unique registration function name based on the function name
-    let register_fn_name = syn::Ident::new(&format!("__register_after_all_fixture_{}", fn_name), fn_name.span());
-
-    let output = quote! {
-        #input_fn
-
-        // We use ctor to register the function at runtime
-        #[ctor::ctor]
-        fn #register_fn_name() {
-            rest::backend::fixtures::register_after_all(
-                module_path!(),
-                Box::new(|| #fn_name())
-            );
-        }
-    };
 
-    TokenStream::from(output)
+    match fixture::expand(attr.into(), input_fn, Fixture::AfterAll) {
+        Ok(output) => TokenStream::from(output),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
 }
 
 /// Registers a function to be run before each test in the current module
 ///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[setup]
@@ -87,33 +83,19 @@ pub fn after_all(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn setup(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn setup(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
-
-    // Create a unique registration function name based on the function name
-    let register_fn_name = syn::Ident::new(&format!("__register_setup_fixture_{}", fn_name), fn_name.span());
-
-    let output = quote! {
-        #input_fn
-
-        // We use ctor to register the function at runtime
-        #[ctor::ctor]
-        fn #register_fn_name() {
-            rest::backend::fixtures::register_setup(
-                module_path!(),
-                Box::new(|| #fn_name())
-            );
-        }
-    };
 
-    TokenStream::from(output)
+    match fixture::expand(attr.into(), input_fn, Fixture::Setup) {
+        Ok(output) => TokenStream::from(output),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
 }
 
 /// Registers a function to be run after each test in the current module
 ///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[tear_down]
@@ -122,33 +104,21 @@ pub fn setup(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 #[proc_macro_attribute]
-pub fn tear_down(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn tear_down(attr: TokenStream, item: TokenStream) -> TokenStream {
     let input_fn = parse_macro_input!(item as ItemFn);
-    let fn_name = &input_fn.sig.ident;
-
-    // Create a unique registration function name based on the function name
-    let register_fn_name = syn::Ident::new(&format!("__register_teardown_fixture_{}", fn_name), fn_name.span());
-
-    let output = quote! {
-        #input_fn
 
-        // We use ctor to register the function at runtime
-        #[ctor::ctor]
-        fn #register_fn_name() {
-            rest::backend::fixtures::register_teardown(
-                module_path!(),
-                Box::new(|| #fn_name())
-            );
-        }
-    };
-
-    TokenStream::from(output)
+    match fixture::expand(attr.into(), input_fn, Fixture::TearDown) {
+        Ok(output) => TokenStream::from(output),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
 }
 
 /// Runs a function with setup and teardown fixtures from the current module
 ///
+/// The function is also registered for test discovery, optionally with tags.
+///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[with_fixtures]
@@ -156,10 +126,38 @@ pub fn tear_down(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     // Test code here
 ///     expect!(2 + 2).to_equal(4);
 /// }
+///
+/// #[with_fixtures(tags = ["slow", "db"])]
+/// fn test_tagged() {
+///     expect!(2 + 2).to_equal(4);
+/// }
 /// ```
 #[proc_macro_attribute]
-pub fn with_fixtures(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn with_fixtures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut tags: Vec<LitStr> = Vec::new();
+    let tags_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("tags") {
+            let array: ExprArray = meta.value()?.parse()?;
+            for element in array.elems {
+                match element {
+                    Expr::Lit(ExprLit { lit: Lit::Str(tag), .. }) => tags.push(tag),
+                    other => return Err(syn::Error::new_spanned(other, "expected a string literal tag")),
+                }
+            }
+            return Ok(());
+        }
+
+        Err(meta.error("unsupported with_fixtures argument, expected `tags = [\"...\"]`"))
+    });
+    parse_macro_input!(attr with tags_parser);
+
     let input_fn = parse_macro_input!(item as ItemFn);
+
+    // The test is called without arguments by the fixture runner
+    if let Some(argument) = input_fn.sig.inputs.first() {
+        return TokenStream::from(syn::Error::new_spanned(argument, "#[with_fixtures] functions can't take arguments").to_compile_error());
+    }
+
     let fn_name = &input_fn.sig.ident;
     let fn_body = &input_fn.block;
     let vis = &input_fn.vis; // Preserve visibility
@@ -167,11 +165,75 @@ pub fn with_fixtures(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let sig = &input_fn.sig; // Get function signature
 
     // Generate a unique internal name for the real implementation
-    let impl_name = syn::Ident::new(&format!("__{}_impl", fn_name), fn_name.span());
+    let impl_name = impl_ident(fn_name);
+
+    // An expected failure goes on the implementation, inside the fixture runner, which then sees the test pass
+    let (impl_attrs, attrs): (Vec<&Attribute>, Vec<&Attribute>) = attrs.iter().partition(|attr| attr.path().is_ident("expected_failure"));
+
+    // Collect discovery metadata from the inert test attributes
+    let register_fn_name = syn::Ident::new(&format!("__register_test_{}", fn_name), fn_name.span());
+    let ignored = attrs.iter().any(|attr| attr.path().is_ident("ignore"));
+    let should_panic = attrs.iter().any(|attr| attr.path().is_ident("should_panic"));
+    let cfgs = attrs.iter().filter(|attr| attr.path().is_ident("cfg"));
+    let line = quote_spanned!(fn_name.span()=> line!());
+
+    // The implementation keeps the signature of the test, so its `async`, `unsafe` and return type, under a private name
+    let mut impl_sig = sig.clone();
+    impl_sig.ident = impl_name.clone();
+    let call = if sig.unsafety.is_some() { quote!(unsafe { #impl_name() }) } else { quote!(#impl_name()) };
+
+    // Return the output of the test, such as a `Result`, to the test harness, blaming its return type if it can't be
+    let output_span = match &sig.output {
+        ReturnType::Type(_, output) => output.span(),
+        ReturnType::Default => fn_name.span(),
+    };
+    let run = if sig.asyncness.is_some() {
+        quote_spanned! {output_span=>
+            rest::backend::fixtures::run_named_async_test_with_fixtures(module_path, stringify!(#fn_name), #call).await
+        }
+    } else {
+        quote_spanned! {output_span=>
+            rest::backend::fixtures::run_named_test_with_fixtures(
+                module_path,
+                stringify!(#fn_name),
+                std::panic::AssertUnwindSafe(|| #call)
+            )
+        }
+    };
+
+    // Async tests need a runtime, which only their test attribute provides
+    let order_runner = if sig.asyncness.is_none() && !ignored {
+        quote! {
+            rest::backend::order::register_runner(module_path!(), stringify!(#fn_name), #line, || {
+                return rest::backend::order::run_test(module_path!(), #should_panic, std::panic::AssertUnwindSafe(|| #call));
+            });
+        }
+    } else {
+        quote!()
+    };
 
+    let version_check = version_check();
     let output = quote! {
+        // Register the test for discovery, only when the test itself is compiled
+        #(#cfgs)*
+        #[rest::__ctor::ctor]
+        fn #register_fn_name() {
+            #version_check
+            rest::backend::discovery::register_test(rest::backend::discovery::TestDescriptor {
+                module: module_path!(),
+                name: stringify!(#fn_name),
+                file: file!(),
+                line: #line,
+                ignored: #ignored,
+                should_panic: #should_panic,
+                tags: &[#(#tags),*],
+            });
+            #order_runner
+        }
+
         // Define the implementation function with a private name
-        fn #impl_name() #fn_body
+        #(#impl_attrs)*
+        #impl_sig #fn_body
 
         // Create the public function with fixtures
         #(#attrs)*
@@ -179,10 +241,7 @@ pub fn with_fixtures(_attr: TokenStream, item: TokenStream) -> TokenStream {
             // Get the current module path - critical for finding the right fixtures
             let module_path = module_path!();
 
-            rest::backend::fixtures::run_test_with_fixtures(
-                module_path,
-                std::panic::AssertUnwindSafe(|| #impl_name())
-            );
+            #run
         }
     };
 
@@ -205,8 +264,8 @@ impl VisitMut for TestFunctionVisitor {
             // Create the with_fixtures attribute
             let with_fixtures_attr: Attribute = syn::parse_quote!(#[with_fixtures]);
 
-            // Add it to the function's attributes
-            node.attrs.push(with_fixtures_attr);
+            // Add it above `#[test]`, which removes the function outside of libtest builds before expanding the rest
+            node.attrs.insert(0, with_fixtures_attr);
         }
 
         // Continue visiting the function's items
@@ -217,7 +276,7 @@ impl VisitMut for TestFunctionVisitor {
 /// Runs all test functions in a module with setup and teardown fixtures
 ///
 /// Example:
-/// ```
+/// ```ignore
 /// use rest::prelude::*;
 ///
 /// #[with_fixtures_module]
@@ -242,31 +301,187 @@ impl VisitMut for TestFunctionVisitor {
 pub fn with_fixtures_module(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input_mod = parse_macro_input!(item as ItemMod);
 
-    // Only process if we have a defined module body
-    if let Some((_, items)) = &mut input_mod.content {
-        // Visit all items in the module
-        let mut visitor = TestFunctionVisitor {};
-        for item in items.iter_mut() {
-            // Check if the item is a function
-            if let Item::Fn(func) = item {
-                visitor.visit_item_fn_mut(func);
-            }
+    // Visit all the functions of the module, including those of nested modules at any depth
+    let mut visitor = TestFunctionVisitor {};
+    visitor.visit_item_mod_mut(&mut input_mod);
 
-            // Recursively process nested modules as well
-            if let Item::Mod(nested_mod) = item
-                && let Some((_, nested_items)) = &mut nested_mod.content
-            {
-                for nested_item in nested_items.iter_mut() {
-                    if let Item::Fn(func) = nested_item {
-                        visitor.visit_item_fn_mut(func);
-                    }
-                }
-            }
+    // Convert back to token stream
+    TokenStream::from(quote! {
+        #input_mod
+    })
+}
+
+/// Allows a fixture-wrapped test to complete without running any assertion
+///
+/// Use it for tests that only check that nothing panics when tests without assertions are
+/// configured to warn or fail. It can be placed before or after `#[with_fixtures]`.
+///
+/// Example:
+/// ```ignore
+/// use rest::prelude::*;
+///
+/// #[with_fixtures]
+/// #[allow_no_assertions]
+/// fn test_shutdown_does_not_panic() {
+///     // Test code without assertions
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn allow_no_assertions(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+
+    // Flag the running test before anything else, whether this wraps the test body or the fixture runner
+    let allow: syn::Stmt = syn::parse_quote!(rest::backend::assertions::count::allow_no_assertions(););
+    input_fn.block.stmts.insert(0, allow);
+    let version_check = version_check();
+    input_fn.block.stmts.insert(0, syn::parse_quote!(#version_check));
+
+    TokenStream::from(quote! {
+        #input_fn
+    })
+}
+
+/// Marks a test expected to fail, with the reason such as the issue tracking the bug
+///
+/// The test still runs: its failure is listed as an expected failure in the session summary instead of failing the
+/// test, and a pass fails it, so that the marker gets removed once the bug is fixed.
+///
+/// Example:
+/// ```ignore
+/// use rest::prelude::*;
+///
+/// #[expected_failure(reason = "totals aren't rounded to cents, issue #42")]
+/// fn test_total() {
+///     expect!(0.1 + 0.2).to_equal(0.3);
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn expected_failure(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut reason: Option<LitStr> = None;
+    let reason_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("reason") {
+            reason = Some(meta.value()?.parse()?);
+            return Ok(());
         }
+
+        Err(meta.error("unsupported expected_failure argument, expected `reason = \"...\"`"))
+    });
+    parse_macro_input!(attr with reason_parser);
+
+    let mut input_fn = parse_macro_input!(item as ItemFn);
+    let Some(reason) = reason else {
+        let message = "#[expected_failure] needs a reason, such as `#[expected_failure(reason = \"issue #42\")]`";
+        return TokenStream::from(syn::Error::new_spanned(&input_fn.sig.ident, message).to_compile_error());
+    };
+    if let Some(asyncness) = &input_fn.sig.asyncness {
+        return TokenStream::from(syn::Error::new_spanned(asyncness, "#[expected_failure] tests can't be async").to_compile_error());
     }
 
-    // Convert back to token stream
+    // `#[with_fixtures]` moves the attribute to the implementation of the test, run inside its fixtures
+    let test_name = test_name(&input_fn.sig.ident);
+    let body = &input_fn.block;
+    let version_check = version_check();
+    input_fn.block = syn::parse_quote!({
+        #version_check
+        rest::backend::expected_failure::run_expected_failure(concat!(module_path!(), "::", #test_name), #reason, || #body)
+    });
+
     TokenStream::from(quote! {
-        #input_mod
+        #input_fn
     })
 }
+
+/// Inserts a guard keeping the verbs of sentences in their base form at the start of every function it visits
+struct NoConjugationVisitor {}
+
+impl VisitMut for NoConjugationVisitor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        // Guards can't be created in constant evaluation
+        if node.sig.constness.is_none() {
+            insert_conjugation_guard(node);
+        }
+
+        visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+/// Disable the conjugation of the sentences of a function for the whole of its body
+fn insert_conjugation_guard(input_fn: &mut ItemFn) {
+    let guard: syn::Stmt = syn::parse_quote!(let _conjugation = rest::backend::assertions::sentence::ConjugationGuard::new(););
+    input_fn.block.stmts.insert(0, guard);
+}
+
+/// Keeps the verbs of assertion sentences in their base form, in a test or in every function of a module
+///
+/// Sentences then read the same whatever the subject name, such as `datos be equal to 3`, for names whose plurality
+/// English singularization gets wrong. It can be placed before or after `#[with_fixtures]` and
+/// `#[with_fixtures_module]`.
+///
+/// Example:
+/// ```ignore
+/// use rest::prelude::*;
+///
+/// #[no_conjugation]
+/// mod tests_es {
+///     use rest::prelude::*;
+///
+///     fn test_datos() {
+///         let datos = vec![1, 2, 3];
+///         expect!(datos).to_contain(3);
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn no_conjugation(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let version_check = version_check();
+    let item = parse_macro_input!(item as syn::Item);
+
+    let item = match item {
+        syn::Item::Fn(mut input_fn) => {
+            insert_conjugation_guard(&mut input_fn);
+            input_fn.block.stmts.insert(0, syn::parse_quote!(#version_check));
+            quote! { #input_fn }
+        }
+        syn::Item::Mod(mut input_mod) => {
+            // Visit all the functions of the module, including those of nested modules at any depth
+            let mut visitor = NoConjugationVisitor {};
+            visitor.visit_item_mod_mut(&mut input_mod);
+            quote! {
+                #version_check
+                #input_mod
+            }
+        }
+        other => syn::Error::new_spanned(other, "#[no_conjugation] applies to functions and modules").to_compile_error(),
+    };
+
+    TokenStream::from(item)
+}
+
+/// Derives `Arbitrary` so the type can be generated for property tests
+///
+/// Fields are generated with their own `Arbitrary` implementation unless constrained:
+/// `#[arbitrary(range = ..)]` samples a number from a range and `#[arbitrary(len = ..)]`
+/// picks the length of a `Vec`, `String` or other collection from a range.
+///
+/// Example:
+/// ```ignore
+/// use rest::prelude::*;
+///
+/// #[derive(Debug, RestArbitrary)]
+/// struct Order {
+///     #[arbitrary(range = 1..=100)]
+///     quantity: u32,
+///     #[arbitrary(len = 1..8)]
+///     sku: String,
+///     express: bool,
+/// }
+/// ```
+#[proc_macro_derive(RestArbitrary, attributes(arbitrary))]
+pub fn derive_rest_arbitrary(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+
+    match arbitrary::expand(input) {
+        Ok(output) => TokenStream::from(output),
+        Err(err) => TokenStream::from(err.to_compile_error()),
+    }
+}
@@ -0,0 +1,181 @@
+//! Files attached to the current test, such as screenshots, dumps or logs
+//!
+//! [`attach_artifact`] associates a file with the test running on the current thread. Byte contents are written
+//! under the artifacts directory first, so every artifact ends up as a path that reports can reference. Each
+//! attachment emits an [`AssertionEvent::ArtifactAttached`](crate::events::AssertionEvent::ArtifactAttached) event,
+//! and the artifacts of a test are listed with its failures on the console and in the panic message.
+
+use crate::backend::discovery::json_string;
+use crate::backend::run::current_run;
+use crate::events::{AssertionEvent, EventEmitter};
+use std::cell::RefCell;
+use std::path::{Path, PathBuf};
+
+// Environment variable overriding the directory where byte artifacts are written
+const ENV_ARTIFACTS_DIR: &str = "REST_ARTIFACTS_DIR";
+
+// Directory under the system temporary directory used when `REST_ARTIFACTS_DIR` isn't set
+const DEFAULT_ARTIFACTS_DIR: &str = "rest-artifacts";
+
+thread_local! {
+    // Artifacts attached by the test running on this thread
+    static ARTIFACTS: RefCell<Vec<Artifact>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Content of an artifact: an existing file, or bytes to write to a new one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtifactContent {
+    /// File already written by the test
+    Path(PathBuf),
+    /// Bytes written to a file under the artifacts directory
+    Bytes(Vec<u8>),
+}
+
+impl From<&str> for ArtifactContent {
+    fn from(path: &str) -> Self {
+        ArtifactContent::Path(PathBuf::from(path))
+    }
+}
+
+impl From<String> for ArtifactContent {
+    fn from(path: String) -> Self {
+        ArtifactContent::Path(PathBuf::from(path))
+    }
+}
+
+impl From<&Path> for ArtifactContent {
+    fn from(path: &Path) -> Self {
+        ArtifactContent::Path(path.to_path_buf())
+    }
+}
+
+impl From<PathBuf> for ArtifactContent {
+    fn from(path: PathBuf) -> Self {
+        ArtifactContent::Path(path)
+    }
+}
+
+impl From<Vec<u8>> for ArtifactContent {
+    fn from(bytes: Vec<u8>) -> Self {
+        ArtifactContent::Bytes(bytes)
+    }
+}
+
+impl From<&[u8]> for ArtifactContent {
+    fn from(bytes: &[u8]) -> Self {
+        ArtifactContent::Bytes(bytes.to_vec())
+    }
+}
+
+/// File attached to a test
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    /// Name given to the artifact
+    pub name: String,
+    /// Name of the thread that attached it, which is the test path under the default test harness
+    pub test: Option<String>,
+    /// Location of the file
+    pub path: PathBuf,
+}
+
+impl Artifact {
+    /// One-line description for failure output, such as `artifact screenshot: /tmp/login.png`
+    pub fn describe(&self) -> String {
+        return format!("artifact {}: {}", self.name, self.path.display());
+    }
+
+    /// Render the artifact as a JSON object
+    pub fn to_json(&self) -> String {
+        let test = self.test.as_deref().map(json_string).unwrap_or_else(|| "null".to_string());
+
+        return format!(
+            "{{\"name\":{},\"test\":{},\"path\":{}}}",
+            json_string(&self.name),
+            test,
+            json_string(&self.path.display().to_string())
+        );
+    }
+}
+
+/// Attach a file to the current test, given as a path or as bytes written to the artifacts directory
+///
+/// Bytes are written to `$REST_ARTIFACTS_DIR/<run id>/<test>/<name>`, the directory defaulting to `rest-artifacts`
+/// under the system temporary directory. Artifacts that can't be written are reported on stderr and not attached.
+pub fn attach_artifact(name: impl Into<String>, content: impl Into<ArtifactContent>) {
+    let name = name.into();
+    let test = std::thread::current().name().map(str::to_string);
+
+    let path = match content.into() {
+        ArtifactContent::Path(path) => path,
+        ArtifactContent::Bytes(bytes) => {
+            let path =
+                artifacts_dir().join(current_run().id.as_str()).join(sanitize(test.as_deref().unwrap_or("unnamed"))).join(sanitize(&name));
+            let written = path.parent().map_or(Ok(()), std::fs::create_dir_all).and_then(|_| std::fs::write(&path, bytes));
+
+            if let Err(error) = written {
+                eprintln!("ERROR: Failed to write artifact {} to {}: {}", name, path.display(), error);
+                return;
+            }
+
+            path
+        }
+    };
+
+    let artifact = Artifact { name, test, path };
+    ARTIFACTS.with(|artifacts| artifacts.borrow_mut().push(artifact.clone()));
+    EventEmitter::emit(AssertionEvent::ArtifactAttached(artifact));
+}
+
+/// Artifacts attached by the test running on the current thread
+pub fn attached_artifacts() -> Vec<Artifact> {
+    return ARTIFACTS.with(|artifacts| artifacts.borrow().clone());
+}
+
+/// Directory where byte artifacts are written
+fn artifacts_dir() -> PathBuf {
+    return std::env::var_os(ENV_ARTIFACTS_DIR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| std::env::temp_dir().join(DEFAULT_ARTIFACTS_DIR));
+}
+
+/// Turn a test path or an artifact name into a single file name
+fn sanitize(name: &str) -> String {
+    let sanitized = name.replace("::", "__").replace(['/', '\\', ':'], "_");
+
+    return if sanitized.is_empty() || sanitized == "." || sanitized == ".." { "artifact".to_string() } else { sanitized };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(sanitize("artifacts::tests::test_sanitize"), "artifacts__tests__test_sanitize");
+        assert_eq!(sanitize("dumps/core:1"), "dumps_core_1");
+        assert_eq!(sanitize(".."), "artifact");
+    }
+
+    #[test]
+    fn test_attach_artifact_from_path_and_bytes() {
+        attach_artifact("log", "/var/log/service.log");
+        attach_artifact("dump", b"\x00\x01\x02".as_slice());
+
+        let artifacts = attached_artifacts();
+        assert_eq!(artifacts.len(), 2);
+        assert_eq!(artifacts[0].path, PathBuf::from("/var/log/service.log"));
+        assert_eq!(artifacts[0].test.as_deref(), Some("backend::artifacts::tests::test_attach_artifact_from_path_and_bytes"));
+        assert_eq!(artifacts[0].describe(), "artifact log: /var/log/service.log");
+
+        assert!(artifacts[1].path.ends_with("backend__artifacts__tests__test_attach_artifact_from_path_and_bytes/dump"));
+        assert_eq!(std::fs::read(&artifacts[1].path).unwrap(), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_artifact_json() {
+        let artifact = Artifact { name: "screenshot".to_string(), test: None, path: PathBuf::from("/tmp/login \"page\".png") };
+
+        assert_eq!(artifact.to_json(), "{\"name\":\"screenshot\",\"test\":null,\"path\":\"/tmp/login \\\"page\\\".png\"}");
+    }
+}
@@ -1,8 +1,23 @@
-use crate::backend::assertions::sentence::AssertionSentence;
-use std::fmt::Debug;
+use crate::backend::assertions::capture;
+use crate::backend::assertions::count;
+use crate::backend::assertions::coverage;
+use crate::backend::assertions::group;
+use crate::backend::assertions::hook::{self, FailureAction};
+use crate::backend::assertions::memo::Memo;
+use crate::backend::assertions::sentence::{AssertionSentence, MatcherDocs};
+use crate::backend::assertions::source;
+use crate::backend::assertions::type_name;
+use crate::backend::panic_hook;
+use crate::config::{PanicFormat, TypeNames};
+use crate::events::EventMetadata;
+use crate::frontend::Symbols;
+use std::fmt::{Debug, Display};
+use std::panic::Location;
+use std::sync::Arc;
+use std::time::Instant;
 
 /// Represents a logical operation in an assertion chain
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogicalOp {
     /// AND operation (&&)
     And,
@@ -10,8 +25,18 @@ pub enum LogicalOp {
     Or,
 }
 
+/// How a failed assertion affects its test
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The failure fails the test
+    #[default]
+    Failure,
+    /// The failure is reported and counted as a warning, without failing the test
+    Warning,
+}
+
 /// Represents a step in an assertion chain
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AssertionStep {
     /// The assertion sentence components
     pub sentence: AssertionSentence,
@@ -19,6 +44,33 @@ pub struct AssertionStep {
     pub passed: bool,
     /// The logical operation connecting this step to the next one
     pub logical_op: Option<LogicalOp>,
+    // Source location of the matcher call that produced this step
+    pub(crate) location: Option<&'static Location<'static>>,
+}
+
+impl AssertionStep {
+    /// Creates a step that isn't attributed to a matcher call
+    pub fn new(sentence: AssertionSentence, passed: bool, logical_op: Option<LogicalOp>) -> Self {
+        return Self { sentence, passed, logical_op, location: None };
+    }
+
+    /// Source location of the matcher call that produced this step
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        return self.location;
+    }
+
+    /// Source text of the matcher call that produced this step, such as `to_be_greater_than(10)`
+    ///
+    /// Read from the source file of the test, so `None` when it isn't available or when the matcher isn't
+    /// `#[track_caller]`.
+    pub fn matcher_call(&self) -> Option<String> {
+        return self.location.and_then(source::matcher_call);
+    }
+
+    /// What the matcher of this step checks, with a link to its documentation, when it documents itself
+    pub fn docs(&self) -> Option<&MatcherDocs> {
+        return self.sentence.docs.as_ref();
+    }
 }
 
 /// Represents the complete assertion with all steps
@@ -38,6 +90,22 @@ pub struct Assertion<T> {
     pub is_final: bool,
     /// Flag to mark this assertion as already evaluated (event copies should not re-trigger Drop)
     pub evaluated: bool,
+    // Context rendered before the assertion message (e.g., "for input Point { x: 0, y: -1 }")
+    pub(crate) context: Option<String>,
+    // Source location of the `expect!` call that created the assertion
+    pub(crate) location: &'static Location<'static>,
+    // Whether a failure fails the test or is only a warning
+    pub(crate) severity: Severity,
+    // Type name of the value being tested, kept when the assertion is type-erased for events
+    pub(crate) type_name: &'static str,
+    // Number of items of the value being tested, when it is a collection
+    pub(crate) item_count: Option<usize>,
+    // When the assertion was created, after its value was evaluated, to time the matchers of the chain
+    pub(crate) created_at: Instant,
+    // Order and times of the event the assertion was sent with, stamped on the copies handed to event handlers
+    pub(crate) event: Option<EventMetadata>,
+    /// Data derived from the value, shared by the steps of the chain
+    pub(crate) memo: Memo,
 }
 
 /// Represents the complete result of a test session
@@ -47,12 +115,21 @@ pub struct TestSessionResult {
     pub passed_count: usize,
     /// Number of failed tests
     pub failed_count: usize,
+    /// Number of registered tests skipped by the harness, such as the tests of other shards
+    pub skipped_count: usize,
+    /// Number of registered tests marked `#[ignore]` and not run
+    pub ignored_count: usize,
     /// Detailed results of failed assertions
     pub failures: Vec<Assertion<()>>,
+    /// Number of failed warning assertions
+    pub warning_count: usize,
+    /// Detailed results of failed warning assertions
+    pub warnings: Vec<Assertion<()>>,
 }
 
 impl<T> Assertion<T> {
-    /// Creates a new assertion
+    /// Creates a new assertion, located at the caller
+    #[track_caller]
     pub fn new(value: T, expr_str: &'static str) -> Self {
         return Self {
             value,
@@ -62,10 +139,91 @@ impl<T> Assertion<T> {
             in_chain: false,
             is_final: true, // By default, single-step assertions are final
             evaluated: false,
+            context: None,
+            location: Location::caller(),
+            severity: Severity::Failure,
+            type_name: std::any::type_name::<T>(),
+            item_count: None,
+            created_at: Instant::now(),
+            event: None,
+            memo: Memo::default(),
         };
     }
 
+    /// Context rendered before the assertion message, such as `for input Point { x: 0, y: -1 }`
+    pub fn context(&self) -> Option<&str> {
+        return self.context.as_deref();
+    }
+
+    /// Source location of the `expect!` call that created the assertion
+    pub fn location(&self) -> &'static Location<'static> {
+        return self.location;
+    }
+
+    /// Whether a failure fails the test or is only a warning
+    pub fn severity(&self) -> Severity {
+        return self.severity;
+    }
+
+    /// Type name of the value being tested, kept when the assertion is type-erased for events
+    pub fn type_name(&self) -> &'static str {
+        return self.type_name;
+    }
+
+    /// Number of items of the value being tested, when it is a collection
+    pub fn item_count(&self) -> Option<usize> {
+        return self.item_count;
+    }
+
+    /// When the assertion was created, after its value was evaluated, to time the matchers of the chain
+    pub fn created_at(&self) -> Instant {
+        return self.created_at;
+    }
+
+    /// Sequence number and emission times of the event this assertion was sent with
+    ///
+    /// Set on the copies that success, failure and warning handlers receive, so that they can order the assertions of
+    /// all threads and measure the latency between them. `None` for an assertion that wasn't emitted.
+    pub fn event_metadata(&self) -> Option<EventMetadata> {
+        return self.event;
+    }
+
+    /// Record the number of items of the value being tested, rendered next to its type in failure details
+    pub fn with_item_count(mut self, item_count: Option<usize>) -> Self {
+        self.item_count = item_count;
+        return self;
+    }
+
+    /// Set the context rendered before the assertion message
+    pub fn with_context(mut self, context: impl Into<String>) -> Self {
+        self.context = Some(context.into());
+        return self;
+    }
+
+    /// Name the subject of the assertion, replacing the expression in its sentences and messages
+    ///
+    /// Reads better for intermediate expressions: `expect!(cfg.servers[0].port).as_("primary server port")`
+    /// reports `primary server port is greater than 1024`.
+    pub fn as_(mut self, subject: &'static str) -> Self {
+        self.expr_str = subject;
+        // Steps already added take the new subject as well
+        for step in &mut self.steps {
+            step.sentence.subject = subject.to_string();
+        }
+        return self;
+    }
+
+    /// Report failures of this assertion as warnings, which are counted separately and don't fail the test
+    pub fn as_warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        return self;
+    }
+
     /// Add an assertion step and get back a cloned Assertion for chaining
+    ///
+    /// The step records the location of the caller, which is the matcher call in the test when the matcher is
+    /// `#[track_caller]`.
+    #[track_caller]
     pub fn add_step(&self, mut sentence: AssertionSentence, result: bool) -> Self
     where
         T: Clone,
@@ -84,7 +242,7 @@ impl<T> Assertion<T> {
         let mut new_steps = self.steps.clone();
 
         // Add the new step
-        new_steps.push(AssertionStep { sentence, passed, logical_op: None });
+        new_steps.push(AssertionStep { sentence, passed, logical_op: None, location: Some(Location::caller()) });
 
         return Self {
             value: self.value.clone(),
@@ -94,9 +252,53 @@ impl<T> Assertion<T> {
             in_chain: true, // Mark this as part of a chain
             is_final: true, // This step is final until a modifier makes it non-final
             evaluated: false,
+            context: self.context.clone(),
+            location: self.location,
+            severity: self.severity,
+            type_name: self.type_name,
+            item_count: self.item_count,
+            created_at: self.created_at,
+            event: None,
+            memo: self.memo.clone(),
         };
     }
 
+    /// Add an assertion step whose sentence is only built when needed, and get back a cloned Assertion for chaining
+    ///
+    /// With the `fast-messages` feature, passing steps skip building the sentence and capturing the actual value,
+    /// and are recorded with an elided sentence instead. Failing steps always get their full sentence.
+    #[track_caller]
+    pub fn add_step_with(&self, result: bool, sentence: impl FnOnce(&T) -> AssertionSentence) -> Self
+    where
+        T: Clone,
+    {
+        // Matcher runs of the test kit check the sentences of passing steps as well
+        #[cfg(feature = "fast-messages")]
+        if result != self.negated && !crate::testkit::is_recording() {
+            let mut assertion = self.add_step(AssertionSentence::elided(), result);
+            // The placeholder reads the same whether the step was negated or not
+            if let Some(step) = assertion.steps.last_mut() {
+                step.sentence.negated = false;
+            }
+            return assertion;
+        }
+
+        return self.add_step(sentence(&self.value), result);
+    }
+
+    /// Derive data from the value once for the whole chain, such as a parsed document or a hash, and share it with
+    /// the next matchers of the chain
+    ///
+    /// The data is stored under its type and a key naming the derivation, so that matchers chained with `and()` or
+    /// `or()` on the same value get it back instead of deriving it again:
+    ///
+    /// ```ignore
+    /// let instance = self.memoized("json instance", |text| serde_json::from_str::<Value>(text));
+    /// ```
+    pub fn memoized<D: Send + Sync + 'static>(&self, key: &'static str, derive: impl FnOnce(&T) -> D) -> Arc<D> {
+        return self.memo.get_or_derive(key, || derive(&self.value));
+    }
+
     /// Set the logical operation for the last step
     pub fn set_last_logic(&mut self, op: LogicalOp) {
         if let Some(last) = self.steps.last_mut() {
@@ -192,18 +394,107 @@ impl<T> Assertion<T> {
         return passed;
     }
 
+    /// Render every step of the chain on its own line, marked with ✓ or ✗, failed steps carrying their actual value
+    /// and each step naming the matcher call that produced it when its source is available
+    ///
+    /// Blocks of multi-line objects and actual values are aligned under their step, and the detail lines of failed
+    /// steps follow them. This is the text listed under a failure by the console and in chain panic messages, with the
+    /// symbols of the current configuration.
+    pub fn format_step_details(&self) -> String {
+        return self.format_step_details_with(crate::config::symbols());
+    }
+
+    /// Render every step of the chain like [`Assertion::format_step_details`], marked with the given symbols
+    pub fn format_step_details_with(&self, symbols: Symbols) -> String {
+        let mut details = String::new();
+
+        for step in &self.steps {
+            let result_symbol = if step.passed { symbols.passed } else { symbols.failed };
+            // For individual steps, conjugate based on the subject name
+            let formatted_sentence = if step.passed {
+                step.sentence.format_with_conjugation(self.expr_str)
+            } else {
+                // On failure, append the actual value for better diagnostics
+                step.sentence.append_actual(step.sentence.format_with_conjugation(self.expr_str))
+            };
+
+            // Always indent and add pass/fail prefix, aligning the blocks of multi-line objects and actual values
+            let mut lines = formatted_sentence.lines();
+            details.push_str(&format!("  {} {}", result_symbol, lines.next().unwrap_or_default()));
+
+            // Name the matcher call that produced the step, to map long chains back to the code
+            match step.matcher_call() {
+                Some(call) => details.push_str(&format!(" [{}]\n", call)),
+                None => details.push('\n'),
+            }
+            for line in lines {
+                details.push_str(&format!("    {}\n", line));
+            }
+
+            // Detail lines only matter for failed steps
+            if !step.passed {
+                for detail in &step.sentence.details {
+                    details.push_str(&format!("      {}\n", detail));
+                }
+                if let Some(docs) = step.docs().filter(|_| crate::config::is_showing_matcher_docs()) {
+                    details.push_str(&format!("      {}\n", docs.render()));
+                }
+            }
+        }
+
+        return details;
+    }
+
+    /// Type of the tested value with its number of items, such as `Vec<HashMap<String, _>> (3 items)`
+    ///
+    /// `None` for assertions without a value, such as the ones built for test counts or property failures.
+    pub fn describe_type(&self, names: TypeNames) -> Option<String> {
+        if self.type_name == "()" {
+            return None;
+        }
+
+        return Some(type_name::describe(self.type_name, self.item_count, names));
+    }
+
     /// Report the assertion result
     fn emit_result(&self, passed: bool) {
+        // Grouped assertions are reported by the chain of their group
+        if group::is_grouping() {
+            group::record(self);
+            return;
+        }
+
+        // Captured assertions are neither reported nor counted; failures only abort the captured closure
+        if capture::is_capturing() {
+            if !passed && self.severity == Severity::Failure {
+                capture::record_failure(self.type_erased());
+                panic!("{}", self.steps.iter().find(|step| !step.passed).unwrap_or(&self.steps[0]).sentence.format_with_actual());
+            }
+            return;
+        }
+
+        count::record_assertion();
+        coverage::record_coverage(self.location);
+
         // Get thread context information once
         let context = self.get_thread_context();
 
+        // Failed warnings are reported but never fail the test
+        if !passed && self.severity == Severity::Warning {
+            self.emit_warning(&context);
+            return;
+        }
+
         // Emit events when enhanced output is enabled
         if context.use_enhanced_output {
+            if !passed {
+                panic_hook::begin_failure_report();
+            }
             self.emit_assertion_events(passed, &context);
         }
 
-        // Handle failure cases with panic
-        if !passed && !context.is_special_test {
+        // Handle failure cases with panic, unless the failure hook lets the test continue
+        if !passed && !context.is_special_test && hook::run_failure_hook(&self.type_erased()) == FailureAction::Panic {
             self.handle_assertion_failure(&context);
         }
     }
@@ -235,15 +526,7 @@ impl<T> Assertion<T> {
         let is_final = !self.steps.is_empty() && (self.steps.last().unwrap().logical_op.is_none() || self.steps.len() > 1);
 
         // Convert to a type-erased assertion for reporting
-        let type_erased = Assertion::<()> {
-            value: (),
-            expr_str: self.expr_str,
-            negated: self.negated,
-            steps: self.steps.clone(),
-            in_chain: self.in_chain,
-            is_final: self.is_final,
-            evaluated: true,
-        };
+        let type_erased = self.type_erased();
 
         // Emit appropriate events based on assertion result
         if passed && is_final {
@@ -255,6 +538,38 @@ impl<T> Assertion<T> {
         }
     }
 
+    /// Report a failed warning assertion, through events when enhanced output is enabled or on stderr otherwise
+    fn emit_warning(&self, context: &ThreadContext) {
+        use crate::events::{AssertionEvent, EventEmitter};
+
+        if context.use_enhanced_output {
+            EventEmitter::emit(AssertionEvent::Warning(self.type_erased()));
+        } else {
+            eprintln!("warning: {}", self.format_stable_message());
+        }
+    }
+
+    /// Copy of this assertion without its value, as carried by events
+    pub(crate) fn type_erased(&self) -> Assertion<()> {
+        return Assertion::<()> {
+            value: (),
+            expr_str: self.expr_str,
+            negated: self.negated,
+            steps: self.steps.clone(),
+            in_chain: self.in_chain,
+            is_final: self.is_final,
+            evaluated: true,
+            context: self.context.clone(),
+            location: self.location,
+            severity: self.severity,
+            type_name: self.type_name,
+            item_count: self.item_count,
+            created_at: self.created_at,
+            event: None,
+            memo: Memo::default(),
+        };
+    }
+
     /// Handle assertion failures with appropriate panic messages
     fn handle_assertion_failure(&self, context: &ThreadContext) {
         // If there are no steps, use a simple default message
@@ -262,13 +577,69 @@ impl<T> Assertion<T> {
             panic!("assertion failed: {}", self.expr_str);
         }
 
-        // Get the first step for error message generation
-        let step = &self.steps[0];
-        let message = self.format_error_message(step, context);
+        // The stable format is only the first failed step and the context, whatever the configuration
+        if crate::config::panic_format() == PanicFormat::Stable {
+            let message = match self.context {
+                Some(ref assertion_context) => format!("{}, {}", assertion_context, self.format_stable_message()),
+                None => self.format_stable_message(),
+            };
 
+            panic_hook::expect_assertion_panic(self.location);
+            panic!("{}", message);
+        }
+
+        // Get the first step for error message generation, unless its sentence was elided because it passed
+        let step = if self.steps[0].sentence.is_elided() {
+            self.steps.iter().find(|step| !step.passed).unwrap_or(&self.steps[0])
+        } else {
+            &self.steps[0]
+        };
+        let mut message = self.format_error_message(step, context);
+
+        if let Some(ref assertion_context) = self.context {
+            message = format!("{}, {}", assertion_context, message);
+        }
+
+        // Chains replay every step with its outcome, so that plain test output shows which one failed;
+        // single assertions only add the detail lines of their step
+        if self.steps.len() > 1 {
+            message.push_str(&format!("\n{}", self.format_step_details().trim_end()));
+        } else {
+            for detail in self.steps.iter().filter(|step| !step.passed).flat_map(|step| step.sentence.details.iter()) {
+                message.push_str(&format!("\n  {}", detail));
+            }
+        }
+
+        if let Some(value_type) = self.describe_type(crate::config::type_names()) {
+            message.push_str(&format!("\n  type: {}", value_type));
+        }
+
+        // Files attached by the test, including from the failure hook, close the message
+        for artifact in crate::backend::artifacts::attached_artifacts() {
+            message.push_str(&format!("\n  {}", artifact.describe()));
+        }
+
+        panic_hook::expect_assertion_panic(self.location);
         panic!("{}", message);
     }
 
+    /// Format the stable message: `expected <expr> to <sentence>, got <actual>`
+    ///
+    /// Assertions without any step only have their expression: `expected <expr>`.
+    pub(crate) fn format_stable_message(&self) -> String {
+        let Some(step) = self.steps.iter().find(|step| !step.passed).or(self.steps.first()) else {
+            return format!("expected {}", self.expr_str.trim_start_matches('&'));
+        };
+        let message = format!("expected {} to {}", step.sentence.subject, step.sentence.format());
+
+        // Multi-line actual values go in a block under the message
+        return match step.sentence.actual_value {
+            Some(_) if step.sentence.is_multiline() => step.sentence.append_actual(message),
+            Some(ref actual) => format!("{}, got {}", message, actual),
+            None => message,
+        };
+    }
+
     /// Format appropriate error message based on context
     fn format_error_message(&self, step: &AssertionStep, context: &ThreadContext) -> String {
         // In test modules, we need exact format for #[should_panic(expected="...")] checks
@@ -316,6 +687,11 @@ impl<T> Drop for Assertion<T> {
             return;
         }
 
+        // Assertions of a test kit matcher run are only recorded
+        if crate::testkit::is_recording() {
+            return;
+        }
+
         // Only evaluate final assertions, not intermediate steps in a chain
         if !self.is_final {
             return;
@@ -340,20 +716,35 @@ impl<T> Drop for Assertion<T> {
                 crate::config::initialize();
             }
 
+            // Reset the flag once done, even when the evaluation panics on failure
+            struct EvaluationGuard;
+            impl Drop for EvaluationGuard {
+                fn drop(&mut self) {
+                    EVALUATION_IN_PROGRESS.with(|flag| {
+                        *flag.borrow_mut() = false;
+                    });
+                }
+            }
+            let _guard = EvaluationGuard;
+
             // Calculate the chain result
             let passed = self.calculate_chain_result();
 
             // Emit an event with the result
             self.emit_result(passed);
-
-            // Reset the flag
-            EVALUATION_IN_PROGRESS.with(|flag| {
-                *flag.borrow_mut() = false;
-            });
         }
     }
 }
 
+/// Report a failed assertion built by the crate itself, then fail the test with the given message
+///
+/// Dropping the assertion reports the failure through the usual events and panics, and the explicit panic fails the
+/// test in the contexts where failed assertions don't panic.
+pub(crate) fn report_and_panic(assertion: Assertion<()>, message: impl Display) -> ! {
+    drop(assertion);
+    panic!("{}", message);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -363,10 +754,25 @@ mod tests {
         let assertion = Assertion::new(42, "test_value");
         assert_eq!(assertion.value, 42);
         assert_eq!(assertion.expr_str, "test_value");
-        assert_eq!(assertion.negated, false);
+        assert!(!assertion.negated);
         assert_eq!(assertion.steps.len(), 0);
-        assert_eq!(assertion.in_chain, false);
-        assert_eq!(assertion.is_final, true);
+        assert!(!assertion.in_chain);
+        assert!(assertion.is_final);
+        assert_eq!(assertion.context(), None);
+        assert_eq!(assertion.severity(), Severity::Failure);
+        assert_eq!(assertion.type_name(), "i32");
+        assert_eq!(assertion.item_count(), None);
+        assert_eq!(assertion.location().file(), file!());
+    }
+
+    #[test]
+    fn test_getters_follow_the_builders() {
+        let assertion = Assertion::new(vec![1, 2], "ids").with_context("for user 7").with_item_count(Some(2)).as_warning();
+
+        assert_eq!(assertion.context(), Some("for user 7"));
+        assert_eq!(assertion.severity(), Severity::Warning);
+        assert_eq!(assertion.item_count(), Some(2));
+        assert!(assertion.created_at() <= Instant::now());
     }
 
     #[test]
@@ -378,13 +784,13 @@ mod tests {
         // Check the new assertion
         assert_eq!(result.value, 42);
         assert_eq!(result.expr_str, "test_value");
-        assert_eq!(result.negated, false);
-        assert_eq!(result.in_chain, true);
-        assert_eq!(result.is_final, true);
+        assert!(!result.negated);
+        assert!(result.in_chain);
+        assert!(result.is_final);
 
         // Check the step
         assert_eq!(result.steps.len(), 1);
-        assert_eq!(result.steps[0].passed, true);
+        assert!(result.steps[0].passed);
         assert_eq!(result.steps[0].logical_op, None);
         assert_eq!(result.steps[0].sentence.subject, "test_value");
     }
@@ -404,6 +810,7 @@ mod tests {
             sentence,
             passed: false, // !true because of negation
             logical_op: None,
+            location: None,
         };
 
         let result = Assertion {
@@ -414,11 +821,80 @@ mod tests {
             in_chain: true,
             is_final: true,
             evaluated: false,
+            context: None,
+            location: Location::caller(),
+            severity: Severity::Failure,
+            type_name: "i32",
+            item_count: None,
+            created_at: Instant::now(),
+            event: None,
+            memo: Memo::default(),
         };
 
         // Verify the expected behavior
-        assert_eq!(result.steps[0].passed, false);
-        assert_eq!(result.negated, false);
+        assert!(!result.steps[0].passed);
+        assert!(!result.negated);
+    }
+
+    #[test]
+    fn test_derived_data_is_shared_by_the_chain() {
+        use crate::backend::modifiers::{AndModifier, NotModifier};
+        use std::cell::Cell;
+
+        let derivations = Cell::new(0);
+        let length = |value: &String| {
+            derivations.set(derivations.get() + 1);
+            return value.len();
+        };
+
+        let mut assertion = Assertion::new("{\"id\": 1}".to_string(), "document");
+        assertion.evaluated = true;
+        assert_eq!(*assertion.memoized("length", length), 9);
+        let mut chained = assertion.add_step(AssertionSentence::new("be", "parsed"), true).and().not();
+        chained.evaluated = true;
+        assert_eq!(*chained.memoized("length", length), 9);
+        assert_eq!(derivations.get(), 1);
+
+        // Type-erased copies don't keep the data of the chain
+        let mut erased = chained.type_erased();
+        assert_eq!(*erased.memoized("length", |_| 0), 0);
+        erased.evaluated = true;
+    }
+
+    #[test]
+    fn test_add_step_with_builds_failing_sentences() {
+        let assertion = Assertion::new(42, "test_value");
+        let result = assertion.add_step_with(false, |value| AssertionSentence::new("be", "negative").with_actual(value.to_string()));
+
+        assert!(!result.steps[0].passed);
+        assert_eq!(result.steps[0].sentence.format_with_actual(), "be negative (got 42)");
+    }
+
+    #[test]
+    fn test_add_step_with_passing_sentences() {
+        use crate::backend::modifiers::NotModifier;
+
+        let built = std::cell::Cell::new(0);
+        let sentence = |value: &i32| {
+            built.set(built.get() + 1);
+            return AssertionSentence::new("be", "positive").with_actual(value.to_string());
+        };
+
+        let passing = Assertion::new(42, "test_value").add_step_with(true, sentence);
+        let negated = Assertion::new(42, "test_value").not().add_step_with(false, sentence);
+        assert!(passing.steps[0].passed);
+        assert!(negated.steps[0].passed);
+
+        // Passing steps only get an elided sentence with the `fast-messages` feature
+        if cfg!(feature = "fast-messages") {
+            assert_eq!(built.get(), 0);
+            assert!(passing.steps[0].sentence.is_elided());
+            assert!(!negated.steps[0].sentence.negated);
+        } else {
+            assert_eq!(built.get(), 2);
+            assert_eq!(passing.steps[0].sentence.format(), "be positive");
+            assert_eq!(negated.steps[0].sentence.format(), "not be positive");
+        }
     }
 
     #[test]
@@ -438,15 +914,25 @@ mod tests {
     fn test_calculate_chain_result_single_step() {
         // Create an assertion with a passing step
         let mut assertion_pass = Assertion::new(42, "test_value");
-        assertion_pass.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "positive"), passed: true, logical_op: None });
+        assertion_pass.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "positive"),
+            passed: true,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_pass.calculate_chain_result(), true);
+        assert!(assertion_pass.calculate_chain_result());
 
         // Create an assertion with a failing step
         let mut assertion_fail = Assertion::new(42, "test_value");
-        assertion_fail.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "negative"), passed: false, logical_op: None });
+        assertion_fail.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "negative"),
+            passed: false,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_fail.calculate_chain_result(), false);
+        assert!(!assertion_fail.calculate_chain_result());
     }
 
     #[test]
@@ -458,11 +944,17 @@ mod tests {
             sentence: AssertionSentence::new("be", "positive"),
             passed: true,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
-        assertion_pass.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "even"), passed: true, logical_op: None });
+        assertion_pass.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "even"),
+            passed: true,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_pass.calculate_chain_result(), true);
+        assert!(assertion_pass.calculate_chain_result());
 
         // Case 2: First step fails -> false
         let mut assertion_fail = Assertion::new(42, "test_value");
@@ -471,11 +963,17 @@ mod tests {
             sentence: AssertionSentence::new("be", "negative"),
             passed: false,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
-        assertion_fail.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "even"), passed: true, logical_op: None });
+        assertion_fail.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "even"),
+            passed: true,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_fail.calculate_chain_result(), false);
+        assert!(!assertion_fail.calculate_chain_result());
     }
 
     #[test]
@@ -487,11 +985,17 @@ mod tests {
             sentence: AssertionSentence::new("be", "negative"),
             passed: false,
             logical_op: Some(LogicalOp::Or),
+            location: None,
         });
 
-        assertion_pass.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "even"), passed: true, logical_op: None });
+        assertion_pass.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "even"),
+            passed: true,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_pass.calculate_chain_result(), true);
+        assert!(assertion_pass.calculate_chain_result());
 
         // Case 2: Both steps fail -> false
         let mut assertion_fail = Assertion::new(42, "test_value");
@@ -500,11 +1004,17 @@ mod tests {
             sentence: AssertionSentence::new("be", "negative"),
             passed: false,
             logical_op: Some(LogicalOp::Or),
+            location: None,
         });
 
-        assertion_fail.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "odd"), passed: false, logical_op: None });
+        assertion_fail.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "odd"),
+            passed: false,
+            logical_op: None,
+            location: None,
+        });
 
-        assert_eq!(assertion_fail.calculate_chain_result(), false);
+        assert!(!assertion_fail.calculate_chain_result());
     }
 
     #[test]
@@ -517,6 +1027,7 @@ mod tests {
             sentence: AssertionSentence::new("be", "positive"),
             passed: true,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
         // Step 2: value < 100 (true)
@@ -524,6 +1035,7 @@ mod tests {
             sentence: AssertionSentence::new("be", "less than 100"),
             passed: true,
             logical_op: Some(LogicalOp::Or),
+            location: None,
         });
 
         // Step 3: value < 0 (false)
@@ -531,10 +1043,16 @@ mod tests {
             sentence: AssertionSentence::new("be", "negative"),
             passed: false,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
         // Step 4: value = 0 (false)
-        assertion.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "zero"), passed: false, logical_op: None });
+        assertion.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "zero"),
+            passed: false,
+            logical_op: None,
+            location: None,
+        });
 
         // Should produce two segments:
         // 1. [0, 1] (positive AND less than 100) -> true
@@ -548,7 +1066,7 @@ mod tests {
         assert_eq!(segments[1], vec![2, 3]);
 
         // Verify the chain result
-        assert_eq!(assertion.calculate_chain_result(), true);
+        assert!(assertion.calculate_chain_result());
     }
 
     #[test]
@@ -581,6 +1099,88 @@ mod tests {
         assert_eq!(standard_message, "assertion failed: test_value");
     }
 
+    #[test]
+    fn test_steps_record_their_matcher_call() {
+        use crate::prelude::*;
+
+        let value = 7;
+        let mut chain = expect!(value).to_be_positive().and().to_be_in_range(0..10).and().to_be_even();
+        chain.evaluated = true;
+
+        let calls = chain.steps.iter().map(AssertionStep::matcher_call).collect::<Vec<_>>();
+        assert_eq!(
+            calls,
+            vec![Some("to_be_positive()".to_string()), Some("to_be_in_range(0..10)".to_string()), Some("to_be_even()".to_string())]
+        );
+        assert!(chain.format_step_details_with(Symbols::UNICODE).contains("  ✗ is even (got 7) [to_be_even()]\n"));
+
+        // Steps added outside of a matcher call don't name one
+        let mut manual = Assertion::new(7, "value").add_step(AssertionSentence::new("be", "odd"), true);
+        manual.evaluated = true;
+        assert_eq!(manual.steps[0].matcher_call(), None);
+    }
+
+    #[test]
+    fn test_subject_alias() {
+        let servers = [80, 8080];
+        let mut aliased = Assertion::new(servers[0], "servers[0]")
+            .as_("primary server port")
+            .add_step(AssertionSentence::new("be", "greater than 1024"), false);
+        aliased.evaluated = true;
+
+        assert_eq!(aliased.expr_str, "primary server port");
+        assert_eq!(aliased.format_stable_message(), "expected primary server port to be greater than 1024");
+
+        // Renaming after a matcher renames its step too
+        let mut renamed =
+            Assertion::new(servers[1], "servers[1]").add_step(AssertionSentence::new("be", "even"), true).as_("fallback port");
+        renamed.evaluated = true;
+        assert_eq!(renamed.steps[0].sentence.subject, "fallback port");
+    }
+
+    #[test]
+    fn test_expect_records_the_value_type() {
+        use crate::prelude::*;
+
+        let scores = std::collections::HashMap::from([("ada", vec![3]), ("grace", vec![5])]);
+        let mut by_value = expect!(vec![scores.clone()]);
+        let mut by_reference = expect!(&scores);
+        let mut scalar = expect!(42);
+        by_value.evaluated = true;
+        by_reference.evaluated = true;
+        scalar.evaluated = true;
+
+        assert_eq!(by_value.describe_type(TypeNames::Abbreviated).unwrap(), "Vec<HashMap<&str, _>> (1 item)");
+        assert_eq!(by_reference.describe_type(TypeNames::Abbreviated).unwrap(), "&HashMap<&str, Vec<i32>> (2 items)");
+        assert_eq!(scalar.describe_type(TypeNames::Full).unwrap(), "i32");
+        assert_eq!(Assertion::new((), "test").describe_type(TypeNames::Full), None);
+    }
+
+    #[test]
+    fn test_format_stable_message() {
+        use crate::backend::modifiers::NotModifier;
+
+        let assertion = Assertion::new(10, "&value");
+
+        // The first failed step of a chain is reported
+        let mut chain = assertion.add_step(AssertionSentence::new("be", "positive").with_actual("10"), true);
+        chain.set_last_logic(LogicalOp::And);
+        let chain = chain.add_step(AssertionSentence::new("be", "greater than 42").with_actual("10"), false);
+        assert_eq!(chain.format_stable_message(), "expected value to be greater than 42, got 10");
+
+        // Negated steps and steps without actual value
+        let negated = Assertion::new(10, "value").not().add_step(AssertionSentence::new("be", "even"), true);
+        assert_eq!(negated.format_stable_message(), "expected value to not be even");
+
+        // Multi-line actual values go in a block
+        let multiline =
+            Assertion::new(10, "result").add_step(AssertionSentence::new("be", "ok").with_actual("Err(\n    Timeout,\n)"), false);
+        assert_eq!(multiline.format_stable_message(), "expected result to be ok\n  got:\n    Err(\n        Timeout,\n    )");
+
+        // Assertions without steps, as left by a matcher that recorded none, only have their expression
+        assert_eq!(assertion.format_stable_message(), "expected value");
+    }
+
     #[test]
     fn test_special_vec_error_message() {
         // Create an assertion with "vec" in the expression string
@@ -589,7 +1189,7 @@ mod tests {
         sentence.subject = String::new(); // Simulate the vec case where subject doesn't contain "vec"
 
         let mut result = assertion;
-        result.steps.push(AssertionStep { sentence, passed: false, logical_op: None });
+        result.steps.push(AssertionStep { sentence, passed: false, logical_op: None, location: None });
 
         let non_test_enhanced = ThreadContext { is_test: false, is_module_test: false, use_enhanced_output: true, is_special_test: false };
 
@@ -608,12 +1208,14 @@ mod tests {
             sentence: AssertionSentence::new("be", "positive"),
             passed: true,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
         assertion.steps.push(AssertionStep {
             sentence: AssertionSentence::new("be", "even"),
             passed: true,
             logical_op: Some(LogicalOp::Or),
+            location: None,
         });
 
         // Second segment (false AND false) = false
@@ -621,12 +1223,14 @@ mod tests {
             sentence: AssertionSentence::new("be", "negative"),
             passed: false,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
         assertion.steps.push(AssertionStep {
             sentence: AssertionSentence::new("be", "odd"),
             passed: false,
             logical_op: Some(LogicalOp::Or),
+            location: None,
         });
 
         // Third segment (true AND false) = false
@@ -634,9 +1238,15 @@ mod tests {
             sentence: AssertionSentence::new("be", "greater than 0"),
             passed: true,
             logical_op: Some(LogicalOp::And),
+            location: None,
         });
 
-        assertion.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "less than 0"), passed: false, logical_op: None });
+        assertion.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "less than 0"),
+            passed: false,
+            logical_op: None,
+            location: None,
+        });
 
         // Should have 3 segments with results: true, false, false
         // Overall chain result should be true (OR of all segments)
@@ -647,6 +1257,6 @@ mod tests {
         assert_eq!(segments[1], vec![2, 3]);
         assert_eq!(segments[2], vec![4, 5]);
 
-        assert_eq!(assertion.calculate_chain_result(), true);
+        assert!(assertion.calculate_chain_result());
     }
 }
@@ -0,0 +1,117 @@
+//! Failure capture for assertions evaluated on behalf of another runner
+//!
+//! Inside [`capture_failure`], assertions are neither reported nor counted: a failed assertion is
+//! recorded and aborts the closure with a panic that isn't printed. Runners such as the property
+//! tester use it to probe many inputs and report only the final failure.
+
+use crate::backend::Assertion;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    // Number of nested captures active on this thread
+    static CAPTURE_DEPTH: Cell<usize> = const { Cell::new(0) };
+    // First failed assertion of the innermost capture
+    static CAPTURED_FAILURE: RefCell<Option<Assertion<()>>> = const { RefCell::new(None) };
+}
+
+/// Check if assertions on this thread are currently captured
+pub(crate) fn is_capturing() -> bool {
+    return CAPTURE_DEPTH.with(|depth| depth.get() > 0);
+}
+
+/// Number of nested captures active on this thread
+pub(crate) fn depth() -> usize {
+    return CAPTURE_DEPTH.with(|depth| depth.get());
+}
+
+/// Record a failed assertion for the innermost capture, keeping the first one
+pub(crate) fn record_failure(assertion: Assertion<()>) {
+    CAPTURED_FAILURE.with(|captured| {
+        let mut captured = captured.borrow_mut();
+        if captured.is_none() {
+            *captured = Some(assertion);
+        }
+    });
+}
+
+/// Run a closure with assertion failures captured instead of reported
+///
+/// Returns the closure result (with any panic caught) and the first failed assertion, if a failed
+/// assertion caused the panic. Panics raised inside the capture are not printed.
+pub(crate) fn capture_failure<R>(f: impl FnOnce() -> R) -> (std::thread::Result<R>, Option<Assertion<()>>) {
+    // The managed panic hook keeps panics inside a capture silent
+    crate::backend::panic_hook::install();
+
+    // Restore the enclosing capture state even if the closure panics
+    struct CaptureGuard {
+        enclosing: Option<Assertion<()>>,
+    }
+
+    impl Drop for CaptureGuard {
+        fn drop(&mut self) {
+            CAPTURE_DEPTH.with(|depth| depth.set(depth.get() - 1));
+            CAPTURED_FAILURE.with(|captured| *captured.borrow_mut() = self.enclosing.take());
+        }
+    }
+
+    let enclosing = CAPTURED_FAILURE.with(|captured| captured.borrow_mut().take());
+    CAPTURE_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let guard = CaptureGuard { enclosing };
+
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    let failure = CAPTURED_FAILURE.with(|captured| captured.borrow_mut().take());
+    drop(guard);
+
+    return (result, failure);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_capture_records_the_failed_assertion() {
+        let (result, failure) = capture_failure(|| {
+            let value = 3;
+            expect!(value).to_be_greater_than(5);
+        });
+
+        assert!(result.is_err());
+        let failure = failure.expect("failed assertion should be captured");
+        assert_eq!(failure.expr_str, "value");
+        assert_eq!(failure.steps[0].sentence.format(), "be greater than 5");
+        assert!(!is_capturing());
+    }
+
+    #[test]
+    fn test_capture_lets_passing_closures_through() {
+        let (result, failure) = capture_failure(|| {
+            expect!(2 + 2).to_equal(4);
+            return 7;
+        });
+
+        assert_eq!(result.ok(), Some(7));
+        assert!(failure.is_none());
+    }
+
+    #[test]
+    fn test_capture_reports_plain_panics_without_assertion() {
+        let (result, failure) = capture_failure(|| panic!("boom"));
+
+        assert!(result.is_err());
+        assert!(failure.is_none());
+    }
+
+    #[test]
+    fn test_assertions_evaluate_again_after_a_captured_failure() {
+        for _ in 0..3 {
+            let (result, failure) = capture_failure(|| {
+                expect!(1).to_equal(2);
+            });
+            assert!(result.is_err());
+            assert!(failure.is_some());
+        }
+    }
+}
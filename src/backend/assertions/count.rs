@@ -0,0 +1,217 @@
+//! Counting of the assertions executed by a test
+//!
+//! Every evaluated assertion chain counts once on the thread that evaluated it. `expect_assertions!(n)`
+//! creates an [`AssertionCount`] guard that fails the test when it goes out of scope after a different
+//! number of assertions ran, catching early returns and loops that never execute their body.
+//! Assertions captured by another runner, such as the ones checked inside a property, are not counted.
+//!
+//! Fixture-wrapped tests that complete without any assertion are also detected, and handled as
+//! configured with `config().vacuous_tests(..)` or `REST_VACUOUS_TESTS`, unless marked
+//! `#[allow_no_assertions]`.
+
+use crate::backend::Assertion;
+use crate::backend::assertions::report_and_panic;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::config::VacuousTests;
+use std::cell::Cell;
+use std::sync::{LazyLock, Mutex};
+
+/// Fixture-wrapped tests that completed without assertions, when configured to warn
+static VACUOUS: LazyLock<Mutex<Vec<String>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+thread_local! {
+    // Number of assertion chains evaluated on this thread
+    static EXECUTED: Cell<usize> = const { Cell::new(0) };
+    // Whether the running test is marked `#[allow_no_assertions]`
+    static ALLOW_NO_ASSERTIONS: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Count an evaluated assertion chain
+pub(crate) fn record_assertion() {
+    EXECUTED.with(|executed| executed.set(executed.get() + 1));
+}
+
+/// Number of assertion chains evaluated on this thread so far
+pub(crate) fn executed_assertions() -> usize {
+    return EXECUTED.with(|executed| executed.get());
+}
+
+/// Guard checking the number of assertions executed while it is alive, as created by `expect_assertions!`
+pub struct AssertionCount {
+    expected: usize,
+    start: usize,
+}
+
+impl AssertionCount {
+    /// Expect `expected` assertions to run on this thread before the guard is dropped
+    pub fn new(expected: usize) -> Self {
+        return Self { expected, start: executed_assertions() };
+    }
+
+    /// Number of assertions executed since the guard was created
+    pub fn executed(&self) -> usize {
+        return executed_assertions() - self.start;
+    }
+}
+
+/// Describe a number of assertions with the right plurality
+fn describe_assertions(count: usize) -> String {
+    return if count == 1 { "1 assertion".to_string() } else { format!("{} assertions", count) };
+}
+
+impl Drop for AssertionCount {
+    fn drop(&mut self) {
+        // A failed assertion already fails the test
+        if std::thread::panicking() {
+            return;
+        }
+
+        let executed = self.executed();
+        if executed == self.expected {
+            return;
+        }
+
+        let sentence = AssertionSentence::new("run", describe_assertions(self.expected)).with_actual(executed.to_string());
+        let assertion = Assertion::new((), "test").add_step(sentence, false);
+
+        report_and_panic(assertion, format!("expected {}, {} ran", describe_assertions(self.expected), executed));
+    }
+}
+
+/// Allow the running fixture-wrapped test to complete without assertions
+///
+/// This is automatically called by the `#[allow_no_assertions]` attribute macro.
+pub fn allow_no_assertions() {
+    ALLOW_NO_ASSERTIONS.with(|allowed| allowed.set(true));
+}
+
+/// Fixture-wrapped tests that completed without running any assertion, as listed in the summary
+///
+/// Only recorded when tests without assertions are configured to warn.
+pub fn vacuous_tests() -> Vec<String> {
+    return VACUOUS.lock().unwrap().clone();
+}
+
+/// Handle a fixture-wrapped test that completed after running `executed` assertions
+pub(crate) fn check_test_assertions(test_path: String, executed: usize) {
+    check_with_handling(test_path, executed, crate::config::vacuous_tests());
+}
+
+/// Handle a test that completed after running `executed` assertions, with the given handling
+fn check_with_handling(test_path: String, executed: usize, handling: VacuousTests) {
+    let allowed = ALLOW_NO_ASSERTIONS.with(|allowed| allowed.replace(false));
+    if executed > 0 || allowed {
+        return;
+    }
+
+    match handling {
+        VacuousTests::Allow => {}
+        VacuousTests::Warn => VACUOUS.lock().unwrap().push(test_path),
+        VacuousTests::Fail => {
+            let sentence = AssertionSentence::new("run", "at least 1 assertion")
+                .with_actual("0")
+                .with_detail("mark the test #[allow_no_assertions] if it only checks that nothing panics");
+            let assertion = Assertion::new((), "test").with_context(test_path.clone()).add_step(sentence, false);
+
+            report_and_panic(assertion, format!("{} completed without running any assertion", test_path));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_matching_count_passes() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect_assertions!(3);
+
+        for value in [2, 4] {
+            expect!(value).to_be_even();
+        }
+        expect!(true).to_be_true();
+    }
+
+    #[test]
+    fn test_chains_count_once() {
+        crate::Reporter::disable_deduplication();
+
+        expect_assertions!(1);
+
+        expect!(4).to_be_positive().and().to_be_even().and().to_be_less_than(10);
+    }
+
+    #[test]
+    fn test_nested_scopes_are_included_in_the_enclosing_count() {
+        crate::Reporter::disable_deduplication();
+
+        expect_assertions!(2);
+        expect!(1).to_be_positive();
+
+        {
+            expect_assertions!(1);
+            expect!(2).to_be_positive();
+        }
+    }
+
+    #[test]
+    fn test_vacuous_test_is_listed_when_warning() {
+        check_with_handling("suite::test_silent".to_string(), 0, VacuousTests::Warn);
+        check_with_handling("suite::test_checked".to_string(), 2, VacuousTests::Warn);
+
+        let vacuous = vacuous_tests();
+        assert!(vacuous.contains(&"suite::test_silent".to_string()));
+        assert!(!vacuous.contains(&"suite::test_checked".to_string()));
+    }
+
+    #[test]
+    fn test_allowed_test_is_not_listed() {
+        allow_no_assertions();
+        check_with_handling("suite::test_allowed".to_string(), 0, VacuousTests::Fail);
+
+        // The allowance only covers one test
+        check_with_handling("suite::test_next".to_string(), 0, VacuousTests::Warn);
+
+        let vacuous = vacuous_tests();
+        assert!(!vacuous.contains(&"suite::test_allowed".to_string()));
+        assert!(vacuous.contains(&"suite::test_next".to_string()));
+    }
+
+    #[test]
+    fn test_vacuous_test_passes_when_allowed() {
+        check_with_handling("suite::test_quiet".to_string(), 0, VacuousTests::Allow);
+        assert!(!vacuous_tests().contains(&"suite::test_quiet".to_string()));
+    }
+
+    #[test]
+    #[should_panic(expected = "suite::test_empty, run at least 1 assertion (got 0)")]
+    fn test_vacuous_test_fails_when_configured() {
+        check_with_handling("suite::test_empty".to_string(), 0, VacuousTests::Fail);
+    }
+
+    #[test]
+    #[should_panic(expected = "run 2 assertions (got 0)")]
+    fn test_skipped_loop_body_fails() {
+        expect_assertions!(2);
+
+        let values: Vec<i32> = Vec::new();
+        for value in values {
+            expect!(value).to_be_positive();
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "run 1 assertion (got 2)")]
+    fn test_extra_assertions_fail() {
+        crate::Reporter::disable_deduplication();
+
+        expect_assertions!(1);
+
+        expect!(1).to_be_positive();
+        expect!(2).to_be_positive();
+    }
+}
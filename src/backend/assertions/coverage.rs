@@ -0,0 +1,109 @@
+//! Assertion coverage: the assertions executed per source file and per test
+//!
+//! With `REST_ASSERTION_COVERAGE=1` or `config().assertion_coverage(true)`, every evaluated assertion chain is
+//! recorded under the source file of its `expect!` call and the test running it, as named by its thread. The session
+//! summary then lists the files with their number of assertions, and the modules of fixture-wrapped tests where no
+//! assertion ran at all. It tells where a suite actually checks something, a lightweight complement to code coverage.
+
+use crate::backend::discovery::discovered_tests;
+use std::collections::{BTreeMap, BTreeSet};
+use std::panic::Location;
+use std::sync::{LazyLock, Mutex};
+
+/// Assertions executed per source file, then per test
+type Recorded = BTreeMap<&'static str, BTreeMap<String, usize>>;
+
+static RECORDED: LazyLock<Mutex<Recorded>> = LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+/// Record an evaluated assertion chain made at the given location, when assertion coverage is enabled
+pub(crate) fn record_coverage(location: &'static Location<'static>) {
+    if !crate::config::is_assertion_coverage_enabled() {
+        return;
+    }
+
+    let thread = std::thread::current();
+    record(location.file(), thread.name().unwrap_or("<unnamed>"));
+}
+
+/// Count an assertion of a test in a source file
+fn record(file: &'static str, test: &str) {
+    *RECORDED.lock().unwrap().entry(file).or_default().entry(test.to_string()).or_default() += 1;
+}
+
+/// Assertions executed by a test in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestCoverage {
+    /// Test path, as named by the thread running it
+    pub test: String,
+    /// Number of assertion chains evaluated
+    pub assertions: usize,
+}
+
+/// Assertions executed in a source file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileCoverage {
+    /// Source file of the `expect!` calls
+    pub file: &'static str,
+    /// Number of assertion chains evaluated
+    pub assertions: usize,
+    /// Tests that ran them, by test path
+    pub tests: Vec<TestCoverage>,
+}
+
+/// Assertion coverage of the session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    /// Source files where assertions ran, by path
+    pub files: Vec<FileCoverage>,
+    /// Modules of fixture-wrapped tests where no test ran an assertion, by module path
+    pub uncovered_modules: Vec<&'static str>,
+}
+
+/// Assertion coverage recorded so far
+pub fn coverage_report() -> CoverageReport {
+    let recorded = RECORDED.lock().unwrap();
+    let files = recorded
+        .iter()
+        .map(|(file, tests)| FileCoverage {
+            file,
+            assertions: tests.values().sum(),
+            tests: tests.iter().map(|(test, assertions)| TestCoverage { test: test.clone(), assertions: *assertions }).collect(),
+        })
+        .collect::<Vec<_>>();
+
+    // Modules are covered by any of their tests running an assertion, wherever its `expect!` is
+    let covered = recorded.values().flat_map(|tests| tests.keys().map(String::as_str)).collect::<BTreeSet<_>>();
+    let tests = discovered_tests();
+    let covered_modules =
+        tests.iter().filter(|test| covered.contains(test.path().as_str())).map(|test| test.module).collect::<BTreeSet<_>>();
+    let uncovered_modules =
+        tests.iter().map(|test| test.module).filter(|module| !covered_modules.contains(module)).collect::<BTreeSet<_>>();
+
+    return CoverageReport { files, uncovered_modules: uncovered_modules.into_iter().collect() };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::discovery::{TestDescriptor, register_test};
+
+    fn descriptor(module: &'static str, name: &'static str) -> TestDescriptor {
+        return TestDescriptor { module, name, file: "tests/cart_test.rs", line: 1, ignored: false, should_panic: false, tags: &[] };
+    }
+
+    #[test]
+    fn test_assertions_are_recorded_per_file_and_test() {
+        register_test(descriptor("coverage::covered", "test_with_assertions"));
+        register_test(descriptor("coverage::uncovered", "test_without_assertions"));
+        record("tests/cart_test.rs", "coverage::covered::test_with_assertions");
+        record("tests/cart_test.rs", "coverage::covered::test_with_assertions");
+        record("tests/cart_helpers.rs", "coverage::covered::test_with_assertions");
+
+        let report = coverage_report();
+        let file = report.files.iter().find(|file| file.file == "tests/cart_test.rs").expect("file of the test");
+        assert_eq!(file.assertions, 2);
+        assert_eq!(file.tests, vec![TestCoverage { test: "coverage::covered::test_with_assertions".to_string(), assertions: 2 }]);
+        assert!(report.uncovered_modules.contains(&"coverage::uncovered"));
+        assert!(!report.uncovered_modules.contains(&"coverage::covered"));
+    }
+}
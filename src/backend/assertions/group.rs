@@ -0,0 +1,165 @@
+//! Grouping of assertions into a single chain
+//!
+//! Inside an [`AssertionGroup`], final assertions are neither reported nor counted on their own: their steps are
+//! collected, and [`AssertionGroup::finish`] folds them into one chain reported as a single assertion. `expect_build!`
+//! uses it to check several properties of a built value at once.
+
+use crate::backend::assertions::capture;
+use crate::backend::{Assertion, AssertionSentence, AssertionStep, LogicalOp};
+use std::cell::RefCell;
+
+thread_local! {
+    // Steps collected by the groups active on this thread, innermost last, with the capture depth they started at
+    static GROUPS: RefCell<Vec<(usize, Vec<AssertionStep>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Check if the assertions evaluated on this thread are collected by a group
+///
+/// Captures started inside the group, such as by `expect_failure!`, take their assertions back until they end.
+pub(crate) fn is_grouping() -> bool {
+    return GROUPS.with(|groups| groups.borrow().last().is_some_and(|(depth, _)| *depth == capture::depth()));
+}
+
+/// Add the steps of an evaluated assertion to the innermost group, as one more part of its chain
+///
+/// A part with an `or()` is folded into a single step, since the steps of a chain are combined as alternatives of
+/// `and()` runs and the parts of a group must all pass.
+pub(crate) fn record<T>(assertion: &Assertion<T>) {
+    let mut steps = assertion.steps.clone();
+    if steps.iter().any(|step| step.logical_op == Some(LogicalOp::Or)) {
+        steps = vec![either_step(assertion)];
+    }
+    if let Some(last) = steps.last_mut() {
+        last.logical_op = Some(LogicalOp::And);
+    }
+
+    GROUPS.with(|groups| {
+        if let Some((_, collected)) = groups.borrow_mut().last_mut() {
+            collected.extend(steps);
+        }
+    });
+}
+
+/// Single step standing for a chain with alternatives: `satisfy (start with "a" OR end with "z")`
+fn either_step<T>(assertion: &Assertion<T>) -> AssertionStep {
+    let mut chain = String::new();
+    for step in &assertion.steps {
+        chain.push_str(&step.sentence.format());
+        match step.logical_op {
+            Some(LogicalOp::And) => chain.push_str(" AND "),
+            Some(LogicalOp::Or) => chain.push_str(" OR "),
+            None => {}
+        }
+    }
+
+    let mut sentence = AssertionSentence::new("satisfy", format!("({})", chain));
+    sentence.subject = assertion.expr_str.trim_start_matches('&').to_string();
+    for step in assertion.steps.iter().filter(|step| !step.passed) {
+        sentence = sentence.with_detail(step.sentence.format_with_actual());
+    }
+
+    return AssertionStep { sentence, passed: assertion.calculate_chain_result(), logical_op: None, location: assertion.steps[0].location };
+}
+
+/// Group collecting the assertions evaluated on this thread until it is finished
+#[doc(hidden)]
+pub struct AssertionGroup {
+    finished: bool,
+}
+
+impl AssertionGroup {
+    /// Start collecting the assertions evaluated on this thread
+    pub fn begin() -> Self {
+        GROUPS.with(|groups| groups.borrow_mut().push((capture::depth(), Vec::new())));
+        return Self { finished: false };
+    }
+
+    /// Stop collecting and move the collected steps into an assertion, reported as one chain when dropped
+    pub fn finish<T>(mut self, mut assertion: Assertion<T>) -> Assertion<T> {
+        self.finished = true;
+        let mut steps = GROUPS.with(|groups| groups.borrow_mut().pop()).map(|(_, steps)| steps).unwrap_or_default();
+        if let Some(last) = steps.last_mut() {
+            last.logical_op = None;
+        }
+
+        assertion.steps = steps;
+        assertion.in_chain = assertion.steps.len() > 1;
+        return assertion;
+    }
+}
+
+impl Drop for AssertionGroup {
+    fn drop(&mut self) {
+        // Stop collecting when the grouped assertions panic before the group is finished
+        if !self.finished {
+            GROUPS.with(|groups| groups.borrow_mut().pop());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    /// Assertion on `five` with one step, whose sentence is built even with the `fast-messages` feature
+    fn step(verb: &str, object: &str, passed: bool) -> Assertion<i32> {
+        return Assertion::new(5, "five").add_step(AssertionSentence::new(verb, object), passed);
+    }
+
+    #[test]
+    fn test_grouped_assertions_are_folded_into_one_chain() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let group = AssertionGroup::begin();
+        drop(step("be", "greater than 3", true));
+        drop(step("be", "less than 10", true).and().add_step(AssertionSentence::new("be", "even"), false));
+        expect!(is_grouping()).to_be_true();
+        let mut assertion = group.finish(Assertion::new(5, "five"));
+        // The folded chain fails, it is only inspected
+        assertion.evaluated = true;
+
+        let outcomes = assertion.steps.iter().map(|step| (step.sentence.format(), step.passed, step.logical_op)).collect::<Vec<_>>();
+        assert_eq!(outcomes[0], ("be greater than 3".to_string(), true, Some(LogicalOp::And)));
+        assert_eq!(outcomes[1], ("be less than 10".to_string(), true, Some(LogicalOp::And)));
+        assert_eq!(outcomes[2], ("be even".to_string(), false, Some(LogicalOp::And)));
+        assert_eq!(outcomes.len(), 4);
+        assert_eq!(outcomes[3].2, None);
+        assert!(!assertion.calculate_chain_result());
+        assert!(!is_grouping());
+    }
+
+    #[test]
+    fn test_alternatives_are_folded_into_one_step() {
+        let group = AssertionGroup::begin();
+        drop(step("start", "with \"a\"", false).or().add_step(AssertionSentence::new("end", "with \"y\""), true));
+        let assertion = group.finish(Assertion::new(5, "five"));
+
+        assert_eq!(assertion.steps.len(), 1);
+        assert_eq!(assertion.steps[0].sentence.format(), "satisfy (start with \"a\" OR end with \"y\")");
+        assert_eq!(assertion.steps[0].sentence.details, vec!["start with \"a\"".to_string()]);
+        assert!(assertion.steps[0].passed);
+    }
+
+    #[test]
+    fn test_captures_inside_a_group_keep_their_assertions() {
+        let group = AssertionGroup::begin();
+        let (result, failure) = capture::capture_failure(|| {
+            expect!(1).to_equal(2);
+        });
+        let assertion = group.finish(Assertion::new((), "nothing"));
+
+        assert!(result.is_err());
+        assert!(failure.is_some());
+        assert!(assertion.steps.is_empty());
+    }
+
+    #[test]
+    fn test_unfinished_group_stops_collecting() {
+        let group = AssertionGroup::begin();
+        drop(group);
+
+        assert!(!is_grouping());
+    }
+}
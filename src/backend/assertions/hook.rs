@@ -0,0 +1,95 @@
+//! Process-wide hook called when an assertion fails, before it panics
+//!
+//! [`on_assertion_failure`] lets a test suite act while the failing state is still alive: break into a debugger,
+//! dump a core, take a screenshot of a GUI under test. The hook then decides whether the failure aborts the test
+//! with the usual panic or lets it continue, the failure being reported either way.
+
+use crate::backend::Assertion;
+use std::cell::Cell;
+use std::panic::Location;
+use std::sync::{Arc, RwLock};
+
+/// Where a failed assertion was written, and the thread that ran it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// Source file of the `expect!` call
+    pub file: &'static str,
+    /// Line of the `expect!` call, starting at 1
+    pub line: u32,
+    /// Column of the `expect!` call, starting at 1
+    pub column: u32,
+    /// Name of the thread that ran the assertion, which is the test path under the default test harness
+    pub thread: Option<String>,
+}
+
+impl CallSite {
+    /// Call site of an assertion created at the given location, run by the current thread
+    fn new(location: &'static Location<'static>) -> Self {
+        return Self {
+            file: location.file(),
+            line: location.line(),
+            column: location.column(),
+            thread: std::thread::current().name().map(str::to_string),
+        };
+    }
+}
+
+/// What a failed assertion does once the failure hook returns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureAction {
+    /// Panic as usual, failing the test
+    Panic,
+    /// Carry on with the rest of the test
+    Continue,
+}
+
+type FailureHook = Arc<dyn Fn(&Assertion<()>, &CallSite) -> FailureAction + Send + Sync>;
+
+static FAILURE_HOOK: RwLock<Option<FailureHook>> = RwLock::new(None);
+
+thread_local! {
+    // Whether the failure hook is running on this thread
+    static IN_HOOK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Register the hook called with every failed assertion and its call site, before the assertion panics
+///
+/// The hook is shared by all threads and replaces any previously registered one. Assertions failing inside the hook
+/// itself panic without calling it again.
+pub fn on_assertion_failure<F>(hook: F)
+where
+    F: Fn(&Assertion<()>, &CallSite) -> FailureAction + Send + Sync + 'static,
+{
+    *FAILURE_HOOK.write().unwrap() = Some(Arc::new(hook));
+}
+
+/// Remove the registered failure hook, if any
+pub fn clear_assertion_failure_hook() {
+    *FAILURE_HOOK.write().unwrap() = None;
+}
+
+/// Call the failure hook with a failed assertion, returning what the assertion should do
+pub(crate) fn run_failure_hook(assertion: &Assertion<()>) -> FailureAction {
+    // The lock isn't held while the hook runs, so that it can register another hook
+    let Some(hook) = FAILURE_HOOK.read().unwrap().clone() else {
+        return FailureAction::Panic;
+    };
+
+    if IN_HOOK.with(Cell::get) {
+        return FailureAction::Panic;
+    }
+
+    // Reset the flag even if the hook panics
+    struct HookGuard;
+
+    impl Drop for HookGuard {
+        fn drop(&mut self) {
+            IN_HOOK.with(|in_hook| in_hook.set(false));
+        }
+    }
+
+    IN_HOOK.with(|in_hook| in_hook.set(true));
+    let _guard = HookGuard;
+
+    return hook(assertion, &CallSite::new(assertion.location));
+}
@@ -0,0 +1,71 @@
+//! Data derived from the value of an assertion chain, computed once for all its steps
+//!
+//! The steps of a chain work on clones of the same value, and share one [`Memo`]: matchers that parse or hash the
+//! value, such as `to_match_json_schema`, store the result under a key naming the derivation, so chaining them
+//! with `and()` or `or()` derives it once. Type-erased copies of an assertion, sent to event handlers, start empty.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+// Derived data of a chain, by type and key
+type Entries = HashMap<(TypeId, &'static str), Arc<dyn Any + Send + Sync>>;
+
+/// Derived data shared by the steps of an assertion chain
+#[derive(Clone, Default)]
+pub(crate) struct Memo {
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl Memo {
+    /// Get the data stored under a key, deriving and storing it on first use
+    ///
+    /// The lock isn't held while deriving, so derivations can use the memo themselves.
+    pub(crate) fn get_or_derive<D: Send + Sync + 'static>(&self, key: &'static str, derive: impl FnOnce() -> D) -> Arc<D> {
+        let id = (TypeId::of::<D>(), key);
+        if let Some(data) = self.lock().get(&id).cloned() {
+            return data.downcast::<D>().expect("memo entries are stored under their type");
+        }
+
+        let data = Arc::new(derive());
+        self.lock().insert(id, data.clone());
+        return data;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        return self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    }
+}
+
+impl Debug for Memo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let keys = self.lock().keys().map(|(_, key)| *key).collect::<Vec<_>>();
+        return f.debug_struct("Memo").field("keys", &keys).finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_data_is_derived_once_per_key_and_type() {
+        let memo = Memo::default();
+        let derivations = Cell::new(0);
+        let derive = || {
+            derivations.set(derivations.get() + 1);
+            return 42_u64;
+        };
+
+        assert_eq!(*memo.get_or_derive("answer", derive), 42);
+        assert_eq!(*memo.clone().get_or_derive("answer", derive), 42);
+        assert_eq!(derivations.get(), 1);
+
+        // Other keys and types are derived apart
+        assert_eq!(*memo.get_or_derive("other", derive), 42);
+        assert_eq!(*memo.get_or_derive("answer", || "text"), "text");
+        assert_eq!(derivations.get(), 2);
+    }
+}
@@ -1,6 +1,17 @@
 //! Module for assertion chain and assertion handling
 
 mod assertion;
+pub(crate) mod capture;
+pub mod count;
+pub mod coverage;
+#[doc(hidden)]
+pub mod group;
+pub mod hook;
+mod memo;
 pub mod sentence;
+pub(crate) mod source;
+#[doc(hidden)]
+pub mod type_name;
 
-pub use assertion::{Assertion, AssertionStep, LogicalOp, TestSessionResult};
+pub(crate) use assertion::report_and_panic;
+pub use assertion::{Assertion, AssertionStep, LogicalOp, Severity, TestSessionResult};
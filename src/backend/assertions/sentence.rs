@@ -1,8 +1,103 @@
-use cruet::Inflector;
+use crate::backend::diff::Diff;
+use crate::backend::discovery::json_string;
+use crate::config::Plurality;
+use std::cell::Cell;
 use std::fmt::{self, Display, Formatter};
 
+/// Verb of the placeholder sentence recorded for passing steps when messages are elided
+const ELIDED_VERB: &str = "pass";
+
+/// Object of the placeholder sentence recorded for passing steps when messages are elided
+const ELIDED_OBJECT: &str = "(message elided)";
+
+/// Indentation of the multi-line objects and actual values rendered under a sentence
+const BLOCK_INDENT: &str = "    ";
+
+/// Plural nouns that don't end in "s"
+const IRREGULAR_PLURALS: &[&str] = &["children", "criteria", "data", "feet", "geese", "men", "mice", "people", "phenomena", "teeth", "women"];
+
+/// Singular nouns ending in "s" that don't end in "ss", "us" or "is" either
+const SINGULARS_ENDING_IN_S: &[&str] = &["alias", "atlas", "bias", "canvas", "gas", "lens", "news", "series", "species"];
+
+thread_local! {
+    // Number of live guards disabling conjugation on this thread, as created by `#[no_conjugation]`
+    static CONJUGATION_DISABLED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Guard keeping verbs in their base form while it is alive, as in `datos be equal to 3` and `datos not contain 3`
+///
+/// This is automatically created by the `#[no_conjugation]` attribute macro, for subjects whose plurality can't be
+/// told from their name and sentences that must read the same whatever the name.
+pub struct ConjugationGuard {
+    _private: (),
+}
+
+impl ConjugationGuard {
+    /// Disable the conjugation of the sentences formatted on this thread until the guard is dropped
+    pub fn new() -> Self {
+        CONJUGATION_DISABLED.with(|disabled| disabled.set(disabled.get() + 1));
+        return Self { _private: () };
+    }
+}
+
+impl Default for ConjugationGuard {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl Drop for ConjugationGuard {
+    fn drop(&mut self) {
+        CONJUGATION_DISABLED.with(|disabled| disabled.set(disabled.get() - 1));
+    }
+}
+
+/// Check if a guard disables conjugation on this thread
+fn is_conjugation_disabled() -> bool {
+    return CONJUGATION_DISABLED.with(|disabled| disabled.get() > 0);
+}
+
+/// Short description of what a matcher checks, with a link to its documentation
+///
+/// Attached by matchers whose failures may puzzle readers unfamiliar with them, such as ULP comparisons, and shown
+/// under failed steps with `config().matcher_docs(true)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatcherDocs {
+    /// What the matcher checks, such as "floats at most N representable values apart"
+    pub summary: String,
+    /// Documentation of the matcher
+    pub url: Option<String>,
+}
+
+impl MatcherDocs {
+    /// Describe what a matcher checks
+    pub fn new(summary: impl Into<String>) -> Self {
+        return Self { summary: summary.into(), url: None };
+    }
+
+    /// Link to the documentation of the matcher
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        return self;
+    }
+
+    /// Render the description with its link, as shown under a failed step
+    pub fn render(&self) -> String {
+        return match self.url {
+            Some(ref url) => format!("checks {} (see {})", self.summary, url),
+            None => format!("checks {}", self.summary),
+        };
+    }
+
+    /// Render the description as a JSON object, such as `{"summary":"...","url":null}`
+    pub fn to_json(&self) -> String {
+        let url = self.url.as_deref().map(json_string).unwrap_or_else(|| "null".to_string());
+        return format!("{{\"summary\":{},\"url\":{}}}", json_string(&self.summary), url);
+    }
+}
+
 /// Represents a complete sentence structure for an assertion
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct AssertionSentence {
     /// The subject of the assertion (usually the variable name)
     pub subject: String,
@@ -16,6 +111,14 @@ pub struct AssertionSentence {
     pub negated: bool,
     /// The actual value being tested, shown on failure (e.g., "5", "\"hello\"")
     pub actual_value: Option<String>,
+    /// Extra lines rendered under the step on failure (e.g., a reproduction seed)
+    pub details: Vec<String>,
+    /// The expected value, for tools comparing it with the actual one (e.g., "42", "[1, 2, 3]")
+    pub expected_value: Option<String>,
+    /// Element-level comparison of the actual and expected values, when the matcher computed one
+    pub diff: Option<Diff>,
+    /// What the matcher checks, when it documents itself
+    pub docs: Option<MatcherDocs>,
 }
 
 impl AssertionSentence {
@@ -28,9 +131,23 @@ impl AssertionSentence {
             qualifiers: Vec::new(),
             negated: false,
             actual_value: None,
+            details: Vec::new(),
+            expected_value: None,
+            diff: None,
+            docs: None,
         };
     }
 
+    /// Create the placeholder sentence of a passing step whose message was not built (`fast-messages` feature)
+    pub fn elided() -> Self {
+        return Self::new(ELIDED_VERB, ELIDED_OBJECT);
+    }
+
+    /// Whether this is the placeholder sentence of a passing step whose message was not built
+    pub fn is_elided(&self) -> bool {
+        return self.verb == ELIDED_VERB && self.object == ELIDED_OBJECT;
+    }
+
     /// Set whether the assertion is negated
     pub fn with_negation(mut self, negated: bool) -> Self {
         self.negated = negated;
@@ -49,85 +166,147 @@ impl AssertionSentence {
         return self;
     }
 
+    /// Add a detail line rendered under the step on failure
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.details.push(detail.into());
+        return self;
+    }
+
+    /// Set the expected value, kept for event handlers and external tools rather than rendered
+    pub fn with_expected(mut self, expected: impl Into<String>) -> Self {
+        self.expected_value = Some(expected.into());
+        return self;
+    }
+
+    /// Attach the element-level diff of a failure, rendered as detail lines under the step
+    pub fn with_diff(mut self, diff: Diff) -> Self {
+        self.details.extend(diff.render());
+        self.diff = Some(diff);
+        return self;
+    }
+
+    /// Attach what the matcher checks, shown under the step when it fails with `config().matcher_docs(true)`
+    pub fn with_docs(mut self, docs: MatcherDocs) -> Self {
+        self.docs = Some(docs);
+        return self;
+    }
+
+    /// Render the sentence as a JSON object with its expected and actual values, details, diff and matcher docs, such as
+    /// `{"sentence":"be equal to 42","expected":"42","actual":"41","details":[],"diff":null,"docs":null}`
+    pub fn to_json(&self) -> String {
+        let optional = |value: Option<&str>| value.map(json_string).unwrap_or_else(|| "null".to_string());
+        let details = self.details.iter().map(|detail| json_string(detail)).collect::<Vec<_>>();
+
+        return format!(
+            "{{\"sentence\":{},\"expected\":{},\"actual\":{},\"details\":[{}],\"diff\":{},\"docs\":{}}}",
+            json_string(&self.format()),
+            optional(self.expected_value.as_deref()),
+            optional(self.actual_value.as_deref()),
+            details.join(","),
+            self.diff.as_ref().map(Diff::to_json).unwrap_or_else(|| "null".to_string()),
+            self.docs.as_ref().map(MatcherDocs::to_json).unwrap_or_else(|| "null".to_string())
+        );
+    }
+
     /// Format the sentence into a readable string (raw format, without subject)
     pub fn format(&self) -> String {
-        let mut result = if self.negated { format!("not {} {}", self.verb, self.object) } else { format!("{} {}", self.verb, self.object) };
+        let verb = if self.negated { format!("not {}", self.verb) } else { self.verb.clone() };
 
-        if !self.qualifiers.is_empty() {
-            result.push(' ');
-            result.push_str(&self.qualifiers.join(" "));
-        }
-
-        return result;
+        return self.phrase(verb, false);
     }
 
     /// Format the sentence with grammatically correct 'not' placement (after the verb)
     /// This is used for display purposes where improved grammar is desired
     pub fn format_grammatical(&self) -> String {
-        let mut result = if self.negated {
-            // Place "not" after the verb for grammatical correctness
-            format!("{} not {}", self.verb, self.object)
-        } else {
-            format!("{} {}", self.verb, self.object)
-        };
+        // Place "not" after the verb for grammatical correctness
+        let verb = if self.negated { format!("{} not", self.verb) } else { self.verb.clone() };
 
-        if !self.qualifiers.is_empty() {
-            result.push(' ');
-            result.push_str(&self.qualifiers.join(" "));
-        }
-
-        return result;
+        return self.phrase(verb, false);
     }
 
     /// Format the sentence into a readable string with actual value appended on failure
     pub fn format_with_actual(&self) -> String {
-        let base = self.format();
-
-        if let Some(ref actual) = self.actual_value {
-            return format!("{} (got {})", base, actual);
-        }
-
-        return base;
+        return self.append_actual(self.format());
     }
 
     /// Format the sentence with the correct verb conjugation based on the subject
     pub fn format_with_conjugation(&self, subject: &str) -> String {
-        // Determine if the subject is plural
-        let is_plural = Self::is_plural_subject(subject);
+        return self.phrase(self.conjugated_verb(subject), false);
+    }
 
-        // Convert the infinitive verb to the correct form based on plurality
-        let conjugated_verb = self.conjugate_verb(is_plural);
+    /// Format the sentence with the correct verb conjugation on a single line, joining the lines of a multi-line object
+    ///
+    /// Used where the sentence is part of a longer line, such as the headline of an assertion chain.
+    pub fn format_with_conjugation_inline(&self, subject: &str) -> String {
+        return self.phrase(self.conjugated_verb(subject), true);
+    }
 
-        let mut result = if self.negated {
-            // Place "not" after the conjugated verb for grammatical correctness
-            format!("{} not {}", conjugated_verb, self.object)
-        } else {
-            format!("{} {}", conjugated_verb, self.object)
+    /// Append the actual value to a formatted sentence, as `(got 5)` or as an indented block for multi-line values
+    pub fn append_actual(&self, formatted: String) -> String {
+        return match self.actual_value {
+            Some(ref actual) if self.is_multiline() => format!("{}\n  got:\n{}", formatted, indent_block(actual)),
+            Some(ref actual) => format!("{} (got {})", formatted, actual),
+            None => formatted,
         };
+    }
+
+    /// Whether the object or the actual value spans several lines, such as a pretty-printed `Debug` output
+    ///
+    /// Multi-line objects and actual values are rendered in indented blocks under the sentence.
+    pub fn is_multiline(&self) -> bool {
+        return self.object.contains('\n') || self.actual_value.as_ref().is_some_and(|actual| actual.contains('\n'));
+    }
+
+    /// Build the phrase from the verb (with its negation), the object and the qualifiers
+    fn phrase(&self, verb: String, inline: bool) -> String {
+        let qualifiers = if self.qualifiers.is_empty() { String::new() } else { format!(" {}", self.qualifiers.join(" ")) };
 
-        if !self.qualifiers.is_empty() {
-            result.push(' ');
-            result.push_str(&self.qualifiers.join(" "));
+        if !self.object.contains('\n') {
+            return format!("{} {}{}", verb, self.object, qualifiers);
         }
 
-        return result;
+        if inline {
+            let object = self.object.lines().map(str::trim).filter(|line| !line.is_empty()).collect::<Vec<_>>().join(" ");
+            return format!("{} {}{}", verb, object, qualifiers);
+        }
+
+        // The object goes in a block under the verb and qualifiers
+        return format!("{}{}:\n{}", verb, qualifiers, indent_block(&self.object));
     }
 
-    /// Determine if a subject name is likely plural using the cruet crate
-    /// for proper English singularization. If singularizing a word changes it,
-    /// the original was plural.
+    /// Conjugate the verb for the subject, followed by "not" when negated
+    ///
+    /// The verb is kept in its base form while a [`ConjugationGuard`] is alive, preceded by "not" as in [`Self::format`].
+    fn conjugated_verb(&self, subject: &str) -> String {
+        if is_conjugation_disabled() {
+            return if self.negated { format!("not {}", self.verb) } else { self.verb.clone() };
+        }
+
+        // Convert the infinitive verb to the correct form based on plurality
+        let conjugated_verb = self.conjugate_verb(Self::is_plural_subject(subject));
+
+        // Place "not" after the conjugated verb for grammatical correctness
+        return if self.negated { format!("{} not", conjugated_verb) } else { conjugated_verb };
+    }
+
+    /// Determine if a subject name is likely plural from the English noun in its last word.
+    ///
+    /// Names given a plurality with `config::subject_plurality` skip the detection.
     fn is_plural_subject(subject: &str) -> bool {
         // Extract the base variable name from expressions like "var.method()" or "&var"
-        let base_name = Self::extract_base_name(subject);
+        let base_name = Self::extract_base_name(subject).to_lowercase();
 
         // For snake_case variable names (common in Rust), check the last word segment
         let last_word = base_name.split('_').next_back().unwrap_or(&base_name);
-        let last_word_lower = last_word.to_lowercase();
+        let last_word_lower = last_word.to_string();
 
-        // Use cruet's singularization: if singularizing changes the word, it was plural
-        let singularized = last_word_lower.to_singular();
+        let plurality =
+            crate::config::subject_plurality_override(&base_name).or_else(|| crate::config::subject_plurality_override(&last_word_lower));
+        if let Some(plurality) = plurality {
+            return plurality == Plurality::Plural;
+        }
 
-        return singularized != last_word_lower;
+        return is_plural_noun(&last_word_lower);
     }
 
     /// Extract the base variable name from expressions
@@ -151,10 +330,8 @@ impl AssertionSentence {
 
     /// Conjugate the verb based on plurality
     ///
-    /// Note: We use a manual match here rather than `cruet` because `cruet` only handles
-    /// noun inflections (pluralize/singularize), not verb conjugation. Since the set of
-    /// verbs used by matchers is small and controlled by this crate, a manual match is
-    /// both correct and sufficient.
+    /// Note: The set of verbs used by matchers is small and controlled by this crate,
+    /// so a manual match is both correct and sufficient.
     fn conjugate_verb(&self, is_plural: bool) -> String {
         // Special case handling for common verbs
         match self.verb.as_str() {
@@ -217,6 +394,23 @@ impl AssertionSentence {
     }
 }
 
+/// Check if a lowercase English noun is likely plural, as in `values`, `entries` or `data` but not `status` or `address`
+fn is_plural_noun(word: &str) -> bool {
+    if IRREGULAR_PLURALS.contains(&word) {
+        return true;
+    }
+    if SINGULARS_ENDING_IN_S.contains(&word) {
+        return false;
+    }
+
+    return word.len() > 1 && word.ends_with('s') && !["ss", "us", "is"].iter().any(|suffix| word.ends_with(suffix));
+}
+
+/// Indent every line of a multi-line object or actual value rendered under a sentence
+fn indent_block(text: &str) -> String {
+    return text.lines().map(|line| format!("{}{}", BLOCK_INDENT, line)).collect::<Vec<_>>().join("\n");
+}
+
 impl Display for AssertionSentence {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         return write!(f, "{}", self.format());
@@ -437,6 +631,90 @@ mod tests {
         assert_eq!(contain_sentence.format_with_conjugation("lists"), "contain element");
     }
 
+    #[test]
+    fn test_multiline_object_is_rendered_in_a_block() {
+        let sentence = AssertionSentence::new("equal", "Point {\n    x: 1,\n}").with_qualifier("exactly").with_negation(true);
+
+        assert!(sentence.is_multiline());
+        assert_eq!(sentence.format(), "not equal exactly:\n    Point {\n        x: 1,\n    }");
+        assert_eq!(sentence.format_with_conjugation("point"), "equals not exactly:\n    Point {\n        x: 1,\n    }");
+        assert_eq!(sentence.format_with_conjugation_inline("point"), "equals not Point { x: 1, } exactly");
+    }
+
+    #[test]
+    fn test_multiline_actual_is_rendered_in_a_block() {
+        let sentence = AssertionSentence::new("be", "ok").with_actual("Err(\n    Timeout,\n)");
+        assert_eq!(sentence.format_with_actual(), "be ok\n  got:\n    Err(\n        Timeout,\n    )");
+
+        // Single-line values stay inline
+        let inline = AssertionSentence::new("be", "ok").with_actual("Err(Timeout)");
+        assert!(!inline.is_multiline());
+        assert_eq!(inline.format_with_actual(), "be ok (got Err(Timeout))");
+        assert_eq!(inline.format_with_conjugation_inline("result"), inline.format_with_conjugation("result"));
+    }
+
+    #[test]
+    fn test_structured_comparison_as_json() {
+        let sentence = AssertionSentence::new("equal", "collection of 2 elements")
+            .with_expected("[1, 4]")
+            .with_actual("[1, 5]")
+            .with_diff(Diff::sequence(&[1, 5], &[1, 4], |actual, expected| actual == expected));
+
+        assert_eq!(sentence.details, vec!["[0] ok", "[1] expected 4, got 5"]);
+        assert_eq!(sentence.diff.as_ref().map(Diff::change_count), Some(1));
+        assert!(sentence.to_json().starts_with(concat!(
+            "{\"sentence\":\"equal collection of 2 elements\",\"expected\":\"[1, 4]\",\"actual\":\"[1, 5]\",",
+            "\"details\":[\"[0] ok\",\"[1] expected 4, got 5\"],\"diff\":{\"entries\":["
+        )));
+        assert!(
+            AssertionSentence::new("be", "positive")
+                .to_json()
+                .ends_with("\"expected\":null,\"actual\":null,\"details\":[],\"diff\":null,\"docs\":null}")
+        );
+    }
+
+    #[test]
+    fn test_subject_plurality_overrides() {
+        assert!(!AssertionSentence::is_plural_subject("kinder"));
+        assert!(!AssertionSentence::is_plural_subject("children_list"));
+
+        crate::config::subject_plurality("Kinder", Plurality::Plural);
+        crate::config::subject_plurality("children_list", Plurality::Plural);
+        crate::config::subject_plurality("series", Plurality::Singular);
+
+        let sentence = AssertionSentence::new("be", "empty");
+        assert_eq!(sentence.format_with_conjugation("kinder.len()"), "are empty");
+        assert_eq!(sentence.format_with_conjugation("&user_kinder"), "are empty");
+        assert_eq!(sentence.format_with_conjugation("children_list"), "are empty");
+        assert_eq!(sentence.format_with_conjugation("time_series"), "is empty");
+    }
+
+    #[test]
+    fn test_conjugation_disabled_by_guard() {
+        let sentence = AssertionSentence::new("contain", "3").with_negation(true);
+        {
+            let _conjugation = ConjugationGuard::new();
+            assert_eq!(sentence.format_with_conjugation("valores"), "not contain 3");
+            assert_eq!(sentence.format_with_conjugation("value"), "not contain 3");
+        }
+
+        assert_eq!(sentence.format_with_conjugation("value"), "contains not 3");
+    }
+
+    #[test]
+    fn test_matcher_docs() {
+        let docs = MatcherDocs::new("floats at most N representable values apart").with_url("https://example.com/ulps");
+        let sentence = AssertionSentence::new("equal", "[1.0] within 4 ULPs").with_docs(docs.clone());
+
+        assert_eq!(docs.render(), "checks floats at most N representable values apart (see https://example.com/ulps)");
+        assert_eq!(MatcherDocs::new("the schema").render(), "checks the schema");
+        assert!(
+            sentence
+                .to_json()
+                .ends_with("\"docs\":{\"summary\":\"floats at most N representable values apart\",\"url\":\"https://example.com/ulps\"}}")
+        );
+    }
+
     #[test]
     fn test_display_trait() {
         let sentence = AssertionSentence::new("be", "positive");
@@ -0,0 +1,136 @@
+//! Source text of the matcher calls that produced assertion steps
+//!
+//! Matchers are `#[track_caller]`, so each step records the location of the call that produced it, such as
+//! `.to_be_greater_than(10)` in the test. The call is read back from the source file only when a failure is rendered,
+//! which keeps passing assertions free of any formatting cost.
+
+use std::collections::HashMap;
+use std::panic::Location;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex};
+
+// Longest matcher call rendered before it is truncated
+const MAX_CALL_LENGTH: usize = 80;
+
+// Source files read so far, `None` when a file can't be found
+static SOURCES: LazyLock<Mutex<HashMap<&'static str, Option<String>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Source text of the matcher call at a location, such as `to_be_greater_than(10)`
+///
+/// Returns `None` when the source file isn't available, or when the location isn't a method call on the assertion,
+/// which happens for custom matchers that aren't `#[track_caller]`.
+pub(crate) fn matcher_call(location: &'static Location<'static>) -> Option<String> {
+    let mut sources = SOURCES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let source = sources.entry(location.file()).or_insert_with(|| read_source(location.file())).as_deref()?;
+
+    let line = source.lines().nth(location.line().checked_sub(1)? as usize)?;
+    // rustc counts columns in characters, not bytes, so non-ASCII text before the call must be skipped as such
+    let start = line.char_indices().nth(location.column().checked_sub(1)? as usize).map(|(index, _)| index)?;
+    // The call may span several lines, so extraction continues past the end of this one
+    let line_offset = line.as_ptr() as usize - source.as_ptr() as usize;
+
+    return extract_call(&source[line_offset + start..]);
+}
+
+/// Read a source file, whose path is relative to the workspace root the crate was compiled from
+fn read_source(file: &str) -> Option<String> {
+    return std::fs::read_to_string(source_path(file)?).ok();
+}
+
+/// Find a source file of a [`Location`], from the current directory or the manifest directory and its ancestors
+pub(crate) fn source_path(file: &str) -> Option<PathBuf> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR").map(PathBuf::from);
+    let mut candidates = std::iter::once(PathBuf::from(file))
+        .chain(manifest_dir.iter().flat_map(|dir| dir.ancestors().map(|ancestor| ancestor.join(file)).collect::<Vec<_>>()));
+
+    return candidates.find(|path| path.is_file());
+}
+
+/// Extract a method call starting at the given text: its name, optional turbofish and balanced arguments
+fn extract_call(text: &str) -> Option<String> {
+    let text = text.trim_start_matches('.');
+    let name_length = text.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(text.len());
+    let name = &text[..name_length];
+
+    // The location of a matcher that isn't `#[track_caller]` is inside it, on the call adding its step
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) || name.starts_with("add_step") {
+        return None;
+    }
+
+    // Only a turbofish may come between the name and the arguments
+    let arguments = name_length + text[name_length..].find('(')?;
+    if !text[name_length..arguments].chars().all(|c| c.is_alphanumeric() || " _:<>,&[]'".contains(c)) {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut end = None;
+
+    for (index, c) in text[arguments..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            _ if in_string => {}
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    end = Some(arguments + index + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Collapse arguments written over several lines
+    let call = text[..end?].split_whitespace().collect::<Vec<_>>().join(" ").replace("( ", "(").replace(" )", ")");
+
+    return Some(if call.chars().count() > MAX_CALL_LENGTH {
+        format!("{}…", call.chars().take(MAX_CALL_LENGTH - 1).collect::<String>())
+    } else {
+        call
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_call() {
+        assert_eq!(extract_call("to_be_greater_than(10);"), Some("to_be_greater_than(10)".to_string()));
+        assert_eq!(extract_call(".to_be_even().and()"), Some("to_be_even()".to_string()));
+        assert_eq!(extract_call("to_contain(\"a)b\").not()"), Some("to_contain(\"a)b\")".to_string()));
+        assert_eq!(extract_call("to_satisfy_within(timeout, |x| x >= &3)"), Some("to_satisfy_within(timeout, |x| x >= &3)".to_string()));
+        assert_eq!(extract_call("to_equal_collection::<i32>(&[1, 2])"), Some("to_equal_collection::<i32>(&[1, 2])".to_string()));
+        assert_eq!(extract_call("to_equal(\n        vec![1, 2],\n    )"), Some("to_equal(vec![1, 2],)".to_string()));
+        assert_eq!(extract_call("add_step_with(result, |actual| {"), None);
+        assert_eq!(extract_call("value + 1"), None);
+    }
+
+    #[test]
+    fn test_long_calls_are_truncated() {
+        let call = extract_call(&format!("to_equal(\"{}\")", "x".repeat(100))).unwrap();
+
+        assert_eq!(call.chars().count(), MAX_CALL_LENGTH);
+        assert!(call.ends_with('…'));
+    }
+
+    #[test]
+    fn test_matcher_call_reads_the_source() {
+        #[track_caller]
+        fn location() -> &'static Location<'static> {
+            return Location::caller();
+        }
+
+        let here = location();
+        assert_eq!(matcher_call(here), Some("location()".to_string()));
+
+        let (_, after_accents) = ("café → thé", location());
+        assert_eq!(matcher_call(after_accents), Some("location()".to_string()));
+    }
+}
@@ -0,0 +1,210 @@
+//! Type of the asserted value, shown in failure details
+//!
+//! `expect!` records the type name of its value and, for collections, how many items it holds. Failure details
+//! render them as `Vec<HashMap<String, _>> (3 items)`: module paths are removed and generic arguments nested more
+//! than two levels deep are collapsed to `_`, unless `TypeNames::Full` is configured.
+//!
+//! The item count relies on autoref-based dispatch: `expect!` calls `item_count` on a reference to an
+//! [`ItemProbe`], and the most specific of the implementations below that applies to the concrete value type wins.
+
+use crate::config::TypeNames;
+
+// Generic arguments nested deeper than this are collapsed to `_` in abbreviated names
+const MAX_GENERIC_DEPTH: usize = 2;
+
+/// Borrowed value whose number of items `expect!` asks for
+#[doc(hidden)]
+pub struct ItemProbe<'a, T>(pub &'a T);
+
+/// Item count of owned collections, such as `Vec<T>` or `HashMap<K, V>`
+#[doc(hidden)]
+pub trait OwnedItems {
+    fn item_count(&self) -> Option<usize>;
+}
+
+impl<T> OwnedItems for &&ItemProbe<'_, T>
+where
+    for<'b> &'b T: IntoIterator<IntoIter: ExactSizeIterator>,
+{
+    fn item_count(&self) -> Option<usize> {
+        Some(self.0.into_iter().len())
+    }
+}
+
+/// Item count of borrowed collections, such as `&Vec<T>` or `&[T]`
+#[doc(hidden)]
+pub trait BorrowedItems {
+    fn item_count(&self) -> Option<usize>;
+}
+
+impl<U: ?Sized> BorrowedItems for &ItemProbe<'_, &U>
+where
+    for<'b> &'b U: IntoIterator<IntoIter: ExactSizeIterator>,
+{
+    fn item_count(&self) -> Option<usize> {
+        Some((*self.0).into_iter().len())
+    }
+}
+
+/// Values that aren't collections have no item count
+#[doc(hidden)]
+pub trait NoItems {
+    fn item_count(&self) -> Option<usize>;
+}
+
+impl<T> NoItems for ItemProbe<'_, T> {
+    fn item_count(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Render a type name with its item count, abbreviated unless full names are requested
+pub(crate) fn describe(type_name: &str, item_count: Option<usize>, names: TypeNames) -> String {
+    let name = match names {
+        TypeNames::Abbreviated => abbreviate(type_name),
+        TypeNames::Full => type_name.to_string(),
+    };
+
+    return match item_count {
+        Some(1) => format!("{} (1 item)", name),
+        Some(count) => format!("{} ({} items)", name, count),
+        None => name,
+    };
+}
+
+/// Remove module paths and collapse deeply nested generic arguments: `Vec<HashMap<String, Vec<u8>>>` reads
+/// `Vec<HashMap<String, _>>`
+fn abbreviate(type_name: &str) -> String {
+    let unqualified = remove_module_paths(type_name);
+    let mut abbreviated = String::with_capacity(unqualified.len());
+    let mut depth = 0;
+    // Start of the current argument at the maximum depth, replaced by `_` when it turns out to be generic
+    let mut argument_start = 0;
+    let mut collapsing = false;
+
+    for c in unqualified.chars() {
+        if collapsing {
+            match c {
+                '<' => depth += 1,
+                '>' | ',' if depth == MAX_GENERIC_DEPTH => collapsing = false,
+                '>' => depth -= 1,
+                _ => {}
+            }
+            if collapsing {
+                continue;
+            }
+        }
+
+        match c {
+            '<' if depth == MAX_GENERIC_DEPTH => {
+                let spaces = abbreviated[argument_start..].len() - abbreviated[argument_start..].trim_start().len();
+                abbreviated.truncate(argument_start + spaces);
+                abbreviated.push('_');
+                depth += 1;
+                collapsing = true;
+            }
+            '<' => {
+                depth += 1;
+                abbreviated.push(c);
+                argument_start = abbreviated.len();
+            }
+            '>' => {
+                depth -= 1;
+                abbreviated.push(c);
+            }
+            ',' => {
+                abbreviated.push(c);
+                argument_start = abbreviated.len();
+            }
+            _ => abbreviated.push(c),
+        }
+    }
+
+    return abbreviated;
+}
+
+/// Remove the path segments of a type name, such as `alloc::vec::` in `alloc::vec::Vec<u8>`
+fn remove_module_paths(type_name: &str) -> String {
+    let mut unqualified = String::with_capacity(type_name.len());
+    let mut rest = type_name;
+
+    while let Some(c) = rest.chars().next() {
+        let identifier_length = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        if identifier_length == 0 {
+            unqualified.push(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+
+        let (identifier, after) = rest.split_at(identifier_length);
+        match after.strip_prefix("::") {
+            Some(after) => rest = after,
+            None => {
+                unqualified.push_str(identifier);
+                rest = after;
+            }
+        }
+    }
+
+    return unqualified;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_abbreviate() {
+        assert_eq!(abbreviate(std::any::type_name::<Vec<HashMap<String, Vec<u8>>>>()), "Vec<HashMap<String, _>>");
+        assert_eq!(abbreviate(std::any::type_name::<HashMap<String, i32>>()), "HashMap<String, i32>");
+        assert_eq!(abbreviate(std::any::type_name::<Option<Result<Vec<u8>, String>>>()), "Option<Result<_, String>>");
+        assert_eq!(abbreviate(std::any::type_name::<&[(u8, Option<String>)]>()), "&[(u8, Option<String>)]");
+        assert_eq!(abbreviate("i32"), "i32");
+        assert_eq!(
+            abbreviate("dyn core::ops::function::Fn(alloc::string::String, core::option::Option<i32>)"),
+            "dyn Fn(String, Option<i32>)"
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        let name = std::any::type_name::<Vec<HashMap<String, Vec<u8>>>>();
+
+        assert_eq!(describe(name, Some(3), TypeNames::Abbreviated), "Vec<HashMap<String, _>> (3 items)");
+        assert_eq!(describe(name, Some(1), TypeNames::Full), format!("{} (1 item)", name));
+        assert_eq!(describe("u64", None, TypeNames::Abbreviated), "u64");
+    }
+
+    // Probes the value the way `expect!` does, so the autoref steps match the macro expansion
+    macro_rules! item_count {
+        ($value:expr) => {
+            (&&&ItemProbe(&$value)).item_count()
+        };
+    }
+
+    #[test]
+    fn test_item_probe() {
+        let numbers = vec![1, 2, 3];
+        let scores = HashMap::from([("ada", 3)]);
+        let text = "four";
+
+        assert_eq!(item_count!(numbers), Some(3));
+        assert_eq!(item_count!(&numbers), Some(3));
+        assert_eq!(item_count!(numbers.as_slice()), Some(3));
+        assert_eq!(item_count!(scores), Some(1));
+        assert_eq!(item_count!([0u8; 4]), Some(4));
+        assert_eq!(item_count!(text), None);
+        assert_eq!(item_count!(42), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "contain 4 (got [1, 2, 3])\n  type: Vec<i32> (3 items)")]
+    fn test_panic_message_names_the_value_type() {
+        use crate::prelude::*;
+
+        let numbers = vec![1, 2, 3];
+        let _assertion = expect!(numbers).to_contain(4);
+        std::hint::black_box(_assertion);
+    }
+}
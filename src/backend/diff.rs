@@ -0,0 +1,358 @@
+//! Element-level diffs of sequences, maps and text
+//!
+//! Matchers comparing two whole values build a [`Diff`] and render it under the failed step, one line per
+//! element, instead of printing both values. Elements are aligned by position, key or line number, and runs of
+//! equal elements are collapsed so that long values stay readable. The diff is also kept in the sentence of the step,
+//! so that failure event handlers and external tools get the compared elements without parsing the rendered lines.
+
+use crate::backend::discovery::json_string;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Maximum number of rendered lines before the remaining differences are summarized
+const MAX_RENDERED_LINES: usize = 20;
+
+/// Where an element sits in the compared values
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Position {
+    /// Index in a sequence, rendered as `[1]`
+    Index(usize),
+    /// Key of a map entry, rendered as `["key"]`
+    Key(String),
+    /// One-based line number in a text, rendered as `line 2:`
+    Line(usize),
+}
+
+/// How an element of the actual value compares to the expected one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// The element is the expected one
+    Same,
+    /// The element differs from the expected one
+    Changed { expected: String, actual: String },
+    /// An expected element has no counterpart in the actual value
+    Missing { expected: String },
+    /// An element of the actual value has no expected counterpart
+    Unexpected { actual: String },
+}
+
+/// One compared element of a diff
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiffEntry {
+    pub position: Position,
+    pub change: Change,
+}
+
+/// Element-level comparison of an actual value with an expected one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Diff {
+    entries: Vec<DiffEntry>,
+}
+
+impl Diff {
+    /// Compare two sequences element by element, by position
+    pub fn sequence<A: Debug, E: Debug>(actual: &[A], expected: &[E], equal: impl Fn(&A, &E) -> bool) -> Self {
+        let entries = (0..actual.len().max(expected.len()))
+            .map(|index| {
+                let change = match (actual.get(index), expected.get(index)) {
+                    (Some(actual), Some(expected)) if equal(actual, expected) => Change::Same,
+                    (Some(actual), Some(expected)) => {
+                        Change::Changed { expected: format!("{:?}", expected), actual: format!("{:?}", actual) }
+                    }
+                    (None, Some(expected)) => Change::Missing { expected: format!("{:?}", expected) },
+                    (Some(actual), None) => Change::Unexpected { actual: format!("{:?}", actual) },
+                    (None, None) => unreachable!("index is within the longest sequence"),
+                };
+                DiffEntry { position: Position::Index(index), change }
+            })
+            .collect();
+
+        return Self { entries };
+    }
+
+    /// Compare two maps entry by entry, by key, listing the keys in their `Debug` order
+    pub fn keyed<K, A, E>(actual: &HashMap<K, A>, expected: &HashMap<K, E>, equal: impl Fn(&A, &E) -> bool) -> Self
+    where
+        K: Hash + Eq + Debug,
+        A: Debug,
+        E: Debug,
+    {
+        let mut keys = actual.keys().chain(expected.keys().filter(|key| !actual.contains_key(*key))).collect::<Vec<_>>();
+        keys.sort_by_cached_key(|key| format!("{:?}", key));
+
+        let entries = keys
+            .into_iter()
+            .map(|key| {
+                let change = match (actual.get(key), expected.get(key)) {
+                    (Some(actual), Some(expected)) if equal(actual, expected) => Change::Same,
+                    (Some(actual), Some(expected)) => {
+                        Change::Changed { expected: format!("{:?}", expected), actual: format!("{:?}", actual) }
+                    }
+                    (None, Some(expected)) => Change::Missing { expected: format!("{:?}", expected) },
+                    (Some(actual), None) => Change::Unexpected { actual: format!("{:?}", actual) },
+                    (None, None) => unreachable!("key comes from one of the maps"),
+                };
+                DiffEntry { position: Position::Key(format!("{:?}", key)), change }
+            })
+            .collect();
+
+        return Self { entries };
+    }
+
+    /// Compare two texts line by line, by line number
+    pub fn lines(actual: &str, expected: &str) -> Self {
+        let actual = actual.lines().collect::<Vec<_>>();
+        let expected = expected.lines().collect::<Vec<_>>();
+
+        let entries = (0..actual.len().max(expected.len()))
+            .map(|index| {
+                let change = match (actual.get(index), expected.get(index)) {
+                    (Some(actual), Some(expected)) if actual == expected => Change::Same,
+                    (Some(actual), Some(expected)) => {
+                        Change::Changed { expected: format!("`{}`", expected), actual: format!("`{}`", actual) }
+                    }
+                    (None, Some(expected)) => Change::Missing { expected: format!("`{}`", expected) },
+                    (Some(actual), None) => Change::Unexpected { actual: format!("`{}`", actual) },
+                    (None, None) => unreachable!("index is within the longest text"),
+                };
+                DiffEntry { position: Position::Line(index + 1), change }
+            })
+            .collect();
+
+        return Self { entries };
+    }
+
+    /// Compare two strings line by line from their `Debug` output, for matchers generic over the compared type
+    ///
+    /// `None` unless both values are written as string literals, such as `String` and `&str`, and one of them spans
+    /// several lines.
+    pub fn debug_lines(actual: &impl Debug, expected: &impl Debug) -> Option<Self> {
+        let actual = debug_text(&format!("{:?}", actual))?;
+        let expected = debug_text(&format!("{:?}", expected))?;
+        if !actual.contains('\n') && !expected.contains('\n') {
+            return None;
+        }
+
+        return Some(Self::lines(&actual, &expected));
+    }
+
+    /// Every compared element, in order
+    pub fn entries(&self) -> &[DiffEntry] {
+        return &self.entries;
+    }
+
+    /// Whether every element is the expected one
+    pub fn is_identical(&self) -> bool {
+        return self.entries.iter().all(|entry| entry.change == Change::Same);
+    }
+
+    /// Number of elements that differ, are missing or are unexpected
+    pub fn change_count(&self) -> usize {
+        return self.entries.iter().filter(|entry| entry.change != Change::Same).count();
+    }
+
+    /// Render the diff as detail lines, such as `[0] ok`, `[1] expected 4, got 5` and `[2] missing 3`
+    pub fn render(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut index = 0;
+
+        while index < self.entries.len() {
+            let entry = &self.entries[index];
+            if entry.change != Change::Same {
+                lines.push(format!("{} {}", render_position(&entry.position), render_change(&entry.change)));
+                index += 1;
+                continue;
+            }
+
+            // Collapse runs of equal elements
+            let run = self.entries[index..].iter().take_while(|entry| entry.change == Change::Same).count();
+            lines.push(format!("{} ok", render_run(&self.entries[index].position, &self.entries[index + run - 1].position, run)));
+            index += run;
+        }
+
+        if lines.len() > MAX_RENDERED_LINES {
+            let hidden = lines.len() - MAX_RENDERED_LINES;
+            lines.truncate(MAX_RENDERED_LINES);
+            lines.push(format!("... and {} more lines", hidden));
+        }
+
+        return lines;
+    }
+
+    /// Render the diff as a JSON object listing every compared element, such as
+    /// `{"entries":[{"position":{"index":1},"change":"changed","expected":"4","actual":"5"}]}`
+    pub fn to_json(&self) -> String {
+        let entries = self.entries.iter().map(entry_json).collect::<Vec<_>>();
+
+        return format!("{{\"entries\":[{}]}}", entries.join(","));
+    }
+}
+
+/// Text of a string literal written by a `Debug` implementation, with its escapes resolved, or `None` for other output
+fn debug_text(debug: &str) -> Option<String> {
+    let literal = debug.strip_prefix('"')?.strip_suffix('"')?;
+    let mut text = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+
+    while let Some(c) = chars.next() {
+        let c = match c {
+            // A quote that isn't escaped ends the literal before the end of the output
+            '"' => return None,
+            '\\' => match chars.next()? {
+                'n' => '\n',
+                'r' => '\r',
+                't' => '\t',
+                '0' => '\0',
+                'u' => {
+                    if chars.next()? != '{' {
+                        return None;
+                    }
+                    let hex = chars.by_ref().take_while(|c| *c != '}').collect::<String>();
+                    char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?
+                }
+                escaped @ ('\\' | '"' | '\'') => escaped,
+                _ => return None,
+            },
+            c => c,
+        };
+        text.push(c);
+    }
+
+    return Some(text);
+}
+
+/// Render a compared element as a JSON object
+fn entry_json(entry: &DiffEntry) -> String {
+    let position = match &entry.position {
+        Position::Index(index) => format!("{{\"index\":{}}}", index),
+        Position::Key(key) => format!("{{\"key\":{}}}", json_string(key)),
+        Position::Line(line) => format!("{{\"line\":{}}}", line),
+    };
+    let change = match &entry.change {
+        Change::Same => "\"change\":\"same\"".to_string(),
+        Change::Changed { expected, actual } => {
+            format!("\"change\":\"changed\",\"expected\":{},\"actual\":{}", json_string(expected), json_string(actual))
+        }
+        Change::Missing { expected } => format!("\"change\":\"missing\",\"expected\":{}", json_string(expected)),
+        Change::Unexpected { actual } => format!("\"change\":\"unexpected\",\"actual\":{}", json_string(actual)),
+    };
+
+    return format!("{{\"position\":{},{}}}", position, change);
+}
+
+/// Render the position of a single element
+fn render_position(position: &Position) -> String {
+    return match position {
+        Position::Index(index) => format!("[{}]", index),
+        Position::Key(key) => format!("[{}]", key),
+        Position::Line(line) => format!("line {}:", line),
+    };
+}
+
+/// Render the positions of a run of consecutive equal elements
+fn render_run(first: &Position, last: &Position, length: usize) -> String {
+    if length == 1 {
+        return render_position(first);
+    }
+
+    return match (first, last) {
+        (Position::Index(first), Position::Index(last)) => format!("[{}-{}]", first, last),
+        (Position::Line(first), Position::Line(last)) => format!("lines {}-{}:", first, last),
+        _ => format!("{} entries", length),
+    };
+}
+
+/// Render how an element changed
+fn render_change(change: &Change) -> String {
+    return match change {
+        Change::Same => "ok".to_string(),
+        Change::Changed { expected, actual } => format!("expected {}, got {}", expected, actual),
+        Change::Missing { expected } => format!("missing {}", expected),
+        Change::Unexpected { actual } => format!("unexpected {}", actual),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequence_diff_marks_each_element() {
+        let diff = Diff::sequence(&[1, 5], &[1, 4, 3], |actual, expected| actual == expected);
+
+        assert_eq!(diff.render(), vec!["[0] ok", "[1] expected 4, got 5", "[2] missing 3"]);
+        assert_eq!(diff.change_count(), 2);
+        assert!(!diff.is_identical());
+    }
+
+    #[test]
+    fn test_equal_runs_are_collapsed() {
+        let diff = Diff::sequence(&[1, 2, 3, 9, 5, 6], &[1, 2, 3, 4, 5, 6], |actual, expected| actual == expected);
+
+        assert_eq!(diff.render(), vec!["[0-2] ok", "[3] expected 4, got 9", "[4-5] ok"]);
+    }
+
+    #[test]
+    fn test_keyed_diff_is_sorted_by_key() {
+        let actual = HashMap::from([("b", 2), ("a", 1), ("d", 4)]);
+        let expected = HashMap::from([("a", 1), ("b", 3), ("c", 3)]);
+        let diff = Diff::keyed(&actual, &expected, |actual, expected| actual == expected);
+
+        assert_eq!(diff.render(), vec!["[\"a\"] ok", "[\"b\"] expected 3, got 2", "[\"c\"] missing 3", "[\"d\"] unexpected 4"]);
+    }
+
+    #[test]
+    fn test_debug_lines_only_compare_multi_line_strings() {
+        let diff = Diff::debug_lines(&"one\n\"two\"\tü", &String::from("one\n\"2\"\tü")).unwrap();
+
+        assert_eq!(diff.render(), vec!["line 1: ok", "line 2: expected `\"2\"\tü`, got `\"two\"\tü`"]);
+        assert_eq!(Diff::debug_lines(&"one line", &"other line"), None);
+        assert_eq!(Diff::debug_lines(&vec!["a\nb"], &vec!["a\nc"]), None);
+        assert_eq!(debug_text(&format!("{:?}", "\u{7f}\0\\'")), Some("\u{7f}\0\\'".to_string()));
+        assert_eq!(debug_text("\"a\", \"b\""), None);
+    }
+
+    #[test]
+    fn test_line_diff_quotes_lines() {
+        let diff = Diff::lines("one\ntwo\nthree\nfour", "one\n2\nthree");
+
+        assert_eq!(diff.render(), vec!["line 1: ok", "line 2: expected `2`, got `two`", "line 3: ok", "line 4: unexpected `four`"]);
+    }
+
+    #[test]
+    fn test_long_diffs_are_truncated() {
+        let actual = (0..50).collect::<Vec<_>>();
+        let expected = (0..50).map(|value| if value % 2 == 0 { value } else { -value }).collect::<Vec<_>>();
+        let lines = Diff::sequence(&actual, &expected, |actual, expected| actual == expected).render();
+
+        assert_eq!(lines.len(), MAX_RENDERED_LINES + 1);
+        assert_eq!(lines.last().unwrap(), "... and 30 more lines");
+    }
+
+    #[test]
+    fn test_diff_as_json() {
+        let diff = Diff::sequence(&[1, 5], &[1, 4, 3], |actual, expected| actual == expected);
+
+        assert_eq!(
+            diff.to_json(),
+            concat!(
+                "{\"entries\":[{\"position\":{\"index\":0},\"change\":\"same\"},",
+                "{\"position\":{\"index\":1},\"change\":\"changed\",\"expected\":\"4\",\"actual\":\"5\"},",
+                "{\"position\":{\"index\":2},\"change\":\"missing\",\"expected\":\"3\"}]}"
+            )
+        );
+        assert_eq!(
+            Diff::keyed(&HashMap::from([("a", 1)]), &HashMap::new(), |_: &i32, _: &i32| true).to_json(),
+            "{\"entries\":[{\"position\":{\"key\":\"\\\"a\\\"\"},\"change\":\"unexpected\",\"actual\":\"1\"}]}"
+        );
+    }
+
+    #[test]
+    fn test_identical_values() {
+        let diff = Diff::lines("same", "same");
+
+        assert!(diff.is_identical());
+        assert_eq!(diff.render(), vec!["line 1: ok"]);
+    }
+}
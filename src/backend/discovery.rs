@@ -0,0 +1,336 @@
+//! Test discovery registry
+//!
+//! Every function wrapped with `#[with_fixtures]` (directly or through `#[with_fixtures_module]`)
+//! registers a [`TestDescriptor`] at load time, and every fixture attribute registers its name for
+//! the module it belongs to. External tools can dump this registry as JSON to shard or select tests
+//! without running them:
+//!
+//! ```text
+//! REST_DISCOVERY_OUTPUT=tests.json cargo test --test my_suite
+//! ```
+//!
+//! When `REST_DISCOVERY_OUTPUT` is set, the test binary writes the registry to the given path (or
+//! to stdout when the value is `-`) and exits as soon as the first test starts a fixture-wrapped
+//! run or an assertion, or a custom harness calls [`dump_in_discovery_mode`]: no fixture, assertion
+//! or fixture-wrapped test body runs, whatever the harness filters.
+
+use crate::backend::run::current_run;
+use crate::backend::shard::{Shard, current_shard};
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::sync::{LazyLock, Mutex, Once};
+
+// Environment variable selecting where the discovery JSON is written
+const ENV_DISCOVERY_OUTPUT: &str = "REST_DISCOVERY_OUTPUT";
+
+/// Static description of a fixture-wrapped test, registered by `#[with_fixtures]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestDescriptor {
+    /// Module path the test is defined in
+    pub module: &'static str,
+    /// Function name of the test
+    pub name: &'static str,
+    /// Source file the test is defined in
+    pub file: &'static str,
+    /// Line of the test function name
+    pub line: u32,
+    /// Whether the test carries `#[ignore]`
+    pub ignored: bool,
+    /// Whether the test carries `#[should_panic]`
+    pub should_panic: bool,
+    /// Tags declared with `#[with_fixtures(tags = [...])]`
+    pub tags: &'static [&'static str],
+}
+
+impl TestDescriptor {
+    /// Full path of the test, as shown by the test harness
+    pub fn path(&self) -> String {
+        return format!("{}::{}", self.module, self.name);
+    }
+}
+
+/// Kind of fixture attached to a module
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FixtureKind {
+    BeforeAll,
+    Setup,
+    TearDown,
+    AfterAll,
+}
+
+impl FixtureKind {
+    /// Name of the fixture kind as used in the discovery output
+    pub fn as_str(&self) -> &'static str {
+        return match self {
+            FixtureKind::BeforeAll => "before_all",
+            FixtureKind::Setup => "setup",
+            FixtureKind::TearDown => "tear_down",
+            FixtureKind::AfterAll => "after_all",
+        };
+    }
+}
+
+static DISCOVERED_TESTS: LazyLock<Mutex<Vec<TestDescriptor>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Fixture names registered per module, tagged with their kind
+type FixtureNames = HashMap<&'static str, Vec<(FixtureKind, &'static str)>>;
+
+static FIXTURE_NAMES: LazyLock<Mutex<FixtureNames>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+static DISCOVERY_OUTPUT: LazyLock<Option<String>> = LazyLock::new(|| discovery_output_from_env(|key| env::var(key).ok()));
+
+static DUMP: Once = Once::new();
+
+/// Register a test for discovery
+///
+/// This is automatically called by the `#[with_fixtures]` attribute macro.
+pub fn register_test(descriptor: TestDescriptor) {
+    let mut tests = DISCOVERED_TESTS.lock().unwrap();
+    tests.push(descriptor);
+}
+
+/// Register the name of a fixture function for a module
+///
+/// This is automatically called by the fixture attribute macros.
+pub fn register_fixture(module_path: &'static str, kind: FixtureKind, name: &'static str) {
+    let mut fixtures = FIXTURE_NAMES.lock().unwrap();
+    fixtures.entry(module_path).or_default().push((kind, name));
+}
+
+/// All registered tests, sorted by module path then name
+pub fn discovered_tests() -> Vec<TestDescriptor> {
+    let mut tests = DISCOVERED_TESTS.lock().unwrap().clone();
+    tests.sort_by(|a, b| a.module.cmp(b.module).then(a.name.cmp(b.name)));
+    tests.dedup();
+    return tests;
+}
+
+/// Names of the fixtures of the given kind registered for a module, in registration order
+pub fn fixtures_for(module_path: &str, kind: FixtureKind) -> Vec<&'static str> {
+    let fixtures = FIXTURE_NAMES.lock().unwrap();
+    return fixtures
+        .get(module_path)
+        .map(|entries| entries.iter().filter(|(entry_kind, _)| *entry_kind == kind).map(|(_, name)| *name).collect())
+        .unwrap_or_default();
+}
+
+/// Render the discovery registry as a JSON document, along with the current run information
+pub fn discovery_json() -> String {
+    let tests = discovered_tests();
+    let mut json = format!("{{\"run\":{},\"tests\":[", current_run().to_json());
+
+    for (index, test) in tests.iter().enumerate() {
+        if index > 0 {
+            json.push(',');
+        }
+
+        let _ = write!(
+            json,
+            "{{\"path\":{},\"module\":{},\"name\":{},\"file\":{},\"line\":{},\"ignored\":{},\"should_panic\":{},\"tags\":{},\"fixtures\":{{",
+            json_string(&test.path()),
+            json_string(test.module),
+            json_string(test.name),
+            json_string(test.file),
+            test.line,
+            test.ignored,
+            test.should_panic,
+            json_array(test.tags.iter().copied()),
+        );
+
+        let kinds = [FixtureKind::BeforeAll, FixtureKind::Setup, FixtureKind::TearDown, FixtureKind::AfterAll];
+        for (kind_index, kind) in kinds.iter().enumerate() {
+            if kind_index > 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "\"{}\":{}", kind.as_str(), json_array(fixtures_for(test.module, *kind).into_iter()));
+        }
+
+        json.push_str("}}");
+    }
+
+    json.push_str("]}");
+    return json;
+}
+
+/// Number of registered tests this process doesn't run, as `(skipped, ignored)`
+///
+/// Tests carrying `#[ignore]` are ignored unless the harness was started with `--ignored` or `--include-ignored`,
+/// and the other tests assigned to another shard are skipped.
+pub fn tests_not_run() -> (usize, usize) {
+    return count_tests_not_run(&discovered_tests(), current_shard(), runs_ignored());
+}
+
+/// Registered tests of a module that this process runs, unless the harness also selects tests by name
+pub(crate) fn tests_run_in(module_path: &str) -> Vec<TestDescriptor> {
+    let (shard, runs_ignored) = (current_shard(), runs_ignored());
+    return discovered_tests()
+        .into_iter()
+        .filter(|test| test.module == module_path)
+        .filter(|test| runs_ignored || !test.ignored)
+        .filter(|test| shard.is_none_or(|shard| shard.contains(test.module, test.name)))
+        .collect();
+}
+
+/// Check if the harness was started with `--ignored` or `--include-ignored`
+fn runs_ignored() -> bool {
+    return env::args().any(|arg| arg == "--ignored" || arg == "--include-ignored");
+}
+
+/// Count the tests that are skipped by the shard selection and the ignored ones
+fn count_tests_not_run(tests: &[TestDescriptor], shard: Option<Shard>, runs_ignored: bool) -> (usize, usize) {
+    let ignored = if runs_ignored { 0 } else { tests.iter().filter(|test| test.ignored).count() };
+    let skipped = tests
+        .iter()
+        .filter(|test| runs_ignored || !test.ignored)
+        .filter(|test| shard.is_some_and(|shard| !shard.contains(test.module, test.name)))
+        .count();
+
+    return (skipped, ignored);
+}
+
+/// Check if the test binary was started in discovery mode
+pub fn is_discovery_mode() -> bool {
+    return DISCOVERY_OUTPUT.is_some();
+}
+
+/// Write the discovery registry to the configured output and exit, when the binary was started in discovery mode
+///
+/// Called as the first fixture-wrapped test or assertion starts, once every test has been registered, and by
+/// [`check_test_order`](crate::backend::order::check_test_order). Custom harnesses call it before running any test.
+pub fn dump_in_discovery_mode() {
+    let Some(output) = DISCOVERY_OUTPUT.as_deref() else {
+        return;
+    };
+
+    DUMP.call_once(|| {
+        let json = discovery_json();
+        let result = match output {
+            "-" => {
+                // Write to the raw stdout handle so the test harness output capture doesn't swallow it
+                let mut stdout = std::io::stdout().lock();
+                stdout.write_all(json.as_bytes()).and_then(|_| stdout.write_all(b"\n")).and_then(|_| stdout.flush())
+            }
+            path => std::fs::write(path, json),
+        };
+
+        if let Err(err) = result {
+            eprintln!("ERROR: Failed to write test discovery output: {}", err);
+            std::process::exit(1);
+        }
+
+        std::process::exit(0);
+    });
+
+    // Another thread is already writing the output and will exit the process
+    loop {
+        std::thread::park();
+    }
+}
+
+/// Read the discovery output target through the provided closure
+fn discovery_output_from_env(get_var: impl Fn(&str) -> Option<String>) -> Option<String> {
+    return get_var(ENV_DISCOVERY_OUTPUT).filter(|value| !value.trim().is_empty());
+}
+
+/// Encode a string as a JSON string literal
+pub(crate) fn json_string(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len() + 2);
+    encoded.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => encoded.push_str("\\\""),
+            '\\' => encoded.push_str("\\\\"),
+            '\n' => encoded.push_str("\\n"),
+            '\r' => encoded.push_str("\\r"),
+            '\t' => encoded.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(encoded, "\\u{:04x}", c as u32);
+            }
+            c => encoded.push(c),
+        }
+    }
+
+    encoded.push('"');
+    return encoded;
+}
+
+/// Encode a list of strings as a JSON array
+fn json_array<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    return format!("[{}]", values.map(json_string).collect::<Vec<_>>().join(","));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string_escaping() {
+        assert_eq!(json_string("plain"), "\"plain\"");
+        assert_eq!(json_string("a \"quoted\" \\ path"), "\"a \\\"quoted\\\" \\\\ path\"");
+        assert_eq!(json_string("line\nbreak\u{1}"), "\"line\\nbreak\\u0001\"");
+    }
+
+    #[test]
+    fn test_discovery_output_from_env() {
+        assert_eq!(discovery_output_from_env(|_| None), None);
+        assert_eq!(discovery_output_from_env(|_| Some("  ".into())), None);
+        assert_eq!(discovery_output_from_env(|_| Some("-".into())), Some("-".to_string()));
+        assert_eq!(discovery_output_from_env(|_| Some("tests.json".into())), Some("tests.json".to_string()));
+    }
+
+    #[test]
+    fn test_count_tests_not_run() {
+        let test = |name: &'static str, ignored: bool| {
+            return TestDescriptor {
+                module: "discovery::counts",
+                name,
+                file: "src/counts.rs",
+                line: 1,
+                ignored,
+                should_panic: false,
+                tags: &[],
+            };
+        };
+        let names = ["test_a", "test_b", "test_c", "test_d", "test_e", "test_f", "test_g", "test_h", "test_i", "test_j"];
+        let tests: Vec<TestDescriptor> = names.iter().enumerate().map(|(index, name)| test(name, index < 3)).collect();
+
+        assert_eq!(count_tests_not_run(&tests, None, false), (0, 3));
+        assert_eq!(count_tests_not_run(&tests, None, true), (0, 0));
+
+        // Every test not ignored runs in exactly one of the shards
+        let shards: Vec<Shard> = (1..=3).map(|index| Shard::new(index, 3).unwrap()).collect();
+        let skipped: usize = shards.iter().map(|shard| count_tests_not_run(&tests, Some(*shard), false).0).sum();
+        assert_eq!(skipped, 7 * 2);
+    }
+
+    #[test]
+    fn test_registered_tests_are_dumped_with_fixtures() {
+        register_fixture("discovery::sample", FixtureKind::Setup, "reset_state");
+        register_fixture("discovery::sample", FixtureKind::TearDown, "cleanup");
+        register_test(TestDescriptor {
+            module: "discovery::sample",
+            name: "test_sample",
+            file: "src/sample.rs",
+            line: 12,
+            ignored: true,
+            should_panic: false,
+            tags: &["slow", "db"],
+        });
+
+        let tests = discovered_tests();
+        let sample = tests.iter().find(|test| test.name == "test_sample").unwrap();
+        assert_eq!(sample.path(), "discovery::sample::test_sample");
+        assert_eq!(fixtures_for("discovery::sample", FixtureKind::Setup), vec!["reset_state"]);
+        assert!(fixtures_for("discovery::sample", FixtureKind::BeforeAll).is_empty());
+
+        let json = discovery_json();
+        assert!(json.contains(
+            "{\"path\":\"discovery::sample::test_sample\",\"module\":\"discovery::sample\",\"name\":\"test_sample\",\
+             \"file\":\"src/sample.rs\",\"line\":12,\"ignored\":true,\"should_panic\":false,\"tags\":[\"slow\",\"db\"],\
+             \"fixtures\":{\"before_all\":[],\"setup\":[\"reset_state\"],\"tear_down\":[\"cleanup\"],\"after_all\":[]}}"
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+//! Tests expected to fail, marked `#[expected_failure(reason = "...")]`
+//!
+//! A marked test still runs. Its failure, whether a failed assertion, another panic or a returned error, is recorded
+//! as an expected failure instead of failing the test, and listed with its reason in the session summary. A marked
+//! test that passes fails loudly instead, so that the marker of a fixed bug gets removed.
+//!
+//! Assertions of a marked test are neither reported nor counted, as if captured by `expect_failure!`.
+
+use crate::backend::Assertion;
+use crate::backend::assertions::capture::capture_failure;
+use crate::backend::assertions::report_and_panic;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::fixtures::TestOutput;
+use crate::backend::matchers::failure::panic_message;
+use std::sync::{LazyLock, Mutex};
+
+/// Test that failed as expected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedFailureRecord {
+    /// Full path of the test
+    pub test: String,
+    /// Reason given to `#[expected_failure]`, such as the issue tracking the bug
+    pub reason: String,
+    /// How the test failed: the message of the failed assertion or the panic, or the returned error
+    pub failure: String,
+}
+
+static EXPECTED_FAILURES: LazyLock<Mutex<Vec<ExpectedFailureRecord>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Tests that failed as expected so far, as listed in the summary
+pub fn expected_failures() -> Vec<ExpectedFailureRecord> {
+    return EXPECTED_FAILURES.lock().unwrap().clone();
+}
+
+/// Run the body of a test expected to fail, returning normally when it fails and failing when it passes
+///
+/// This is automatically called by the `#[expected_failure]` attribute macro.
+pub fn run_expected_failure<R: TestOutput>(test: &str, reason: &str, body: impl FnOnce() -> R) -> R {
+    // The assertions of the body aren't counted, which a fixture runner around it must not mistake for a vacuous test
+    crate::backend::assertions::count::allow_no_assertions();
+
+    let (result, failure) = capture_failure(body);
+    let failure = match (result, failure) {
+        (Ok(output), _) if output.passed() => unexpected_pass(test, reason),
+        (Ok(_), _) => "returned an error".to_string(),
+        (Err(_), Some(failure)) => failure.format_stable_message(),
+        (Err(payload), None) => format!("panicked with {:?}", panic_message(&payload)),
+    };
+
+    EXPECTED_FAILURES.lock().unwrap().push(ExpectedFailureRecord { test: test.to_string(), reason: reason.to_string(), failure });
+    return R::not_run();
+}
+
+/// Fail a test expected to fail that passed
+fn unexpected_pass(test: &str, reason: &str) -> ! {
+    let sentence = AssertionSentence::new("fail", format!("as expected ({})", reason))
+        .with_actual("passed")
+        .with_detail("remove #[expected_failure] now that the test passes");
+    let assertion = Assertion::new((), "test").with_context(test.to_string()).add_step(sentence, false);
+
+    report_and_panic(assertion, format!("{} passed but is marked #[expected_failure(reason = {:?})]", test, reason));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_failures_are_recorded_with_their_reason() {
+        let limit = 3;
+        run_expected_failure("suite::test_limit", "issue #12", || {
+            expect!(limit).to_be_greater_than(5);
+        });
+        let output = run_expected_failure("suite::test_parse", "issue #13", || "x".parse::<u8>().map(|_| ()));
+
+        assert!(output.is_ok());
+        let failures = expected_failures();
+        assert!(failures.contains(&ExpectedFailureRecord {
+            test: "suite::test_limit".to_string(),
+            reason: "issue #12".to_string(),
+            failure: "expected limit to be greater than 5, got 3".to_string(),
+        }));
+        assert!(failures.iter().any(|record| record.test == "suite::test_parse" && record.failure == "returned an error"));
+    }
+
+    #[test]
+    #[should_panic(expected = "suite::test_fixed, fail as expected (issue #14) (got passed)")]
+    fn test_unexpected_pass_fails() {
+        run_expected_failure("suite::test_fixed", "issue #14", || {
+            expect!(2 + 2).to_equal(4);
+        });
+    }
+}
@@ -4,10 +4,16 @@
 //! It works with procedural macros to provide a clean API for setting up and tearing
 //! down test environments.
 
+pub mod progress;
+
+use progress::TestCompletion;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::future::{self, Future};
 use std::panic::{self, AssertUnwindSafe};
 use std::sync::{LazyLock, Mutex};
+use std::task::Poll;
+use std::thread;
 
 /// Simple fixture registration system that uses a global hashmap instead of inventory
 pub type FixtureFunc = Box<dyn Fn() + Send + Sync + 'static>;
@@ -63,13 +69,77 @@ thread_local! {
     static IN_FIXTURE_TEST: RefCell<bool> = const { RefCell::new(false) };
 }
 
-/// Run a test function with appropriate setup and teardown
+/// Value returned by a fixture-wrapped test, handed back to the test harness
+///
+/// Tests return `()` or a `Result` whose error fails the test, as `#[test]` functions do.
+#[diagnostic::on_unimplemented(
+    message = "`#[with_fixtures]` tests can't return `{Self}`",
+    label = "return `()` or a `Result<(), E>` from the test",
+    note = "the fixture runner hands the value back to the test harness, which only accepts these"
+)]
+pub trait TestOutput {
+    /// Value of a test that didn't run, such as a test of another shard
+    fn not_run() -> Self;
+
+    /// Whether the test passed, so that its assertions are checked
+    fn passed(&self) -> bool;
+}
+
+impl TestOutput for () {
+    fn not_run() -> Self {}
+
+    fn passed(&self) -> bool {
+        return true;
+    }
+}
+
+impl<T: TestOutput, E> TestOutput for Result<T, E> {
+    fn not_run() -> Self {
+        return Ok(T::not_run());
+    }
+
+    fn passed(&self) -> bool {
+        return self.as_ref().is_ok_and(|output| output.passed());
+    }
+}
+
+/// Run a test function with appropriate setup and teardown, returning what it returned
+pub fn run_test_with_fixtures<F, R>(module_path: &'static str, test_fn: AssertUnwindSafe<F>) -> R
+where
+    F: FnOnce() -> R,
+{
+    begin_test(module_path);
+    let result = panic::catch_unwind(test_fn);
+    return end_test(module_path, result);
+}
+
+/// Run an async test with appropriate setup and teardown, returning what it returned
 ///
-/// This is automatically called by the `#[with_fixtures]` attribute macro.
-pub fn run_test_with_fixtures<F>(module_path: &'static str, test_fn: AssertUnwindSafe<F>)
+/// Fixtures run synchronously on the thread polling the test, before its first poll and after its completion.
+pub async fn run_async_test_with_fixtures<Fut>(module_path: &'static str, test: Fut) -> Fut::Output
 where
-    F: FnOnce(),
+    Fut: Future,
 {
+    begin_test(module_path);
+
+    // Poll the test, capturing any panics, as `catch_unwind` does for the synchronous ones
+    let mut test = Box::pin(test);
+    let result = future::poll_fn(|cx| {
+        return match panic::catch_unwind(AssertUnwindSafe(|| test.as_mut().poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(err) => Poll::Ready(Err(err)),
+        };
+    })
+    .await;
+
+    return end_test(module_path, result);
+}
+
+/// Prepare the current thread for a test and run the fixtures preceding it
+fn begin_test(module_path: &'static str) {
+    // In discovery mode, list the registered tests instead of running them
+    crate::backend::discovery::dump_in_discovery_mode();
+
     // Set the fixture test flag
     IN_FIXTURE_TEST.with(|flag| {
         *flag.borrow_mut() = true;
@@ -88,8 +158,13 @@ where
         }
     }
 
-    // Run the test function, capturing any panics
-    let result = panic::catch_unwind(test_fn);
+    // The panic hooks set by the test are removed by `end_test`
+    crate::backend::panic_hook::install();
+}
+
+/// Run the fixtures following a test, then return its output or re-throw its panic
+fn end_test<R>(module_path: &'static str, result: thread::Result<R>) -> R {
+    crate::backend::panic_hook::end_test();
 
     // Always run teardown, even if the test panics
     if let Ok(fixtures) = TEARDOWN_FIXTURES.lock()
@@ -110,9 +185,100 @@ where
     register_after_all_handler(module_path);
 
     // Re-throw any panic that occurred during the test
-    if let Err(err) = result {
-        panic::resume_unwind(err);
+    return match result {
+        Ok(output) => output,
+        Err(err) => panic::resume_unwind(err),
+    };
+}
+
+/// Run a named test with its module fixtures, honoring the current shard selection
+///
+/// This is automatically called by the `#[with_fixtures]` attribute macro. Tests assigned to
+/// another shard return immediately without running any fixture.
+pub fn run_named_test_with_fixtures<F, R>(module_path: &'static str, test_name: &'static str, test_fn: AssertUnwindSafe<F>) -> R
+where
+    F: FnOnce() -> R,
+    R: TestOutput,
+{
+    if !should_run(module_path, test_name) {
+        return R::not_run();
+    }
+
+    // Measure the memory used by the test body alone, without its fixtures
+    #[cfg(feature = "memory-tracking")]
+    let test_fn = AssertUnwindSafe(move || crate::backend::memory::measure(module_path, test_name, test_fn.0));
+
+    // Every fixture-wrapped test gets its own metrics capture, and log capture when enabled, fixtures included
+    #[cfg(feature = "log-capture")]
+    let _logs = crate::config::is_capturing_logs().then(crate::backend::logs::capture_logs);
+    #[cfg(feature = "metrics-capture")]
+    let _metrics = crate::backend::metrics::capture_metrics();
+
+    // Counted for the completion of the module even when the test panics
+    let mut completion = TestCompletion::track(module_path, test_name);
+
+    let executed = crate::backend::assertions::count::executed_assertions();
+    let output = run_test_with_fixtures(module_path, test_fn);
+
+    // Only reached when the test didn't panic
+    completion.returned(&output);
+    check_assertions(module_path, test_name, executed, &output);
+    return output;
+}
+
+/// Run a named async test with its module fixtures, honoring the current shard selection
+///
+/// This is automatically called by the `#[with_fixtures]` attribute macro on `async` tests. The test body is polled
+/// on the thread of the runtime's `block_on`, where logs, metrics and assertions are captured and counted; the memory
+/// usage of async tests isn't recorded.
+pub async fn run_named_async_test_with_fixtures<Fut>(module_path: &'static str, test_name: &'static str, test: Fut) -> Fut::Output
+where
+    Fut: Future,
+    Fut::Output: TestOutput,
+{
+    if !should_run(module_path, test_name) {
+        return Fut::Output::not_run();
+    }
+
+    #[cfg(feature = "log-capture")]
+    let _logs = crate::config::is_capturing_logs().then(crate::backend::logs::capture_logs);
+    #[cfg(feature = "metrics-capture")]
+    let _metrics = crate::backend::metrics::capture_metrics();
+
+    let mut completion = TestCompletion::track(module_path, test_name);
+
+    let executed = crate::backend::assertions::count::executed_assertions();
+    let output = run_async_test_with_fixtures(module_path, test).await;
+
+    completion.returned(&output);
+    check_assertions(module_path, test_name, executed, &output);
+    return output;
+}
+
+/// Check if a named test runs in this process, reporting it as skipped when it belongs to another shard
+fn should_run(module_path: &'static str, test_name: &'static str) -> bool {
+    // Discovery lists every test, whatever the shard
+    crate::backend::discovery::dump_in_discovery_mode();
+
+    let Some(shard) = crate::backend::shard::current_shard() else {
+        return true;
+    };
+    if shard.contains(module_path, test_name) {
+        return true;
     }
+
+    crate::Reporter::test_skipped(module_path, test_name, shard);
+    return false;
+}
+
+/// Check the number of assertions of a test that completed, unless it failed by returning an error
+fn check_assertions(module_path: &'static str, test_name: &'static str, executed_before: usize, output: &impl TestOutput) {
+    if !output.passed() {
+        return;
+    }
+
+    let executed = crate::backend::assertions::count::executed_assertions() - executed_before;
+    crate::backend::assertions::count::check_test_assertions(format!("{}::{}", module_path, test_name), executed);
 }
 
 /// Run before_all fixtures for a module if they haven't been run yet
@@ -0,0 +1,112 @@
+//! Completion of the modules of fixture-wrapped tests
+//!
+//! Every `#[with_fixtures]` test records its outcome for its module. When the last registered test of a module that
+//! this process runs completes, the reporter prints the module's progress line and emits a `ModuleCompleted` event.
+//! Tests selected by name on the harness command line leave their modules incomplete, without progress lines.
+
+use crate::backend::discovery;
+use crate::backend::fixtures::TestOutput;
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+use std::thread;
+
+/// Outcomes of the tests of a completed module
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModuleStats {
+    /// Number of tests that passed
+    pub passed: usize,
+    /// Number of tests that failed, by panicking or returning an error
+    pub failed: usize,
+}
+
+/// Tests of a module run so far, against the registered tests the process runs
+struct ModuleProgress {
+    expected: usize,
+    should_panic: HashSet<&'static str>,
+    stats: ModuleStats,
+}
+
+impl ModuleProgress {
+    /// Progress of a module before its first test completes
+    fn start(module_path: &str) -> Self {
+        let tests = discovery::tests_run_in(module_path);
+        let should_panic = tests.iter().filter(|test| test.should_panic).map(|test| test.name).collect();
+        return Self { expected: tests.len(), should_panic, stats: ModuleStats::default() };
+    }
+}
+
+static MODULE_PROGRESS: LazyLock<Mutex<HashMap<&'static str, ModuleProgress>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Record the outcome of a test, returning the stats of its module when it was the last test left to run
+fn record(module_path: &'static str, test_name: &'static str, panicked: bool, returned_error: bool) -> Option<ModuleStats> {
+    let mut modules = MODULE_PROGRESS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let progress = modules.entry(module_path).or_insert_with(|| ModuleProgress::start(module_path));
+
+    // `#[should_panic]` tests pass by panicking, and can't return errors
+    if panicked == progress.should_panic.contains(test_name) && !returned_error {
+        progress.stats.passed += 1;
+    } else {
+        progress.stats.failed += 1;
+    }
+
+    let completed = progress.stats.passed + progress.stats.failed;
+    return (completed == progress.expected).then_some(progress.stats);
+}
+
+/// Guard recording the outcome of a fixture-wrapped test when it ends, whether it returns or panics
+pub(crate) struct TestCompletion {
+    module_path: &'static str,
+    test_name: &'static str,
+    returned_error: bool,
+}
+
+impl TestCompletion {
+    /// Start tracking a test
+    pub(crate) fn track(module_path: &'static str, test_name: &'static str) -> Self {
+        return Self { module_path, test_name, returned_error: false };
+    }
+
+    /// Record the value returned by the test, which fails it when it is an error
+    pub(crate) fn returned(&mut self, output: &impl TestOutput) {
+        self.returned_error = !output.passed();
+    }
+}
+
+impl Drop for TestCompletion {
+    fn drop(&mut self) {
+        if let Some(stats) = record(self.module_path, self.test_name, thread::panicking(), self.returned_error) {
+            crate::Reporter::module_completed(self.module_path, stats);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestDescriptor;
+
+    fn register(module: &'static str, name: &'static str, ignored: bool, should_panic: bool) {
+        discovery::register_test(TestDescriptor { module, name, file: "src/progress.rs", line: 1, ignored, should_panic, tags: &[] });
+    }
+
+    #[test]
+    fn test_module_completes_with_its_last_test() {
+        register("progress::sample", "test_a", false, false);
+        register("progress::sample", "test_b", false, true);
+        register("progress::sample", "test_c", false, false);
+        register("progress::sample", "test_ignored", true, false);
+
+        assert_eq!(record("progress::sample", "test_a", false, false), None);
+        assert_eq!(record("progress::sample", "test_b", true, false), None);
+        assert_eq!(record("progress::sample", "test_c", false, true), Some(ModuleStats { passed: 2, failed: 1 }));
+    }
+
+    #[test]
+    fn test_should_panic_tests_fail_without_panicking() {
+        register("progress::panics", "test_panics", false, true);
+        register("progress::panics", "test_returns", false, true);
+
+        assert_eq!(record("progress::panics", "test_panics", true, false), None);
+        assert_eq!(record("progress::panics", "test_returns", false, false), Some(ModuleStats { passed: 1, failed: 1 }));
+    }
+}
@@ -0,0 +1,302 @@
+//! Per-test capture of log records
+//!
+//! Available with the `log-capture` feature. Records emitted through the `log` crate and events
+//! emitted through `tracing` are captured per test thread while a capture is active. With
+//! `config().capture_logs(true)` or `REST_CAPTURE_LOGS=1`, every fixture-wrapped test gets its own
+//! capture; other tests start one with [`capture_logs`].
+//! `expect_logs!()` then asserts on what was captured so far:
+//!
+//! ```ignore
+//! let _logs = capture_logs();
+//! client.fetch();
+//! expect_logs!().to_contain_entry(Level::Warn, "retrying");
+//! ```
+//!
+//! Capturing `log` records installs a global logger, so it only works when the test binary doesn't
+//! install one itself, and a capture sets the `tracing` default subscriber of its thread, which hides
+//! the global subscriber while it is active. Records emitted on threads spawned by a test are not
+//! captured.
+
+use std::cell::RefCell;
+use std::fmt::{self, Debug, Display};
+use std::sync::Once;
+use tracing::field::{Field, Visit};
+use tracing::span;
+
+static LOGGER: Once = Once::new();
+
+thread_local! {
+    // Entries captured on this thread, `None` when no capture is active
+    static CAPTURED: RefCell<Option<Vec<LogEntry>>> = const { RefCell::new(None) };
+}
+
+/// Severity of a captured entry, shared by `log` records and `tracing` events
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        return f.write_str(name);
+    }
+}
+
+impl From<log::Level> for Level {
+    fn from(level: log::Level) -> Self {
+        return match level {
+            log::Level::Error => Level::Error,
+            log::Level::Warn => Level::Warn,
+            log::Level::Info => Level::Info,
+            log::Level::Debug => Level::Debug,
+            log::Level::Trace => Level::Trace,
+        };
+    }
+}
+
+impl From<&tracing::Level> for Level {
+    fn from(level: &tracing::Level) -> Self {
+        return match *level {
+            tracing::Level::ERROR => Level::Error,
+            tracing::Level::WARN => Level::Warn,
+            tracing::Level::INFO => Level::Info,
+            tracing::Level::DEBUG => Level::Debug,
+            _ => Level::Trace,
+        };
+    }
+}
+
+/// A captured log record or tracing event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    pub level: Level,
+    /// Module path or explicit target of the record
+    pub target: String,
+    /// Formatted message; structured tracing fields follow it as `key=value`
+    pub message: String,
+}
+
+impl Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "[{} {}] {}", self.level, self.target, self.message);
+    }
+}
+
+/// Entries captured on the current thread, as asserted by `expect_logs!()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CapturedLogs {
+    entries: Vec<LogEntry>,
+}
+
+impl CapturedLogs {
+    /// The captured entries, oldest first
+    pub fn entries(&self) -> &[LogEntry] {
+        return &self.entries;
+    }
+}
+
+/// Guard keeping a log capture active on the current thread until dropped
+///
+/// Nested captures start empty and restore the enclosing capture when dropped.
+pub struct LogCapture {
+    enclosing: Option<Vec<LogEntry>>,
+    _tracing: tracing::subscriber::DefaultGuard,
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        let enclosing = self.enclosing.take();
+        let _ = CAPTURED.try_with(|captured| *captured.borrow_mut() = enclosing);
+    }
+}
+
+/// Start capturing the log records and tracing events of the current thread
+pub fn capture_logs() -> LogCapture {
+    install_logger();
+
+    let enclosing = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let tracing = tracing::subscriber::set_default(CaptureSubscriber);
+
+    return LogCapture { enclosing, _tracing: tracing };
+}
+
+/// Entries captured so far by the active capture of the current thread
+pub fn captured_logs() -> CapturedLogs {
+    let entries = CAPTURED.with(|captured| captured.borrow().clone()).unwrap_or_default();
+    return CapturedLogs { entries };
+}
+
+/// Check if a capture is active on the current thread
+fn is_capturing() -> bool {
+    return CAPTURED.try_with(|captured| captured.try_borrow().map(|entries| entries.is_some()).unwrap_or(false)).unwrap_or(false);
+}
+
+/// Add an entry to the active capture, if any
+fn record_entry(entry: LogEntry) {
+    let _ = CAPTURED.try_with(|captured| {
+        if let Ok(mut captured) = captured.try_borrow_mut()
+            && let Some(entries) = captured.as_mut()
+        {
+            entries.push(entry);
+        }
+    });
+}
+
+/// Install the capturing `log` logger once, unless the binary already installed one
+fn install_logger() {
+    LOGGER.call_once(|| {
+        if log::set_boxed_logger(Box::new(CaptureLogger)).is_ok() {
+            log::set_max_level(log::LevelFilter::Trace);
+        }
+    });
+}
+
+/// `log` logger recording into the capture of the calling thread
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        return is_capturing();
+    }
+
+    fn log(&self, record: &log::Record) {
+        if is_capturing() {
+            record_entry(LogEntry {
+                level: record.level().into(),
+                target: record.target().to_string(),
+                message: record.args().to_string(),
+            });
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// `tracing` subscriber recording events into the capture of the calling thread
+///
+/// Spans are accepted but not recorded; only events become entries.
+struct CaptureSubscriber;
+
+impl tracing::Subscriber for CaptureSubscriber {
+    fn enabled(&self, _metadata: &tracing::Metadata<'_>) -> bool {
+        return true;
+    }
+
+    fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        return span::Id::from_u64(1);
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &tracing::Event<'_>) {
+        let mut visitor = EventVisitor::default();
+        event.record(&mut visitor);
+
+        let mut message = visitor.message;
+        for field in visitor.fields {
+            if !message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&field);
+        }
+
+        let metadata = event.metadata();
+        record_entry(LogEntry { level: metadata.level().into(), target: metadata.target().to_string(), message });
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+/// Collects the message and the other fields of a tracing event
+#[derive(Default)]
+struct EventVisitor {
+    message: String,
+    fields: Vec<String>,
+}
+
+impl Visit for EventVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.push(format!("{}={}", field.name(), value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.push(format!("{}={:?}", field.name(), value));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_records_are_captured() {
+        let _logs = capture_logs();
+
+        log::warn!("retrying in {}s", 5);
+        log::info!(target: "billing", "invoice sent");
+
+        let entries = captured_logs().entries().to_vec();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, Level::Warn);
+        assert_eq!(entries[0].message, "retrying in 5s");
+        assert_eq!(entries[1].to_string(), "[INFO billing] invoice sent");
+    }
+
+    #[test]
+    fn test_tracing_events_are_captured_with_fields() {
+        let _logs = capture_logs();
+
+        tracing::error!(attempt = 3, "upload failed");
+        tracing::debug!("connected");
+
+        let entries = captured_logs().entries().to_vec();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].level, Level::Error);
+        assert_eq!(entries[0].message, "upload failed attempt=3");
+        assert_eq!(entries[1].message, "connected");
+    }
+
+    #[test]
+    fn test_nothing_is_captured_without_capture() {
+        log::warn!("not captured");
+        assert!(captured_logs().entries().is_empty());
+    }
+
+    #[test]
+    fn test_nested_capture_restores_the_enclosing_one() {
+        let _outer = capture_logs();
+        log::warn!("outer");
+
+        {
+            let _inner = capture_logs();
+            log::warn!("inner");
+            assert_eq!(captured_logs().entries().len(), 1);
+        }
+
+        let messages = captured_logs().entries().iter().map(|entry| entry.message.clone()).collect::<Vec<_>>();
+        assert_eq!(messages, vec!["outer"]);
+    }
+}
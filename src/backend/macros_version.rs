@@ -0,0 +1,42 @@
+//! Version check of the code generated by `rest-macros`
+//!
+//! The macros are re-exported by this crate with its `macros` feature, pinned to the same version. Code generated
+//! by a `rest-macros` of another version, such as one added as a separate dependency, fails to compile with a message
+//! saying so, instead of obscure errors about the functions it calls.
+
+/// Fail the compilation unless the macros generating the call were built from the same version as this crate
+///
+/// This is automatically called in a constant by every macro of `rest-macros`.
+pub const fn check_macros_version(macros_version: &str) {
+    let (expected, actual) = (env!("CARGO_PKG_VERSION").as_bytes(), macros_version.as_bytes());
+    let mut matches = expected.len() == actual.len();
+    let mut index = 0;
+    while matches && index < expected.len() {
+        matches = expected[index] == actual[index];
+        index += 1;
+    }
+
+    if !matches {
+        panic!(concat!(
+            "rest-macros doesn't match rest ",
+            env!("CARGO_PKG_VERSION"),
+            ": use the macros re-exported by rest with its `macros` feature instead of depending on rest-macros"
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_version_compiles() {
+        const _: () = check_macros_version(env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    #[should_panic(expected = "rest-macros doesn't match rest")]
+    fn test_other_version_fails() {
+        check_macros_version("0.0.1");
+    }
+}
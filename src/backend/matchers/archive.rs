@@ -0,0 +1,388 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use flate2::read::GzDecoder;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::fs;
+use std::io::{self, Cursor, Read};
+use std::path::{Path, PathBuf};
+
+// Leading bytes of zip archives, with entries or empty, and of gzip streams
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+const EMPTY_ZIP_MAGIC: &[u8] = b"PK\x05\x06";
+const GZIP_MAGIC: &[u8] = b"\x1f\x8b";
+
+// Number of entry names listed under a failed step
+const LISTED_ENTRIES: usize = 10;
+
+// Number of characters of an entry shown as the actual content of a failed step
+const CONTENT_PREVIEW: usize = 80;
+
+/// Trait for assertions on the content of zip and tar archives.
+///
+/// Available with the `archive` feature, for archives read from a `Path` or `PathBuf`, or held in byte buffers. Tar
+/// archives may be gzipped. Only file entries are considered, directories aside; the archive is read once for all the
+/// steps of a chain.
+pub trait ArchiveMatchers {
+    /// Check that the value is a readable zip archive, whose entries all match their checksums
+    #[track_caller]
+    fn to_be_valid_zip(self) -> Self;
+
+    /// Check that the archive has a file entry with the given name, such as `docs/readme.md`
+    #[track_caller]
+    fn to_contain_archive_entry(self, name: &str) -> Self;
+
+    /// Check the content of a file entry, either exactly or with [`containing`]
+    #[track_caller]
+    fn to_have_entry_with_content(self, name: &str, content: impl Into<EntryContent>) -> Self;
+}
+
+/// Expected content of an archive entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryContent {
+    /// The entry holds exactly these bytes
+    Exactly(Vec<u8>),
+    /// The entry is text containing this substring
+    Containing(String),
+}
+
+/// Expect an archive entry to contain the given text, with `to_have_entry_with_content`
+pub fn containing(text: impl Into<String>) -> EntryContent {
+    return EntryContent::Containing(text.into());
+}
+
+impl EntryContent {
+    fn matches(&self, content: &[u8]) -> bool {
+        return match self {
+            EntryContent::Exactly(expected) => content == expected.as_slice(),
+            EntryContent::Containing(text) => String::from_utf8_lossy(content).contains(text.as_str()),
+        };
+    }
+
+    fn describe(&self) -> String {
+        return match self {
+            EntryContent::Exactly(expected) => format!("content {}", preview(expected)),
+            EntryContent::Containing(text) => format!("content containing {:?}", text),
+        };
+    }
+}
+
+impl From<&str> for EntryContent {
+    fn from(content: &str) -> Self {
+        return EntryContent::Exactly(content.as_bytes().to_vec());
+    }
+}
+
+impl From<String> for EntryContent {
+    fn from(content: String) -> Self {
+        return EntryContent::Exactly(content.into_bytes());
+    }
+}
+
+impl From<&[u8]> for EntryContent {
+    fn from(content: &[u8]) -> Self {
+        return EntryContent::Exactly(content.to_vec());
+    }
+}
+
+impl From<Vec<u8>> for EntryContent {
+    fn from(content: Vec<u8>) -> Self {
+        return EntryContent::Exactly(content);
+    }
+}
+
+/// Formats of the archives the matchers read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Zip,
+    Tar,
+    GzippedTar,
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        return match self {
+            Format::Zip => "zip",
+            Format::Tar => "tar",
+            Format::GzippedTar => "gzipped tar",
+        };
+    }
+}
+
+/// File entries of an archive, with their content, in archive order
+#[derive(Debug)]
+struct Archive {
+    format: Format,
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+impl Archive {
+    /// Read an archive, guessing its format from its leading bytes
+    fn read(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.starts_with(ZIP_MAGIC) || bytes.starts_with(EMPTY_ZIP_MAGIC) {
+            return Self::read_zip(bytes).map_err(|e| format!("invalid zip archive: {}", e));
+        }
+        if bytes.starts_with(GZIP_MAGIC) {
+            let mut tar = Vec::new();
+            GzDecoder::new(bytes).read_to_end(&mut tar).map_err(|e| format!("invalid gzip stream: {}", e))?;
+            return Self::read_tar(&tar, Format::GzippedTar).map_err(|e| format!("invalid tar archive: {}", e));
+        }
+        if bytes.is_empty() {
+            return Err("empty input, not an archive".to_string());
+        }
+
+        return Self::read_tar(bytes, Format::Tar).map_err(|e| format!("not a zip or tar archive: {}", e));
+    }
+
+    fn read_zip(bytes: &[u8]) -> io::Result<Self> {
+        let mut zip = zip::ZipArchive::new(Cursor::new(bytes))?;
+        let mut entries = Vec::new();
+
+        for index in 0..zip.len() {
+            let mut file = zip.by_index(index)?;
+            if file.is_dir() {
+                continue;
+            }
+
+            // Reading to the end checks the CRC of the entry
+            let mut content = Vec::new();
+            file.read_to_end(&mut content)?;
+            entries.push((file.name().to_string(), content));
+        }
+
+        return Ok(Self { format: Format::Zip, entries });
+    }
+
+    fn read_tar(bytes: &[u8], format: Format) -> io::Result<Self> {
+        let mut tar = tar::Archive::new(bytes);
+        let mut entries = Vec::new();
+
+        for entry in tar.entries()? {
+            let mut entry = entry?;
+            if entry.header().entry_type().is_dir() {
+                continue;
+            }
+
+            let name = entry.path()?.to_string_lossy().into_owned();
+            let mut content = Vec::new();
+            entry.read_to_end(&mut content)?;
+            entries.push((name, content));
+        }
+
+        return Ok(Self { format, entries });
+    }
+
+    fn entry(&self, name: &str) -> Option<&[u8]> {
+        return self.entries.iter().find(|(entry, _)| entry == name).map(|(_, content)| content.as_slice());
+    }
+
+    /// Names of the first entries, listed under failed steps
+    fn listing(&self) -> String {
+        if self.entries.is_empty() {
+            return "the archive has no file entries".to_string();
+        }
+
+        let names = self.entries.iter().take(LISTED_ENTRIES).map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ");
+        return match self.entries.len().checked_sub(LISTED_ENTRIES).filter(|more| *more > 0) {
+            Some(more) => format!("entries: {}, and {} more", names, more),
+            None => format!("entries: {}", names),
+        };
+    }
+}
+
+/// Show content as text when it is UTF-8, truncated, and by its size otherwise
+fn preview(content: &[u8]) -> String {
+    return match std::str::from_utf8(content) {
+        Ok(text) if text.chars().count() > CONTENT_PREVIEW => format!("{:?}…", text.chars().take(CONTENT_PREVIEW).collect::<String>()),
+        Ok(text) => format!("{:?}", text),
+        Err(_) => format!("of {} binary bytes", content.len()),
+    };
+}
+
+/// Helper trait for paths and byte buffers holding archives
+trait AsArchive {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>>;
+}
+
+impl<A: AsArchive + ?Sized> AsArchive for &A {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        return (**self).archive_bytes();
+    }
+}
+
+impl AsArchive for Path {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        return fs::read(self).map(Cow::Owned);
+    }
+}
+
+impl AsArchive for PathBuf {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        return self.as_path().archive_bytes();
+    }
+}
+
+impl AsArchive for [u8] {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        return Ok(Cow::Borrowed(self));
+    }
+}
+
+impl AsArchive for Vec<u8> {
+    fn archive_bytes(&self) -> io::Result<Cow<'_, [u8]>> {
+        return Ok(Cow::Borrowed(self.as_slice()));
+    }
+}
+
+// Single implementation for any type that implements AsArchive
+impl<V> ArchiveMatchers for Assertion<V>
+where
+    V: AsArchive + Debug + Clone,
+{
+    fn to_be_valid_zip(self) -> Self {
+        let archive = self.memoized("archive", read_archive);
+        let result = archive.as_ref().as_ref().is_ok_and(|archive| archive.format == Format::Zip);
+
+        return self.add_step_with(result, |_| {
+            let actual = match archive.as_ref() {
+                Ok(archive) if archive.format == Format::Zip => format!("a zip archive of {} file entries", archive.entries.len()),
+                Ok(archive) => format!("a {} archive", archive.format.name()),
+                Err(error) => error.clone(),
+            };
+            return AssertionSentence::new("be", "a valid zip archive").with_actual(actual);
+        });
+    }
+
+    fn to_contain_archive_entry(self, name: &str) -> Self {
+        let archive = self.memoized("archive", read_archive);
+        let result = archive.as_ref().as_ref().is_ok_and(|archive| archive.entry(name).is_some());
+
+        return self.add_step_with(result, |_| {
+            let sentence = AssertionSentence::new("contain", format!("archive entry {:?}", name));
+            return match archive.as_ref() {
+                Ok(archive) => sentence.with_detail(archive.listing()),
+                Err(error) => sentence.with_actual(error.clone()),
+            };
+        });
+    }
+
+    fn to_have_entry_with_content(self, name: &str, content: impl Into<EntryContent>) -> Self {
+        let content = content.into();
+        let archive = self.memoized("archive", read_archive);
+        let entry = archive.as_ref().as_ref().map(|archive| archive.entry(name));
+        let result = entry.as_ref().is_ok_and(|entry| entry.is_some_and(|actual| content.matches(actual)));
+
+        return self.add_step_with(result, |_| {
+            let sentence = AssertionSentence::new("have", format!("entry {:?} with {}", name, content.describe()));
+            return match (archive.as_ref(), entry) {
+                (Ok(_), Ok(Some(actual))) => sentence.with_actual(preview(actual)),
+                (Ok(archive), _) => sentence.with_actual(format!("no entry {:?}", name)).with_detail(archive.listing()),
+                (Err(error), _) => sentence.with_actual(error.clone()),
+            };
+        });
+    }
+}
+
+/// Read the archive held by a value, describing why it can't be
+fn read_archive<V: AsArchive>(value: &V) -> Result<Archive, String> {
+    let bytes = value.archive_bytes().map_err(|e| format!("unreadable archive: {}", e))?;
+    return Archive::read(&bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::io::Write;
+
+    fn zip_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        zip.add_directory("docs/", zip::write::SimpleFileOptions::default()).unwrap();
+        for (name, content) in entries {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+
+        return zip.finish().unwrap().into_inner();
+    }
+
+    fn tar_of(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut tar = tar::Builder::new(Vec::new());
+        for (name, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, content.as_bytes()).unwrap();
+        }
+
+        return tar.into_inner().unwrap();
+    }
+
+    fn gzip(bytes: &[u8]) -> Vec<u8> {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(bytes).unwrap();
+        return encoder.finish().unwrap();
+    }
+
+    #[test]
+    fn test_zip_archives() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let package = zip_of(&[("docs/readme.md", "# Package"), ("cfg.json", "{\"version\": 2}")]);
+
+        expect!(&package)
+            .to_be_valid_zip()
+            .and()
+            .to_contain_archive_entry("docs/readme.md")
+            .and()
+            .to_have_entry_with_content("cfg.json", containing("version"));
+        expect!(package.as_slice()).to_have_entry_with_content("docs/readme.md", "# Package");
+        expect!(&package).not().to_contain_archive_entry("docs/");
+        expect!(&package).not().to_have_entry_with_content("cfg.json", containing("name"));
+    }
+
+    #[test]
+    fn test_tar_archives() {
+        let tar = tar_of(&[("release/notes.txt", "Fixed the build")]);
+
+        expect!(&tar).not().to_be_valid_zip();
+        expect!(&tar).to_have_entry_with_content("release/notes.txt", containing("build"));
+        expect!(gzip(&tar)).to_contain_archive_entry("release/notes.txt");
+    }
+
+    #[test]
+    fn test_archives_read_from_paths() {
+        let path = std::env::temp_dir().join(format!("rest-archive-{}.zip", std::process::id()));
+        std::fs::write(&path, zip_of(&[("cfg.json", "{}")])).unwrap();
+
+        expect!(path.as_path()).to_be_valid_zip().and().to_have_entry_with_content("cfg.json", "{}");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_entry_listing() {
+        let archive = Archive::read(&tar_of(&[("a", ""), ("b", "")])).unwrap();
+        assert_eq!(archive.listing(), "entries: a, b");
+        assert_eq!(preview(&[0xff, 0x00]), "of 2 binary bytes");
+    }
+
+    #[test]
+    #[should_panic(expected = "entry \"cfg.json\" with content containing \"version\" (got \"{}\")")]
+    fn test_entry_content_mismatch_fails() {
+        let _assertion = expect!(zip_of(&[("cfg.json", "{}")])).to_have_entry_with_content("cfg.json", containing("version"));
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "(got invalid zip archive:")]
+    fn test_corrupted_zip_fails() {
+        let mut package = zip_of(&[("cfg.json", "{}")]);
+        package.truncate(package.len() - 10);
+
+        let _assertion = expect!(package).to_be_valid_zip();
+        std::hint::black_box(_assertion);
+    }
+}
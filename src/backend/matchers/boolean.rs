@@ -3,11 +3,18 @@ use crate::backend::assertions::sentence::AssertionSentence;
 use std::fmt::Debug;
 
 pub trait BooleanMatchers {
+    #[track_caller]
     fn to_be_true(self) -> Self;
+    #[track_caller]
     fn to_be_false(self) -> Self;
 }
 
 /// Helper trait for boolean-like types
+#[diagnostic::on_unimplemented(
+    message = "`BooleanMatchers` can't check `{Self}`, which isn't a boolean",
+    label = "not a boolean",
+    note = "`BooleanMatchers` apply to `bool` and `&bool`"
+)]
 trait AsBoolean {
     fn is_true(&self) -> bool;
     fn is_false(&self) -> bool;
@@ -42,16 +49,14 @@ where
 {
     fn to_be_true(self) -> Self {
         let result = self.value.is_true();
-        let sentence = AssertionSentence::new("be", "true").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "true").with_actual(format!("{:?}", actual)));
     }
 
     fn to_be_false(self) -> Self {
         let result = self.value.is_false();
-        let sentence = AssertionSentence::new("be", "false").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "false").with_actual(format!("{:?}", actual)));
     }
 }
 
@@ -0,0 +1,137 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+
+/// Trait for memory-shape assertions on growable buffers.
+///
+/// Useful to verify pre-allocation behavior of performance-critical builders.
+/// Supported for `&Vec<T>` and `&String`: clones don't keep the capacity of their original, so the steps of a chain
+/// check the borrowed buffer instead of copies of an owned one.
+pub trait CapacityMatchers {
+    /// Check that the allocated capacity is at least `expected` elements
+    #[track_caller]
+    fn to_have_capacity_at_least(self, expected: usize) -> Self;
+
+    /// Check that at least `expected` elements can be pushed without reallocating
+    #[track_caller]
+    fn to_have_spare_capacity(self, expected: usize) -> Self;
+}
+
+/// Helper trait for types exposing an allocated capacity
+#[diagnostic::on_unimplemented(
+    message = "`CapacityMatchers` can't check `{Self}`, which isn't a borrowed buffer",
+    label = "not a borrowed `Vec` or `String`",
+    note = "`CapacityMatchers` apply to `&Vec<T>` and `&String`, since clones of owned buffers lose their capacity"
+)]
+trait AsCapacity {
+    fn capacity_value(&self) -> usize;
+    fn length_value(&self) -> usize;
+}
+
+impl<T> AsCapacity for &Vec<T> {
+    fn capacity_value(&self) -> usize {
+        return self.capacity();
+    }
+
+    fn length_value(&self) -> usize {
+        return self.len();
+    }
+}
+
+impl AsCapacity for &String {
+    fn capacity_value(&self) -> usize {
+        return self.capacity();
+    }
+
+    fn length_value(&self) -> usize {
+        return self.len();
+    }
+}
+
+// Single implementation for any type that implements AsCapacity
+impl<V> CapacityMatchers for Assertion<V>
+where
+    V: AsCapacity + Debug + Clone,
+{
+    fn to_have_capacity_at_least(self, expected: usize) -> Self {
+        let capacity = self.value.capacity_value();
+        let result = capacity >= expected;
+
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("have", format!("capacity of at least {}", expected))
+                .with_actual(format!("capacity {}", capacity));
+        });
+    }
+
+    fn to_have_spare_capacity(self, expected: usize) -> Self {
+        let capacity = self.value.capacity_value();
+        let length = self.value.length_value();
+        let spare = capacity - length;
+        let result = spare >= expected;
+
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("have", format!("spare capacity of at least {}", expected))
+                .with_actual(format!("spare capacity {} (length {}, capacity {})", spare, length, capacity));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_to_have_capacity_at_least() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let buffer: Vec<u8> = Vec::with_capacity(64);
+        let text = String::with_capacity(32);
+
+        expect!(&buffer).to_have_capacity_at_least(64);
+        expect!(&buffer).not().to_have_capacity_at_least(usize::MAX);
+        expect!(&text).to_have_capacity_at_least(32);
+        expect!(&text).to_have_capacity_at_least(16);
+    }
+
+    #[test]
+    fn test_chained_steps_check_the_original_capacity() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let buffer: Vec<u8> = Vec::with_capacity(64);
+        let text = String::with_capacity(32);
+
+        expect!(&buffer).to_have_capacity_at_least(64).and().to_have_capacity_at_least(64);
+        expect!(&buffer).to_be_empty().and().to_have_spare_capacity(64);
+        expect!(&text).to_have_capacity_at_least(32).and().to_have_spare_capacity(32);
+    }
+
+    #[test]
+    fn test_to_have_spare_capacity() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let mut buffer: Vec<u8> = Vec::with_capacity(16);
+        buffer.extend_from_slice(&[1, 2, 3, 4]);
+
+        expect!(&buffer).to_have_spare_capacity(12);
+        expect!(&buffer).not().to_have_spare_capacity(usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "have capacity of at least")]
+    fn test_small_capacity_fails() {
+        let buffer: Vec<u8> = Vec::new();
+        let _assertion = expect!(&buffer).to_have_capacity_at_least(8);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "have spare capacity of at least")]
+    fn test_full_buffer_has_no_spare_capacity_fails() {
+        let buffer = vec![1, 2, 3];
+        let _assertion = expect!(&buffer).to_have_spare_capacity(buffer.capacity() + 1);
+        std::hint::black_box(_assertion);
+    }
+}
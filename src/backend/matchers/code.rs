@@ -0,0 +1,217 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::diff::Diff;
+use proc_macro2::{LineColumn, TokenStream};
+use std::fmt::Debug;
+
+/// Trait for assertions on generated Rust code, aimed at codegen and proc-macro crates.
+///
+/// Available with the `syn` feature, for strings owned or borrowed. Parse failures are reported with
+/// the offending line and a caret under the error location.
+pub trait CodeMatchers {
+    /// Check that the string is a valid Rust identifier (raw identifiers such as `r#type` included)
+    #[track_caller]
+    fn to_be_valid_rust_identifier(self) -> Self;
+
+    /// Check that the string lexes into Rust tokens: balanced delimiters, valid literals and punctuation
+    #[track_caller]
+    fn to_parse_as_rust_tokens(self) -> Self;
+
+    /// Check that the code, once pretty-printed, reads exactly like the expected code pretty-printed
+    ///
+    /// Both sides must parse as a Rust file (items such as functions, structs or impls), so generated
+    /// code can be compared to readable, rustfmt-formatted expectations. Panics if the expected code
+    /// does not parse.
+    #[track_caller]
+    fn to_be_formatted_like(self, expected: &str) -> Self;
+}
+
+/// Helper trait for strings holding Rust code
+trait AsCode {
+    fn code(&self) -> &str;
+}
+
+impl<C: AsCode + ?Sized> AsCode for &C {
+    fn code(&self) -> &str {
+        return (**self).code();
+    }
+}
+
+impl AsCode for String {
+    fn code(&self) -> &str {
+        return self.as_str();
+    }
+}
+
+impl AsCode for str {
+    fn code(&self) -> &str {
+        return self;
+    }
+}
+
+/// Show the line of an error with a caret under its location, falling back to the bare message
+fn span_context(code: &str, start: LineColumn, message: &str) -> Vec<String> {
+    let Some(source_line) = start.line.checked_sub(1).and_then(|index| code.lines().nth(index)) else {
+        return vec![message.to_string()];
+    };
+
+    let number = start.line.to_string();
+    let indent = source_line.chars().take(start.column).map(|c| if c == '\t' { '\t' } else { ' ' }).collect::<String>();
+
+    return vec![format!("{} | {}", number, source_line), format!("{} | {}^ {}", " ".repeat(number.len()), indent, message)];
+}
+
+/// Parse code as a Rust file, returning the error with its span context on failure
+fn parse_file(code: &str) -> Result<syn::File, Vec<String>> {
+    return syn::parse_file(code).map_err(|error| span_context(code, error.span().start(), &error.to_string()));
+}
+
+// Single implementation for any type that implements AsCode
+impl<V> CodeMatchers for Assertion<V>
+where
+    V: AsCode + Debug + Clone,
+{
+    fn to_be_valid_rust_identifier(self) -> Self {
+        let code = self.value.code();
+        let error = match syn::parse_str::<syn::Ident>(code) {
+            // Whitespace and comments around the identifier are lexed away but aren't part of it
+            Ok(ident) if ident == code => None,
+            Ok(_) => Some("unexpected characters around the identifier".to_string()),
+            Err(error) => Some(error.to_string()),
+        };
+
+        let mut sentence = AssertionSentence::new("be", "a valid Rust identifier").with_actual(format!("{:?}", code));
+        if let Some(ref error) = error {
+            sentence = sentence.with_detail(error.clone());
+        }
+
+        return self.add_step(sentence, error.is_none());
+    }
+
+    fn to_parse_as_rust_tokens(self) -> Self {
+        let code = self.value.code();
+        let (result, actual, details) = match code.parse::<TokenStream>() {
+            Ok(_) => (true, "valid tokens".to_string(), Vec::new()),
+            Err(error) => {
+                let start = error.span().start();
+                (false, format!("lex error at {}:{}", start.line, start.column + 1), span_context(code, start, &error.to_string()))
+            }
+        };
+
+        let mut sentence = AssertionSentence::new("parse", "as Rust tokens").with_actual(actual);
+        for detail in details {
+            sentence = sentence.with_detail(detail);
+        }
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_be_formatted_like(self, expected: &str) -> Self {
+        let expected_file =
+            parse_file(expected).unwrap_or_else(|context| panic!("Expected code does not parse as Rust:\n{}", context.join("\n")));
+        let expected_pretty = prettyplease::unparse(&expected_file);
+
+        let sentence = AssertionSentence::new("be", "formatted like the expected code").with_expected(expected_pretty.clone());
+        // Chained comparisons parse and pretty-print the code once
+        let actual_pretty = self.memoized("pretty-printed code", |value| parse_file(value.code()).map(|file| prettyplease::unparse(&file)));
+        let (result, sentence) = match actual_pretty.as_ref() {
+            Err(context) => {
+                let sentence = context.iter().fold(sentence.with_actual("code that does not parse"), AssertionSentence::with_detail);
+                (false, sentence)
+            }
+            Ok(actual_pretty) => {
+                let diff = Diff::lines(actual_pretty, &expected_pretty);
+                let actual = match diff.change_count() {
+                    0 => "identical code".to_string(),
+                    1 => "1 differing line".to_string(),
+                    count => format!("{} differing lines", count),
+                };
+                let sentence = sentence.with_actual(actual);
+                if diff.is_identical() { (true, sentence) } else { (false, sentence.with_diff(diff)) }
+            }
+        };
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_to_be_valid_rust_identifier() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect!("user_id").to_be_valid_rust_identifier();
+        expect!("_private").to_be_valid_rust_identifier();
+        expect!("r#type").to_be_valid_rust_identifier();
+        expect!("größe".to_string()).to_be_valid_rust_identifier();
+        expect!("fn").not().to_be_valid_rust_identifier();
+        expect!("_").not().to_be_valid_rust_identifier();
+        expect!("2fast").not().to_be_valid_rust_identifier();
+        expect!("user-id").not().to_be_valid_rust_identifier();
+        expect!(" padded").not().to_be_valid_rust_identifier();
+        expect!("name // comment").not().to_be_valid_rust_identifier();
+        expect!("").not().to_be_valid_rust_identifier();
+    }
+
+    #[test]
+    fn test_to_parse_as_rust_tokens() {
+        crate::Reporter::disable_deduplication();
+
+        expect!("fn main() { println!(\"hi\"); }").to_parse_as_rust_tokens();
+        expect!("a + b * (c - 1)").to_parse_as_rust_tokens();
+        expect!("fn main() { (1, 2 }").not().to_parse_as_rust_tokens();
+        expect!("let s = \"unterminated;").not().to_parse_as_rust_tokens();
+    }
+
+    #[test]
+    fn test_to_be_formatted_like() {
+        crate::Reporter::disable_deduplication();
+
+        // Typical output of `quote!(..).to_string()`
+        let generated = "impl Point { pub fn new (x : i32 , y : i32) -> Self { Self { x , y } } }";
+
+        expect!(generated).to_be_formatted_like(
+            "impl Point {
+                pub fn new(x: i32, y: i32) -> Self {
+                    Self { x, y }
+                }
+            }",
+        );
+        expect!(generated).not().to_be_formatted_like("impl Point { pub fn new(x: i32, y: i32) -> Self { Self { y, x } } }");
+        expect!("struct {").not().to_be_formatted_like("struct Unit;");
+    }
+
+    #[test]
+    #[should_panic(expected = "be a valid Rust identifier (got \"match\")\n  expected identifier, found keyword `match`")]
+    fn test_keyword_is_not_an_identifier() {
+        let _assertion = expect!("match").to_be_valid_rust_identifier();
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "parse as Rust tokens (got lex error at 2:13)\n  2 |     let s = \"oops;\n    |             ^ ")]
+    fn test_lex_error_is_shown_in_context() {
+        let _assertion = expect!("fn main() {\n    let s = \"oops;\n}").to_parse_as_rust_tokens();
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "be formatted like the expected code (got 1 differing line)\n  line 1: ok\n  line 2: expected `    a + b`, got `    a - b`"
+    )]
+    fn test_formatting_difference_is_listed_by_line() {
+        let _assertion = expect!("fn add(a: u8, b: u8) -> u8 { a - b }").to_be_formatted_like("fn add(a: u8, b: u8) -> u8 { a + b }");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected code does not parse as Rust")]
+    fn test_unparsable_expected_code_panics() {
+        let _assertion = expect!("struct Unit;").to_be_formatted_like("struct {");
+        std::hint::black_box(_assertion);
+    }
+}
@@ -1,22 +1,61 @@
 use crate::backend::Assertion;
 use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::diff::Diff;
+use crate::backend::property::Gen;
+use crate::backend::run::{ENV_SEED, current_run};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::panic::Location;
 
 /// Define the primary matcher trait for collections
 pub trait CollectionMatchers<T> {
-    fn to_be_empty(self) -> Self;
+    #[track_caller]
     fn to_have_length(self, expected: usize) -> Self;
+    #[track_caller]
     fn to_contain<U: PartialEq<T> + Debug>(self, expected: U) -> Self;
+    /// Check that the collection contains every expected element, in any order
+    ///
+    /// With `shuffle_matchers` enabled, the expected and actual elements are probed in a shuffled order.
+    #[track_caller]
     fn to_contain_all_of<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self;
+    #[track_caller]
     fn to_equal_collection<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self;
+
+    /// Check that both collections hold the same elements the same number of times, in any order
+    ///
+    /// With `shuffle_matchers` enabled, the elements of both collections are counted in a shuffled order.
+    #[track_caller]
+    fn to_have_same_elements_as<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self;
+
+    /// Check that every sliding window of the given size satisfies the predicate, such as adjacent pairs being increasing
+    #[track_caller]
+    fn to_have_all_windows_satisfying<F: Fn(&[T]) -> bool>(self, size: usize, predicate: F) -> Self;
+
+    /// Check the number of chunks of the given size the collection splits into, the last one possibly shorter
+    #[track_caller]
+    fn to_have_chunk_count(self, size: usize, expected: usize) -> Self;
+
+    /// Check that the collection holds the original elements sorted by the key, with the elements of equal keys in
+    /// their original relative order, as a stable sort leaves them
+    #[track_caller]
+    fn to_be_stably_sorted_by<K: Ord + Debug, F: Fn(&T) -> K>(self, original: &[T], key: F) -> Self;
 }
 
+/// Maximum number of failing windows listed in failure details
+const MAX_LISTED_WINDOWS: usize = 5;
+
 /// Helper trait for types that can be examined as collections
+#[diagnostic::on_unimplemented(
+    message = "`CollectionMatchers` can't check `{Self}`, which isn't a collection",
+    label = "not a collection",
+    note = "`CollectionMatchers` apply to `Vec<T>`, `&Vec<T>`, slices and arrays; use `HashMapMatchers` for maps and `to_have_same_elements_as` for other iterables"
+)]
 trait AsCollection {
     type Item;
 
-    fn is_empty(&self) -> bool;
     fn length(&self) -> usize;
+    fn items(&self) -> &[Self::Item];
     fn contains_item<U>(&self, item: &U) -> bool
     where
         U: PartialEq<Self::Item>;
@@ -32,14 +71,14 @@ trait AsCollection {
 impl<T: PartialEq> AsCollection for &[T] {
     type Item = T;
 
-    fn is_empty(&self) -> bool {
-        <[T]>::is_empty(self)
-    }
-
     fn length(&self) -> usize {
         self.len()
     }
 
+    fn items(&self) -> &[Self::Item] {
+        self
+    }
+
     fn contains_item<U>(&self, item: &U) -> bool
     where
         U: PartialEq<Self::Item>,
@@ -70,14 +109,14 @@ impl<T: PartialEq> AsCollection for &[T] {
 impl<T: PartialEq> AsCollection for &Vec<T> {
     type Item = T;
 
-    fn is_empty(&self) -> bool {
-        Vec::is_empty(self)
-    }
-
     fn length(&self) -> usize {
         self.len()
     }
 
+    fn items(&self) -> &[Self::Item] {
+        self
+    }
+
     fn contains_item<U>(&self, item: &U) -> bool
     where
         U: PartialEq<Self::Item>,
@@ -108,14 +147,14 @@ impl<T: PartialEq> AsCollection for &Vec<T> {
 impl<T: PartialEq> AsCollection for Vec<T> {
     type Item = T;
 
-    fn is_empty(&self) -> bool {
-        Vec::is_empty(self)
-    }
-
     fn length(&self) -> usize {
         self.len()
     }
 
+    fn items(&self) -> &[Self::Item] {
+        self
+    }
+
     fn contains_item<U>(&self, item: &U) -> bool
     where
         U: PartialEq<Self::Item>,
@@ -146,14 +185,14 @@ impl<T: PartialEq> AsCollection for Vec<T> {
 impl<T: PartialEq, const N: usize> AsCollection for &[T; N] {
     type Item = T;
 
-    fn is_empty(&self) -> bool {
-        N == 0
-    }
-
     fn length(&self) -> usize {
         N
     }
 
+    fn items(&self) -> &[Self::Item] {
+        self.as_slice()
+    }
+
     fn contains_item<U>(&self, item: &U) -> bool
     where
         U: PartialEq<Self::Item>,
@@ -186,48 +225,290 @@ where
     T: Debug + Clone + PartialEq,
     V: AsCollection<Item = T> + Debug + Clone,
 {
-    fn to_be_empty(self) -> Self {
-        let result = self.value.is_empty();
-        let sentence = AssertionSentence::new("be", "empty").with_actual(format!("{:?}", self.value));
-
-        return self.add_step(sentence, result);
-    }
-
     fn to_have_length(self, expected: usize) -> Self {
         let actual_length = self.value.length();
         let result = actual_length == expected;
-        let sentence = AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
+        });
     }
 
     fn to_contain<U: PartialEq<T> + Debug>(self, expected: U) -> Self {
         let result = self.value.contains_item(&expected);
-        let sentence = AssertionSentence::new("contain", format!("{:?}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("{:?}", expected)).with_actual(format!("{:?}", actual));
+        });
     }
 
     fn to_contain_all_of<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self {
-        let result = self.value.contains_all_items(expected);
-        let sentence = AssertionSentence::new("contain", format!("all of {:?}", expected)).with_actual(format!("{:?}", self.value));
+        let mut order = ProbeOrder::at(Location::caller());
+        let result = if order.is_shuffled() {
+            let actual_items = order.arrange(self.value.items());
+            order.arrange(expected).into_iter().all(|item| actual_items.iter().any(|actual_item| item == *actual_item))
+        } else {
+            self.value.contains_all_items(expected)
+        };
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            let sentence = AssertionSentence::new("contain", format!("all of {:?}", expected)).with_actual(format!("{:?}", actual));
+            return if result { sentence } else { order.with_replay_detail(sentence) };
+        });
     }
 
     fn to_equal_collection<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self {
         let result = self.value.equals_items(expected);
 
-        // Different message if lengths don't match
-        if self.value.length() != expected.len() {
-            let sentence = AssertionSentence::new("equal", format!("collection {:?} (different lengths)", expected))
-                .with_actual(format!("{:?}", self.value));
-            return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            // Render an element-level diff rather than both whole collections
+            let sentence = AssertionSentence::new("equal", format!("collection of {}", describe_elements(expected.len())))
+                .with_expected(format!("{:?}", expected))
+                .with_actual(describe_elements(actual.length()));
+            let diff = Diff::sequence(actual.items(), expected, |actual, expected| expected == actual);
+
+            return if diff.is_identical() { sentence } else { sentence.with_diff(diff) };
+        });
+    }
+
+    fn to_have_same_elements_as<U: PartialEq<T> + Debug>(self, expected: &[U]) -> Self {
+        let mut order = ProbeOrder::at(Location::caller());
+        let counts = count_elements(&order.arrange(self.value.items()), &order.arrange(expected));
+        let result = counts.iter().all(|count| count.actual == count.expected);
+
+        return self.add_step_with(result, |actual| {
+            let mut sentence =
+                AssertionSentence::new("have", format!("the same elements as {} in any order", describe_elements(expected.len())))
+                    .with_actual(describe_elements(actual.length()));
+            if !result {
+                let only_in_actual = counts.iter().filter(|count| count.expected == 0).map(|count| (count.element.as_str(), count.actual));
+                let only_in_expected =
+                    counts.iter().filter(|count| count.actual == 0).map(|count| (count.element.as_str(), count.expected));
+                let mismatches = counts
+                    .iter()
+                    .filter(|count| count.actual > 0 && count.expected > 0 && count.actual != count.expected)
+                    .map(|count| format!("{} ({} in actual, {} expected)", count.element, count.actual, count.expected))
+                    .collect::<Vec<_>>();
+
+                sentence = sentence
+                    .with_detail(format!("only in actual: {}", list_elements(only_in_actual)))
+                    .with_detail(format!("only in expected: {}", list_elements(only_in_expected)))
+                    .with_detail(format!(
+                        "count mismatches: {}",
+                        if mismatches.is_empty() { "none".to_string() } else { mismatches.join(", ") }
+                    ));
+                sentence = order.with_replay_detail(sentence);
+            }
+
+            return sentence;
+        });
+    }
+
+    fn to_have_all_windows_satisfying<F: Fn(&[T]) -> bool>(self, size: usize, predicate: F) -> Self {
+        assert!(size != 0, "window size must be non-zero");
+        let windows = self.value.items().windows(size);
+        let total = windows.len();
+        let failing = windows.enumerate().filter(|(_, window)| !predicate(window)).map(|(start, _)| start).collect::<Vec<_>>();
+        let result = failing.is_empty();
+
+        return self.add_step_with(result, |actual| {
+            let mut sentence = AssertionSentence::new("have", format!("all windows of {} satisfying the predicate", size))
+                .with_actual(format!("{} of {} windows failing", failing.len(), total));
+            for &start in failing.iter().take(MAX_LISTED_WINDOWS) {
+                let window = &actual.items()[start..start + size];
+                sentence = sentence.with_detail(format!("window at {}..{}: {:?}", start, start + size, window));
+            }
+            if failing.len() > MAX_LISTED_WINDOWS {
+                sentence = sentence.with_detail(format!("... and {} more", failing.len() - MAX_LISTED_WINDOWS));
+            }
+
+            return sentence;
+        });
+    }
+
+    fn to_have_chunk_count(self, size: usize, expected: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        let actual_count = self.value.length().div_ceil(size);
+        let result = actual_count == expected;
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("have", format!("{} of {}", describe_chunks(expected), size)).with_actual(format!(
+                "{} in {}",
+                describe_chunks(actual_count),
+                describe_elements(actual.length())
+            ));
+        });
+    }
+
+    fn to_be_stably_sorted_by<K: Ord + Debug, F: Fn(&T) -> K>(self, original: &[T], key: F) -> Self {
+        let violation = find_sort_violation(self.value.items(), original, key);
+        let result = violation.is_none();
+
+        return self.add_step_with(result, |_| {
+            let sentence = AssertionSentence::new("be", "stably sorted by key");
+            return match &violation {
+                Some(violation) => violation.elements.iter().fold(sentence.with_actual(violation.summary.clone()), |sentence, element| {
+                    return sentence.with_detail(element.clone());
+                }),
+                None => sentence,
+            };
+        });
+    }
+}
+
+/// First pair of elements breaking a stable sort, or the element breaking the permutation of the original
+struct SortViolation {
+    summary: String,
+    elements: Vec<String>,
+}
+
+/// Find the first violation of a stable sort of the original elements by a key
+fn find_sort_violation<T: PartialEq + Debug, K: Ord + Debug>(sorted: &[T], original: &[T], key: impl Fn(&T) -> K) -> Option<SortViolation> {
+    // Position of each element in the original, equal elements taking the earliest unused positions, in order
+    let mut used = vec![false; original.len()];
+    let mut positions = Vec::with_capacity(sorted.len());
+    for (index, item) in sorted.iter().enumerate() {
+        let Some(position) = (0..original.len()).find(|&position| !used[position] && original[position] == *item) else {
+            let summary = format!("element at {} not in the original", index);
+            return Some(SortViolation { summary, elements: vec![format!("at {}: {:?}", index, item)] });
+        };
+        used[position] = true;
+        positions.push(position);
+    }
+    if let Some(position) = used.iter().position(|used| !used) {
+        let summary = format!("element at {} of the original missing", position);
+        return Some(SortViolation { summary, elements: vec![format!("at {} of the original: {:?}", position, original[position])] });
+    }
+
+    for index in 1..sorted.len() {
+        let (previous, next) = (key(&sorted[index - 1]), key(&sorted[index]));
+        let summary = if previous > next {
+            format!("elements at {} and {} out of order", index - 1, index)
+        } else if previous == next && positions[index - 1] > positions[index] {
+            format!("elements at {} and {} with equal keys swapped", index - 1, index)
+        } else {
+            continue;
+        };
+
+        let elements = [(index - 1, previous), (index, next)]
+            .into_iter()
+            .map(|(at, key)| format!("at {}: {:?}, key {:?}, at {} in the original", at, sorted[at], key, positions[at]))
+            .collect();
+        return Some(SortViolation { summary, elements });
+    }
+
+    return None;
+}
+
+/// Describe a number of chunks with the right plurality
+fn describe_chunks(count: usize) -> String {
+    return if count == 1 { "1 chunk".to_string() } else { format!("{} chunks", count) };
+}
+
+/// Distinct elements of a collection, with the number of times they appear in both collections
+struct ElementCount {
+    element: String,
+    actual: usize,
+    expected: usize,
+}
+
+/// Count the elements of both collections in their probe order, grouping equal ones
+fn count_elements<T: PartialEq + Debug, U: PartialEq<T> + Debug>(actual: &[&T], expected: &[&U]) -> Vec<ElementCount> {
+    // The first element of each group of equal actual elements represents the group
+    let mut representatives: Vec<&T> = Vec::new();
+    let mut counts: Vec<ElementCount> = Vec::new();
+
+    for &item in actual {
+        match representatives.iter().position(|representative| *representative == item) {
+            Some(index) => counts[index].actual += 1,
+            None => {
+                representatives.push(item);
+                counts.push(ElementCount { element: format!("{:?}", item), actual: 1, expected: 0 });
+            }
+        }
+    }
+
+    for &item in expected {
+        if let Some(index) = representatives.iter().position(|representative| *item == **representative) {
+            counts[index].expected += 1;
+            continue;
+        }
+
+        // Expected elements equal to no actual element are grouped by their representation
+        let element = format!("{:?}", item);
+        match counts[representatives.len()..].iter_mut().find(|count| count.element == element) {
+            Some(count) => count.expected += 1,
+            None => counts.push(ElementCount { element, actual: 0, expected: 1 }),
+        }
+    }
+
+    return counts;
+}
+
+/// Order in which order-insensitive matchers probe elements, shuffled when `shuffle_matchers` is enabled
+///
+/// The permutation is drawn from the seed of the run and the location of the matcher call, so that each call site is
+/// shuffled its own way and `REST_SEED` replays a failing order.
+struct ProbeOrder {
+    rng: Option<Gen>,
+}
+
+impl ProbeOrder {
+    /// Probe order of the matcher called at the given location, following the current configuration
+    fn at(location: &Location) -> Self {
+        if !crate::config::is_shuffling_matchers() {
+            return Self { rng: None };
         }
 
-        let sentence = AssertionSentence::new("equal", format!("collection {:?}", expected)).with_actual(format!("{:?}", self.value));
-        return self.add_step(sentence, result);
+        let mut hasher = DefaultHasher::new();
+        (current_run().seed, location.file(), location.line(), location.column()).hash(&mut hasher);
+        return Self::shuffled(hasher.finish());
     }
+
+    /// Probe order shuffled from the given seed
+    fn shuffled(seed: u64) -> Self {
+        return Self { rng: Some(Gen::new(seed)) };
+    }
+
+    /// Check if the elements are probed in a shuffled order
+    fn is_shuffled(&self) -> bool {
+        return self.rng.is_some();
+    }
+
+    /// References to the elements in their probe order, with a Fisher-Yates shuffle when shuffling
+    fn arrange<'a, X>(&mut self, items: &'a [X]) -> Vec<&'a X> {
+        let mut arranged = items.iter().collect::<Vec<_>>();
+        if let Some(rng) = &mut self.rng {
+            for index in (1..arranged.len()).rev() {
+                arranged.swap(index, rng.gen_index(index + 1));
+            }
+        }
+
+        return arranged;
+    }
+
+    /// Add the seed replaying a shuffled order to the sentence of a failure
+    fn with_replay_detail(&self, sentence: AssertionSentence) -> AssertionSentence {
+        if !self.is_shuffled() {
+            return sentence;
+        }
+
+        return sentence.with_detail(format!("probed in shuffled order, reproduce with {}={}", ENV_SEED, current_run().seed));
+    }
+}
+
+/// List elements with their number of occurrences when repeated, or `none`
+fn list_elements<'a>(elements: impl Iterator<Item = (&'a str, usize)>) -> String {
+    let listed = elements
+        .map(|(element, occurrences)| if occurrences == 1 { element.to_string() } else { format!("{} (x{})", element, occurrences) })
+        .collect::<Vec<_>>();
+
+    return if listed.is_empty() { "none".to_string() } else { listed.join(", ") };
+}
+
+/// Describe a number of elements with the right plurality
+fn describe_elements(count: usize) -> String {
+    return if count == 1 { "1 element".to_string() } else { format!("{} elements", count) };
 }
 
 /// Extension trait for adding helper methods to collections
@@ -258,6 +539,7 @@ impl<T> CollectionExtensions<T> for &[T] {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::prelude::*;
 
     #[test]
@@ -367,13 +649,90 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "different lengths")]
+    #[should_panic(expected = "equal collection of 3 elements (got 5 elements)\n  [0-2] ok\n  [3] unexpected 4\n  [4] unexpected 5")]
     fn test_shorter_collection_fails() {
         let collection = vec![1, 2, 3, 4, 5];
         let slice = collection.as_slice();
         expect!(slice).to_equal_collection(&[1, 2, 3]);
     }
 
+    #[test]
+    #[should_panic(expected = "equal collection of 3 elements (got 2 elements)\n  [0] ok\n  [1] expected 4, got 5\n  [2] missing 6")]
+    fn test_collection_diff_marks_each_element() {
+        let _assertion = expect!(vec![3, 5]).to_equal_collection(&[3, 4, 6]);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_collection_diff_is_kept_in_the_step() {
+        let run = crate::testkit::run_matcher(vec![3, 5], |value| value.to_equal_collection(&[3, 4, 6]));
+
+        let sentence = &run.steps()[0].sentence;
+        expect!(sentence.expected_value.as_deref()).to_equal(Some("[3, 4, 6]"));
+        expect!(sentence.diff.as_ref().map(Diff::change_count)).to_equal(Some(2));
+        expect!(sentence.details.len()).to_equal(3);
+    }
+
+    #[test]
+    fn test_collection_same_elements() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let rows = vec!["carol", "ada", "bob", "ada"];
+
+        expect!(&rows).to_have_same_elements_as(&["ada", "bob", "ada", "carol"]);
+        expect!(rows.as_slice()).not().to_have_same_elements_as(&["ada", "bob", "carol"]);
+        expect!(&[1, 2, 3]).not().to_have_same_elements_as(&[1, 2, 4]);
+        expect!(Vec::<i32>::new()).to_have_same_elements_as(&[] as &[i32]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "have the same elements as 5 elements in any order (got 5 elements)\n  only in actual: 7\n  only in expected: 4 (x2)\n  count mismatches: 1 (2 in actual, 1 expected)"
+    )]
+    fn test_different_elements_are_listed() {
+        let _assertion = expect!(vec![3, 1, 7, 1, 2]).to_have_same_elements_as(&[1, 2, 3, 4, 4]);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "only in actual: none\n  only in expected: none\n  count mismatches: 2 (1 in actual, 2 expected)")]
+    fn test_count_mismatches_are_listed() {
+        let _assertion = expect!(vec![2, 5]).to_have_same_elements_as(&[5, 2, 2]);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_shuffled_probe_order_is_a_replayable_permutation() {
+        let items = (0..20).collect::<Vec<_>>();
+        let shuffled = ProbeOrder::shuffled(42).arrange(&items);
+
+        expect!(ProbeOrder::shuffled(42).arrange(&items)).to_equal(shuffled.clone());
+        expect!(&shuffled).to_have_same_elements_as(&items.iter().collect::<Vec<_>>());
+        expect!(&shuffled).not().to_equal_collection(&items.iter().collect::<Vec<_>>());
+        expect!(ProbeOrder { rng: None }.arrange(&items)).to_equal(items.iter().collect::<Vec<_>>());
+
+        let sentence = ProbeOrder::shuffled(42).with_replay_detail(AssertionSentence::new("contain", "all of [1]"));
+        expect!(sentence.details[0].as_str()).to_start_with("probed in shuffled order, reproduce with REST_SEED=");
+    }
+
+    #[test]
+    fn test_shuffled_counts_match_the_collection_order() {
+        let actual = vec![3, 1, 7, 1, 2];
+        let expected = vec![1, 2, 3, 4, 4];
+        let summarize = |mut order: ProbeOrder| {
+            let counts = count_elements(&order.arrange(&actual), &order.arrange(&expected));
+            let mut summary = counts.into_iter().map(|count| (count.element, count.actual, count.expected)).collect::<Vec<_>>();
+            summary.sort();
+            return summary;
+        };
+
+        let in_order = summarize(ProbeOrder { rng: None });
+        for seed in 0..16 {
+            expect!(summarize(ProbeOrder::shuffled(seed))).to_equal(in_order.clone());
+        }
+    }
+
     #[test]
     #[should_panic(expected = "not equal collection")]
     fn test_same_collection_not_fails() {
@@ -382,6 +741,86 @@ mod tests {
         expect!(slice).not().to_equal_collection(&[1, 2, 3, 4, 5]);
     }
 
+    #[test]
+    fn test_collection_windows() {
+        let readings = vec![1, 3, 4, 8, 10];
+
+        expect!(&readings).to_have_all_windows_satisfying(2, |pair| pair[0] < pair[1]);
+        expect!(&readings).not().to_have_all_windows_satisfying(3, |triple| triple[2] - triple[0] < 4);
+
+        // Collections shorter than the window have no window to check
+        let short = [1];
+        expect!(&short).to_have_all_windows_satisfying(2, |_| false);
+    }
+
+    #[test]
+    #[should_panic(expected = "have all windows of 2 satisfying the predicate (got 2 of 5 windows failing)")]
+    fn test_failing_windows_fail() {
+        let readings = vec![1, 3, 2, 4, 4, 5];
+        expect!(&readings).to_have_all_windows_satisfying(2, |pair| pair[0] < pair[1]);
+    }
+
+    #[test]
+    fn test_failing_windows_are_listed() {
+        let readings = [5, 4, 3, 2, 1, 0, -1, -2];
+        let run = crate::testkit::run_matcher(&readings, |value| value.to_have_all_windows_satisfying(2, |pair| pair[0] < pair[1]));
+
+        let details = &run.steps()[0].sentence.details;
+        expect!(details.len()).to_equal(6);
+        expect!(details[0].as_str()).to_equal("window at 0..2: [5, 4]");
+        expect!(details[5].as_str()).to_equal("... and 2 more");
+    }
+
+    #[test]
+    fn test_collection_chunk_count() {
+        let bytes = vec![0u8; 10];
+
+        expect!(&bytes).to_have_chunk_count(4, 3);
+        expect!(&bytes).to_have_chunk_count(5, 2);
+        expect!(&bytes).not().to_have_chunk_count(3, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "have 2 chunks of 4 (got 3 chunks in 10 elements)")]
+    fn test_wrong_chunk_count_fails() {
+        let bytes = vec![0u8; 10];
+        expect!(&bytes).to_have_chunk_count(4, 2);
+    }
+
+    #[test]
+    fn test_collection_stably_sorted() {
+        let tasks = vec![("deploy", 2), ("build", 1), ("test", 2), ("lint", 1)];
+        let mut sorted = tasks.clone();
+        sorted.sort_by_key(|task| task.1);
+
+        expect!(&sorted).to_be_stably_sorted_by(&tasks, |task| task.1);
+        expect!(vec![("build", 1), ("lint", 1), ("test", 2), ("deploy", 2)]).not().to_be_stably_sorted_by(&tasks, |task| task.1);
+        expect!(vec![("build", 1), ("deploy", 2), ("lint", 1), ("test", 2)]).not().to_be_stably_sorted_by(&tasks, |task| task.1);
+        expect!(vec![("build", 1), ("lint", 1), ("deploy", 2)]).not().to_be_stably_sorted_by(&tasks, |task| task.1);
+
+        // Equal elements can't tell their order apart
+        expect!(vec![1, 1, 2]).to_be_stably_sorted_by(&[2, 1, 1], |n| *n);
+    }
+
+    #[test]
+    fn test_sort_violations_name_the_pair() {
+        let tasks = vec![("deploy", 2), ("build", 1), ("test", 2)];
+        let violation = find_sort_violation(&[("build", 1), ("test", 2), ("deploy", 2)], &tasks, |task| task.1).unwrap();
+
+        expect!(violation.summary.as_str()).to_equal("elements at 1 and 2 with equal keys swapped");
+        expect!(violation.elements).to_equal(vec![
+            "at 1: (\"test\", 2), key 2, at 2 in the original".to_string(),
+            "at 2: (\"deploy\", 2), key 2, at 0 in the original".to_string(),
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "be stably sorted by key (got elements at 0 and 1 out of order)")]
+    fn test_unsorted_collection_fails() {
+        let _assertion = expect!(vec![3, 1, 2]).to_be_stably_sorted_by(&[1, 2, 3], |n| *n);
+        std::hint::black_box(_assertion);
+    }
+
     #[test]
     fn test_empty_collection() {
         // Disable deduplication for tests
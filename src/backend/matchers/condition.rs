@@ -0,0 +1,168 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Pause between two polls of a condition
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Closure polled until it reports the awaited state, as created by `expect_condition!`
+pub struct Condition<F> {
+    probe: Rc<F>,
+}
+
+impl<F> Condition<F> {
+    /// Wrap a closure that is called on every poll
+    pub fn new(probe: F) -> Self {
+        return Self { probe: Rc::new(probe) };
+    }
+}
+
+impl<F> Clone for Condition<F> {
+    fn clone(&self) -> Self {
+        Self { probe: Rc::clone(&self.probe) }
+    }
+}
+
+impl<F> Debug for Condition<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Condition")
+    }
+}
+
+/// Outcome of polling a condition until it is met or the timeout expires
+struct Polling<T> {
+    met: bool,
+    attempts: usize,
+    elapsed: Duration,
+    last: Option<T>,
+}
+
+impl<T: Debug> Polling<T> {
+    /// Poll the probe until the predicate holds or the timeout expires, probing at least once
+    fn run(probe: impl Fn() -> T, predicate: impl Fn(&T) -> bool, timeout: Duration) -> Self {
+        let start = Instant::now();
+        let mut attempts = 0;
+
+        loop {
+            let value = probe();
+            attempts += 1;
+
+            let met = predicate(&value);
+            let elapsed = start.elapsed();
+            if met || elapsed >= timeout {
+                return Self { met, attempts, elapsed, last: Some(value) };
+            }
+
+            thread::sleep(POLL_INTERVAL.min(timeout - elapsed));
+        }
+    }
+
+    /// Attach the last polled value and the progress made to a sentence
+    fn describe(&self, sentence: AssertionSentence) -> AssertionSentence {
+        let attempts = if self.attempts == 1 { "1 attempt".to_string() } else { format!("{} attempts", self.attempts) };
+
+        return sentence
+            .with_actual(format!("{:?}", self.last.as_ref().expect("a condition is polled at least once")))
+            .with_detail(format!("polled {} over {:?}", attempts, self.elapsed));
+    }
+}
+
+/// Trait for conditions polled until they hold, such as flags set by another thread or messages sent on a channel
+pub trait ConditionMatchers<T> {
+    /// Poll the condition until it returns `true`, failing once the timeout expires
+    #[track_caller]
+    fn to_become_true_within(self, timeout: Duration) -> Self
+    where
+        T: Into<bool> + Copy;
+
+    /// Poll the condition until its value satisfies the predicate, failing once the timeout expires
+    #[track_caller]
+    fn to_satisfy_within<P: Fn(&T) -> bool>(self, timeout: Duration, predicate: P) -> Self;
+}
+
+impl<T, F> ConditionMatchers<T> for Assertion<Condition<F>>
+where
+    T: Debug,
+    F: Fn() -> T,
+{
+    fn to_become_true_within(self, timeout: Duration) -> Self
+    where
+        T: Into<bool> + Copy,
+    {
+        let polling = Polling::run(|| (self.value.probe)(), |value| (*value).into(), timeout);
+
+        return self.add_step_with(polling.met, |_| {
+            return polling.describe(AssertionSentence::new("become", format!("true within {:?}", timeout)));
+        });
+    }
+
+    fn to_satisfy_within<P: Fn(&T) -> bool>(self, timeout: Duration, predicate: P) -> Self {
+        let polling = Polling::run(|| (self.value.probe)(), predicate, timeout);
+
+        return self.add_step_with(polling.met, |_| {
+            return polling.describe(AssertionSentence::new("satisfy", format!("the predicate within {:?}", timeout)));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_condition_matchers() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let ready = Arc::new(AtomicBool::new(false));
+        let setter = Arc::clone(&ready);
+        let worker = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(30));
+            setter.store(true, Ordering::SeqCst);
+        });
+
+        expect_condition!(|| ready.load(Ordering::SeqCst)).to_become_true_within(Duration::from_secs(5));
+        worker.join().unwrap();
+
+        let idle = AtomicBool::new(false);
+        expect_condition!(|| idle.load(Ordering::SeqCst)).not().to_become_true_within(Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_condition_on_channel() {
+        crate::Reporter::disable_deduplication();
+
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            sender.send("done").unwrap();
+        });
+
+        expect_condition!(|| receiver.try_recv()).to_satisfy_within(Duration::from_secs(5), |message| message == &Ok("done"));
+    }
+
+    #[test]
+    fn test_condition_is_polled_until_met() {
+        crate::Reporter::disable_deduplication();
+
+        let polls = AtomicUsize::new(0);
+        expect_condition!(|| polls.fetch_add(1, Ordering::SeqCst) + 1).to_satisfy_within(Duration::from_secs(5), |count| *count == 3);
+
+        expect!(polls.load(Ordering::SeqCst)).to_equal(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "become true within 20ms (got false)\n  polled")]
+    fn test_condition_timeout_reports_progress() {
+        let flag = AtomicBool::new(false);
+        let _assertion = expect_condition!(|| flag.load(Ordering::SeqCst)).to_become_true_within(Duration::from_millis(20));
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,153 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+
+/// Trait for ControlFlow<B, C> assertions, as returned by visitors and `try_for_each`-style iteration
+///
+/// Flows that can't break or continue, such as `ControlFlow<Infallible, T>`, are supported as well.
+pub trait ControlFlowMatchers<B: Debug, C: Debug> {
+    #[track_caller]
+    fn to_break(self) -> Self;
+    #[track_caller]
+    fn to_continue(self) -> Self;
+    #[track_caller]
+    fn to_break_with<U: PartialEq<B> + Debug>(self, expected: &U) -> Self;
+    #[track_caller]
+    fn to_continue_with<U: PartialEq<C> + Debug>(self, expected: &U) -> Self;
+}
+
+/// Helper trait for ControlFlow-like types
+trait AsControlFlow<B: Debug + Clone, C: Debug + Clone> {
+    fn is_break_flow(&self) -> bool;
+    fn breaks_with<U: PartialEq<B> + Debug>(&self, expected: &U) -> bool;
+    fn continues_with<U: PartialEq<C> + Debug>(&self, expected: &U) -> bool;
+}
+
+// Implementation for ControlFlow<B, C>
+impl<B: Debug + Clone, C: Debug + Clone> AsControlFlow<B, C> for ControlFlow<B, C> {
+    fn is_break_flow(&self) -> bool {
+        self.is_break()
+    }
+
+    fn breaks_with<U: PartialEq<B> + Debug>(&self, expected: &U) -> bool {
+        match self {
+            ControlFlow::Break(actual) => expected == actual,
+            ControlFlow::Continue(_) => false,
+        }
+    }
+
+    fn continues_with<U: PartialEq<C> + Debug>(&self, expected: &U) -> bool {
+        match self {
+            ControlFlow::Break(_) => false,
+            ControlFlow::Continue(actual) => expected == actual,
+        }
+    }
+}
+
+// Implementation for &ControlFlow<B, C>
+impl<B: Debug + Clone, C: Debug + Clone> AsControlFlow<B, C> for &ControlFlow<B, C> {
+    fn is_break_flow(&self) -> bool {
+        self.is_break()
+    }
+
+    fn breaks_with<U: PartialEq<B> + Debug>(&self, expected: &U) -> bool {
+        match self {
+            ControlFlow::Break(actual) => expected == actual,
+            ControlFlow::Continue(_) => false,
+        }
+    }
+
+    fn continues_with<U: PartialEq<C> + Debug>(&self, expected: &U) -> bool {
+        match self {
+            ControlFlow::Break(_) => false,
+            ControlFlow::Continue(actual) => expected == actual,
+        }
+    }
+}
+
+// Single implementation for any type that implements AsControlFlow
+impl<V, B, C> ControlFlowMatchers<B, C> for Assertion<V>
+where
+    B: Debug + Clone,
+    C: Debug + Clone,
+    V: AsControlFlow<B, C> + Debug + Clone,
+{
+    fn to_break(self) -> Self {
+        let result = self.value.is_break_flow();
+
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "break").with_actual(format!("{:?}", actual)));
+    }
+
+    fn to_continue(self) -> Self {
+        let result = !self.value.is_break_flow();
+
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "continue").with_actual(format!("{:?}", actual)));
+    }
+
+    fn to_break_with<U: PartialEq<B> + Debug>(self, expected: &U) -> Self {
+        let result = self.value.breaks_with(expected);
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("break", format!("with {:?}", expected)).with_actual(format!("{:?}", actual));
+        });
+    }
+
+    fn to_continue_with<U: PartialEq<C> + Debug>(self, expected: &U) -> Self {
+        let result = self.value.continues_with(expected);
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("continue", format!("with {:?}", expected)).with_actual(format!("{:?}", actual));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::convert::Infallible;
+    use std::ops::ControlFlow;
+
+    /// Find the first negative number, as a visitor would
+    fn first_negative(values: &[i32]) -> ControlFlow<i32> {
+        return values.iter().try_for_each(|value| if *value < 0 { ControlFlow::Break(*value) } else { ControlFlow::Continue(()) });
+    }
+
+    #[test]
+    fn test_control_flow_matchers() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect!(first_negative(&[3, -2, -5])).to_break();
+        expect!(first_negative(&[3, -2, -5])).to_break_with(&-2);
+        expect!(first_negative(&[3, -2])).not().to_break_with(&3);
+        let completed = first_negative(&[1, 2]);
+        expect!(completed).to_continue();
+        expect!(&completed).to_continue_with(&());
+        expect!(completed).not().to_break();
+    }
+
+    #[test]
+    fn test_flows_that_never_break() {
+        crate::Reporter::disable_deduplication();
+
+        let flow: ControlFlow<Infallible, u8> = ControlFlow::Continue(7);
+
+        expect!(flow).to_continue_with(&7);
+        expect!(flow).not().to_break();
+    }
+
+    #[test]
+    #[should_panic(expected = "break with -1 (got Continue(()))")]
+    fn test_continue_does_not_break_with() {
+        let _assertion = expect!(first_negative(&[1])).to_break_with(&-1);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be continue (got Break(-4))")]
+    fn test_break_does_not_continue() {
+        let _assertion = expect!(first_negative(&[-4])).to_continue();
+        std::hint::black_box(_assertion);
+    }
+}
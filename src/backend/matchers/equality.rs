@@ -1,12 +1,15 @@
 use crate::backend::Assertion;
 use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::diff::Diff;
 use std::fmt::Debug;
 
 pub trait EqualityMatchers<T> {
     /// Check if the value is equal to the expected value
+    #[track_caller]
     fn to_equal(self, expected: T) -> Self;
 
     /// Type-specific version of to_equal to avoid trait conflicts
+    #[track_caller]
     fn to_equal_value(self, expected: T) -> Self;
 }
 
@@ -41,9 +44,22 @@ where
 
     fn to_equal_value(self, expected: T) -> Self {
         let result = self.value.equals(&expected);
-        let sentence = AssertionSentence::new("be", format!("equal to {:?}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            // Multi-line strings are compared line by line rather than printed whole
+            if let Some(diff) = Diff::debug_lines(actual, &expected) {
+                let sentence = AssertionSentence::new("be", "equal to the expected text").with_expected(format!("{:?}", expected));
+                return match diff.change_count() {
+                    0 => sentence.with_actual("identical text"),
+                    1 => sentence.with_actual("1 differing line").with_diff(diff),
+                    count => sentence.with_actual(format!("{} differing lines", count)).with_diff(diff),
+                };
+            }
+
+            return AssertionSentence::new("be", format!("equal to {:?}", expected))
+                .with_expected(format!("{:?}", expected))
+                .with_actual(format!("{:?}", actual));
+        });
     }
 }
 
@@ -83,6 +99,16 @@ mod tests {
         std::hint::black_box(_assertion);
     }
 
+    #[test]
+    #[should_panic(
+        expected = "be equal to the expected text (got 1 differing line)\n  line 1: ok\n  line 2: expected `port = 80`, got `port = 8080`"
+    )]
+    fn test_multi_line_strings_fail_with_a_line_diff() {
+        let config = String::from("host = \"localhost\"\nport = 8080");
+        let _assertion = expect!(config).to_equal(String::from("host = \"localhost\"\nport = 80"));
+        std::hint::black_box(_assertion);
+    }
+
     #[test]
     #[should_panic(expected = "not be equal to")]
     fn test_equality_not_fails() {
@@ -0,0 +1,342 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::assertions::type_name;
+use std::error::Error;
+use std::fmt::Debug;
+
+/// Trait for assertions on type-erased errors, such as `Result<T, Box<dyn Error>>`, `anyhow::Result<T>` or
+/// `eyre::Report`
+///
+/// The error is downcast to the expected type, so tests don't need to write the downcast themselves. Owned boxed
+/// errors can't be cloned, so assert on a reference: `expect!(&result).to_be_err_of_type::<io::Error>()`.
+pub trait ErrorMatchers {
+    /// Check that the result is an error of the given concrete type
+    #[track_caller]
+    fn to_be_err_of_type<E: Error + Send + Sync + 'static>(self) -> Self;
+
+    /// Check that the message of an error in the chain, from the outermost context to the root cause, contains the text
+    #[track_caller]
+    fn to_have_context_containing(self, text: &str) -> Self;
+
+    /// Check that the last error of the chain, which the contexts wrap, is of the given concrete type
+    #[track_caller]
+    fn to_have_root_cause_of_type<E: Error + Send + Sync + 'static>(self) -> Self;
+}
+
+/// Helper trait for results whose error type is erased
+#[diagnostic::on_unimplemented(
+    message = "`ErrorMatchers` can't check `{Self}`, which isn't a result with a boxed or report error",
+    label = "not a result with a boxed or report error",
+    note = "`ErrorMatchers` apply to `&Result<T, Box<dyn Error>>`, and with the `anyhow` or `eyre` features to their reports and results"
+)]
+trait AsDynError {
+    /// The error, or `None` for an ok result
+    fn dyn_error(&self) -> Option<&(dyn Error + 'static)>;
+
+    /// Debug rendering of the ok value, or `None` for an error
+    fn ok_debug(&self) -> Option<String>;
+
+    /// Whether the error is of the given type
+    fn is_err_of_type<E: Error + Send + Sync + 'static>(&self) -> bool {
+        return self.dyn_error().is_some_and(|error| error.is::<E>());
+    }
+}
+
+impl<T: Debug> AsDynError for &Result<T, Box<dyn Error>> {
+    fn dyn_error(&self) -> Option<&(dyn Error + 'static)> {
+        return self.as_ref().err().map(|error| error.as_ref());
+    }
+
+    fn ok_debug(&self) -> Option<String> {
+        return self.as_ref().ok().map(|value| format!("{:?}", value));
+    }
+}
+
+impl<T: Debug> AsDynError for &Result<T, Box<dyn Error + Send + Sync>> {
+    fn dyn_error(&self) -> Option<&(dyn Error + 'static)> {
+        return self.as_ref().err().map(|error| error.as_ref() as &(dyn Error + 'static));
+    }
+
+    fn ok_debug(&self) -> Option<String> {
+        return self.as_ref().ok().map(|value| format!("{:?}", value));
+    }
+}
+
+// Reports of error-handling crates, asserted on their own or in results; their downcast also finds the error under
+// the context attached to it
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+macro_rules! impl_report_error {
+    ($report:ty) => {
+        impl<T: Debug> AsDynError for &Result<T, $report> {
+            fn dyn_error(&self) -> Option<&(dyn Error + 'static)> {
+                return self.as_ref().err().map(|error| error.as_ref() as &(dyn Error + 'static));
+            }
+
+            fn ok_debug(&self) -> Option<String> {
+                return self.as_ref().ok().map(|value| format!("{:?}", value));
+            }
+
+            fn is_err_of_type<E: Error + Send + Sync + 'static>(&self) -> bool {
+                return self.as_ref().err().is_some_and(|error| error.downcast_ref::<E>().is_some());
+            }
+        }
+
+        impl AsDynError for &$report {
+            fn dyn_error(&self) -> Option<&(dyn Error + 'static)> {
+                return Some(self.as_ref() as &(dyn Error + 'static));
+            }
+
+            fn ok_debug(&self) -> Option<String> {
+                return None;
+            }
+
+            fn is_err_of_type<E: Error + Send + Sync + 'static>(&self) -> bool {
+                return self.downcast_ref::<E>().is_some();
+            }
+        }
+    };
+}
+
+#[cfg(feature = "anyhow")]
+impl_report_error!(anyhow::Error);
+
+#[cfg(feature = "eyre")]
+impl_report_error!(eyre::Report);
+
+/// Errors of the chain, from the given one to its root cause
+fn error_chain<'a>(error: &'a (dyn Error + 'static)) -> Vec<&'a (dyn Error + 'static)> {
+    return std::iter::successors(Some(error), |&error| error.source()).collect();
+}
+
+/// Last error of the chain of a result, or `None` for an ok result
+fn root_cause(value: &impl AsDynError) -> Option<&(dyn Error + 'static)> {
+    return value.dyn_error().and_then(|error| error_chain(error).last().copied());
+}
+
+/// Render an error chain as its messages separated by colons, like `{:#}` does for anyhow errors
+fn format_chain(error: &(dyn Error + 'static)) -> String {
+    return error_chain(error).iter().map(|error| error.to_string()).collect::<Vec<_>>().join(": ");
+}
+
+/// Name the concrete type of an error: probed among the standard library errors, otherwise read from its `Debug`
+/// rendering, which starts with the struct or enum variant name for derived implementations
+fn error_type_name(error: &(dyn Error + 'static)) -> String {
+    macro_rules! probe_types {
+        ($($t:ty),* $(,)?) => {
+            $(
+                if error.is::<$t>() {
+                    return type_name::describe(std::any::type_name::<$t>(), None, crate::config::type_names());
+                }
+            )*
+        };
+    }
+
+    probe_types!(
+        std::io::Error,
+        std::fmt::Error,
+        std::num::ParseIntError,
+        std::num::ParseFloatError,
+        std::num::TryFromIntError,
+        std::str::ParseBoolError,
+        std::str::Utf8Error,
+        std::string::FromUtf8Error,
+        std::char::ParseCharError,
+        std::net::AddrParseError,
+        std::env::VarError,
+        std::ffi::NulError,
+        std::time::SystemTimeError,
+    );
+
+    let debug = format!("{:?}", error);
+    let name_length = debug.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(debug.len());
+
+    return match &debug[..name_length] {
+        name if name.starts_with(|c: char| c.is_ascii_uppercase()) => name.to_string(),
+        _ => "dyn Error".to_string(),
+    };
+}
+
+// Single implementation for any type that implements AsDynError
+impl<V> ErrorMatchers for Assertion<V>
+where
+    V: AsDynError + Debug + Clone,
+{
+    fn to_be_err_of_type<E: Error + Send + Sync + 'static>(self) -> Self {
+        let result = self.value.is_err_of_type::<E>();
+
+        return self.add_step_with(result, |actual| {
+            let expected = type_name::describe(std::any::type_name::<E>(), None, crate::config::type_names());
+            let sentence = AssertionSentence::new("be", format!("err of type {}", expected));
+
+            return match actual.dyn_error() {
+                Some(error) => sentence.with_actual(format!("{}: {}", error_type_name(error), error)),
+                None => sentence.with_actual(format!("Ok: {}", actual.ok_debug().unwrap_or_default())),
+            };
+        });
+    }
+
+    fn to_have_context_containing(self, text: &str) -> Self {
+        let result = self.value.dyn_error().is_some_and(|error| error_chain(error).iter().any(|error| error.to_string().contains(text)));
+
+        return self.add_step_with(result, |actual| {
+            let sentence = AssertionSentence::new("have", format!("context containing {:?}", text));
+
+            return match actual.dyn_error() {
+                Some(error) => sentence.with_actual(format!("{:?}", format_chain(error))),
+                None => sentence.with_actual(format!("Ok: {}", actual.ok_debug().unwrap_or_default())),
+            };
+        });
+    }
+
+    fn to_have_root_cause_of_type<E: Error + Send + Sync + 'static>(self) -> Self {
+        let result = root_cause(&self.value).is_some_and(|error| error.is::<E>());
+
+        return self.add_step_with(result, |actual| {
+            let expected = type_name::describe(std::any::type_name::<E>(), None, crate::config::type_names());
+            let sentence = AssertionSentence::new("have", format!("root cause of type {}", expected));
+
+            return match root_cause(actual) {
+                Some(error) => sentence.with_actual(format!("{}: {}", error_type_name(error), error)),
+                None => sentence.with_actual(format!("Ok: {}", actual.ok_debug().unwrap_or_default())),
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::io;
+    use std::num::ParseIntError;
+
+    #[derive(Debug)]
+    struct ConfigError {
+        key: &'static str,
+    }
+
+    impl std::fmt::Display for ConfigError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "missing key {}", self.key)
+        }
+    }
+
+    impl Error for ConfigError {}
+
+    fn parse(text: &str) -> Result<u8, Box<dyn Error>> {
+        return Ok(text.parse::<u8>()?);
+    }
+
+    #[test]
+    fn test_to_be_err_of_type() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let invalid = parse("two");
+        expect!(&invalid).to_be_err_of_type::<ParseIntError>();
+        expect!(&invalid).not().to_be_err_of_type::<io::Error>();
+        let valid = parse("2");
+        expect!(&valid).not().to_be_err_of_type::<ParseIntError>();
+
+        let shared: Result<(), Box<dyn Error + Send + Sync>> = Err(Box::new(ConfigError { key: "port" }));
+        expect!(&shared).to_be_err_of_type::<ConfigError>();
+    }
+
+    #[test]
+    fn test_error_type_name() {
+        let not_found = io::Error::new(io::ErrorKind::NotFound, "config.toml");
+        let message: Box<dyn Error> = "plain message".into();
+
+        assert_eq!(error_type_name(&not_found), "Error");
+        assert_eq!(error_type_name(&"x".parse::<u8>().unwrap_err()), "ParseIntError");
+        assert_eq!(error_type_name(&ConfigError { key: "port" }), "ConfigError");
+        assert_eq!(error_type_name(message.as_ref()), "dyn Error");
+    }
+
+    #[test]
+    #[should_panic(expected = "be err of type Error (got ParseIntError: invalid digit found in string)")]
+    fn test_wrong_error_type_fails() {
+        let result = parse("two");
+        let _assertion = expect!(&result).to_be_err_of_type::<io::Error>();
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be err of type ParseIntError (got Ok: 2)")]
+    fn test_ok_fails() {
+        let result = parse("2");
+        let _assertion = expect!(&result).to_be_err_of_type::<ParseIntError>();
+        std::hint::black_box(_assertion);
+    }
+
+    #[derive(Debug)]
+    struct LoadError {
+        source: ParseIntError,
+    }
+
+    impl std::fmt::Display for LoadError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "while reading config")
+        }
+    }
+
+    impl Error for LoadError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.source)
+        }
+    }
+
+    #[test]
+    fn test_error_chains() {
+        crate::Reporter::disable_deduplication();
+
+        let result: Result<u8, Box<dyn Error>> = Err(Box::new(LoadError { source: "two".parse::<u8>().unwrap_err() }));
+        expect!(&result).to_have_context_containing("while reading config");
+        expect!(&result).to_have_context_containing("invalid digit");
+        expect!(&result).not().to_have_context_containing("while writing");
+        expect!(&result).to_have_root_cause_of_type::<ParseIntError>();
+        expect!(&result).not().to_have_root_cause_of_type::<LoadError>();
+    }
+
+    #[test]
+    #[should_panic(expected = "have context containing \"while writing\" (got \"while reading config: invalid digit found in string\")")]
+    fn test_missing_context_fails() {
+        let result: Result<u8, Box<dyn Error>> = Err(Box::new(LoadError { source: "two".parse::<u8>().unwrap_err() }));
+        let _assertion = expect!(&result).to_have_context_containing("while writing");
+        std::hint::black_box(_assertion);
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn test_anyhow_errors_are_downcast_through_context() {
+        use anyhow::Context;
+
+        crate::Reporter::disable_deduplication();
+
+        let result: anyhow::Result<u8> = "two".parse::<u8>().context("while reading the port").context("while loading config");
+        expect!(&result).to_be_err_of_type::<ParseIntError>();
+        expect!(&result).not().to_be_err_of_type::<ConfigError>();
+        expect!(&result).to_have_context_containing("reading the port");
+        expect!(&result).to_have_root_cause_of_type::<ParseIntError>();
+
+        let error = result.unwrap_err();
+        expect!(&error).to_have_context_containing("loading config");
+        expect!(&error).not().to_have_root_cause_of_type::<io::Error>();
+    }
+
+    #[cfg(feature = "eyre")]
+    #[test]
+    fn test_eyre_reports() {
+        use eyre::WrapErr;
+
+        crate::Reporter::disable_deduplication();
+
+        let result: eyre::Result<u8> = "two".parse::<u8>().wrap_err("while reading the port");
+        expect!(&result).to_be_err_of_type::<ParseIntError>();
+        expect!(&result).to_have_context_containing("reading the port");
+        expect!(&result).to_have_root_cause_of_type::<ParseIntError>();
+
+        let report = result.unwrap_err();
+        expect!(&report).not().to_have_context_containing("writing");
+    }
+}
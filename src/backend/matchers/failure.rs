@@ -0,0 +1,167 @@
+use crate::backend::assertions::capture;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::{Assertion, LogicalOp};
+use std::any::Any;
+
+/// Outcome of a closure run by [`expect_failure!`](crate::expect_failure), with the assertion that failed in it
+///
+/// Assertions in the closure are neither reported nor counted, and the panic of a failed one is kept silent.
+#[derive(Debug, Clone)]
+pub struct ExpectedFailure {
+    failure: Option<Assertion<()>>,
+    panic_message: Option<String>,
+}
+
+impl ExpectedFailure {
+    /// Run the closure, capturing the first failed assertion instead of failing the test
+    pub fn capture<R>(f: impl FnOnce() -> R) -> Self {
+        let (result, failure) = capture::capture_failure(f);
+        let panic_message = result.err().map(|payload| panic_message(&payload));
+        return Self { failure, panic_message };
+    }
+
+    /// The failed assertion, without its value
+    pub fn failure(&self) -> Option<&Assertion<()>> {
+        return self.failure.as_ref();
+    }
+
+    /// Message of the failed assertion: `expected <expr> to <sentence>, got <actual>`
+    pub fn message(&self) -> Option<String> {
+        return self.failure.as_ref().map(|failure| failure.format_stable_message());
+    }
+
+    /// What the closure did, for failure messages
+    fn outcome(&self) -> String {
+        return match (&self.failure, &self.panic_message) {
+            (Some(_), _) => format!("failed with {:?}", self.message().unwrap_or_default()),
+            (None, Some(message)) => format!("panicked with {:?}", message),
+            (None, None) => "passed".to_string(),
+        };
+    }
+}
+
+/// Trait for assertions on the outcome of [`expect_failure!`](crate::expect_failure)
+///
+/// Tests custom matchers on their failing side without `#[should_panic]`, which only tells that something in the
+/// whole test panicked.
+pub trait FailureMatchers {
+    /// Check that an assertion failed in the closure; other panics don't count
+    #[track_caller]
+    fn to_fail(self) -> Self;
+
+    /// Check that an assertion failed with a message containing the text
+    #[track_caller]
+    fn with_message_containing(self, text: &str) -> Self;
+}
+
+impl FailureMatchers for Assertion<ExpectedFailure> {
+    fn to_fail(self) -> Self {
+        let result = self.value.failure.is_some();
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("fail", "an assertion").with_actual(actual.outcome());
+        });
+    }
+
+    fn with_message_containing(mut self, text: &str) -> Self {
+        // Follows `to_fail` in the same chain rather than evaluating it on its own
+        self.mark_as_intermediate();
+        self.set_last_logic(LogicalOp::And);
+        let result = self.value.message().is_some_and(|message| message.contains(text));
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("fail", format!("with a message containing {:?}", text)).with_actual(actual.outcome());
+        });
+    }
+}
+
+/// Message of a panic payload, as passed to `panic!`
+pub(crate) fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return "non-string panic payload".to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_failed_assertion_is_captured() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let x = 3;
+        expect_failure!(|| {
+            expect!(x).to_equal(5);
+        })
+        .with_message_containing("equal to 5");
+    }
+
+    #[test]
+    fn test_message_of_the_failed_assertion() {
+        let failure = ExpectedFailure::capture(|| {
+            let name = "rest";
+            expect!(name).to_have_length(5);
+        });
+
+        expect!(failure.message()).to_equal(Some("expected name to have length 5, got 4".to_string()));
+        expect!(failure.failure().map(|failure| failure.expr_str)).to_equal(Some("name"));
+    }
+
+    #[test]
+    fn test_own_matchers_fail_as_documented() {
+        let values = vec![1, 2, 3];
+        expect_failure!(|| {
+            expect!(&values).to_contain(4);
+        })
+        .with_message_containing("contain 4");
+
+        expect_failure!(|| {
+            expect!(2).not().to_be_less_than(3);
+        })
+        .with_message_containing("not be less than 3");
+    }
+
+    #[test]
+    fn test_passing_closure_is_reported() {
+        let failure = ExpectedFailure::capture(|| {
+            expect!(1).to_equal(1);
+        });
+
+        expect!(failure.message()).to_be_none();
+        expect!(failure.outcome()).to_equal("passed".to_string());
+    }
+
+    #[test]
+    fn test_plain_panics_are_not_assertion_failures() {
+        let failure = ExpectedFailure::capture(|| panic!("boom"));
+
+        expect!(failure.failure().is_none()).to_be_true();
+        expect!(failure.outcome()).to_equal("panicked with \"boom\"".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "fail an assertion")]
+    fn test_passing_closure_fails_the_expectation() {
+        let _assertion = expect_failure!(|| {
+            expect!(1).to_equal(1);
+        });
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "with a message containing")]
+    fn test_other_message_fails_the_expectation() {
+        let _assertion = expect_failure!(|| {
+            expect!(1).to_equal(2);
+        })
+        .with_message_containing("greater than");
+        std::hint::black_box(_assertion);
+    }
+}
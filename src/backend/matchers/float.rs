@@ -0,0 +1,217 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::{AssertionSentence, MatcherDocs};
+use crate::backend::number::{FormatNumber, format_numbers};
+use std::fmt::Debug;
+
+/// Documentation of `to_equal_within_ulps`, shown under its failures with `config().matcher_docs(true)`
+const ULPS_DOCS_URL: &str = "https://github.com/mister-good-deal/rest/wiki/Float-Slice-Matchers#to_equal_within_ulps";
+
+/// Trait for float slice and array assertions, comparing elements by units in the last place (ULPs)
+///
+/// Unlike an absolute epsilon, a ULP tolerance scales with the magnitude of the values, which suits results of
+/// vectorized or reordered float computations. Supported for `f32` and `f64` slices, arrays and vectors.
+pub trait FloatSliceMatchers<F> {
+    /// Check that every element is at most `max_ulps` representable floats away from the expected one
+    #[track_caller]
+    fn to_equal_within_ulps(self, expected: &[F], max_ulps: u64) -> Self;
+}
+
+/// Helper trait for floats whose distance can be measured in ULPs
+trait UlpFloat: Copy + Debug + FormatNumber {
+    /// Number of representable floats between two values, `u64::MAX` when either is NaN
+    fn ulp_distance(self, other: Self) -> u64;
+}
+
+macro_rules! impl_ulp_float {
+    ($($t:ty => $int:ty),*) => {
+        $(
+            impl UlpFloat for $t {
+                fn ulp_distance(self, other: Self) -> u64 {
+                    if self.is_nan() || other.is_nan() {
+                        return u64::MAX;
+                    }
+
+                    // Map the sign-magnitude bits to integers ordered like the floats, so that both zeros meet at 0
+                    let ordered = |value: $t| {
+                        let bits = value.to_bits() as $int;
+                        return if bits < 0 { <$int>::MIN - bits } else { bits };
+                    };
+
+                    return (ordered(self) as i128 - ordered(other) as i128).unsigned_abs() as u64;
+                }
+            }
+        )*
+    };
+}
+
+impl_ulp_float!(f32 => i32, f64 => i64);
+
+/// Helper trait for types that can be examined as float slices
+trait AsFloatSlice {
+    type Float: UlpFloat;
+
+    fn floats(&self) -> &[Self::Float];
+}
+
+impl<F: UlpFloat> AsFloatSlice for Vec<F> {
+    type Float = F;
+
+    fn floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: UlpFloat> AsFloatSlice for &Vec<F> {
+    type Float = F;
+
+    fn floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: UlpFloat> AsFloatSlice for &[F] {
+    type Float = F;
+
+    fn floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: UlpFloat, const N: usize> AsFloatSlice for [F; N] {
+    type Float = F;
+
+    fn floats(&self) -> &[F] {
+        self
+    }
+}
+
+impl<F: UlpFloat, const N: usize> AsFloatSlice for &[F; N] {
+    type Float = F;
+
+    fn floats(&self) -> &[F] {
+        *self
+    }
+}
+
+/// Element differing the most from the expected one
+struct WorstOffender<F> {
+    index: usize,
+    actual: F,
+    expected: F,
+    distance: u64,
+}
+
+/// Find the element with the largest ULP distance, if any element differs at all
+fn worst_offender<F: UlpFloat>(actual: &[F], expected: &[F]) -> Option<WorstOffender<F>> {
+    return actual
+        .iter()
+        .zip(expected)
+        .enumerate()
+        .map(|(index, (&actual, &expected))| WorstOffender { index, actual, expected, distance: actual.ulp_distance(expected) })
+        .filter(|offender| offender.distance > 0)
+        .max_by_key(|offender| offender.distance);
+}
+
+/// Render a ULP distance, spelling out the one returned for NaN
+fn format_ulps(distance: u64) -> String {
+    return match distance {
+        u64::MAX => "NaN".to_string(),
+        1 => "1 ULP".to_string(),
+        _ => format!("{} ULPs", distance),
+    };
+}
+
+// Single implementation of FloatSliceMatchers for any type that implements AsFloatSlice
+impl<F, V> FloatSliceMatchers<F> for Assertion<V>
+where
+    F: UlpFloat,
+    V: AsFloatSlice<Float = F> + Debug + Clone,
+{
+    fn to_equal_within_ulps(self, expected: &[F], max_ulps: u64) -> Self {
+        let actual = self.value.floats();
+        let same_length = actual.len() == expected.len();
+        let offender = worst_offender(actual, expected);
+        let result = same_length && offender.as_ref().is_none_or(|offender| offender.distance <= max_ulps);
+
+        return self.add_step_with(result, |_| {
+            let docs = MatcherDocs::new(format!(
+                "that each element is at most {} representable floats away from the expected one, a tolerance scaling with their magnitude",
+                max_ulps
+            ));
+            let sentence = AssertionSentence::new("equal", format!("{} within {}", format_numbers(expected), format_ulps(max_ulps)))
+                .with_docs(docs.with_url(ULPS_DOCS_URL));
+
+            return match offender {
+                _ if !same_length => sentence.with_actual(format!("{} elements, expected {}", actual.len(), expected.len())),
+                Some(offender) => sentence.with_actual(format!(
+                    "{} at index {}, expected {}, {} away",
+                    offender.actual.format_number(),
+                    offender.index,
+                    offender.expected.format_number(),
+                    format_ulps(offender.distance)
+                )),
+                None => sentence.with_actual(format_numbers(actual)),
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_ulp_distance() {
+        assert_eq!(1.0_f64.ulp_distance(1.0), 0);
+        assert_eq!(1.0_f64.ulp_distance(1.0 + f64::EPSILON), 1);
+        assert_eq!(0.0_f64.ulp_distance(-0.0), 0);
+        assert_eq!(f32::from_bits(1).ulp_distance(-f32::from_bits(1)), 2);
+        assert_eq!(f64::MAX.ulp_distance(f64::INFINITY), 1);
+        assert_eq!(f32::NAN.ulp_distance(f32::NAN), u64::MAX);
+        assert_eq!(f64::MIN.ulp_distance(f64::MAX), 2 * f64::MAX.to_bits());
+    }
+
+    #[test]
+    fn test_float_slice_matchers() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let sum = (0..10).map(|_| 0.1_f64).sum::<f64>();
+        let computed = vec![sum, 0.3_f64, -0.0];
+        expect!(&computed).to_equal_within_ulps(&[1.0, 0.1 + 0.2, 0.0], 2);
+        expect!(&computed).not().to_equal_within_ulps(&[1.0, 0.1 + 0.2, 0.0], 0);
+        expect!(computed).not().to_equal_within_ulps(&[1.0, 0.3], 2);
+
+        let samples = [1.0_f32, 2.0, 3.0];
+        expect!(samples).to_equal_within_ulps(&[1.0, 2.0, 3.0], 0);
+        expect!(&samples[..2]).to_equal_within_ulps(&[1.0, 2.0], 0);
+        expect!(&samples).not().to_equal_within_ulps(&[1.0, f32::NAN, 3.0], u64::MAX - 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "equal [1.0, 2.0, 3.0] within 1 ULP (got 2.0000005 at index 1, expected 2.0, 2 ULPs away)")]
+    fn test_worst_offender_is_reported() {
+        let samples = vec![1.0000001_f32, 2.0000005, 3.0];
+        let _assertion = expect!(samples).to_equal_within_ulps(&[1.0, 2.0, 3.0], 1);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "equal [1.0] within 4 ULPs (got 2 elements, expected 1)")]
+    fn test_length_mismatch_fails() {
+        let _assertion = expect!(vec![1.0_f64, 2.0]).to_equal_within_ulps(&[1.0], 4);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_failures_document_ulps() {
+        let failure = crate::backend::matchers::failure::ExpectedFailure::capture(|| {
+            expect!(vec![1.5_f64]).to_equal_within_ulps(&[1.0], 4);
+        });
+
+        let docs = failure.failure().and_then(|failure| failure.steps[0].docs()).expect("docs of the ULP matcher");
+        assert!(docs.summary.starts_with("that each element is at most 4 representable floats away"));
+        assert_eq!(docs.url.as_deref(), Some(ULPS_DOCS_URL));
+    }
+}
@@ -0,0 +1,325 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::assertions::source::source_path;
+use crate::backend::matchers::snapshot::{remove_review_files, with_review, write_review_file};
+use crate::backend::matchers::string::strip_ansi;
+use std::cell::Cell;
+use std::fmt::Debug;
+use std::fs;
+use std::panic::Location;
+use std::path::{Path, PathBuf};
+
+// Directory of the snapshots, next to the source file of the test
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+// Extension of the snapshot files
+const SNAPSHOT_EXTENSION: &str = "frame";
+
+// Suffix of the file holding a frame that doesn't match its snapshot, such as `test_cart_view.frame.new`
+const NEW_SUFFIX: &str = ".new";
+
+// Number of differing cells listed under a failed step
+const LISTED_CELLS: usize = 10;
+
+thread_local! {
+    // Snapshots taken by the test running on this thread, numbering the following ones
+    static SNAPSHOTS_TAKEN: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Trait for snapshot assertions on the frames drawn by terminal user interfaces.
+///
+/// Frames are text as printed to the terminal, in strings or byte buffers, or ratatui `Buffer`s with the `ratatui`
+/// feature. ANSI codes, trailing whitespace and trailing blank lines are removed before comparing, so that only the
+/// characters on screen matter. Snapshots are stored as text files under a `snapshots` directory next to the source
+/// file of the test, and named after the test and its module path. A frame that doesn't match is written next to its
+/// snapshot with a `.new` suffix for review, and removed once the snapshot matches again.
+pub trait FrameMatchers {
+    /// Check that the frame renders like its snapshot, cell by cell
+    ///
+    /// A missing snapshot fails the test. `REST_UPDATE_SNAPSHOTS=1`, or
+    /// [`Config::update_snapshots`](crate::Config::update_snapshots), writes the missing snapshots and rewrites the ones
+    /// that don't match.
+    #[track_caller]
+    fn to_render_like_snapshot(self) -> Self;
+}
+
+/// Helper trait for the frame types
+trait AsFrame {
+    fn frame_text(&self) -> String;
+}
+
+impl<F: AsFrame + ?Sized> AsFrame for &F {
+    fn frame_text(&self) -> String {
+        return (**self).frame_text();
+    }
+}
+
+impl AsFrame for str {
+    fn frame_text(&self) -> String {
+        return self.to_string();
+    }
+}
+
+impl AsFrame for String {
+    fn frame_text(&self) -> String {
+        return self.clone();
+    }
+}
+
+impl AsFrame for [u8] {
+    fn frame_text(&self) -> String {
+        return String::from_utf8_lossy(self).into_owned();
+    }
+}
+
+impl AsFrame for Vec<u8> {
+    fn frame_text(&self) -> String {
+        return self.as_slice().frame_text();
+    }
+}
+
+#[cfg(feature = "ratatui")]
+impl AsFrame for ratatui_core::buffer::Buffer {
+    fn frame_text(&self) -> String {
+        use unicode_width::UnicodeWidthStr;
+
+        let mut text = String::new();
+        for row in self.content().chunks(self.area().width.max(1) as usize) {
+            // Wide symbols cover the cells that follow them
+            let mut covered = 0;
+            for cell in row {
+                if covered > 0 {
+                    covered -= 1;
+                    continue;
+                }
+                text.push_str(cell.symbol());
+                covered = cell.symbol().width().saturating_sub(1);
+            }
+            text.push('\n');
+        }
+
+        return text;
+    }
+}
+
+/// Lines of a frame as seen on screen: without ANSI codes, trailing whitespace and trailing blank lines
+fn normalize(text: &str) -> Vec<String> {
+    let mut lines = strip_ansi(text).lines().map(|line| line.trim_end().to_string()).collect::<Vec<_>>();
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+
+    return lines;
+}
+
+/// Cell whose character differs between the snapshot and the frame, blank past the end of a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CellDiff {
+    row: usize,
+    column: usize,
+    expected: char,
+    actual: char,
+}
+
+impl CellDiff {
+    /// Describe the cell with 1-based coordinates, as editors show them
+    fn describe(&self) -> String {
+        return format!("row {}, column {}: expected {:?}, got {:?}", self.row + 1, self.column + 1, self.expected, self.actual);
+    }
+}
+
+/// Compare two frames character by character
+fn diff_cells(expected: &[String], actual: &[String]) -> Vec<CellDiff> {
+    let mut cells = Vec::new();
+
+    for row in 0..expected.len().max(actual.len()) {
+        let expected_row = expected.get(row).map(|line| line.chars().collect::<Vec<_>>()).unwrap_or_default();
+        let actual_row = actual.get(row).map(|line| line.chars().collect::<Vec<_>>()).unwrap_or_default();
+
+        for column in 0..expected_row.len().max(actual_row.len()) {
+            let expected = expected_row.get(column).copied().unwrap_or(' ');
+            let actual = actual_row.get(column).copied().unwrap_or(' ');
+            if expected != actual {
+                cells.push(CellDiff { row, column, expected, actual });
+            }
+        }
+    }
+
+    return cells;
+}
+
+/// Outcome of checking a frame against its snapshot
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Matched,
+    /// The snapshot was missing or didn't match, and was written as asked
+    Written,
+    Missing,
+    Differs(Vec<CellDiff>),
+    Failed(String),
+}
+
+/// Check a frame against the snapshot at a path, writing the snapshot when it is missing or differs if asked to
+fn check_snapshot(path: &Path, frame: &[String], update: bool) -> Outcome {
+    let cells = match fs::read_to_string(path) {
+        Ok(snapshot) => diff_cells(&normalize(&snapshot), frame),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound && update => return write_snapshot(path, frame),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Outcome::Missing,
+        Err(error) => return Outcome::Failed(format!("unreadable snapshot: {}", error)),
+    };
+
+    return match (cells.is_empty(), update) {
+        (true, _) => Outcome::Matched,
+        (false, true) => write_snapshot(path, frame),
+        (false, false) => Outcome::Differs(cells),
+    };
+}
+
+fn write_snapshot(path: &Path, frame: &[String]) -> Outcome {
+    let written = path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|_| fs::write(path, frame_file(frame)));
+
+    return match written {
+        Ok(()) => Outcome::Written,
+        Err(error) => Outcome::Failed(format!("unwritable snapshot: {}", error)),
+    };
+}
+
+/// Content of a snapshot file, one line of the frame per line
+fn frame_file(frame: &[String]) -> String {
+    return frame.iter().map(|line| format!("{}\n", line)).collect();
+}
+
+/// Path of the file holding a frame that doesn't match the snapshot at a path
+fn new_frame_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(NEW_SUFFIX);
+    return path.with_file_name(name);
+}
+
+/// Path of the next snapshot of the running test: `<test>.frame`, then `<test>-2.frame` and so on
+///
+/// The test is named with its module path, joined with `__`, so that tests of the same name in different modules of a
+/// source file don't share a snapshot: `cart__tests__test_total.frame`.
+fn snapshot_path(location: &'static Location<'static>) -> PathBuf {
+    let source = source_path(location.file()).unwrap_or_else(|| PathBuf::from(location.file()));
+    let test = std::thread::current().name().map(|name| name.replace("::", "__")).unwrap_or_else(|| "snapshot".to_string());
+
+    let taken = SNAPSHOTS_TAKEN.with(|taken| {
+        taken.set(taken.get() + 1);
+        return taken.get();
+    });
+    let name = if taken == 1 { test } else { format!("{}-{}", test, taken) };
+
+    return source.with_file_name(SNAPSHOTS_DIR).join(name).with_extension(SNAPSHOT_EXTENSION);
+}
+
+// Single implementation for any type that implements AsFrame
+impl<V> FrameMatchers for Assertion<V>
+where
+    V: AsFrame + Debug + Clone,
+{
+    fn to_render_like_snapshot(self) -> Self {
+        let path = snapshot_path(Location::caller());
+        let frame = normalize(&self.value.frame_text());
+
+        // Under `not()`, a missing snapshot can't be recorded from a frame expected to differ
+        let outcome = match self.negated {
+            false => check_snapshot(&path, &frame, crate::config::is_updating_snapshots()),
+            true if path.is_file() => check_snapshot(&path, &frame, false),
+            true => Outcome::Failed("no snapshot to compare with".to_string()),
+        };
+        let result = matches!(outcome, Outcome::Matched | Outcome::Written);
+
+        // Frames expected to differ have nothing to review
+        let new_path = new_frame_path(&path);
+        let new = match (&outcome, self.negated) {
+            (Outcome::Missing | Outcome::Differs(_), false) => write_review_file(&new_path, frame_file(&frame).as_bytes()),
+            (Outcome::Matched | Outcome::Written, false) => {
+                remove_review_files(&[&new_path]);
+                None
+            }
+            _ => None,
+        };
+
+        return self.add_step_with(result, |_| {
+            let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let sentence = AssertionSentence::new("render", format!("like snapshot {}", name));
+
+            return match &outcome {
+                Outcome::Matched | Outcome::Written => sentence,
+                Outcome::Failed(error) => sentence.with_actual(error.clone()),
+                Outcome::Missing => with_review(sentence.with_actual("no snapshot"), &path, new.as_deref(), None),
+                Outcome::Differs(cells) => {
+                    let mut sentence = sentence.with_actual(format!("{} differing cells", cells.len()));
+                    for cell in cells.iter().take(LISTED_CELLS) {
+                        sentence = sentence.with_detail(cell.describe());
+                    }
+                    if cells.len() > LISTED_CELLS {
+                        sentence = sentence.with_detail(format!("and {} more", cells.len() - LISTED_CELLS));
+                    }
+                    with_review(sentence, &path, new.as_deref(), None)
+                }
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &str) -> Vec<String> {
+        return text.lines().map(str::to_string).collect();
+    }
+
+    #[test]
+    fn test_frames_are_normalized() {
+        let frame = "\x1b[1m┌Total┐\x1b[0m   \n│ 12  │\r\n└─────┘\n\n  \n";
+        assert_eq!(normalize(frame), lines("┌Total┐\n│ 12  │\n└─────┘"));
+    }
+
+    #[test]
+    fn test_cells_are_diffed_with_their_coordinates() {
+        let cells = diff_cells(&lines("│ 12 │\nok"), &lines("│ 13 │"));
+
+        assert_eq!(
+            cells.iter().map(CellDiff::describe).collect::<Vec<_>>(),
+            vec![
+                "row 1, column 4: expected '2', got '3'",
+                "row 2, column 1: expected 'o', got ' '",
+                "row 2, column 2: expected 'k', got ' '",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshots_are_written_then_compared() {
+        let path = std::env::temp_dir().join(format!("rest-frame-{}", std::process::id())).join("counter.frame");
+
+        assert_eq!(check_snapshot(&path, &lines("count: 1"), false), Outcome::Missing);
+        assert!(!path.exists());
+        assert_eq!(check_snapshot(&path, &lines("count: 1"), true), Outcome::Written);
+        assert_eq!(check_snapshot(&path, &lines("count: 1"), false), Outcome::Matched);
+        assert!(matches!(check_snapshot(&path, &lines("count: 2"), false), Outcome::Differs(_)));
+        assert_eq!(check_snapshot(&path, &lines("count: 2"), true), Outcome::Written);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "count: 2\n");
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_snapshots_are_named_after_the_test() {
+        let first = snapshot_path(Location::caller());
+        let second = snapshot_path(Location::caller());
+
+        assert!(first.ends_with("snapshots/backend__matchers__frame__tests__test_snapshots_are_named_after_the_test.frame"));
+        assert!(second.ends_with("snapshots/backend__matchers__frame__tests__test_snapshots_are_named_after_the_test-2.frame"));
+    }
+
+    #[cfg(feature = "ratatui")]
+    #[test]
+    fn test_ratatui_buffers() {
+        let buffer = ratatui_core::buffer::Buffer::with_lines(["界 ok", "ok"]);
+        assert_eq!(buffer.frame_text(), "界 ok\nok   \n");
+    }
+}
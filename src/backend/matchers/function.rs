@@ -0,0 +1,327 @@
+//! Algebraic properties of pure functions, checked over given or generated inputs
+//!
+//! `expect_fn!(normalize)` starts an assertion on a function from a type to itself. Its matchers evaluate a property
+//! for every input and report the first one violating it, with the values that disagree:
+//!
+//! ```
+//! use rest::prelude::*;
+//!
+//! fn normalize(name: String) -> String {
+//!     name.trim().to_lowercase()
+//! }
+//!
+//! expect_fn!(normalize).to_be_idempotent_for(["  Ada ".to_string(), "GRACE".to_string()]);
+//! ```
+//!
+//! Matchers without the `_for` suffix generate their inputs like [`for_all`](crate::backend::property::for_all), with
+//! the `REST_PROPERTY_CASES` and `REST_PROPERTY_SEED` settings, and report the seed reproducing a violation. Unlike
+//! `for_all`, the violating input isn't shrunk.
+
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::property::{Arbitrary, ENV_PROPERTY_SEED, Property};
+use std::fmt::{self, Debug};
+use std::rc::Rc;
+
+/// Function from a type to itself whose properties are checked, as created by `expect_fn!`
+pub struct PureFunction<F> {
+    function: Rc<F>,
+}
+
+impl<F> PureFunction<F> {
+    /// Wrap a function called on every checked input
+    pub fn new(function: F) -> Self {
+        return Self { function: Rc::new(function) };
+    }
+}
+
+impl<F> Clone for PureFunction<F> {
+    fn clone(&self) -> Self {
+        Self { function: Rc::clone(&self.function) }
+    }
+}
+
+impl<F> Debug for PureFunction<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PureFunction")
+    }
+}
+
+/// First input violating a property, with the values that disagree
+struct Violation {
+    /// Values computed for the input, such as `normalize(normalize(" A")) = "a", normalize(" A") = " a"`
+    description: String,
+    /// Seed reproducing the input, when it was generated
+    seed: Option<u64>,
+}
+
+/// Outcome of checking a property over a sequence of inputs
+struct Evaluation {
+    /// Number of inputs, including the ones left unchecked after a violation
+    inputs: usize,
+    violation: Option<Violation>,
+}
+
+impl Evaluation {
+    /// Check the property over every input until one violates it
+    fn run<T>(inputs: impl IntoIterator<Item = (Option<u64>, T)>, check: impl Fn(T) -> Option<String>) -> Self {
+        let mut inputs = inputs.into_iter();
+        let mut count = 0;
+        while let Some((seed, input)) = inputs.next() {
+            count += 1;
+            if let Some(description) = check(input) {
+                return Self { inputs: count + inputs.count(), violation: Some(Violation { description, seed }) };
+            }
+        }
+
+        return Self { inputs: count, violation: None };
+    }
+
+    /// Given inputs, without seeds
+    fn given<T>(inputs: impl IntoIterator<Item = T>, check: impl Fn(T) -> Option<String>) -> Self {
+        return Self::run(inputs.into_iter().map(|input| (None, input)), check);
+    }
+
+    /// Inputs generated with the property runner settings, with the seed of each
+    fn generated<T: Arbitrary>(check: impl Fn(T) -> Option<String>) -> Self {
+        let property = Property::new();
+        return Self::run(property.inputs::<T>().map(|(seed, input)| (Some(seed), input)), check);
+    }
+
+    fn passed(&self) -> bool {
+        return self.violation.is_none();
+    }
+
+    /// Sentence of the property, with the violating input as the actual value
+    fn describe(&self, verb: &str, property: String, generated: bool) -> AssertionSentence {
+        let inputs = match (self.inputs, generated) {
+            (1, false) => "1 input".to_string(),
+            (1, true) => "1 generated input".to_string(),
+            (count, false) => format!("{} inputs", count),
+            (count, true) => format!("{} generated inputs", count),
+        };
+        let sentence = AssertionSentence::new(verb, format!("{} over {}", property, inputs));
+
+        return match self.violation {
+            Some(ref violation) => {
+                let sentence = sentence.with_actual(violation.description.clone());
+                match violation.seed {
+                    Some(seed) => sentence.with_detail(format!("reproduce with {}={}", ENV_PROPERTY_SEED, seed)),
+                    None => sentence,
+                }
+            }
+            None => sentence,
+        };
+    }
+}
+
+/// Short name of a function type for sentences, such as `trim` for `my_crate::text::trim`
+fn function_name<G>() -> String {
+    let name = std::any::type_name::<G>();
+    if name.contains("{{closure}}") {
+        return "the closure".to_string();
+    }
+
+    return name.rsplit("::").next().unwrap_or(name).to_string();
+}
+
+/// Trait for algebraic properties of functions from a type to itself, such as normalizers and encoders
+pub trait FunctionMatchers<T> {
+    /// Check that applying the function twice gives the same result as applying it once, `f(f(x)) == f(x)`
+    #[track_caller]
+    fn to_be_idempotent_for<I: IntoIterator<Item = T>>(self, inputs: I) -> Self;
+
+    /// Check that the function is idempotent for generated inputs
+    #[track_caller]
+    fn to_be_idempotent(self) -> Self
+    where
+        T: Arbitrary;
+
+    /// Check that the function is its own inverse, `f(f(x)) == x`, such as a reversal or a toggle
+    #[track_caller]
+    fn to_be_involutive_for<I: IntoIterator<Item = T>>(self, inputs: I) -> Self;
+
+    /// Check that the function is its own inverse for generated inputs
+    #[track_caller]
+    fn to_be_involutive(self) -> Self
+    where
+        T: Arbitrary;
+
+    /// Check that applying both functions gives the same result in either order, `f(g(x)) == g(f(x))`
+    #[track_caller]
+    fn to_commute_with_for<G: Fn(T) -> T, I: IntoIterator<Item = T>>(self, other: G, inputs: I) -> Self;
+
+    /// Check that both functions commute for generated inputs
+    #[track_caller]
+    fn to_commute_with<G: Fn(T) -> T>(self, other: G) -> Self
+    where
+        T: Arbitrary;
+}
+
+impl<T, F> FunctionMatchers<T> for Assertion<PureFunction<F>>
+where
+    T: Clone + PartialEq + Debug,
+    F: Fn(T) -> T,
+{
+    fn to_be_idempotent_for<I: IntoIterator<Item = T>>(self, inputs: I) -> Self {
+        let evaluation = Evaluation::given(inputs, |input| idempotence_violation(self.expr_str, &*self.value.function, input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("be", "idempotent".to_string(), false));
+    }
+
+    fn to_be_idempotent(self) -> Self
+    where
+        T: Arbitrary,
+    {
+        let evaluation = Evaluation::generated(|input| idempotence_violation(self.expr_str, &*self.value.function, input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("be", "idempotent".to_string(), true));
+    }
+
+    fn to_be_involutive_for<I: IntoIterator<Item = T>>(self, inputs: I) -> Self {
+        let evaluation = Evaluation::given(inputs, |input| involution_violation(self.expr_str, &*self.value.function, input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("be", "involutive".to_string(), false));
+    }
+
+    fn to_be_involutive(self) -> Self
+    where
+        T: Arbitrary,
+    {
+        let evaluation = Evaluation::generated(|input| involution_violation(self.expr_str, &*self.value.function, input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("be", "involutive".to_string(), true));
+    }
+
+    fn to_commute_with_for<G: Fn(T) -> T, I: IntoIterator<Item = T>>(self, other: G, inputs: I) -> Self {
+        let other_name = function_name::<G>();
+        let evaluation =
+            Evaluation::given(inputs, |input| commutation_violation((self.expr_str, &*self.value.function), (&other_name, &other), input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("commute", format!("with {}", other_name), false));
+    }
+
+    fn to_commute_with<G: Fn(T) -> T>(self, other: G) -> Self
+    where
+        T: Arbitrary,
+    {
+        let other_name = function_name::<G>();
+        let evaluation =
+            Evaluation::generated(|input| commutation_violation((self.expr_str, &*self.value.function), (&other_name, &other), input));
+
+        return self.add_step_with(evaluation.passed(), |_| evaluation.describe("commute", format!("with {}", other_name), true));
+    }
+}
+
+/// Values disagreeing when `f(f(x)) != f(x)`
+fn idempotence_violation<T: Clone + PartialEq + Debug>(name: &str, function: &impl Fn(T) -> T, input: T) -> Option<String> {
+    let once = function(input.clone());
+    let twice = function(once.clone());
+    if twice == once {
+        return None;
+    }
+
+    return Some(format!("{name}({name}({:?})) = {:?}, {name}({:?}) = {:?}", input, twice, input, once));
+}
+
+/// Values disagreeing when `f(f(x)) != x`
+fn involution_violation<T: Clone + PartialEq + Debug>(name: &str, function: &impl Fn(T) -> T, input: T) -> Option<String> {
+    let twice = function(function(input.clone()));
+    if twice == input {
+        return None;
+    }
+
+    return Some(format!("{name}({name}({:?})) = {:?}", input, twice));
+}
+
+/// Values disagreeing when `f(g(x)) != g(f(x))`
+fn commutation_violation<T: Clone + PartialEq + Debug>(
+    (name, function): (&str, &impl Fn(T) -> T),
+    (other_name, other): (&str, &impl Fn(T) -> T),
+    input: T,
+) -> Option<String> {
+    let function_first = other(function(input.clone()));
+    let other_first = function(other(input.clone()));
+    if function_first == other_first {
+        return None;
+    }
+
+    return Some(format!(
+        "{name}({other_name}({:?})) = {:?}, {other_name}({name}({:?})) = {:?}",
+        input, other_first, input, function_first
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::matchers::failure::ExpectedFailure;
+    use crate::prelude::*;
+
+    fn normalize(name: String) -> String {
+        return name.trim().to_lowercase();
+    }
+
+    fn capitalize(name: String) -> String {
+        let mut chars = name.chars();
+        return match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name,
+        };
+    }
+
+    fn negate(value: i64) -> i64 {
+        return value.wrapping_neg();
+    }
+
+    fn halve(value: i64) -> i64 {
+        return value / 2;
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        return values.iter().map(|value| value.to_string()).collect();
+    }
+
+    #[test]
+    fn test_properties_over_given_inputs() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect_fn!(normalize).to_be_idempotent_for(strings(&["  Ada ", "GRACE", ""]));
+        expect_fn!(negate).to_be_involutive_for([0, 1, -7, i64::MIN]);
+        expect_fn!(negate).to_commute_with_for(halve, [4, -8, 10]);
+        expect_fn!(capitalize).not().to_be_involutive_for(strings(&["ada"]));
+    }
+
+    #[test]
+    fn test_properties_over_generated_inputs() {
+        crate::Reporter::disable_deduplication();
+
+        expect_fn!(normalize).to_be_idempotent();
+        expect_fn!(|values: Vec<u8>| values.into_iter().rev().collect::<Vec<_>>()).to_be_involutive();
+        expect_fn!(|value: i32| value.wrapping_mul(3)).to_commute_with(|value: i32| value.wrapping_neg());
+    }
+
+    #[test]
+    fn test_first_violation_is_reported() {
+        let failure = ExpectedFailure::capture(|| {
+            expect_fn!(capitalize).to_commute_with_for(normalize, strings(&["ada", " Grace"]));
+        });
+
+        let details = failure.failure().map(|failure| failure.format_step_details()).unwrap_or_default();
+        assert!(details.contains("commutes with normalize over 2 inputs"), "{}", details);
+        assert!(details.contains(r#"(got capitalize(normalize("ada")) = "Ada", normalize(capitalize("ada")) = "ada")"#), "{}", details);
+    }
+
+    #[test]
+    fn test_generated_violation_reports_its_seed() {
+        let failure = ExpectedFailure::capture(|| {
+            expect_fn!(halve).to_be_idempotent();
+        });
+
+        let message = failure.message().unwrap_or_default();
+        assert!(message.starts_with("expected halve to be idempotent over "), "{}", message);
+        assert!(message.contains("halve(halve("), "{}", message);
+        let details = failure.failure().map(|failure| failure.format_step_details()).unwrap_or_default();
+        assert!(details.contains("reproduce with REST_PROPERTY_SEED="), "{}", details);
+    }
+}
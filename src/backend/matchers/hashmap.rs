@@ -1,29 +1,54 @@
 use crate::backend::Assertion;
 use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::diff::Diff;
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
 pub trait HashMapMatchers<K, V> {
-    fn to_be_empty(self) -> Self;
+    #[track_caller]
     fn to_have_length(self, expected: usize) -> Self;
+    #[track_caller]
     fn to_contain_key<Q>(self, key: &Q) -> Self
     where
         K: Borrow<Q>,
         Q: Hash + Eq + Debug + ?Sized;
+    #[track_caller]
     fn to_contain_entry<Q, R>(self, key: &Q, value: &R) -> Self
     where
         K: Borrow<Q>,
         V: Borrow<R>,
         Q: Hash + Eq + Debug + ?Sized,
         R: PartialEq + Debug + ?Sized;
+    #[track_caller]
+    fn to_equal_map(self, expected: &HashMap<K, V>) -> Self
+    where
+        V: PartialEq;
+
+    /// Check that every key satisfies the predicate, described as a verb phrase such as `"be lowercase"`, listing
+    /// the entries of the failing keys
+    #[track_caller]
+    fn to_have_all_keys_satisfying<F: Fn(&K) -> bool>(self, description: &str, predicate: F) -> Self;
+
+    /// Check that every value satisfies the predicate, described as a verb phrase such as `"be non-empty"`, listing
+    /// the entries of the failing values
+    #[track_caller]
+    fn to_have_all_values_satisfying<F: Fn(&V) -> bool>(self, description: &str, predicate: F) -> Self;
 }
 
+/// Maximum number of failing entries listed in failure details
+const MAX_LISTED_ENTRIES: usize = 5;
+
 /// Helper trait for HashMap-like types
+#[diagnostic::on_unimplemented(
+    message = "`HashMapMatchers` can't check `{Self}`, which isn't a map",
+    label = "not a map",
+    note = "`HashMapMatchers` apply to `HashMap<K, V>` and `&HashMap<K, V>`"
+)]
 trait AsHashMap<K, V> {
-    fn is_map_empty(&self) -> bool;
     fn map_length(&self) -> usize;
+    fn as_map(&self) -> &HashMap<K, V>;
     fn map_contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
@@ -42,14 +67,14 @@ where
     K: Hash + Eq,
     V: Clone,
 {
-    fn is_map_empty(&self) -> bool {
-        self.is_empty()
-    }
-
     fn map_length(&self) -> usize {
         self.len()
     }
 
+    fn as_map(&self) -> &HashMap<K, V> {
+        self
+    }
+
     fn map_contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
@@ -75,14 +100,14 @@ where
     K: Hash + Eq,
     V: Clone,
 {
-    fn is_map_empty(&self) -> bool {
-        self.is_empty()
-    }
-
     fn map_length(&self) -> usize {
         self.len()
     }
 
+    fn as_map(&self) -> &HashMap<K, V> {
+        self
+    }
+
     fn map_contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
@@ -109,19 +134,13 @@ where
     V: Debug + Clone,
     M: AsHashMap<K, V> + Debug + Clone,
 {
-    fn to_be_empty(self) -> Self {
-        let result = self.value.is_map_empty();
-        let sentence = AssertionSentence::new("be", "empty").with_actual(format!("{:?}", self.value));
-
-        return self.add_step(sentence, result);
-    }
-
     fn to_have_length(self, expected: usize) -> Self {
         let actual_length = self.value.map_length();
         let result = actual_length == expected;
-        let sentence = AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
+        });
     }
 
     fn to_contain_key<Q>(self, key: &Q) -> Self
@@ -130,9 +149,10 @@ where
         Q: Hash + Eq + Debug + ?Sized,
     {
         let result = self.value.map_contains_key(key);
-        let sentence = AssertionSentence::new("contain", format!("key {:?}", key)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("key {:?}", key)).with_actual(format!("{:?}", actual));
+        });
     }
 
     fn to_contain_entry<Q, R>(self, key: &Q, value: &R) -> Self
@@ -143,11 +163,76 @@ where
         R: PartialEq + Debug + ?Sized,
     {
         let result = self.value.map_contains_entry(key, value);
-        let sentence =
-            AssertionSentence::new("contain", format!("entry ({:?}, {:?})", key, value)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("entry ({:?}, {:?})", key, value)).with_actual(format!("{:?}", actual));
+        });
+    }
+
+    fn to_equal_map(self, expected: &HashMap<K, V>) -> Self
+    where
+        V: PartialEq,
+    {
+        let result = self.value.as_map() == expected;
+
+        return self.add_step_with(result, |actual| {
+            // Render an entry-level diff rather than both whole maps
+            let sentence = AssertionSentence::new("equal", format!("map of {}", describe_entries(expected.len())))
+                .with_expected(format!("{:?}", expected))
+                .with_actual(describe_entries(actual.map_length()));
+            if result {
+                return sentence;
+            }
+
+            return sentence.with_diff(Diff::keyed(actual.as_map(), expected, |actual, expected| actual == expected));
+        });
+    }
+
+    fn to_have_all_keys_satisfying<F: Fn(&K) -> bool>(self, description: &str, predicate: F) -> Self {
+        let map = self.value.as_map();
+        let result = map.keys().all(&predicate);
+
+        return self.add_step_with(result, |actual| {
+            let failing = actual.as_map().iter().filter(|(key, _)| !predicate(key)).collect();
+            return quantifier_sentence("key", description, failing, actual.map_length());
+        });
+    }
+
+    fn to_have_all_values_satisfying<F: Fn(&V) -> bool>(self, description: &str, predicate: F) -> Self {
+        let map = self.value.as_map();
+        let result = map.values().all(&predicate);
+
+        return self.add_step_with(result, |actual| {
+            let failing = actual.as_map().iter().filter(|(_, value)| !predicate(value)).collect();
+            return quantifier_sentence("value", description, failing, actual.map_length());
+        });
+    }
+}
+
+/// Sentence of a quantifier over the keys or values of a map, listing the failing entries in key order
+fn quantifier_sentence<K: Debug, V: Debug>(part: &str, description: &str, failing: Vec<(&K, &V)>, total: usize) -> AssertionSentence {
+    let mut failing = failing.into_iter().map(|(key, value)| (format!("{:?}", key), format!("{:?}", value))).collect::<Vec<_>>();
+    failing.sort();
+
+    let mut sentence = AssertionSentence::new("have", format!("all {}s {}", part, description)).with_actual(format!(
+        "{} of {} {}s failing",
+        failing.len(),
+        total,
+        part
+    ));
+    for (key, value) in failing.iter().take(MAX_LISTED_ENTRIES) {
+        sentence = sentence.with_detail(format!("[{}] {}", key, value));
     }
+    if failing.len() > MAX_LISTED_ENTRIES {
+        sentence = sentence.with_detail(format!("... and {} more", failing.len() - MAX_LISTED_ENTRIES));
+    }
+
+    return sentence;
+}
+
+/// Describe a number of entries with the right plurality
+fn describe_entries(count: usize) -> String {
+    return if count == 1 { "1 entry".to_string() } else { format!("{} entries", count) };
 }
 
 #[cfg(test)]
@@ -276,4 +361,61 @@ mod tests {
         let _assertion = expect!(&map).to_contain_entry(&1, &3);
         std::hint::black_box(_assertion);
     }
+
+    #[test]
+    fn test_hashmap_to_equal_map() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let map: HashMap<&str, i32> = HashMap::from([("a", 1), ("b", 2)]);
+
+        // These should pass
+        expect!(&map).to_equal_map(&HashMap::from([("b", 2), ("a", 1)]));
+        expect!(&map).not().to_equal_map(&HashMap::from([("a", 1)]));
+        expect!(&map).not().to_equal_map(&HashMap::from([("a", 1), ("b", 3)]));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "equal map of 3 entries (got 3 entries)\n  [\"a\"] ok\n  [\"b\"] expected 3, got 2\n  [\"c\"] missing 4\n  [\"d\"] unexpected 5"
+    )]
+    fn test_map_diff_marks_each_entry() {
+        let map: HashMap<&str, i32> = HashMap::from([("a", 1), ("b", 2), ("d", 5)]);
+        let _assertion = expect!(&map).to_equal_map(&HashMap::from([("a", 1), ("b", 3), ("c", 4)]));
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_hashmap_quantifiers() {
+        let owners: HashMap<&str, &str> = HashMap::from([("api", "ada"), ("db", "grace")]);
+
+        expect!(&owners).to_have_all_keys_satisfying("be lowercase", |key| key.chars().all(char::is_lowercase));
+        expect!(&owners).to_have_all_values_satisfying("be non-empty", |value| !value.is_empty());
+        expect!(&owners).not().to_have_all_values_satisfying("be short", |value| value.len() < 4);
+
+        // Empty maps have no entry to check
+        let empty: HashMap<&str, &str> = HashMap::new();
+        expect!(&empty).to_have_all_values_satisfying("be non-empty", |_| false);
+    }
+
+    #[test]
+    #[should_panic(expected = "have all values be non-empty (got 2 of 3 values failing)\n  [\"cache\"] \"\"\n  [\"db\"] \"\"")]
+    fn test_failing_values_are_listed_in_key_order() {
+        let owners: HashMap<&str, &str> = HashMap::from([("db", ""), ("api", "ada"), ("cache", "")]);
+        let _assertion = expect!(&owners).to_have_all_values_satisfying("be non-empty", |value| !value.is_empty());
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_failing_keys_are_listed_with_their_values() {
+        let ports: HashMap<String, u16> = (0..8).map(|index| (format!("Service{}", index), 8000 + index)).collect();
+        let run = crate::testkit::run_matcher(&ports, |value| {
+            value.to_have_all_keys_satisfying("be lowercase", |key| key.chars().all(|c| !c.is_uppercase()))
+        });
+
+        let details = &run.steps()[0].sentence.details;
+        expect!(details.len()).to_equal(6);
+        expect!(details[0].as_str()).to_equal("[\"Service0\"] 8000");
+        expect!(details[5].as_str()).to_equal("... and 3 more");
+    }
 }
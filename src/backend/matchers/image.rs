@@ -0,0 +1,312 @@
+use crate::backend::Assertion;
+use crate::backend::artifacts::attach_artifact;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::matchers::snapshot::{remove_review_files, with_review, write_review_file};
+use image::{DynamicImage, ImageFormat, Rgba, RgbaImage};
+use std::fmt::Debug;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+// Largest YIQ distance between two colors, between black and white
+const MAX_DELTA: f64 = 35215.0;
+
+// Share of the largest distance under which two colors look the same, absorbing anti-aliasing and rounding noise
+const COLOR_THRESHOLD: f64 = 0.1;
+
+/// Trait for visual comparisons of images against reference files.
+///
+/// Available with the `image` feature, for `RgbaImage` and `DynamicImage` buffers and for paths of image files.
+/// Pixels are compared by perceived color, so that rendering noise doesn't count as a difference. When the images
+/// differ more than allowed, the image is written next to the reference with a `.new` suffix, such as `login.new.png`,
+/// and a diff image with the differing pixels in red as `login.diff.png`, attached to the test as an artifact.
+pub trait ImageMatchers {
+    /// Check that the image looks like the reference image at `path`, with at most `max_diff_percent` percent of its
+    /// pixels looking different
+    ///
+    /// `REST_UPDATE_SNAPSHOTS=1`, or [`Config::update_snapshots`](crate::Config::update_snapshots), writes the image as
+    /// the reference instead of failing.
+    #[track_caller]
+    fn to_look_like(self, path: impl AsRef<Path>, max_diff_percent: f64) -> Self;
+}
+
+/// Helper trait for images held in buffers or files
+trait AsImage {
+    fn rgba(&self) -> Result<RgbaImage, String>;
+}
+
+impl<I: AsImage + ?Sized> AsImage for &I {
+    fn rgba(&self) -> Result<RgbaImage, String> {
+        return (**self).rgba();
+    }
+}
+
+impl AsImage for RgbaImage {
+    fn rgba(&self) -> Result<RgbaImage, String> {
+        return Ok(self.clone());
+    }
+}
+
+impl AsImage for DynamicImage {
+    fn rgba(&self) -> Result<RgbaImage, String> {
+        return Ok(self.to_rgba8());
+    }
+}
+
+impl AsImage for Path {
+    fn rgba(&self) -> Result<RgbaImage, String> {
+        return read_image(self).map_err(|e| format!("unreadable image {}: {}", self.display(), e));
+    }
+}
+
+impl AsImage for PathBuf {
+    fn rgba(&self) -> Result<RgbaImage, String> {
+        return self.as_path().rgba();
+    }
+}
+
+fn read_image(path: &Path) -> Result<RgbaImage, image::ImageError> {
+    return image::open(path).map(|image| image.to_rgba8());
+}
+
+/// Pixels of an image looking different from a reference image of the same size
+struct Comparison {
+    differing: usize,
+    total: usize,
+    diff: RgbaImage,
+}
+
+impl Comparison {
+    fn of(actual: &RgbaImage, expected: &RgbaImage) -> Result<Self, String> {
+        if actual.dimensions() != expected.dimensions() {
+            let (width, height) = actual.dimensions();
+            let (expected_width, expected_height) = expected.dimensions();
+            return Err(format!("{}x{} pixels, the reference has {}x{}", width, height, expected_width, expected_height));
+        }
+
+        let mut differing = 0;
+        let diff = RgbaImage::from_fn(actual.width(), actual.height(), |x, y| {
+            let expected = *expected.get_pixel(x, y);
+            if looks_different(*actual.get_pixel(x, y), expected) {
+                differing += 1;
+                return Rgba([255, 0, 0, 255]);
+            }
+
+            // Unchanged pixels are shown faded, to locate the differences in the picture
+            let faded = 255.0 - (255.0 - yiq(expected).0) * 0.1;
+            return Rgba([faded as u8, faded as u8, faded as u8, 255]);
+        });
+
+        return Ok(Self { differing, total: (actual.width() * actual.height()) as usize, diff });
+    }
+
+    fn percent(&self) -> f64 {
+        return if self.total == 0 { 0.0 } else { self.differing as f64 * 100.0 / self.total as f64 };
+    }
+
+    fn diff_png(&self) -> Vec<u8> {
+        let mut png = Vec::new();
+        self.diff.write_to(&mut Cursor::new(&mut png), ImageFormat::Png).expect("encoding an RGBA image as PNG can't fail");
+        return png;
+    }
+}
+
+/// Luma and chroma of a pixel, blended over white by its alpha
+fn yiq(pixel: Rgba<u8>) -> (f64, f64, f64) {
+    let alpha = pixel[3] as f64 / 255.0;
+    let [r, g, b] = [0, 1, 2].map(|channel| 255.0 + (pixel[channel] as f64 - 255.0) * alpha);
+
+    let y = r * 0.29889531 + g * 0.58662247 + b * 0.11448223;
+    let i = r * 0.59597799 - g * 0.27417610 - b * 0.32180189;
+    let q = r * 0.21147017 - g * 0.52261711 + b * 0.31114694;
+    return (y, i, q);
+}
+
+/// Check if two colors look different, weighting luma and chroma as the eye perceives them
+fn looks_different(actual: Rgba<u8>, expected: Rgba<u8>) -> bool {
+    if actual == expected {
+        return false;
+    }
+
+    let (actual_y, actual_i, actual_q) = yiq(actual);
+    let (expected_y, expected_i, expected_q) = yiq(expected);
+    let delta =
+        0.5053 * (actual_y - expected_y).powi(2) + 0.299 * (actual_i - expected_i).powi(2) + 0.1957 * (actual_q - expected_q).powi(2);
+    return delta > MAX_DELTA * COLOR_THRESHOLD * COLOR_THRESHOLD;
+}
+
+/// Name of the diff artifact of a reference image, such as `login.diff.png` for `login.png`
+fn diff_name(reference: &Path) -> String {
+    return format!("{}.diff.png", stem(reference));
+}
+
+/// Path of the image that doesn't look like a reference, such as `login.new.png` for `login.png`
+fn new_image_path(reference: &Path) -> PathBuf {
+    let extension = reference.extension().map(|extension| extension.to_string_lossy().into_owned()).unwrap_or_else(|| "png".to_string());
+    return reference.with_file_name(format!("{}.new.{}", stem(reference), extension));
+}
+
+fn stem(reference: &Path) -> String {
+    return reference.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_else(|| "image".to_string());
+}
+
+/// Encode an image in the format of the extension of a path, PNG when it has none, reporting failures on stderr
+fn encode_as(image: &RgbaImage, path: &Path) -> Option<Vec<u8>> {
+    let format = ImageFormat::from_path(path).unwrap_or(ImageFormat::Png);
+    let mut bytes = Vec::new();
+    if let Err(error) = image.write_to(&mut Cursor::new(&mut bytes), format) {
+        eprintln!("ERROR: Failed to encode {}: {}", path.display(), error);
+        return None;
+    }
+
+    return Some(bytes);
+}
+
+// Single implementation for any type that implements AsImage
+impl<V> ImageMatchers for Assertion<V>
+where
+    V: AsImage + Debug + Clone,
+{
+    fn to_look_like(self, path: impl AsRef<Path>, max_diff_percent: f64) -> Self {
+        let path = path.as_ref();
+        let image = self.value.rgba();
+        let comparison = image.clone().and_then(|actual| {
+            let expected = read_image(path).map_err(|e| format!("unreadable reference image {}: {}", path.display(), e))?;
+            return Comparison::of(&actual, &expected);
+        });
+        let mut result = comparison.as_ref().is_ok_and(|comparison| comparison.percent() <= max_diff_percent);
+
+        let new_path = new_image_path(path);
+        let diff_path = path.with_file_name(diff_name(path));
+        let mut review = None;
+
+        // Images expected to differ have nothing to review or update
+        if let (false, Ok(actual)) = (self.negated, &image) {
+            if result {
+                remove_review_files(&[&new_path, &diff_path]);
+            } else if crate::config::is_updating_snapshots() {
+                result = encode_as(actual, path).and_then(|bytes| write_review_file(path, &bytes)).is_some();
+                remove_review_files(&[&new_path, &diff_path]);
+            } else {
+                let new = encode_as(actual, &new_path).and_then(|bytes| write_review_file(&new_path, &bytes));
+                let diff = comparison.as_ref().ok().and_then(|comparison| write_review_file(&diff_path, &comparison.diff_png()));
+                if let Some(ref diff) = diff {
+                    attach_artifact(diff_name(path), diff.as_path());
+                }
+                review = Some((new, diff));
+            }
+        }
+
+        return self.add_step_with(result, |_| {
+            let actual = match &comparison {
+                Ok(comparison) => {
+                    format!("{:.2}% of pixels differing, {} of {}", comparison.percent(), comparison.differing, comparison.total)
+                }
+                Err(error) => error.clone(),
+            };
+            let object = format!("like {} with at most {}% of pixels differing", path.display(), max_diff_percent);
+            let sentence = AssertionSentence::new("look", object).with_actual(actual);
+
+            return match &review {
+                Some((new, diff)) => with_review(sentence, path, new.as_deref(), diff.as_deref()),
+                None => sentence,
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::artifacts::attached_artifacts;
+    use crate::backend::matchers::failure::ExpectedFailure;
+    use crate::prelude::*;
+
+    // 10x10 white image with a black square in its top left corner
+    fn picture() -> RgbaImage {
+        return RgbaImage::from_fn(10, 10, |x, y| if x < 5 && y < 5 { Rgba([0, 0, 0, 255]) } else { Rgba([255, 255, 255, 255]) });
+    }
+
+    fn reference(name: &str, image: &RgbaImage) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rest-image-{}-{}.png", name, std::process::id()));
+        image.save(&path).unwrap();
+        return path;
+    }
+
+    #[test]
+    fn test_images_look_alike() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let path = reference("alike", &picture());
+
+        // Colors too close to tell apart aren't differences
+        let mut noisy = picture();
+        noisy.put_pixel(9, 9, Rgba([250, 250, 250, 255]));
+        expect!(&noisy).to_look_like(&path, 0.0);
+
+        // One pixel out of a hundred
+        let mut changed = picture();
+        changed.put_pixel(9, 9, Rgba([255, 0, 0, 255]));
+        expect!(&changed).to_look_like(&path, 1.0);
+        expect!(DynamicImage::ImageRgba8(changed)).not().to_look_like(&path, 0.5);
+        expect!(path.as_path()).to_look_like(&path, 0.0);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_review_files_are_written_on_failure() {
+        let path = reference("review", &picture());
+        let mut changed = picture();
+        changed.put_pixel(9, 9, Rgba([255, 0, 0, 255]));
+
+        let failure = ExpectedFailure::capture(|| {
+            expect!(&changed).to_look_like(&path, 0.0);
+        });
+        let details = failure.failure().map(|assertion| assertion.steps[0].sentence.details.clone()).unwrap_or_default();
+
+        let new_path = new_image_path(&path);
+        let diff_path = path.with_file_name(diff_name(&path));
+        assert_eq!(details[0], format!("stored: {}", path.display()));
+        assert_eq!(details[1], format!("new: {}", new_path.display()));
+        assert_eq!(details[2], format!("diff: {}", diff_path.display()));
+        assert!(details[3].starts_with("accept with: REST_UPDATE_SNAPSHOTS=1 cargo test -- --exact "), "{}", details[3]);
+        assert_eq!(read_image(&new_path).unwrap(), changed);
+
+        // Matching again removes the files of the previous failure
+        expect!(picture()).to_look_like(&path, 0.0);
+        assert!(!new_path.exists() && !diff_path.exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_diff_image_is_attached_on_failure() {
+        let path = reference("diff", &picture());
+        let mut changed = picture();
+        changed.put_pixel(9, 9, Rgba([255, 0, 0, 255]));
+        changed.put_pixel(0, 0, Rgba([255, 255, 255, 255]));
+
+        let failure = ExpectedFailure::capture(|| {
+            expect!(&changed).to_look_like(&path, 1.0);
+        });
+        expect!(failure.message().unwrap_or_default()).to_contain("got 2.00% of pixels differing, 2 of 100");
+
+        let artifacts = attached_artifacts();
+        let diff = artifacts.iter().find(|artifact| artifact.name.ends_with(".diff.png")).expect("diff artifact");
+        let diff = image::open(&diff.path).unwrap().to_rgba8();
+        assert_eq!(*diff.get_pixel(9, 9), Rgba([255, 0, 0, 255]));
+        assert_eq!(*diff.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+        assert_eq!(*diff.get_pixel(5, 5), Rgba([255, 255, 255, 255]));
+
+        remove_review_files(&[&path, &new_image_path(&path), &path.with_file_name(diff_name(&path))]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(got 20x10 pixels, the reference has 10x10)")]
+    fn test_size_mismatch_fails() {
+        let path = reference("size", &picture());
+        let _assertion = expect!(RgbaImage::new(20, 10)).to_look_like(&path, 100.0);
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::{AssertionSentence, MatcherDocs};
+use serde_json::Value;
+use std::fmt::Debug;
+
+/// Documentation of `to_match_json_schema`, shown under its failures with `config().matcher_docs(true)`
+const SCHEMA_DOCS_URL: &str = "https://github.com/mister-good-deal/rest/wiki/JSON-Schema-Matchers#to_match_json_schema";
+
+/// Trait for validating JSON documents against a JSON Schema.
+///
+/// Available with the `json-schema` feature, for JSON strings and `serde_json::Value`, owned or borrowed.
+/// Each schema violation is reported on its own line in the failure details.
+pub trait JsonSchemaMatchers {
+    /// Check that the value is valid against a JSON Schema
+    ///
+    /// The schema is either inline JSON (`{"type": "object"}`, `true`, `false`) or the path of a schema file.
+    #[track_caller]
+    fn to_match_json_schema(self, schema: &str) -> Self;
+}
+
+/// Helper trait for values holding a JSON document
+trait AsJsonInstance {
+    fn json_instance(&self) -> Result<Value, String>;
+}
+
+impl<J: AsJsonInstance + ?Sized> AsJsonInstance for &J {
+    fn json_instance(&self) -> Result<Value, String> {
+        return (**self).json_instance();
+    }
+}
+
+impl AsJsonInstance for Value {
+    fn json_instance(&self) -> Result<Value, String> {
+        return Ok(self.clone());
+    }
+}
+
+impl AsJsonInstance for String {
+    fn json_instance(&self) -> Result<Value, String> {
+        return self.as_str().json_instance();
+    }
+}
+
+impl AsJsonInstance for str {
+    fn json_instance(&self) -> Result<Value, String> {
+        return serde_json::from_str(self).map_err(|e| format!("invalid JSON: {}", e));
+    }
+}
+
+/// Load a schema given inline or as a file path, panicking on unusable schemas like `to_match` does for regexes
+fn load_schema(schema: &str) -> (Value, String) {
+    let trimmed = schema.trim();
+    let is_inline = trimmed.starts_with('{') || trimmed == "true" || trimmed == "false";
+
+    if is_inline {
+        let value = serde_json::from_str(trimmed).unwrap_or_else(|e| panic!("Invalid JSON schema: {}", e));
+        return (value, "JSON schema".to_string());
+    }
+
+    let text = std::fs::read_to_string(trimmed).unwrap_or_else(|e| panic!("Cannot read JSON schema '{}': {}", trimmed, e));
+    let value = serde_json::from_str(&text).unwrap_or_else(|e| panic!("Invalid JSON schema '{}': {}", trimmed, e));
+    return (value, format!("JSON schema {}", trimmed));
+}
+
+/// Validate an instance, returning one "path: message" line per violation
+fn schema_violations(schema: &Value, instance: &Value) -> Vec<String> {
+    let validator = jsonschema::validator_for(schema).unwrap_or_else(|e| panic!("Invalid JSON schema: {}", e));
+
+    return validator
+        .iter_errors(instance)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            let path = if path.is_empty() { "/".to_string() } else { path };
+            format!("{}: {}", path, error)
+        })
+        .collect();
+}
+
+// Single implementation for any type that implements AsJsonInstance
+impl<V> JsonSchemaMatchers for Assertion<V>
+where
+    V: AsJsonInstance + Debug + Clone,
+{
+    fn to_match_json_schema(self, schema: &str) -> Self {
+        let (schema, description) = load_schema(schema);
+
+        // Chained schema checks parse the document once
+        let instance = self.memoized("json instance", |value| value.json_instance());
+        let violations = match instance.as_ref() {
+            Ok(instance) => schema_violations(&schema, instance),
+            Err(error) => vec![error.clone()],
+        };
+        let result = violations.is_empty();
+
+        let actual = match violations.len() {
+            0 => "no violations".to_string(),
+            1 => "1 violation".to_string(),
+            count => format!("{} violations", count),
+        };
+        let docs = MatcherDocs::new("that the document satisfies every keyword of the schema, each violation naming the failing location")
+            .with_url(SCHEMA_DOCS_URL);
+        let mut sentence = AssertionSentence::new("match", description).with_actual(actual).with_docs(docs);
+        for violation in violations {
+            sentence = sentence.with_detail(violation);
+        }
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use serde_json::json;
+
+    const USER_SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["id", "name"],
+        "properties": {
+            "id": { "type": "integer", "minimum": 1 },
+            "name": { "type": "string" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        }
+    }"#;
+
+    #[test]
+    fn test_to_match_json_schema() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let user = json!({ "id": 7, "name": "Ada", "tags": ["admin"] });
+        let body = r#"{ "id": 1, "name": "Grace" }"#;
+
+        expect!(&user).to_match_json_schema(USER_SCHEMA);
+        expect!(user).to_match_json_schema("true");
+        expect!(body).to_match_json_schema(USER_SCHEMA);
+        expect!(body.to_string()).to_match_json_schema(USER_SCHEMA);
+        expect!(json!({ "id": 0 })).not().to_match_json_schema(USER_SCHEMA);
+        expect!("not json").not().to_match_json_schema(USER_SCHEMA);
+    }
+
+    #[test]
+    fn test_schema_loaded_from_file() {
+        crate::Reporter::disable_deduplication();
+
+        let path = std::env::temp_dir().join(format!("rest-user-schema-{}.json", std::process::id()));
+        std::fs::write(&path, USER_SCHEMA).unwrap();
+
+        expect!(json!({ "id": 3, "name": "Linus" })).to_match_json_schema(path.to_str().unwrap());
+        expect!(json!({ "name": 3 })).not().to_match_json_schema(path.to_str().unwrap());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_each_violation_is_a_detail_line() {
+        crate::Reporter::disable_deduplication();
+
+        // Negated so the assertion passes while keeping the violations
+        let assertion = expect!(json!({ "id": 0, "tags": [1] })).not().to_match_json_schema(USER_SCHEMA);
+        let details = assertion.steps[0].sentence.details.clone();
+
+        expect!(details.len()).to_equal(3);
+        expect!(details.iter().any(|detail| detail.starts_with("/: ") && detail.contains("\"name\""))).to_be_true();
+        expect!(details.iter().any(|detail| detail.starts_with("/id: "))).to_be_true();
+        expect!(details.iter().any(|detail| detail.starts_with("/tags/0: "))).to_be_true();
+    }
+
+    #[test]
+    #[should_panic(expected = "match JSON schema (got 1 violation)\n  /id: ")]
+    fn test_invalid_document_fails_with_violations() {
+        let _assertion = expect!(json!({ "id": -1, "name": "Ada" })).to_match_json_schema(USER_SCHEMA);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid JSON schema")]
+    fn test_invalid_schema_panics() {
+        let _assertion = expect!(json!({})).to_match_json_schema("{ not a schema");
+        std::hint::black_box(_assertion);
+    }
+}
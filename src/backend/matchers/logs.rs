@@ -0,0 +1,119 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::logs::{CapturedLogs, Level, LogEntry};
+use std::fmt::Debug;
+
+/// Number of captured entries listed in failure details
+const MAX_LISTED_ENTRIES: usize = 20;
+
+/// Trait for assertions on captured log records, as returned by `expect_logs!()`.
+///
+/// Available with the `log-capture` feature.
+pub trait LogMatchers {
+    /// Check that an entry with the given level contains the message
+    #[track_caller]
+    fn to_contain_entry(self, level: Level, message: &str) -> Self;
+
+    /// Check that an entry of any level contains the message
+    #[track_caller]
+    fn to_contain_message(self, message: &str) -> Self;
+}
+
+/// Helper trait for captured logs
+trait AsCapturedLogs {
+    fn captured_entries(&self) -> &[LogEntry];
+}
+
+impl AsCapturedLogs for CapturedLogs {
+    fn captured_entries(&self) -> &[LogEntry] {
+        return self.entries();
+    }
+}
+
+impl AsCapturedLogs for &CapturedLogs {
+    fn captured_entries(&self) -> &[LogEntry] {
+        return self.entries();
+    }
+}
+
+/// Build the sentence for a log search, listing the captured entries as details
+fn log_sentence(object: String, entries: &[LogEntry]) -> AssertionSentence {
+    let actual = match entries.len() {
+        0 => "no captured entries".to_string(),
+        1 => "1 captured entry".to_string(),
+        count => format!("{} captured entries", count),
+    };
+
+    let mut sentence = AssertionSentence::new("contain", object).with_actual(actual);
+    for entry in entries.iter().take(MAX_LISTED_ENTRIES) {
+        sentence = sentence.with_detail(entry.to_string());
+    }
+    if entries.len() > MAX_LISTED_ENTRIES {
+        sentence = sentence.with_detail(format!("... and {} more", entries.len() - MAX_LISTED_ENTRIES));
+    }
+
+    return sentence;
+}
+
+// Single implementation for any type that implements AsCapturedLogs
+impl<V> LogMatchers for Assertion<V>
+where
+    V: AsCapturedLogs + Debug + Clone,
+{
+    fn to_contain_entry(self, level: Level, message: &str) -> Self {
+        let entries = self.value.captured_entries();
+        let result = entries.iter().any(|entry| entry.level == level && entry.message.contains(message));
+        let sentence = log_sentence(format!("{} entry {:?}", level, message), entries);
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_contain_message(self, message: &str) -> Self {
+        let entries = self.value.captured_entries();
+        let result = entries.iter().any(|entry| entry.message.contains(message));
+        let sentence = log_sentence(format!("message {:?}", message), entries);
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::logs::capture_logs;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_to_contain_entry() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let _logs = capture_logs();
+        log::warn!("retrying request in 5s");
+        tracing::info!(user = "ada", "logged in");
+
+        expect_logs!().to_contain_entry(Level::Warn, "retrying");
+        expect_logs!().to_contain_entry(Level::Info, "logged in user=ada");
+        expect_logs!().not().to_contain_entry(Level::Error, "retrying");
+    }
+
+    #[test]
+    fn test_to_contain_message() {
+        crate::Reporter::disable_deduplication();
+
+        let _logs = capture_logs();
+        log::debug!("cache miss for key 42");
+
+        expect_logs!().to_contain_message("cache miss");
+        expect_logs!().not().to_contain_message("cache hit");
+    }
+
+    #[test]
+    #[should_panic(expected = "contain ERROR entry \"timeout\" (got 1 captured entry)\n  [WARN")]
+    fn test_missing_entry_fails_with_captured_entries() {
+        let _logs = capture_logs();
+        log::warn!("slow response");
+
+        let _assertion = expect_logs!().to_contain_entry(Level::Error, "timeout");
+        std::hint::black_box(_assertion);
+    }
+}
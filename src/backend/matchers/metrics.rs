@@ -0,0 +1,176 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::metrics::Metric;
+use std::fmt::Debug;
+
+/// Trait for assertions on captured metrics, as returned by `expect_metric!`.
+///
+/// Available with the `metrics-capture` feature. Values are summed across the label sets of the
+/// metric; pass labels to `expect_metric!` to check a single series.
+pub trait MetricMatchers {
+    /// Check that the metric was emitted
+    #[track_caller]
+    fn to_be_recorded(self) -> Self;
+
+    /// Check the value of a counter or gauge
+    #[track_caller]
+    fn to_have_value<N: Into<f64>>(self, expected: N) -> Self;
+
+    /// Check the number of observations recorded by a histogram
+    #[track_caller]
+    fn to_have_observation_count(self, expected: usize) -> Self;
+
+    /// Check that a series of the metric carries the label
+    #[track_caller]
+    fn to_have_label(self, key: &str, value: &str) -> Self;
+}
+
+/// Helper trait for captured metrics
+trait AsMetric {
+    fn metric(&self) -> &Metric;
+}
+
+impl AsMetric for Metric {
+    fn metric(&self) -> &Metric {
+        return self;
+    }
+}
+
+impl AsMetric for &Metric {
+    fn metric(&self) -> &Metric {
+        return self;
+    }
+}
+
+/// Build a sentence with the metric as actual value, listing its series when there are several
+fn metric_sentence(verb: &str, object: String, metric: &Metric) -> AssertionSentence {
+    let mut sentence = AssertionSentence::new(verb, object).with_actual(metric.to_string());
+    if metric.series().len() > 1 {
+        for series in metric.series() {
+            sentence = sentence.with_detail(series.to_string());
+        }
+    }
+
+    return sentence;
+}
+
+// Single implementation for any type that implements AsMetric
+impl<V> MetricMatchers for Assertion<V>
+where
+    V: AsMetric + Debug + Clone,
+{
+    fn to_be_recorded(self) -> Self {
+        let metric = self.value.metric();
+        let result = metric.is_recorded();
+        let sentence = metric_sentence("be", "recorded".to_string(), metric);
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_value<N: Into<f64>>(self, expected: N) -> Self {
+        let expected = expected.into();
+        let metric = self.value.metric();
+        let result = metric.value() == Some(expected);
+        let sentence = metric_sentence("have", format!("value {}", expected), metric);
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_observation_count(self, expected: usize) -> Self {
+        let metric = self.value.metric();
+        let result = metric.is_recorded() && metric.value().is_none() && metric.observations().len() == expected;
+        let object = if expected == 1 { "1 observation".to_string() } else { format!("{} observations", expected) };
+        let sentence = metric_sentence("have", object, metric);
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_label(self, key: &str, value: &str) -> Self {
+        let metric = self.value.metric();
+        let result = metric.series().iter().any(|series| series.has_label(key, value));
+        let mut sentence = AssertionSentence::new("have", format!("label {}={:?}", key, value)).with_actual(metric.to_string());
+        for series in metric.series() {
+            sentence = sentence.with_detail(series.to_string());
+        }
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::metrics::capture_metrics;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_to_have_value() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let _metrics = capture_metrics();
+        ::metrics::counter!("requests_total", "status" => "200").increment(2);
+        ::metrics::counter!("requests_total", "status" => "500").increment(1);
+        ::metrics::gauge!("queue_depth").set(1.5);
+
+        expect_metric!("requests_total").to_have_value(3);
+        expect_metric!("requests_total", "status" => "500").to_have_value(1);
+        expect_metric!("queue_depth").to_have_value(1.5);
+        expect_metric!("queue_depth").not().to_have_value(2);
+    }
+
+    #[test]
+    fn test_to_be_recorded() {
+        crate::Reporter::disable_deduplication();
+
+        let _metrics = capture_metrics();
+        ::metrics::counter!("requests_total").increment(1);
+
+        expect_metric!("requests_total").to_be_recorded();
+        expect_metric!("errors_total").not().to_be_recorded();
+        expect_metric!("requests_total", "status" => "200").not().to_be_recorded();
+    }
+
+    #[test]
+    fn test_to_have_observation_count() {
+        crate::Reporter::disable_deduplication();
+
+        let _metrics = capture_metrics();
+        ::metrics::histogram!("latency_seconds").record(0.1);
+        ::metrics::histogram!("latency_seconds").record(0.3);
+        ::metrics::counter!("requests_total").increment(2);
+
+        expect_metric!("latency_seconds").to_have_observation_count(2);
+        expect_metric!("requests_total").not().to_have_observation_count(2);
+    }
+
+    #[test]
+    fn test_to_have_label() {
+        crate::Reporter::disable_deduplication();
+
+        let _metrics = capture_metrics();
+        ::metrics::counter!("requests_total", "method" => "GET").increment(1);
+
+        expect_metric!("requests_total").to_have_label("method", "GET");
+        expect_metric!("requests_total").not().to_have_label("method", "POST");
+    }
+
+    #[test]
+    #[should_panic(expected = "have value 3 (got counter 2 across 2 series)\n  requests_total{status=\"200\"} counter 1")]
+    fn test_wrong_value_fails_with_series() {
+        let _metrics = capture_metrics();
+        ::metrics::counter!("requests_total", "status" => "200").increment(1);
+        ::metrics::counter!("requests_total", "status" => "500").increment(1);
+
+        let _assertion = expect_metric!("requests_total").to_have_value(3);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be recorded (got not recorded)")]
+    fn test_missing_metric_fails() {
+        let _metrics = capture_metrics();
+
+        let _assertion = expect_metric!("requests_total").to_be_recorded();
+        std::hint::black_box(_assertion);
+    }
+}
@@ -1,19 +1,84 @@
+#[cfg(feature = "archive")]
+pub mod archive;
 pub mod boolean;
+pub mod capacity;
+#[cfg(feature = "syn")]
+pub mod code;
 pub mod collection;
+pub mod condition;
+pub mod control_flow;
 pub mod equality;
+pub mod error;
+pub mod failure;
+pub mod float;
+pub mod frame;
+pub mod function;
 pub mod hashmap;
+#[cfg(feature = "image")]
+pub mod image;
+#[cfg(feature = "json-schema")]
+pub mod json_schema;
+#[cfg(feature = "log-capture")]
+pub mod logs;
+#[cfg(feature = "metrics-capture")]
+pub mod metrics;
 pub mod numeric;
 pub mod option;
+pub mod path;
+#[cfg(feature = "regex")]
+pub mod pattern;
+pub mod poll;
+#[cfg(feature = "prost")]
+pub mod protobuf;
 pub mod result;
+pub mod round_trip;
+pub mod size;
+mod snapshot;
+#[cfg(feature = "sqlx")]
+pub mod sql;
 pub mod string;
+#[cfg(feature = "unicode")]
+pub mod unicode;
 
 // Instead of glob imports, we explicitly export the trait names
 // to avoid conflicts and ambiguities
+#[cfg(feature = "archive")]
+pub use archive::{ArchiveMatchers, EntryContent, containing};
 pub use boolean::BooleanMatchers;
+pub use capacity::CapacityMatchers;
+#[cfg(feature = "syn")]
+pub use code::CodeMatchers;
 pub use collection::{CollectionExtensions, CollectionMatchers};
+pub use condition::ConditionMatchers;
+pub use control_flow::ControlFlowMatchers;
 pub use equality::EqualityMatchers;
+pub use error::ErrorMatchers;
+pub use failure::FailureMatchers;
+pub use float::FloatSliceMatchers;
+pub use frame::FrameMatchers;
+pub use function::FunctionMatchers;
 pub use hashmap::HashMapMatchers;
-pub use numeric::NumericMatchers;
+#[cfg(feature = "image")]
+pub use image::ImageMatchers;
+#[cfg(feature = "json-schema")]
+pub use json_schema::JsonSchemaMatchers;
+#[cfg(feature = "log-capture")]
+pub use logs::LogMatchers;
+#[cfg(feature = "metrics-capture")]
+pub use metrics::MetricMatchers;
+pub use numeric::{CheckedArithmeticMatchers, NumericMatchers};
 pub use option::OptionMatchers;
+pub use path::PathMatchers;
+#[cfg(feature = "regex")]
+pub use pattern::PatternMatchers;
+pub use poll::PollMatchers;
+#[cfg(feature = "prost")]
+pub use protobuf::ProtobufMatchers;
 pub use result::ResultMatchers;
+pub use round_trip::RoundTripMatchers;
+pub use size::SizeMatchers;
+#[cfg(feature = "sqlx")]
+pub use sql::SqlMatchers;
 pub use string::StringMatchers;
+#[cfg(feature = "unicode")]
+pub use unicode::{Form, UnicodeMatchers};
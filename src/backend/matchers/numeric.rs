@@ -1,27 +1,56 @@
 use crate::backend::Assertion;
 use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::number::FormatNumber;
 use std::fmt::{Debug, Display};
 use std::ops::Range;
 
 /// Trait for numeric assertions.
 ///
 /// Provides matchers for comparing, classifying, and range-checking numeric values.
-/// Supported for all standard numeric types: `i8`..`i128`, `u8`..`u128`, `isize`, `usize`, `f32`, `f64`.
+/// Supported for all standard numeric types: `i8`..`i128`, `u8`..`u128`, `isize`, `usize`, `f32`, `f64`,
+/// as well as `rust_decimal::Decimal` with the `decimal` feature and `num_bigint::BigInt` / `BigUint` with the
+/// `bigint` feature.
 pub trait NumericMatchers<T> {
+    #[track_caller]
     fn to_be_positive(self) -> Self;
+    #[track_caller]
     fn to_be_negative(self) -> Self;
+    #[track_caller]
     fn to_be_zero(self) -> Self;
+    #[track_caller]
     fn to_be_greater_than(self, expected: T) -> Self;
+    #[track_caller]
     fn to_be_greater_than_or_equal(self, expected: T) -> Self;
+    #[track_caller]
     fn to_be_less_than(self, expected: T) -> Self;
+    #[track_caller]
     fn to_be_less_than_or_equal(self, expected: T) -> Self;
+    #[track_caller]
     fn to_be_in_range(self, range: Range<T>) -> Self;
+    #[track_caller]
     fn to_be_even(self) -> Self;
+    #[track_caller]
     fn to_be_odd(self) -> Self;
 }
 
+/// Trait for integer arithmetic that must not overflow, such as sizes computed from untrusted input.
+///
+/// The matchers run the `checked_*` operation of the integer type and report the overflowing operands.
+/// Supported for all standard integer types: `i8`..`i128`, `u8`..`u128`, `isize`, `usize`.
+pub trait CheckedArithmeticMatchers<T> {
+    #[track_caller]
+    fn to_add_without_overflow(self, rhs: T) -> Self;
+    #[track_caller]
+    fn to_multiply_without_overflow(self, rhs: T) -> Self;
+}
+
 /// Internal helper trait implemented by all supported numeric types.
-trait Numeric: PartialOrd + PartialEq + Display + Clone + Copy {
+#[diagnostic::on_unimplemented(
+    message = "`NumericMatchers` can't check `{Self}`, which isn't a number",
+    label = "not a number",
+    note = "`NumericMatchers` apply to the primitive integers and floats, and to `Decimal` and `BigInt` with their features"
+)]
+trait Numeric: PartialOrd + PartialEq + Display + FormatNumber + Clone {
     fn zero() -> Self;
     fn is_even(&self) -> bool;
     fn is_odd(&self) -> bool;
@@ -74,9 +103,112 @@ macro_rules! impl_numeric_float {
     };
 }
 
+/// Internal helper trait implemented by all integer types, exposing their checked operations.
+trait CheckedInteger: Display + Copy {
+    fn checked_add_to(self, rhs: Self) -> Option<Self>;
+    fn checked_multiply_by(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_integer {
+    ($($t:ty),*) => {
+        $(
+            impl CheckedInteger for $t {
+                fn checked_add_to(self, rhs: Self) -> Option<Self> { self.checked_add(rhs) }
+                fn checked_multiply_by(self, rhs: Self) -> Option<Self> { self.checked_mul(rhs) }
+            }
+
+            impl AsCheckedInteger for $t {
+                type Int = $t;
+
+                fn as_integer(&self) -> $t { *self }
+            }
+
+            impl AsCheckedInteger for &$t {
+                type Int = $t;
+
+                fn as_integer(&self) -> $t { **self }
+            }
+        )*
+    };
+}
+
+/// Helper trait for owned and referenced integers
+trait AsCheckedInteger {
+    type Int: CheckedInteger;
+
+    fn as_integer(&self) -> Self::Int;
+}
+
+/// Describe a checked operation for the actual value, such as `250 + 10 overflows u8` or `2 * 3 = 6`
+fn describe_operation<T: Display>(lhs: T, operator: &str, rhs: T, outcome: Option<T>) -> String {
+    return match outcome {
+        Some(value) => format!("{} {} {} = {}", lhs, operator, rhs, value),
+        None => format!("{} {} {} overflows {}", lhs, operator, rhs, std::any::type_name::<T>()),
+    };
+}
+
 impl_numeric_signed!(i8, i16, i32, i64, i128, isize);
 impl_numeric_unsigned!(u8, u16, u32, u64, u128, usize);
 impl_numeric_float!(f32, f64);
+#[cfg(feature = "decimal")]
+impl Numeric for rust_decimal::Decimal {
+    fn zero() -> Self {
+        rust_decimal::Decimal::ZERO
+    }
+
+    fn is_even(&self) -> bool {
+        return self.fract().is_zero() && (*self % rust_decimal::Decimal::TWO).is_zero();
+    }
+
+    fn is_odd(&self) -> bool {
+        return self.fract().is_zero() && !(*self % rust_decimal::Decimal::TWO).is_zero();
+    }
+
+    fn is_negative(&self) -> bool {
+        // Decimals keep the sign of a negative zero
+        return self.is_sign_negative() && !self.is_zero();
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Numeric for num_bigint::BigInt {
+    fn zero() -> Self {
+        num_bigint::BigInt::default()
+    }
+
+    fn is_even(&self) -> bool {
+        !self.bit(0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.bit(0)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.sign() == num_bigint::Sign::Minus
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl Numeric for num_bigint::BigUint {
+    fn zero() -> Self {
+        num_bigint::BigUint::default()
+    }
+
+    fn is_even(&self) -> bool {
+        !self.bit(0)
+    }
+
+    fn is_odd(&self) -> bool {
+        self.bit(0)
+    }
+
+    fn is_negative(&self) -> bool {
+        false
+    }
+}
+
+impl_checked_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
 
 /// Implementation for owned numeric values
 impl<V> NumericMatchers<V> for Assertion<V>
@@ -85,74 +217,75 @@ where
 {
     fn to_be_positive(self) -> Self {
         let result = self.value > V::zero();
-        let sentence = AssertionSentence::new("be", "positive").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "positive").with_actual(actual.format_number()));
     }
 
     fn to_be_negative(self) -> Self {
         let result = self.value.is_negative();
-        let sentence = AssertionSentence::new("be", "negative").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "negative").with_actual(actual.format_number()));
     }
 
     fn to_be_zero(self) -> Self {
         let result = self.value == V::zero();
-        let sentence = AssertionSentence::new("be", "zero").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "zero").with_actual(actual.format_number()));
     }
 
     fn to_be_greater_than(self, expected: V) -> Self {
         let result = self.value > expected;
-        let sentence = AssertionSentence::new("be", format!("greater than {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("greater than {}", expected.format_number())).with_actual(actual.format_number());
+        });
     }
 
     fn to_be_greater_than_or_equal(self, expected: V) -> Self {
         let result = self.value >= expected;
-        let sentence =
-            AssertionSentence::new("be", format!("greater than or equal to {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("greater than or equal to {}", expected.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_less_than(self, expected: V) -> Self {
         let result = self.value < expected;
-        let sentence = AssertionSentence::new("be", format!("less than {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("less than {}", expected.format_number())).with_actual(actual.format_number());
+        });
     }
 
     fn to_be_less_than_or_equal(self, expected: V) -> Self {
         let result = self.value <= expected;
-        let sentence = AssertionSentence::new("be", format!("less than or equal to {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("less than or equal to {}", expected.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_in_range(self, range: Range<V>) -> Self {
         let result = range.contains(&self.value);
-        let sentence =
-            AssertionSentence::new("be", format!("in range {}..{}", range.start, range.end)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("in range {}..{}", range.start.format_number(), range.end.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_even(self) -> Self {
         let result = self.value.is_even();
-        let sentence = AssertionSentence::new("be", "even").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "even").with_actual(actual.format_number()));
     }
 
     fn to_be_odd(self) -> Self {
         let result = self.value.is_odd();
-        let sentence = AssertionSentence::new("be", "odd").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "odd").with_actual(actual.format_number()));
     }
 }
 
@@ -163,74 +296,102 @@ where
 {
     fn to_be_positive(self) -> Self {
         let result = *self.value > V::zero();
-        let sentence = AssertionSentence::new("be", "positive").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "positive").with_actual(actual.format_number()));
     }
 
     fn to_be_negative(self) -> Self {
         let result = self.value.is_negative();
-        let sentence = AssertionSentence::new("be", "negative").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "negative").with_actual(actual.format_number()));
     }
 
     fn to_be_zero(self) -> Self {
         let result = *self.value == V::zero();
-        let sentence = AssertionSentence::new("be", "zero").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "zero").with_actual(actual.format_number()));
     }
 
     fn to_be_greater_than(self, expected: V) -> Self {
         let result = *self.value > expected;
-        let sentence = AssertionSentence::new("be", format!("greater than {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("greater than {}", expected.format_number())).with_actual(actual.format_number());
+        });
     }
 
     fn to_be_greater_than_or_equal(self, expected: V) -> Self {
         let result = *self.value >= expected;
-        let sentence =
-            AssertionSentence::new("be", format!("greater than or equal to {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("greater than or equal to {}", expected.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_less_than(self, expected: V) -> Self {
         let result = *self.value < expected;
-        let sentence = AssertionSentence::new("be", format!("less than {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("less than {}", expected.format_number())).with_actual(actual.format_number());
+        });
     }
 
     fn to_be_less_than_or_equal(self, expected: V) -> Self {
         let result = *self.value <= expected;
-        let sentence = AssertionSentence::new("be", format!("less than or equal to {}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("less than or equal to {}", expected.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_in_range(self, range: Range<V>) -> Self {
         let result = range.contains(self.value);
-        let sentence =
-            AssertionSentence::new("be", format!("in range {}..{}", range.start, range.end)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("in range {}..{}", range.start.format_number(), range.end.format_number()))
+                .with_actual(actual.format_number());
+        });
     }
 
     fn to_be_even(self) -> Self {
         let result = self.value.is_even();
-        let sentence = AssertionSentence::new("be", "even").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "even").with_actual(actual.format_number()));
     }
 
     fn to_be_odd(self) -> Self {
         let result = self.value.is_odd();
-        let sentence = AssertionSentence::new("be", "odd").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "odd").with_actual(actual.format_number()));
+    }
+}
+
+// Single implementation of CheckedArithmeticMatchers for owned and referenced integers
+impl<T, V> CheckedArithmeticMatchers<T> for Assertion<V>
+where
+    T: CheckedInteger,
+    V: AsCheckedInteger<Int = T> + Debug + Clone,
+{
+    fn to_add_without_overflow(self, rhs: T) -> Self {
+        let lhs = self.value.as_integer();
+        let outcome = lhs.checked_add_to(rhs);
+
+        return self.add_step_with(outcome.is_some(), |_| {
+            return AssertionSentence::new("add", format!("{} without overflow", rhs))
+                .with_actual(describe_operation(lhs, "+", rhs, outcome));
+        });
+    }
+
+    fn to_multiply_without_overflow(self, rhs: T) -> Self {
+        let lhs = self.value.as_integer();
+        let outcome = lhs.checked_multiply_by(rhs);
+
+        return self.add_step_with(outcome.is_some(), |_| {
+            return AssertionSentence::new("multiply", format!("by {} without overflow", rhs))
+                .with_actual(describe_operation(lhs, "*", rhs, outcome));
+        });
     }
 }
 
@@ -483,4 +644,107 @@ mod tests {
     fn test_f64_not_greater_fails() {
         expect!(2.0_f64).to_be_greater_than(5.0_f64);
     }
+
+    #[test]
+    #[should_panic(expected = "be greater than 0.5 (got 1e-7)")]
+    fn test_small_floats_are_scientific_fails() {
+        let _assertion = expect!(1e-7_f64).to_be_greater_than(0.5);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be in range 0.0..1000.0 (got 1250000.0)")]
+    fn test_floats_are_written_out_fails() {
+        let _assertion = expect!(1.25e6_f32).to_be_in_range(0.0..1000.0);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    fn test_checked_arithmetic_matchers() {
+        crate::Reporter::disable_deduplication();
+
+        expect!(200_u8).to_add_without_overflow(55);
+        expect!(200_u8).not().to_add_without_overflow(56);
+        expect!(i32::MIN).to_add_without_overflow(i32::MAX);
+        expect!(-1_i64).not().to_add_without_overflow(i64::MIN);
+
+        let header_len: usize = 4096;
+        expect!(&header_len).to_multiply_without_overflow(1024);
+        expect!(&header_len).not().to_multiply_without_overflow(usize::MAX);
+        expect!(i8::MIN).not().to_multiply_without_overflow(-1);
+        expect!(0_u128).to_multiply_without_overflow(u128::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "add 10 without overflow (got 250 + 10 overflows u8)")]
+    fn test_add_overflow_fails() {
+        let _assertion = expect!(250_u8).to_add_without_overflow(10);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "multiply by 3 without overflow (got 2 * 3 = 6)")]
+    fn test_not_multiply_overflow_fails() {
+        let _assertion = expect!(2_i16).not().to_multiply_without_overflow(3);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    fn test_decimal_matchers() {
+        use rust_decimal::Decimal;
+        crate::Reporter::disable_deduplication();
+
+        let price = Decimal::new(1999, 2);
+        expect!(price).to_be_positive();
+        expect!(price).to_be_greater_than(Decimal::new(19, 0));
+        expect!(&price).to_be_less_than_or_equal(Decimal::new(2000, 2));
+        expect!(price).to_be_in_range(Decimal::ZERO..Decimal::ONE_HUNDRED);
+        expect!(price).not().to_be_even();
+        expect!(price).not().to_be_odd();
+
+        expect!(Decimal::new(-400, 2)).to_be_even();
+        expect!(Decimal::new(-300, 2)).to_be_odd();
+        expect!(Decimal::new(-300, 2)).to_be_negative();
+        expect!(Decimal::new(-0, 2)).to_be_zero();
+        expect!(Decimal::new(-0, 2)).not().to_be_negative();
+    }
+
+    #[test]
+    #[cfg(feature = "decimal")]
+    #[should_panic(expected = "be less than 10.00 (got 10.01)")]
+    fn test_decimal_not_less_fails() {
+        use rust_decimal::Decimal;
+
+        let _assertion = expect!(Decimal::new(1001, 2)).to_be_less_than(Decimal::new(1000, 2));
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    fn test_bigint_matchers() {
+        use num_bigint::{BigInt, BigUint};
+        crate::Reporter::disable_deduplication();
+
+        let huge: BigInt = BigInt::from(u128::MAX) * 4u8;
+        expect!(&huge).to_be_positive();
+        expect!(&huge).to_be_even();
+        expect!(huge.clone()).to_be_greater_than(BigInt::from(u128::MAX));
+        expect!(-huge.clone() - 1).to_be_negative();
+        expect!(-huge - 1).to_be_odd();
+        expect!(BigInt::from(0)).to_be_zero();
+
+        let modulus = BigUint::from(2u8).pow(255) - 19u8;
+        expect!(&modulus).to_be_odd();
+        expect!(&modulus).not().to_be_negative();
+        expect!(modulus).to_be_in_range(BigUint::from(1u8)..BigUint::from(2u8).pow(256));
+    }
+
+    #[test]
+    #[cfg(feature = "bigint")]
+    #[should_panic(expected = "be negative (got 340282366920938463463374607431768211456)")]
+    fn test_bigint_not_negative_fails() {
+        let _assertion = expect!(num_bigint::BigInt::from(u128::MAX) + 1).to_be_negative();
+        std::hint::black_box(_assertion);
+    }
 }
@@ -4,14 +4,26 @@ use std::fmt::Debug;
 
 /// Trait for Option<T> assertions
 pub trait OptionMatchers<T: Debug> {
+    #[track_caller]
     fn to_be_some(self) -> Self;
+    #[track_caller]
     fn to_be_none(self) -> Self;
+    #[track_caller]
     fn to_contain(self, expected: &T) -> Self
     where
         T: PartialEq;
+
+    /// Check the number of values of the option as a container, 0 for `None` and 1 for `Some`
+    #[track_caller]
+    fn to_have_length(self, expected: usize) -> Self;
 }
 
 /// Helper trait for Optiony types
+#[diagnostic::on_unimplemented(
+    message = "`OptionMatchers` can't check `{Self}`, which isn't an option",
+    label = "not an option",
+    note = "`OptionMatchers` apply to `Option<T>` and `&Option<T>`"
+)]
 trait AsOption {
     type Item: Debug;
 
@@ -20,6 +32,7 @@ trait AsOption {
     fn contains_item<U>(&self, expected: &U) -> bool
     where
         U: PartialEq<Self::Item>;
+    fn as_option(&self) -> Option<&Self::Item>;
 }
 
 // Implementation for Option<T>
@@ -43,6 +56,10 @@ impl<T: Debug + PartialEq> AsOption for Option<T> {
             None => false,
         }
     }
+
+    fn as_option(&self) -> Option<&Self::Item> {
+        self.as_ref()
+    }
 }
 
 // Implementation for &Option<T>
@@ -66,6 +83,10 @@ impl<T: Debug + PartialEq> AsOption for &Option<T> {
             None => false,
         }
     }
+
+    fn as_option(&self) -> Option<&Self::Item> {
+        self.as_ref()
+    }
 }
 
 // Single implementation of OptionMatchers for any type that implements AsOption
@@ -76,16 +97,14 @@ where
 {
     fn to_be_some(self) -> Self {
         let result = self.value.is_some_option();
-        let sentence = AssertionSentence::new("be", "some").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "some").with_actual(describe_variant(actual.as_option())));
     }
 
     fn to_be_none(self) -> Self {
         let result = self.value.is_none_option();
-        let sentence = AssertionSentence::new("be", "none").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "none").with_actual(describe_variant(actual.as_option())));
     }
 
     fn to_contain(self, expected: &T) -> Self
@@ -93,12 +112,29 @@ where
         T: PartialEq,
     {
         let result = self.value.contains_item(expected);
-        let sentence = AssertionSentence::new("contain", format!("{:?}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("{:?}", expected)).with_actual(describe_variant(actual.as_option()));
+        });
+    }
+
+    fn to_have_length(self, expected: usize) -> Self {
+        let result = usize::from(self.value.is_some_option()) == expected;
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("have", format!("length {}", expected)).with_actual(describe_variant(actual.as_option()));
+        });
     }
 }
 
+/// Describe an option by its variant first, then its value: `Some: 42`
+fn describe_variant<T: Debug>(option: Option<&T>) -> String {
+    return match option {
+        Some(value) => format!("Some: {:?}", value),
+        None => "None".to_string(),
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -119,7 +155,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "be some")]
+    #[should_panic(expected = "be some (got None)")]
     fn test_none_to_be_some_fails() {
         let value: Option<i32> = None;
         let _assertion = expect!(value).to_be_some();
@@ -127,7 +163,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "be none")]
+    #[should_panic(expected = "be none (got Some: 42)")]
     fn test_some_to_be_none_fails() {
         let value: Option<i32> = Some(42);
         let _assertion = expect!(value).to_be_none();
@@ -158,7 +194,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "contain 43")]
+    #[should_panic(expected = "contain 43 (got Some: 42)")]
     fn test_missing_value_fails() {
         let value: Option<i32> = Some(42);
         let _assertion = expect!(value).to_contain(&43);
@@ -166,10 +202,30 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "contain 42")]
+    #[should_panic(expected = "contain 42 (got None)")]
     fn test_none_value_fails() {
         let value: Option<i32> = None;
         let _assertion = expect!(value).to_contain(&42);
         std::hint::black_box(_assertion);
     }
+
+    #[test]
+    fn test_option_to_have_length() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let none: Option<i32> = None;
+
+        expect!(Some(42)).to_have_length(1);
+        expect!(&none).to_have_length(0);
+        expect!(none).not().to_have_length(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "have length 0 (got Some: 42)")]
+    fn test_some_to_have_length_zero_fails() {
+        let value: Option<i32> = Some(42);
+        let _assertion = expect!(value).to_have_length(0);
+        std::hint::black_box(_assertion);
+    }
 }
@@ -0,0 +1,206 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Trait for filesystem metadata assertions on paths.
+///
+/// Useful in build-tool and cache-invalidation tests. Supported for `Path`, `PathBuf` and strings, owned or borrowed.
+/// The metadata is read when the matcher runs, following symbolic links; a path whose metadata can't be read doesn't
+/// match, and the step reports the error as the actual value.
+pub trait PathMatchers {
+    /// Check that the file was last modified at most `duration` ago
+    #[track_caller]
+    fn to_have_been_modified_within(self, duration: Duration) -> Self;
+
+    /// Check that the file was last modified at or after `time`
+    #[track_caller]
+    fn to_have_been_modified_since(self, time: SystemTime) -> Self;
+
+    /// Check that the file holds more than `bytes` bytes
+    #[track_caller]
+    fn to_have_size_greater_than(self, bytes: u64) -> Self;
+
+    /// Check the permission bits of the file, such as `0o644`, ignoring the file type bits of its mode
+    #[cfg(unix)]
+    #[track_caller]
+    fn to_have_permissions(self, mode: u32) -> Self;
+}
+
+/// Helper trait for path-like types
+trait AsPath {
+    fn path(&self) -> &Path;
+}
+
+impl<P: AsPath + ?Sized> AsPath for &P {
+    fn path(&self) -> &Path {
+        return (**self).path();
+    }
+}
+
+impl AsPath for Path {
+    fn path(&self) -> &Path {
+        return self;
+    }
+}
+
+impl AsPath for PathBuf {
+    fn path(&self) -> &Path {
+        return self.as_path();
+    }
+}
+
+impl AsPath for str {
+    fn path(&self) -> &Path {
+        return Path::new(self);
+    }
+}
+
+impl AsPath for String {
+    fn path(&self) -> &Path {
+        return Path::new(self.as_str());
+    }
+}
+
+/// Read a property from the metadata of a path, describing the error when either can't be read
+fn read<T>(path: &Path, property: impl FnOnce(&Metadata) -> io::Result<T>) -> Result<T, String> {
+    return fs::metadata(path).and_then(|metadata| property(&metadata)).map_err(|e| format!("unreadable metadata: {}", e));
+}
+
+/// Time elapsed since a modification, to the millisecond; modifications in the future, from clock skew, are recent
+fn age_of(modified: SystemTime) -> Duration {
+    let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+    return Duration::from_millis(age.as_millis() as u64);
+}
+
+// Single implementation for any type that implements AsPath
+impl<V> PathMatchers for Assertion<V>
+where
+    V: AsPath + Debug + Clone,
+{
+    fn to_have_been_modified_within(self, duration: Duration) -> Self {
+        let age = read(self.value.path(), |metadata| metadata.modified().map(age_of));
+        let result = age.as_ref().is_ok_and(|age| *age <= duration);
+
+        return self.add_step_with(result, |_| {
+            let actual = match &age {
+                Ok(age) => format!("modified {:?} ago", age),
+                Err(error) => error.clone(),
+            };
+            return AssertionSentence::new("have", format!("been modified within {:?}", duration)).with_actual(actual);
+        });
+    }
+
+    fn to_have_been_modified_since(self, time: SystemTime) -> Self {
+        let modified = read(self.value.path(), Metadata::modified);
+        let result = modified.as_ref().is_ok_and(|modified| *modified >= time);
+
+        return self.add_step_with(result, |_| {
+            let actual = match &modified {
+                Ok(modified) => format!("modified {:?} ago", age_of(*modified)),
+                Err(error) => error.clone(),
+            };
+            return AssertionSentence::new("have", format!("been modified since {:?} ago", age_of(time))).with_actual(actual);
+        });
+    }
+
+    fn to_have_size_greater_than(self, bytes: u64) -> Self {
+        let size = read(self.value.path(), |metadata| Ok(metadata.len()));
+        let result = size.as_ref().is_ok_and(|size| *size > bytes);
+
+        return self.add_step_with(result, |_| {
+            let actual = match &size {
+                Ok(size) => format!("{} bytes", size),
+                Err(error) => error.clone(),
+            };
+            return AssertionSentence::new("have", format!("size greater than {} bytes", bytes)).with_actual(actual);
+        });
+    }
+
+    #[cfg(unix)]
+    fn to_have_permissions(self, mode: u32) -> Self {
+        use std::os::unix::fs::PermissionsExt;
+
+        let permissions = read(self.value.path(), |metadata| Ok(metadata.permissions().mode() & 0o7777));
+        let result = permissions.as_ref().is_ok_and(|permissions| *permissions == mode);
+
+        return self.add_step_with(result, |_| {
+            let actual = match &permissions {
+                Ok(permissions) => format!("{:#o}", permissions),
+                Err(error) => error.clone(),
+            };
+            return AssertionSentence::new("have", format!("permissions {:#o}", mode)).with_actual(actual);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    /// Write a temporary file named after the test, so parallel tests don't share it
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("rest-path-{}-{}", name, std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        return path;
+    }
+
+    #[test]
+    fn test_modification_time() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let before = SystemTime::now() - Duration::from_secs(60);
+        let path = temp_file("modified", "cached");
+
+        expect!(&path).to_have_been_modified_within(Duration::from_secs(60));
+        expect!(path.as_path()).to_have_been_modified_since(before);
+        expect!(&path).not().to_have_been_modified_since(SystemTime::now() + Duration::from_secs(60));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_size() {
+        let path = temp_file("size", "0123456789");
+
+        expect!(&path).to_have_size_greater_than(9);
+        expect!(path.to_str().unwrap()).not().to_have_size_greater_than(10);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = temp_file("permissions", "");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+        expect!(&path).to_have_permissions(0o640);
+        expect!(&path).not().to_have_permissions(0o644);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "size greater than 100 bytes (got 2 bytes)")]
+    fn test_small_file_fails() {
+        let path = temp_file("small", "ok");
+        let _assertion = expect!(&path).to_have_size_greater_than(100);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "(got unreadable metadata:")]
+    fn test_missing_file_fails() {
+        let _assertion = expect!("rest-missing-file.bin").to_have_been_modified_within(Duration::from_secs(60));
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,165 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::template::{Template, TemplateCaptures};
+use regex::Regex;
+use std::fmt::Debug;
+
+/// Trait for regex and template string assertions.
+///
+/// Available with the `regex` feature, for strings owned or borrowed. The other string matchers are in
+/// [`StringMatchers`](crate::backend::matchers::string::StringMatchers), which builds without the regex engine.
+pub trait PatternMatchers {
+    /// Check if the string matches a regex pattern, anywhere unless anchored with `^` and `$`
+    #[track_caller]
+    fn to_match(self, pattern: &str) -> Self;
+
+    /// Check the string against a template with named placeholders, such as `Hello {name}, you have {count} messages`
+    #[track_caller]
+    fn to_match_template(self, template: &str) -> Self;
+
+    /// Check the string against a template like `to_match_template`, and return the placeholder values for follow-up
+    /// assertions
+    #[track_caller]
+    fn to_match_template_capturing(self, template: &str) -> TemplateCaptures;
+}
+
+/// Helper trait for string-like types
+trait AsPatternText {
+    fn text(&self) -> &str;
+}
+
+impl<U: AsPatternText + ?Sized> AsPatternText for &U {
+    fn text(&self) -> &str {
+        return (**self).text();
+    }
+}
+
+impl AsPatternText for String {
+    fn text(&self) -> &str {
+        return self.as_str();
+    }
+}
+
+impl AsPatternText for str {
+    fn text(&self) -> &str {
+        return self;
+    }
+}
+
+// Single implementation for any type that implements AsPatternText
+impl<V> PatternMatchers for Assertion<V>
+where
+    V: AsPatternText + Debug + Clone,
+{
+    fn to_match(self, pattern: &str) -> Self {
+        let re = Regex::new(pattern).unwrap_or_else(|e| {
+            panic!("Invalid regex pattern '{}': {}", pattern, e);
+        });
+        let result = re.is_match(self.value.text());
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("match", format!("pattern /{}/", pattern)).with_actual(format!("{:?}", actual));
+        });
+    }
+
+    fn to_match_template(self, template: &str) -> Self {
+        let parsed = parse_template(template);
+        let result = parsed.captures(self.value.text()).is_some();
+
+        return self.add_step_with(result, |actual| {
+            let mut sentence = AssertionSentence::new("match", format!("template \"{}\"", template)).with_actual(format!("{:?}", actual));
+            if !result {
+                for detail in parsed.explain_mismatch(actual.text()) {
+                    sentence = sentence.with_detail(detail);
+                }
+            }
+
+            return sentence;
+        });
+    }
+
+    fn to_match_template_capturing(self, template: &str) -> TemplateCaptures {
+        let captures = parse_template(template).captures(self.value.text());
+        drop(self.to_match_template(template));
+
+        // A failure that doesn't panic, such as with a failure hook that continues, leaves nothing to return
+        return captures.unwrap_or_else(|| panic!("to_match_template_capturing: no captures to return for template \"{}\"", template));
+    }
+}
+
+/// Parse a template given to a matcher, panicking on an invalid one like an invalid regex pattern
+fn parse_template(template: &str) -> Template {
+    return Template::parse(template).unwrap_or_else(|e| {
+        panic!("Invalid template '{}': {}", template, e);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_string_to_match() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        // Plain substring patterns (backward compatible)
+        expect!("hello world").to_match("world");
+        expect!("hello world").not().to_match("goodbye");
+        expect!(String::from("hello world")).to_match("hello");
+
+        // Actual regex patterns
+        expect!("hello123").to_match("\\d+");
+        expect!("hello123").to_match("^hello\\d+$");
+        expect!("hello").not().to_match("\\d+");
+        expect!("2024-01-15").to_match("\\d{4}-\\d{2}-\\d{2}");
+        expect!("test@example.com").to_match("[a-zA-Z]+@[a-zA-Z]+\\.[a-zA-Z]+");
+        expect!(String::from("abc123")).to_match("[a-z]+\\d+");
+    }
+
+    #[test]
+    fn test_string_to_match_template() {
+        let message = "Hello Ada, you have 3 messages";
+
+        expect!(message).to_match_template("Hello {name}, you have {count} messages");
+        expect!(message).not().to_match_template("Hello {name}, you have {count} new messages");
+
+        let captures = expect!(message).to_match_template_capturing("Hello {name}, you have {count} messages");
+        expect!(&captures["name"]).to_equal("Ada");
+        expect!(captures["count"].parse::<u32>()).to_be_ok();
+    }
+
+    #[test]
+    #[should_panic(expected = "match template \"Hello {name}, you have {count} messages\"")]
+    fn test_template_mismatch_fails() {
+        let message = "Hello Ada, you had 3 messages".to_string();
+        expect!(message).to_match_template("Hello {name}, you have {count} messages");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid template")]
+    fn test_invalid_template_panics() {
+        expect!("Hello").to_match_template("Hello {name");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid regex pattern")]
+    fn test_invalid_regex_panics() {
+        let _assertion = expect!("hello").to_match("[invalid");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "not match")]
+    fn test_not_matches_when_it_does_fails() {
+        let _assertion = expect!("hello world").not().to_match("hello");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "match")]
+    fn test_matches_when_it_doesnt_fails() {
+        let _assertion = expect!("hello world").to_match("goodbye");
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+/// Trait for Poll<T> assertions, for futures and state machines polled by hand
+pub trait PollMatchers<T: Debug> {
+    #[track_caller]
+    fn to_be_ready(self) -> Self;
+    #[track_caller]
+    fn to_be_pending(self) -> Self;
+    #[track_caller]
+    fn to_be_ready_with<U: PartialEq<T> + Debug>(self, expected: &U) -> Self;
+}
+
+/// Helper trait for Poll-like types
+trait AsPoll {
+    type Item: Debug;
+
+    fn is_ready_poll(&self) -> bool;
+    fn is_ready_with<U>(&self, expected: &U) -> bool
+    where
+        U: PartialEq<Self::Item>;
+}
+
+// Implementation for Poll<T>
+impl<T: Debug> AsPoll for Poll<T> {
+    type Item = T;
+
+    fn is_ready_poll(&self) -> bool {
+        self.is_ready()
+    }
+
+    fn is_ready_with<U>(&self, expected: &U) -> bool
+    where
+        U: PartialEq<Self::Item>,
+    {
+        match self {
+            Poll::Ready(actual) => expected == actual,
+            Poll::Pending => false,
+        }
+    }
+}
+
+// Implementation for &Poll<T>
+impl<T: Debug> AsPoll for &Poll<T> {
+    type Item = T;
+
+    fn is_ready_poll(&self) -> bool {
+        self.is_ready()
+    }
+
+    fn is_ready_with<U>(&self, expected: &U) -> bool
+    where
+        U: PartialEq<Self::Item>,
+    {
+        match self {
+            Poll::Ready(actual) => expected == actual,
+            Poll::Pending => false,
+        }
+    }
+}
+
+// Single implementation of PollMatchers for any type that implements AsPoll
+impl<T, V> PollMatchers<T> for Assertion<V>
+where
+    T: Debug,
+    V: AsPoll<Item = T> + Debug + Clone,
+{
+    fn to_be_ready(self) -> Self {
+        let result = self.value.is_ready_poll();
+
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "ready").with_actual(format!("{:?}", actual)));
+    }
+
+    fn to_be_pending(self) -> Self {
+        let result = !self.value.is_ready_poll();
+
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "pending").with_actual(format!("{:?}", actual)));
+    }
+
+    fn to_be_ready_with<U: PartialEq<T> + Debug>(self, expected: &U) -> Self {
+        let result = self.value.is_ready_with(expected);
+
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("ready with {:?}", expected)).with_actual(format!("{:?}", actual));
+        });
+    }
+}
+
+/// Context whose waker does nothing, for calling `poll` methods by hand
+pub fn noop_context() -> Context<'static> {
+    return Context::from_waker(Waker::noop());
+}
+
+/// Poll a future once with a waker that does nothing
+///
+/// Futures that aren't `Unpin` can be pinned on the stack first with `std::pin::pin!`.
+pub fn poll_once<F: Future + Unpin + ?Sized>(future: &mut F) -> Poll<F::Output> {
+    return Pin::new(future).poll(&mut noop_context());
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::pin::pin;
+    use std::task::Poll;
+
+    /// Future that is pending a given number of times before completing
+    struct Countdown(u32);
+
+    impl Future for Countdown {
+        type Output = &'static str;
+
+        fn poll(mut self: std::pin::Pin<&mut Self>, _context: &mut std::task::Context<'_>) -> Poll<Self::Output> {
+            if self.0 == 0 {
+                return Poll::Ready("liftoff");
+            }
+
+            self.0 -= 1;
+            return Poll::Pending;
+        }
+    }
+
+    #[test]
+    fn test_poll_matchers() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let ready: Poll<i32> = Poll::Ready(42);
+        let pending: Poll<i32> = Poll::Pending;
+
+        expect!(ready).to_be_ready();
+        expect!(ready).to_be_ready_with(&42);
+        expect!(&ready).not().to_be_ready_with(&7);
+        expect!(ready).not().to_be_pending();
+        expect!(pending).to_be_pending();
+        expect!(&pending).not().to_be_ready();
+        expect!(pending).not().to_be_ready_with(&42);
+    }
+
+    #[test]
+    fn test_poll_once_drives_a_future() {
+        crate::Reporter::disable_deduplication();
+
+        let mut countdown = Countdown(1);
+        expect!(poll_once(&mut countdown)).to_be_pending();
+        expect!(poll_once(&mut countdown)).to_be_ready_with(&"liftoff");
+
+        let mut ready = pin!(async { 3 + 4 });
+        expect!(poll_once(&mut ready)).to_be_ready_with(&7);
+    }
+
+    #[test]
+    fn test_noop_context_polls_state_machines() {
+        crate::Reporter::disable_deduplication();
+
+        let mut countdown = Countdown(0);
+        expect!(std::pin::Pin::new(&mut countdown).poll(&mut noop_context())).to_be_ready();
+    }
+
+    #[test]
+    #[should_panic(expected = "be ready with 42 (got Pending)")]
+    fn test_pending_is_not_ready_with() {
+        let _assertion = expect!(Poll::<i32>::Pending).to_be_ready_with(&42);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be pending (got Ready(1))")]
+    fn test_ready_is_not_pending() {
+        let _assertion = expect!(Poll::Ready(1)).to_be_pending();
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,528 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use prost::Message;
+use std::fmt::Debug;
+
+/// Trait for field-level assertions on protobuf messages generated by prost.
+///
+/// Available with the `prost` feature, for messages owned or borrowed. Failures list the differing
+/// fields instead of the `Debug` output of the whole message, which is unreadable for large protos.
+pub trait ProtobufMatchers<M> {
+    /// Check that the message equals the expected one, reporting each differing field
+    #[track_caller]
+    fn to_equal_message(self, expected: M) -> Self;
+
+    /// Check that a top-level field holds a value other than its default
+    ///
+    /// An `optional` field explicitly set to its default value (`Some(0)`) counts as set. Check nested
+    /// messages with their own assertion.
+    #[track_caller]
+    fn to_have_field_set(self, field: &str) -> Self;
+}
+
+/// Helper trait for types holding a prost message
+trait AsMessage<M> {
+    fn message(&self) -> &M;
+}
+
+impl<M: Message> AsMessage<M> for M {
+    fn message(&self) -> &M {
+        return self;
+    }
+}
+
+impl<M: Message> AsMessage<M> for &M {
+    fn message(&self) -> &M {
+        return self;
+    }
+}
+
+/// Leaf values of a message, keyed by their field path (`address.city`, `tags[1]`)
+type FieldValues = Vec<(String, String)>;
+
+/// Leaf values of a message, read from its `Debug` output
+///
+/// prost messages carry no field names besides the ones of their derived `Debug` implementation, so its compact output
+/// is parsed, string and character literals included, rather than split on separators that values may contain.
+fn field_values(message: &impl Debug) -> FieldValues {
+    let text = format!("{:?}", message);
+    let mut values = Vec::new();
+
+    match DebugParser::parse(&text) {
+        Some(value) => flatten_value(&value, String::new(), &mut values),
+        // Output of a custom `Debug` implementation that isn't Rust syntax: the message is a single value
+        None => values.push(("message".to_string(), text.clone())),
+    }
+
+    return values;
+}
+
+/// Structure of a value written by a `Debug` implementation
+#[derive(Debug)]
+enum DebugValue<'a> {
+    /// Struct with its fields in declaration order: `Address { city: "London", zip: 0 }`
+    Struct(Vec<(&'a str, DebugValue<'a>)>),
+    /// Tuple, tuple struct or variant with its name, empty for a tuple: `Some(7)`
+    Tuple(&'a str, Vec<DebugValue<'a>>),
+    /// List of elements: `["admin", "ops"]`
+    List(Vec<DebugValue<'a>>),
+    /// Map entries, with their key as written: `{"env": "prod"}`
+    Map(Vec<(&'a str, DebugValue<'a>)>),
+    /// Anything else as written, such as numbers, strings, `None`, unit variants and empty collections
+    Leaf(&'a str),
+}
+
+/// Parser of the compact `Debug` output of a value
+struct DebugParser<'a> {
+    text: &'a str,
+    position: usize,
+}
+
+impl<'a> DebugParser<'a> {
+    /// Parse a whole `Debug` output, or `None` if it isn't valid `Debug` syntax
+    fn parse(text: &'a str) -> Option<DebugValue<'a>> {
+        let mut parser = Self { text, position: 0 };
+        let value = parser.value()?;
+
+        parser.skip_spaces();
+        return if parser.position == text.len() { Some(value) } else { None };
+    }
+
+    fn peek(&self) -> Option<u8> {
+        return self.text.as_bytes().get(self.position).copied();
+    }
+
+    fn skip_spaces(&mut self) {
+        while self.peek().is_some_and(|byte| byte.is_ascii_whitespace()) {
+            self.position += 1;
+        }
+    }
+
+    /// Consume the given byte, after spaces
+    fn expect(&mut self, expected: u8) -> Option<()> {
+        self.skip_spaces();
+        if self.peek()? != expected {
+            return None;
+        }
+
+        self.position += 1;
+        return Some(());
+    }
+
+    /// Text from a position to the current one
+    fn since(&self, start: usize) -> &'a str {
+        return &self.text[start..self.position];
+    }
+
+    fn value(&mut self) -> Option<DebugValue<'a>> {
+        self.skip_spaces();
+        let start = self.position;
+
+        return match self.peek()? {
+            b'[' => {
+                self.position += 1;
+                let items = self.items(b']')?;
+                Some(if items.is_empty() { DebugValue::Leaf(self.since(start)) } else { DebugValue::List(items) })
+            }
+            b'{' => {
+                self.position += 1;
+                let entries = self.entries(|parser| parser.value().map(|_| ()))?;
+                Some(if entries.is_empty() { DebugValue::Leaf(self.since(start)) } else { DebugValue::Map(entries) })
+            }
+            b'(' => {
+                self.position += 1;
+                Some(DebugValue::Tuple("", self.items(b')')?))
+            }
+            b'"' | b'\'' => {
+                self.quoted()?;
+                Some(DebugValue::Leaf(self.since(start)))
+            }
+            _ => {
+                self.word()?;
+                let name = self.since(start);
+                let after_name = self.position;
+                self.skip_spaces();
+
+                match self.peek() {
+                    Some(b'{') => {
+                        self.position += 1;
+                        let fields = self.entries(|parser| parser.word())?;
+                        Some(if fields.is_empty() { DebugValue::Leaf(self.since(start)) } else { DebugValue::Struct(fields) })
+                    }
+                    Some(b'(') => {
+                        self.position += 1;
+                        Some(DebugValue::Tuple(name, self.items(b')')?))
+                    }
+                    _ => {
+                        self.position = after_name;
+                        Some(DebugValue::Leaf(name))
+                    }
+                }
+            }
+        };
+    }
+
+    /// Consume a string or character literal, escapes included
+    fn quoted(&mut self) -> Option<()> {
+        let quote = self.peek()?;
+        self.position += 1;
+
+        loop {
+            match self.peek()? {
+                b'\\' => self.position += 2,
+                byte if byte == quote => {
+                    self.position += 1;
+                    return Some(());
+                }
+                _ => self.position += 1,
+            }
+        }
+    }
+
+    /// Consume a name, path or number, such as `Status::Active`, `-1.5e-7` or the `b` prefix of `b"raw"`
+    fn word(&mut self) -> Option<()> {
+        let start = self.position;
+
+        while let Some(byte) = self.peek() {
+            match byte {
+                b':' if self.text.as_bytes().get(self.position + 1) == Some(&b':') => self.position += 2,
+                b'"' | b'\'' => self.quoted()?,
+                b',' | b':' | b'{' | b'}' | b'(' | b')' | b'[' | b']' => break,
+                byte if byte.is_ascii_whitespace() => break,
+                _ => self.position += 1,
+            }
+        }
+
+        return if self.position > start { Some(()) } else { None };
+    }
+
+    /// Comma-separated values up to the closing byte
+    fn items(&mut self, close: u8) -> Option<Vec<DebugValue<'a>>> {
+        let mut items = Vec::new();
+
+        loop {
+            self.skip_spaces();
+            if self.peek()? == close {
+                self.position += 1;
+                return Some(items);
+            }
+
+            items.push(self.value()?);
+            self.skip_spaces();
+            match self.peek()? {
+                b',' => self.position += 1,
+                byte if byte == close => {}
+                _ => return None,
+            }
+        }
+    }
+
+    /// Comma-separated `key: value` entries up to the closing brace, with keys read by the given function
+    fn entries(&mut self, key: impl Fn(&mut Self) -> Option<()>) -> Option<Vec<(&'a str, DebugValue<'a>)>> {
+        let mut entries = Vec::new();
+
+        loop {
+            self.skip_spaces();
+            match self.peek()? {
+                b'}' => {
+                    self.position += 1;
+                    return Some(entries);
+                }
+                // Structs with hidden fields end with `..`
+                b'.' if self.text[self.position..].starts_with("..") => {
+                    self.position += 2;
+                    continue;
+                }
+                _ => {}
+            }
+
+            let start = self.position;
+            key(self)?;
+            let name = self.since(start);
+            self.expect(b':')?;
+            entries.push((name, self.value()?));
+
+            self.skip_spaces();
+            match self.peek()? {
+                b',' => self.position += 1,
+                b'}' => {}
+                _ => return None,
+            }
+        }
+    }
+}
+
+/// Flatten a value into its leaf values, descending into structs, tuples, lists and maps
+fn flatten_value(value: &DebugValue, path: String, values: &mut FieldValues) {
+    let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+
+    match value {
+        DebugValue::Leaf(text) => values.push((path.clone(), text.to_string())),
+        DebugValue::Struct(fields) => {
+            for (name, field) in fields {
+                flatten_value(field, join(name), values);
+            }
+        }
+        // `Some(..)` is transparent, other variants name their value so that a change of variant is a difference
+        DebugValue::Tuple(name, items) if items.len() == 1 => {
+            let item_path = if name.is_empty() || *name == "Some" { path.clone() } else { join(name) };
+            flatten_value(&items[0], item_path, values);
+        }
+        DebugValue::Tuple(_, items) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten_value(item, join(&index.to_string()), values);
+            }
+        }
+        DebugValue::List(items) => {
+            for (index, item) in items.iter().enumerate() {
+                flatten_value(item, format!("{}[{}]", path, index), values);
+            }
+        }
+        DebugValue::Map(entries) => {
+            for (key, entry) in entries {
+                flatten_value(entry, format!("{}[{}]", path, key), values);
+            }
+        }
+    }
+}
+
+/// Values under a field path, including nested ones
+fn values_under<'a>(values: &'a FieldValues, field: &str) -> Vec<&'a (String, String)> {
+    return values
+        .iter()
+        .filter(|(path, _)| path == field || path.starts_with(&format!("{}.", field)) || path.starts_with(&format!("{}[", field)))
+        .collect();
+}
+
+/// Render the values under a field path on a single line
+fn describe_field(values: &[&(String, String)], field: &str) -> String {
+    if let [(path, value)] = values
+        && path == field
+    {
+        return format!("{}: {}", field, value);
+    }
+
+    return values.iter().map(|(path, value)| format!("{}: {}", path, value)).collect::<Vec<_>>().join(", ");
+}
+
+/// One "path: expected .., got .." line per field that differs between two messages
+fn field_differences(expected: &FieldValues, actual: &FieldValues) -> Vec<String> {
+    let mut differences = Vec::new();
+
+    for (path, expected_value) in expected {
+        match actual.iter().find(|(actual_path, _)| actual_path == path) {
+            Some((_, actual_value)) if actual_value == expected_value => {}
+            Some((_, actual_value)) => differences.push(format!("{}: expected {}, got {}", path, expected_value, actual_value)),
+            None => differences.push(format!("{}: expected {}, got nothing", path, expected_value)),
+        }
+    }
+    for (path, actual_value) in actual {
+        if !expected.iter().any(|(expected_path, _)| expected_path == path) {
+            differences.push(format!("{}: expected nothing, got {}", path, actual_value));
+        }
+    }
+
+    return differences;
+}
+
+/// Short name of a message type, without its module path
+fn message_name<M>() -> &'static str {
+    let name = std::any::type_name::<M>();
+    return name.rsplit("::").next().unwrap_or(name);
+}
+
+// Single implementation for any type that implements AsMessage
+impl<V, M> ProtobufMatchers<M> for Assertion<V>
+where
+    M: Message + Default + PartialEq + Debug,
+    V: AsMessage<M> + Debug + Clone,
+{
+    fn to_equal_message(self, expected: M) -> Self {
+        let actual = self.value.message();
+        let result = *actual == expected;
+
+        let differences = if result { Vec::new() } else { field_differences(&field_values(&expected), &field_values(actual)) };
+        let actual_description = match differences.len() {
+            0 => "identical message".to_string(),
+            1 => "1 differing field".to_string(),
+            count => format!("{} differing fields", count),
+        };
+
+        let mut sentence =
+            AssertionSentence::new("be", format!("equal to the expected {}", message_name::<M>())).with_actual(actual_description);
+        for difference in differences {
+            sentence = sentence.with_detail(difference);
+        }
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_field_set(self, field: &str) -> Self {
+        let actual = field_values(self.value.message());
+        let defaults = field_values(&M::default());
+
+        let actual_field = values_under(&actual, field);
+        let default_field = values_under(&defaults, field);
+        if field.contains(['.', '[']) || (actual_field.is_empty() && default_field.is_empty()) {
+            panic!("Message {} has no field '{}'", message_name::<M>(), field);
+        }
+
+        let result = actual_field != default_field;
+        let sentence = AssertionSentence::new("have", format!("field {} set", field)).with_actual(describe_field(&actual_field, field));
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct Address {
+        #[prost(string, tag = "1")]
+        city: String,
+        #[prost(uint32, tag = "2")]
+        zip: u32,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    struct User {
+        #[prost(uint64, tag = "1")]
+        user_id: u64,
+        #[prost(string, tag = "2")]
+        name: String,
+        #[prost(message, optional, tag = "3")]
+        address: Option<Address>,
+        #[prost(string, repeated, tag = "4")]
+        tags: Vec<String>,
+        #[prost(int32, optional, tag = "5")]
+        age: Option<i32>,
+    }
+
+    fn ada() -> User {
+        return User {
+            user_id: 42,
+            name: "Ada".to_string(),
+            address: Some(Address { city: "London".to_string(), zip: 0 }),
+            tags: vec!["admin".to_string()],
+            age: None,
+        };
+    }
+
+    #[test]
+    fn test_to_equal_message() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let user = ada();
+
+        expect!(&user).to_equal_message(ada());
+        expect!(user).not().to_equal_message(User::default());
+    }
+
+    #[test]
+    fn test_differences_are_listed_per_field() {
+        crate::Reporter::disable_deduplication();
+
+        let mut renamed = ada();
+        renamed.name = "Grace".to_string();
+        renamed.address = Some(Address { city: "Paris".to_string(), zip: 0 });
+        renamed.tags.push("ops".to_string());
+
+        // Negated so the assertion passes while keeping the differences
+        let assertion = expect!(&renamed).not().to_equal_message(ada());
+        let details = assertion.steps[0].sentence.details.clone();
+
+        expect!(details).to_equal(vec![
+            "name: expected \"Ada\", got \"Grace\"".to_string(),
+            "address.city: expected \"London\", got \"Paris\"".to_string(),
+            "tags[1]: expected nothing, got \"ops\"".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_values_with_separators_and_newlines() {
+        crate::Reporter::disable_deduplication();
+
+        let mut expected = ada();
+        expected.name = "Ada: first\nprogrammer".to_string();
+        expected.tags = vec!["role: admin, team: \"core\"".to_string()];
+        let mut actual = expected.clone();
+        actual.address = Some(Address { city: "London\nzip: 0".to_string(), zip: 0 });
+
+        let assertion = expect!(&actual).not().to_equal_message(expected.clone());
+        let details = assertion.steps[0].sentence.details.clone();
+
+        expect!(details).to_equal(vec!["address.city: expected \"London\", got \"London\\nzip: 0\"".to_string()]);
+        expect!(&actual).to_equal_message(actual.clone());
+        expect!(&actual).to_have_field_set("tags");
+    }
+
+    #[test]
+    fn test_debug_output_is_parsed_structurally() {
+        let values = super::field_values(&ada());
+        let paths = values.iter().map(|(path, value)| format!("{}={}", path, value)).collect::<Vec<_>>();
+        expect!(paths).to_equal(
+            ["user_id=42", "name=\"Ada\"", "address.city=\"London\"", "address.zip=0", "tags[0]=\"admin\"", "age=None"]
+                .map(String::from)
+                .to_vec(),
+        );
+
+        let text = r#"Event { labels: {"env": "a: b", "zone": ""}, kind: Some(Kind::Email("x@y")), raw: b"\"}", empty: [] }"#;
+        let mut values = Vec::new();
+        super::flatten_value(&super::DebugParser::parse(text).unwrap(), String::new(), &mut values);
+        let paths = values.iter().map(|(path, value)| format!("{}={}", path, value)).collect::<Vec<_>>();
+        expect!(paths).to_equal(
+            ["labels[\"env\"]=\"a: b\"", "labels[\"zone\"]=\"\"", "kind.Kind::Email=\"x@y\"", "raw=b\"\\\"}\"", "empty=[]"]
+                .map(String::from)
+                .to_vec(),
+        );
+
+        expect!(super::DebugParser::parse("User { name: \"unterminated }").is_none()).to_be_true();
+        expect!(super::DebugParser::parse("").is_none()).to_be_true();
+    }
+
+    #[test]
+    fn test_to_have_field_set() {
+        crate::Reporter::disable_deduplication();
+
+        let user = ada();
+
+        expect!(&user).to_have_field_set("user_id");
+        expect!(&user).to_have_field_set("address");
+        expect!(user.address.clone().unwrap()).to_have_field_set("city");
+        expect!(user.address.clone().unwrap()).not().to_have_field_set("zip");
+        expect!(&user).not().to_have_field_set("age");
+        expect!(User { age: Some(0), ..ada() }).to_have_field_set("age");
+        expect!(User::default()).not().to_have_field_set("tags");
+    }
+
+    #[test]
+    #[should_panic(expected = "be equal to the expected User (got 1 differing field)\n  user_id: expected 42, got 7")]
+    fn test_different_message_fails_with_field_diff() {
+        let _assertion = expect!(User { user_id: 7, ..ada() }).to_equal_message(ada());
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "have field name set (got name: \"\")")]
+    fn test_default_field_is_not_set() {
+        let _assertion = expect!(User::default()).to_have_field_set("name");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "Message User has no field 'email'")]
+    fn test_unknown_field_panics() {
+        let _assertion = expect!(ada()).to_have_field_set("email");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "Message User has no field 'address.city'")]
+    fn test_nested_field_path_panics() {
+        let _assertion = expect!(ada()).to_have_field_set("address.city");
+        std::hint::black_box(_assertion);
+    }
+}
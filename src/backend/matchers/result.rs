@@ -4,18 +4,28 @@ use std::fmt::Debug;
 
 /// Trait for Result<T, E> assertions
 pub trait ResultMatchers<T: Debug, E: Debug> {
+    #[track_caller]
     fn to_be_ok(self) -> Self;
+    #[track_caller]
     fn to_be_err(self) -> Self;
+    #[track_caller]
     fn to_contain_ok<U: PartialEq<T> + Debug>(self, expected: &U) -> Self;
+    #[track_caller]
     fn to_contain_err<U: PartialEq<E> + Debug>(self, expected: &U) -> Self;
 }
 
 /// Helper trait for Result-like types
+#[diagnostic::on_unimplemented(
+    message = "`ResultMatchers` can't check `{Self}`, which isn't a result",
+    label = "not a result",
+    note = "`ResultMatchers` apply to `Result<T, E>` and `&Result<T, E>`"
+)]
 trait AsResult<T: Debug + Clone, E: Debug + Clone> {
     fn is_ok_result(&self) -> bool;
     fn is_err_result(&self) -> bool;
     fn contains_ok<U: PartialEq<T> + Debug>(&self, expected: &U) -> bool;
     fn contains_err<U: PartialEq<E> + Debug>(&self, expected: &U) -> bool;
+    fn as_result(&self) -> Result<&T, &E>;
 }
 
 // Implementation for Result<T, E>
@@ -41,6 +51,10 @@ impl<T: Debug + Clone, E: Debug + Clone> AsResult<T, E> for Result<T, E> {
             Err(actual) => expected == actual,
         }
     }
+
+    fn as_result(&self) -> Result<&T, &E> {
+        self.as_ref()
+    }
 }
 
 // Implementation for &Result<T, E>
@@ -66,6 +80,10 @@ impl<T: Debug + Clone, E: Debug + Clone> AsResult<T, E> for &Result<T, E> {
             Err(actual) => expected == actual,
         }
     }
+
+    fn as_result(&self) -> Result<&T, &E> {
+        self.as_ref()
+    }
 }
 
 // Single implementation for any type that implements AsResult
@@ -77,33 +95,45 @@ where
 {
     fn to_be_ok(self) -> Self {
         let result = self.value.is_ok_result();
-        let sentence = AssertionSentence::new("be", "ok").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "ok").with_actual(describe_variant(actual.as_result())));
     }
 
     fn to_be_err(self) -> Self {
         let result = self.value.is_err_result();
-        let sentence = AssertionSentence::new("be", "err").with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "err").with_actual(describe_variant(actual.as_result())));
     }
 
     fn to_contain_ok<U: PartialEq<T> + Debug>(self, expected: &U) -> Self {
         let result = self.value.contains_ok(expected);
-        let sentence = AssertionSentence::new("contain", format!("ok value {:?}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("ok value {:?}", expected)).with_actual(describe_variant(actual.as_result()));
+        });
     }
 
     fn to_contain_err<U: PartialEq<E> + Debug>(self, expected: &U) -> Self {
         let result = self.value.contains_err(expected);
-        let sentence = AssertionSentence::new("contain", format!("err value {:?}", expected)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("err value {:?}", expected))
+                .with_actual(describe_variant(actual.as_result()));
+        });
     }
 }
 
+/// Describe a result by its variant first, then its value: `Err: ParseError { line: 3 }`
+///
+/// The variant stays readable at the start of the actual value however long the `Debug` output of the value is.
+#[doc(hidden)]
+pub fn describe_variant<T: Debug, E: Debug>(result: Result<&T, &E>) -> String {
+    return match result {
+        Ok(value) => format!("Ok: {:?}", value),
+        Err(error) => format!("Err: {:?}", error),
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -124,7 +154,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "be ok")]
+    #[should_panic(expected = "be ok (got Err: \"error\")")]
     fn test_err_to_be_ok_fails() {
         let value: Result<i32, &str> = Err("error");
         let _assertion = expect!(value).to_be_ok();
@@ -132,7 +162,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "be err")]
+    #[should_panic(expected = "be err (got Ok: 42)")]
     fn test_ok_to_be_err_fails() {
         let value: Result<i32, &str> = Ok(42);
         let _assertion = expect!(value).to_be_err();
@@ -155,7 +185,7 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "contain ok value")]
+    #[should_panic(expected = "contain ok value 43 (got Ok: 42)")]
     fn test_ok_wrong_value_fails() {
         let value: Result<i32, &str> = Ok(42);
         let _assertion = expect!(value).to_contain_ok(&43);
@@ -170,6 +200,14 @@ mod tests {
         std::hint::black_box(_assertion);
     }
 
+    #[test]
+    #[should_panic(expected = "contain ok value 43 (got Err: NotFound { id: 7 })")]
+    fn test_err_instead_of_ok_value_fails() {
+        let value: Result<i32, StoreError> = Err(StoreError::NotFound { id: 7 });
+        let _assertion = expect!(value).to_contain_ok(&43);
+        std::hint::black_box(_assertion);
+    }
+
     #[test]
     #[should_panic(expected = "contain err value")]
     fn test_err_wrong_value_fails() {
@@ -185,4 +223,38 @@ mod tests {
         let _assertion = expect!(value).not().to_contain_err(&"error");
         std::hint::black_box(_assertion);
     }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum StoreError {
+        NotFound { id: u32 },
+        Conflict { id: u32, version: u32 },
+    }
+
+    #[test]
+    fn test_expect_err_matches() {
+        crate::Reporter::disable_deduplication();
+
+        let missing: Result<&str, StoreError> = Err(StoreError::NotFound { id: 42 });
+        expect_err_matches!(missing, StoreError::NotFound { id: 42 });
+        expect_err_matches!(missing, StoreError::NotFound { id } if *id > 40);
+
+        let conflict: Result<&str, StoreError> = Err(StoreError::Conflict { id: 7, version: 3 });
+        let (id, version) = expect_err_matches!(conflict, StoreError::Conflict { id, version } => (*id, *version));
+        expect!(id).to_equal(7);
+        expect!(version).to_be_greater_than(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "match Err(StoreError::NotFound { id: 42 }) (got Err: Conflict { id: 42, version: 1 })")]
+    fn test_err_matches_other_variant_fails() {
+        let result: Result<&str, StoreError> = Err(StoreError::Conflict { id: 42, version: 1 });
+        expect_err_matches!(result, StoreError::NotFound { id: 42 });
+    }
+
+    #[test]
+    #[should_panic(expected = "match Err(StoreError::NotFound { id }) if *id > 50 (got Ok: \"stored\")")]
+    fn test_err_matches_ok_fails() {
+        let result: Result<&str, StoreError> = Ok("stored");
+        expect_err_matches!(result, StoreError::NotFound { id } if *id > 50 => *id);
+    }
 }
@@ -0,0 +1,123 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+
+/// Outputs of a decoder: the decoded value, or why it couldn't be decoded
+///
+/// Implemented for `Result`, as returned by most decoders and parsers, and for `Option`.
+pub trait Decoded<T> {
+    /// The decoded value, or the description of the decoding failure
+    fn into_decoded(self) -> Result<T, String>;
+}
+
+impl<T, E: Debug> Decoded<T> for Result<T, E> {
+    fn into_decoded(self) -> Result<T, String> {
+        return self.map_err(|error| format!("{:?}", error));
+    }
+}
+
+impl<T> Decoded<T> for Option<T> {
+    fn into_decoded(self) -> Result<T, String> {
+        return self.ok_or_else(|| "None".to_string());
+    }
+}
+
+/// Trait for values checked against a pair of encoding and decoding functions, such as serializers and parsers
+pub trait RoundTripMatchers<T> {
+    /// Check that decoding the encoded value gives the value back, `decode(encode(value)) == value`
+    ///
+    /// The decoder returns a `Result` or an `Option`. Failures show the intermediate representation along with the
+    /// decoded value or the decoding error.
+    #[track_caller]
+    fn to_round_trip_through<R, O>(self, encode: impl Fn(&T) -> R, decode: impl Fn(&R) -> O) -> Self
+    where
+        R: Debug,
+        O: Decoded<T>;
+}
+
+impl<T> RoundTripMatchers<T> for Assertion<T>
+where
+    T: Debug + PartialEq + Clone,
+{
+    fn to_round_trip_through<R, O>(self, encode: impl Fn(&T) -> R, decode: impl Fn(&R) -> O) -> Self
+    where
+        R: Debug,
+        O: Decoded<T>,
+    {
+        let encoded = encode(&self.value);
+        let decoded = decode(&encoded).into_decoded();
+        let result = decoded.as_ref().is_ok_and(|decoded| decoded == &self.value);
+
+        return self.add_step_with(result, |value| {
+            let sentence = AssertionSentence::new("round-trip", "through its encoding")
+                .with_expected(format!("{:?}", value))
+                .with_detail(format!("encoded as {:?}", encoded));
+
+            return match decoded {
+                Ok(ref decoded) => sentence.with_actual(format!("{:?}", decoded)),
+                Err(ref error) => sentence.with_actual(format!("a decoding error {}", error)),
+            };
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::matchers::failure::ExpectedFailure;
+    use crate::prelude::*;
+
+    fn encode(values: &[u32]) -> String {
+        return values.iter().map(|value| value.to_string()).collect::<Vec<_>>().join(",");
+    }
+
+    fn decode(text: &str) -> Result<Vec<u32>, std::num::ParseIntError> {
+        return text.split(',').filter(|part| !part.is_empty()).map(str::parse).collect();
+    }
+
+    #[test]
+    fn test_round_trips() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect!(vec![1_u32, 20, 300]).to_round_trip_through(|values| encode(values), |text| decode(text));
+        expect!(vec![0_u32]).to_round_trip_through(|values| encode(values), |text| decode(text));
+        expect!(42_u8).to_round_trip_through(|value| value.to_string(), |text| text.parse::<u8>());
+        expect!('a').to_round_trip_through(|c| *c as u32, |code| char::from_u32(*code));
+
+        // An empty list and a list holding an empty string both encode as ""
+        expect!(vec![String::new()]).not().to_round_trip_through(
+            |values| values.join(","),
+            |text| Some(text.split_terminator(',').map(str::to_string).collect::<Vec<_>>()),
+        );
+    }
+
+    #[test]
+    fn test_failure_shows_the_encoding() {
+        let readings = vec![1.5_f64, 2.0];
+        let failure = ExpectedFailure::capture(|| {
+            expect!(readings).to_round_trip_through(
+                |values| format!("{:?}", values.iter().map(|v| *v as i64).collect::<Vec<_>>()),
+                |text| {
+                    text.trim_matches(|c| c == '[' || c == ']').split(", ").map(|part| part.parse::<f64>()).collect::<Result<Vec<_>, _>>()
+                },
+            );
+        });
+
+        let details = failure.failure().map(|failure| failure.format_step_details()).unwrap_or_default();
+        assert!(details.contains("round-trip through its encoding (got [1.0, 2.0])"), "{}", details);
+        assert!(details.contains("encoded as \"[1, 2]\""), "{}", details);
+    }
+
+    #[test]
+    fn test_failure_shows_the_decoding_error() {
+        let port = 8080_u16;
+        let failure = ExpectedFailure::capture(|| {
+            expect!(port).to_round_trip_through(|port| format!("port {}", port), |text| text.parse::<u16>());
+        });
+
+        assert_eq!(
+            failure.message().as_deref(),
+            Some("expected port to round-trip through its encoding, got a decoding error ParseIntError { kind: InvalidDigit }")
+        );
+    }
+}
@@ -0,0 +1,284 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Trait for size assertions shared by every container-like type.
+///
+/// Implemented for strings, slices, arrays, `Vec`, `VecDeque`, maps, sets, ranges, the common
+/// iterator adapters, and `Option` as a container of zero or one value, so generic helpers
+/// can assert emptiness without caring about the source type.
+pub trait SizeMatchers {
+    #[track_caller]
+    fn to_be_empty(self) -> Self;
+}
+
+/// Helper trait for types that have a notion of emptiness
+trait AsSized {
+    fn is_empty_value(&self) -> bool;
+}
+
+// Every reference to a sized container is itself a sized container
+impl<C: AsSized + ?Sized> AsSized for &C {
+    fn is_empty_value(&self) -> bool {
+        return (**self).is_empty_value();
+    }
+}
+
+impl AsSized for str {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl AsSized for String {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T> AsSized for [T] {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T, const N: usize> AsSized for [T; N] {
+    fn is_empty_value(&self) -> bool {
+        return N == 0;
+    }
+}
+
+impl<T> AsSized for Vec<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T> AsSized for VecDeque<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<K, V, S> AsSized for HashMap<K, V, S> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T, S> AsSized for HashSet<T, S> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<K, V> AsSized for BTreeMap<K, V> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T> AsSized for BTreeSet<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T: PartialOrd> AsSized for Range<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_empty();
+    }
+}
+
+impl<T> AsSized for std::slice::Iter<'_, T> {
+    fn is_empty_value(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+impl<T> AsSized for std::vec::IntoIter<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.as_slice().is_empty();
+    }
+}
+
+// Options hold zero or one value, as when iterated
+impl<T> AsSized for Option<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.is_none();
+    }
+}
+
+impl<T> AsSized for std::option::Iter<'_, T> {
+    fn is_empty_value(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+impl<T> AsSized for std::option::IntoIter<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+impl<T> AsSized for std::result::Iter<'_, T> {
+    fn is_empty_value(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+impl<T> AsSized for std::result::IntoIter<T> {
+    fn is_empty_value(&self) -> bool {
+        return self.len() == 0;
+    }
+}
+
+// Iterator adapters are probed on a clone so the asserted value is never consumed
+macro_rules! impl_sized_for_iterators {
+    ($($iter:ident<$($generic:ident),*>),* $(,)?) => {
+        $(
+            impl<$($generic),*> AsSized for std::iter::$iter<$($generic),*>
+            where
+                std::iter::$iter<$($generic),*>: Iterator + Clone,
+            {
+                fn is_empty_value(&self) -> bool {
+                    return self.clone().next().is_none();
+                }
+            }
+        )*
+    };
+}
+
+impl_sized_for_iterators!(
+    Empty<T>,
+    Once<T>,
+    Map<I, F>,
+    Filter<I, P>,
+    FilterMap<I, F>,
+    TakeWhile<I, P>,
+    SkipWhile<I, P>,
+    Take<I>,
+    Skip<I>,
+    StepBy<I>,
+    Chain<A, B>,
+    Zip<A, B>,
+    Rev<I>,
+    Enumerate<I>,
+    Cloned<I>,
+    Copied<I>,
+);
+
+// Single implementation for any type that implements AsSized
+impl<V> SizeMatchers for Assertion<V>
+where
+    V: AsSized + Debug + Clone,
+{
+    fn to_be_empty(self) -> Self {
+        let result = self.value.is_empty_value();
+
+        return self.add_step_with(result, |actual| AssertionSentence::new("be", "empty").with_actual(format!("{:?}", actual)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+
+    #[test]
+    fn test_to_be_empty_on_containers() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let empty_vec: Vec<i32> = Vec::new();
+        let empty_deque: VecDeque<i32> = VecDeque::new();
+        let empty_map: HashMap<&str, i32> = HashMap::new();
+        let empty_set: HashSet<i32> = HashSet::new();
+        let empty_tree_map: BTreeMap<i32, i32> = BTreeMap::new();
+        let empty_tree_set: BTreeSet<i32> = BTreeSet::new();
+
+        expect!(&empty_vec).to_be_empty();
+        expect!(empty_vec.as_slice()).to_be_empty();
+        expect!(&empty_deque).to_be_empty();
+        expect!(&empty_map).to_be_empty();
+        expect!(&empty_set).to_be_empty();
+        expect!(&empty_tree_map).to_be_empty();
+        expect!(&empty_tree_set).to_be_empty();
+        expect!([0; 0]).to_be_empty();
+        expect!("").to_be_empty();
+
+        expect!(vec![1]).not().to_be_empty();
+        expect!(HashSet::from([1])).not().to_be_empty();
+        expect!(BTreeMap::from([(1, 2)])).not().to_be_empty();
+        expect!("hello").not().to_be_empty();
+    }
+
+    #[test]
+    fn test_to_be_empty_on_iterators() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let numbers = [1, 2, 3];
+
+        expect!(numbers.iter()).not().to_be_empty();
+        expect!(numbers.iter().filter(|n| **n > 5)).to_be_empty();
+        expect!(numbers.iter().map(|n| n * 2)).not().to_be_empty();
+        expect!(numbers.iter().skip(3)).to_be_empty();
+        expect!(std::iter::empty::<i32>()).to_be_empty();
+        expect!(0..0).to_be_empty();
+        expect!(0..3).not().to_be_empty();
+    }
+
+    #[test]
+    fn test_to_be_empty_on_options() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let none: Option<i32> = None;
+        let failed: Result<i32, &str> = Err("timeout");
+
+        expect!(none).to_be_empty();
+        expect!(Some(1)).not().to_be_empty();
+        expect!(none.iter()).to_be_empty();
+        expect!(Some(1).into_iter().filter(|n| *n > 1)).to_be_empty();
+        expect!(failed.iter().map(|n| n * 2)).to_be_empty();
+    }
+
+    #[test]
+    fn test_typed_empty_constructors() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let reserved: Vec<u8> = Vec::with_capacity(16);
+        let lookup: std::collections::HashMap<&str, i32> = std::collections::HashMap::new();
+        let negatives = [1, 2, 3];
+
+        expect!(Vec::<i32>::new()).to_be_empty();
+        expect!(std::vec::Vec::<u8>::new()).to_be_empty();
+        expect!(HashMap::<&str, i32>::new()).to_be_empty();
+        expect!(HashSet::<u8>::new()).to_be_empty();
+        expect!([0_u8; 0]).to_be_empty();
+        expect!(reserved).to_be_empty();
+        expect!(&lookup).to_be_empty();
+        expect!(String::new()).to_be_empty();
+        expect!(negatives.iter().filter(|n| **n < 0)).to_be_empty();
+    }
+
+    #[test]
+    #[should_panic(expected = "be empty")]
+    fn test_non_empty_iterator_fails() {
+        let numbers = [1, 2, 3];
+        let _assertion = expect!(numbers.iter().filter(|n| **n > 1)).to_be_empty();
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "not be empty")]
+    fn test_empty_set_not_to_be_empty_fails() {
+        let empty: HashSet<i32> = HashSet::new();
+        let _assertion = expect!(empty).not().to_be_empty();
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,90 @@
+//! Review of the snapshots and reference files that a test doesn't match
+//!
+//! When a frame or an image doesn't match its stored file, the matcher writes what the test produced next to it, such
+//! as `cart.frame.new` or `login.new.png`, and its failure lists the stored file, the new one and the command accepting
+//! the change. CI logs then say which files to inspect and how to update them, rather than only how they differ.
+
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::config::ENV_UPDATE_SNAPSHOTS;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Write a review file, returning its path, or `None` after reporting on stderr when it can't be written
+pub(crate) fn write_review_file(path: &Path, content: &[u8]) -> Option<PathBuf> {
+    let written = path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|_| fs::write(path, content));
+    if let Err(error) = written {
+        eprintln!("ERROR: Failed to write {}: {}", path.display(), error);
+        return None;
+    }
+
+    return Some(path.to_path_buf());
+}
+
+/// Remove the review files left by a previous mismatch, once the stored file matches again
+pub(crate) fn remove_review_files(paths: &[&Path]) {
+    for path in paths {
+        // Missing files are the common case, and a file that can't be removed only leaves a stale review
+        let _ = fs::remove_file(path);
+    }
+}
+
+/// List the files to review and the command accepting the change under a failed step
+pub(crate) fn with_review(sentence: AssertionSentence, stored: &Path, new: Option<&Path>, diff: Option<&Path>) -> AssertionSentence {
+    let mut sentence = sentence.with_detail(format!("stored: {}", stored.display()));
+    if let Some(new) = new {
+        sentence = sentence.with_detail(format!("new: {}", new.display()));
+    }
+    if let Some(diff) = diff {
+        sentence = sentence.with_detail(format!("diff: {}", diff.display()));
+    }
+
+    return sentence.with_detail(format!("accept with: {}", accept_command(std::thread::current().name())));
+}
+
+/// Command rerunning a test with updated snapshots, such as `REST_UPDATE_SNAPSHOTS=1 cargo test -- --exact cart::test_total`
+fn accept_command(test: Option<&str>) -> String {
+    return match test {
+        // Outside of the test harness, the thread doesn't name a test
+        Some(test) if test != "main" => format!("{}=1 cargo test -- --exact {}", ENV_UPDATE_SNAPSHOTS, test),
+        _ => format!("{}=1 cargo test", ENV_UPDATE_SNAPSHOTS),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_review_lists_the_files_and_the_accept_command() {
+        let sentence = with_review(
+            AssertionSentence::new("render", "like snapshot cart.frame"),
+            Path::new("tests/snapshots/cart.frame"),
+            Some(Path::new("tests/snapshots/cart.frame.new")),
+            None,
+        );
+
+        assert_eq!(
+            sentence.details,
+            vec![
+                "stored: tests/snapshots/cart.frame",
+                "new: tests/snapshots/cart.frame.new",
+                "accept with: REST_UPDATE_SNAPSHOTS=1 cargo test -- --exact \
+                 backend::matchers::snapshot::tests::test_review_lists_the_files_and_the_accept_command",
+            ]
+        );
+        assert_eq!(accept_command(Some("main")), "REST_UPDATE_SNAPSHOTS=1 cargo test");
+    }
+
+    #[test]
+    fn test_review_files_are_written_and_removed() {
+        let path = std::env::temp_dir().join(format!("rest-review-{}", std::process::id())).join("cart.frame.new");
+
+        assert_eq!(write_review_file(&path, b"total: 12\n"), Some(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "total: 12\n");
+
+        remove_review_files(&[&path, Path::new("missing.frame.new")]);
+        assert!(!path.exists());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
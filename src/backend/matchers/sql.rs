@@ -0,0 +1,173 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use sqlx::{Column, Row};
+
+/// Trait for assertions on query results fetched with sqlx.
+///
+/// Available with the `sqlx` feature, for any driver's rows borrowed as `&Vec<R>` or `&[R]`
+/// (sqlx rows are neither `Clone` nor `Debug`, so results are asserted by reference).
+pub trait SqlMatchers<R> {
+    /// Check that the query returned exactly `expected` rows
+    #[track_caller]
+    fn to_have_row_count(self, expected: usize) -> Self;
+
+    /// Check that the result set has a column with the given name
+    ///
+    /// Columns are read from the first row, so an empty result set has no columns.
+    #[track_caller]
+    fn to_have_column(self, name: &str) -> Self;
+
+    /// Check that at least one row satisfies the predicate
+    #[track_caller]
+    fn to_contain_row_matching<F: Fn(&R) -> bool>(self, predicate: F) -> Self;
+}
+
+/// Helper trait for borrowed query results
+trait AsRows<R> {
+    fn rows(&self) -> &[R];
+}
+
+impl<R: Row> AsRows<R> for &Vec<R> {
+    fn rows(&self) -> &[R] {
+        return self.as_slice();
+    }
+}
+
+impl<R: Row> AsRows<R> for &[R] {
+    fn rows(&self) -> &[R] {
+        return self;
+    }
+}
+
+/// Column names of a result set, taken from its first row
+fn column_names<R: Row>(rows: &[R]) -> Vec<String> {
+    return rows.first().map(|row| row.columns().iter().map(|column| column.name().to_string()).collect()).unwrap_or_default();
+}
+
+/// Describe a row count with the right plurality
+fn describe_rows(count: usize) -> String {
+    return if count == 1 { "1 row".to_string() } else { format!("{} rows", count) };
+}
+
+// Single implementation for any type that implements AsRows
+impl<V, R> SqlMatchers<R> for Assertion<V>
+where
+    R: Row,
+    V: AsRows<R> + Clone,
+{
+    fn to_have_row_count(self, expected: usize) -> Self {
+        let count = self.value.rows().len();
+        let result = count == expected;
+        let sentence = AssertionSentence::new("have", format!("row count {}", expected)).with_actual(describe_rows(count));
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_column(self, name: &str) -> Self {
+        let columns = column_names(self.value.rows());
+        let result = columns.iter().any(|column| column == name);
+        let actual = if self.value.rows().is_empty() { "no rows".to_string() } else { format!("columns [{}]", columns.join(", ")) };
+        let sentence = AssertionSentence::new("have", format!("column {:?}", name)).with_actual(actual);
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_contain_row_matching<F: Fn(&R) -> bool>(self, predicate: F) -> Self {
+        let rows = self.value.rows();
+        let matching = rows.iter().filter(|row| predicate(row)).count();
+        let result = matching > 0;
+        let sentence = AssertionSentence::new("contain", "a row matching the predicate").with_actual(format!(
+            "{} matching out of {}",
+            matching,
+            describe_rows(rows.len())
+        ));
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use sqlx::Row;
+    use sqlx::sqlite::{SqliteConnection, SqliteRow};
+    use sqlx::{Connection, Executor};
+
+    /// Run a query against a seeded in-memory database
+    fn query(sql: &str) -> Vec<SqliteRow> {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+
+        return runtime.block_on(async {
+            let mut connection = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            connection
+                .execute(
+                    "CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL, active BOOLEAN NOT NULL);
+                     INSERT INTO users (email, active) VALUES ('ada@example.com', 1), ('grace@example.com', 0);",
+                )
+                .await
+                .unwrap();
+            return sqlx::query(sql).fetch_all(&mut connection).await.unwrap();
+        });
+    }
+
+    #[test]
+    fn test_to_have_row_count() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let rows = query("SELECT * FROM users");
+        let none = query("SELECT * FROM users WHERE id > 10");
+
+        expect!(&rows).to_have_row_count(2);
+        expect!(&rows).not().to_have_row_count(3);
+        expect!(rows.as_slice()).to_have_row_count(2);
+        expect!(&none).to_have_row_count(0);
+    }
+
+    #[test]
+    fn test_to_have_column() {
+        crate::Reporter::disable_deduplication();
+
+        let rows = query("SELECT id, email FROM users");
+        let none = query("SELECT * FROM users WHERE id > 10");
+
+        expect!(&rows).to_have_column("email");
+        expect!(&rows).not().to_have_column("active");
+        expect!(&none).not().to_have_column("email");
+    }
+
+    #[test]
+    fn test_to_contain_row_matching() {
+        crate::Reporter::disable_deduplication();
+
+        let rows = query("SELECT * FROM users");
+
+        expect!(&rows).to_contain_row_matching(|row| row.get::<String, _>("email") == "grace@example.com");
+        expect!(&rows).not().to_contain_row_matching(|row| row.get::<String, _>("email").ends_with(".org"));
+        expect!(&rows).to_contain_row_matching(|row| !row.get::<bool, _>("active"));
+    }
+
+    #[test]
+    #[should_panic(expected = "have row count 1 (got 2 rows)")]
+    fn test_wrong_row_count_fails() {
+        let rows = query("SELECT * FROM users");
+        let _assertion = expect!(&rows).to_have_row_count(1);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "have column \"name\" (got columns [id, email, active])")]
+    fn test_missing_column_fails() {
+        let rows = query("SELECT * FROM users");
+        let _assertion = expect!(&rows).to_have_column("name");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "contain a row matching the predicate (got 0 matching out of 2 rows)")]
+    fn test_no_matching_row_fails() {
+        let rows = query("SELECT * FROM users");
+        let _assertion = expect!(&rows).to_contain_row_matching(|row| row.get::<i64, _>("id") > 5);
+        std::hint::black_box(_assertion);
+    }
+}
@@ -1,40 +1,52 @@
 use crate::backend::Assertion;
 use crate::backend::assertions::sentence::AssertionSentence;
-use regex::Regex;
 use std::fmt::Debug;
 
 /// Trait for string assertions
+///
+/// Regex and template matching, which need the regex engine, are in `PatternMatchers` with the `regex` feature.
 pub trait StringMatchers {
-    fn to_be_empty(self) -> Self;
+    #[track_caller]
     fn to_have_length(self, expected: usize) -> Self;
 
     /// Check if the string contains a substring
+    #[track_caller]
     fn to_contain(self, substring: &str) -> Self;
 
     /// Type-specific version of to_contain to avoid trait conflicts
+    #[track_caller]
     fn to_contain_substring(self, substring: &str) -> Self;
 
+    #[track_caller]
     fn to_start_with(self, prefix: &str) -> Self;
+    #[track_caller]
     fn to_end_with(self, suffix: &str) -> Self;
-    fn to_match(self, pattern: &str) -> Self;
+
+    /// Check if the string equals the expected one once ANSI escape codes, such as colors, are stripped from both
+    #[track_caller]
+    fn to_equal_ignoring_ansi(self, expected: &str) -> Self;
+
+    /// Check if the string contains a substring once ANSI escape codes, such as colors, are stripped from both
+    #[track_caller]
+    fn to_contain_ignoring_ansi(self, substring: &str) -> Self;
 }
 
 /// Helper trait for string-like types
+#[diagnostic::on_unimplemented(
+    message = "`StringMatchers` can't check `{Self}`, which isn't a string",
+    label = "not a string",
+    note = "`StringMatchers` apply to `String` and `&str`; call `.as_str()` or `.to_string()` on other text types"
+)]
 trait AsString {
-    fn is_empty_string(&self) -> bool;
     fn length_string(&self) -> usize;
     fn contains_substring(&self, substring: &str) -> bool;
     fn starts_with_substring(&self, prefix: &str) -> bool;
     fn ends_with_substring(&self, suffix: &str) -> bool;
-    fn matches_pattern(&self, pattern: &str) -> bool;
+    fn without_ansi(&self) -> String;
 }
 
 // Implementation for String
 impl AsString for String {
-    fn is_empty_string(&self) -> bool {
-        self.is_empty()
-    }
-
     fn length_string(&self) -> usize {
         self.len()
     }
@@ -51,21 +63,13 @@ impl AsString for String {
         self.ends_with(suffix)
     }
 
-    fn matches_pattern(&self, pattern: &str) -> bool {
-        let re = Regex::new(pattern).unwrap_or_else(|e| {
-            panic!("Invalid regex pattern '{}': {}", pattern, e);
-        });
-
-        return re.is_match(self);
+    fn without_ansi(&self) -> String {
+        strip_ansi(self)
     }
 }
 
 // Implementation for &str
 impl AsString for &str {
-    fn is_empty_string(&self) -> bool {
-        self.is_empty()
-    }
-
     fn length_string(&self) -> usize {
         self.len()
     }
@@ -82,12 +86,8 @@ impl AsString for &str {
         self.ends_with(suffix)
     }
 
-    fn matches_pattern(&self, pattern: &str) -> bool {
-        let re = Regex::new(pattern).unwrap_or_else(|e| {
-            panic!("Invalid regex pattern '{}': {}", pattern, e);
-        });
-
-        return re.is_match(self);
+    fn without_ansi(&self) -> String {
+        strip_ansi(self)
     }
 }
 
@@ -96,19 +96,13 @@ impl<V> StringMatchers for Assertion<V>
 where
     V: AsString + Debug + Clone,
 {
-    fn to_be_empty(self) -> Self {
-        let result = self.value.is_empty_string();
-        let sentence = AssertionSentence::new("be", "empty").with_actual(format!("{:?}", self.value));
-
-        return self.add_step(sentence, result);
-    }
-
     fn to_have_length(self, expected: usize) -> Self {
         let actual_length = self.value.length_string();
         let result = actual_length == expected;
-        let sentence = AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("have", format!("length {}", expected)).with_actual(format!("{}", actual_length));
+        });
     }
 
     fn to_contain(self, substring: &str) -> Self {
@@ -117,33 +111,95 @@ where
 
     fn to_contain_substring(self, substring: &str) -> Self {
         let result = self.value.contains_substring(substring);
-        let sentence = AssertionSentence::new("contain", format!("\"{}\"", substring)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("contain", format!("\"{}\"", substring)).with_actual(format!("{:?}", actual));
+        });
     }
 
     fn to_start_with(self, prefix: &str) -> Self {
         let result = self.value.starts_with_substring(prefix);
-        let sentence = AssertionSentence::new("start with", format!("\"{}\"", prefix)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("start with", format!("\"{}\"", prefix)).with_actual(format!("{:?}", actual));
+        });
     }
 
     fn to_end_with(self, suffix: &str) -> Self {
         let result = self.value.ends_with_substring(suffix);
-        let sentence = AssertionSentence::new("end with", format!("\"{}\"", suffix)).with_actual(format!("{:?}", self.value));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |actual| {
+            return AssertionSentence::new("end with", format!("\"{}\"", suffix)).with_actual(format!("{:?}", actual));
+        });
+    }
+
+    fn to_equal_ignoring_ansi(self, expected: &str) -> Self {
+        let plain = self.value.without_ansi();
+        let result = plain == strip_ansi(expected);
+
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("equal", format!("{:?}", strip_ansi(expected)))
+                .with_qualifier("ignoring ANSI codes")
+                .with_actual(format!("{:?}", plain));
+        });
     }
 
-    fn to_match(self, pattern: &str) -> Self {
-        let result = self.value.matches_pattern(pattern);
-        let sentence = AssertionSentence::new("match", format!("pattern /{}/", pattern)).with_actual(format!("{:?}", self.value));
+    fn to_contain_ignoring_ansi(self, substring: &str) -> Self {
+        let plain = self.value.without_ansi();
+        let result = plain.contains(&strip_ansi(substring));
 
-        return self.add_step(sentence, result);
+        return self.add_step_with(result, |_| {
+            return AssertionSentence::new("contain", format!("{:?}", strip_ansi(substring)))
+                .with_qualifier("ignoring ANSI codes")
+                .with_actual(format!("{:?}", plain));
+        });
     }
 }
 
+/// Remove the ANSI escape codes of a string: CSI sequences such as colors and cursor moves, and OSC sequences such as
+/// hyperlinks. Incomplete sequences are kept.
+pub(crate) fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('\x1b') {
+        plain.push_str(&rest[..start]);
+        let escape = &rest[start..];
+        match csi_length(escape).or_else(|| osc_length(escape)) {
+            Some(length) => rest = &escape[length..],
+            None => {
+                plain.push('\x1b');
+                rest = &escape[1..];
+            }
+        }
+    }
+    plain.push_str(rest);
+
+    return plain;
+}
+
+/// Length of the CSI sequence starting the text: `ESC [`, parameter bytes, intermediate bytes and a final byte
+fn csi_length(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes().strip_prefix(b"\x1b[")?;
+    let parameters = bytes.iter().take_while(|byte| (0x30..=0x3f).contains(*byte)).count();
+    let intermediates = bytes[parameters..].iter().take_while(|byte| (0x20..=0x2f).contains(*byte)).count();
+    let final_byte = *bytes.get(parameters + intermediates)?;
+
+    return (0x40..=0x7e).contains(&final_byte).then_some(2 + parameters + intermediates + 1);
+}
+
+/// Length of the OSC sequence starting the text: `ESC ]` and a string ended by `BEL` or `ESC \`
+fn osc_length(text: &str) -> Option<usize> {
+    let body = text.strip_prefix("\x1b]")?;
+    let end = body.find(['\x07', '\x1b'])?;
+
+    return match &body[end..] {
+        terminator if terminator.starts_with('\x07') => Some(2 + end + 1),
+        terminator if terminator.starts_with("\x1b\\") => Some(2 + end + 2),
+        _ => None,
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -275,42 +331,29 @@ mod tests {
     }
 
     #[test]
-    fn test_string_to_match() {
-        // Disable deduplication for tests
-        crate::Reporter::disable_deduplication();
+    fn test_string_ignoring_ansi() {
+        let output = "\x1b[32m✓\x1b[0m \x1b[1mlogin\x1b[22m passed \x1b]8;;https://example.com\x07docs\x1b]8;;\x07";
 
-        // Plain substring patterns (backward compatible)
-        expect!("hello world").to_match("world");
-        expect!("hello world").not().to_match("goodbye");
-        expect!(String::from("hello world")).to_match("hello");
-
-        // Actual regex patterns
-        expect!("hello123").to_match("\\d+");
-        expect!("hello123").to_match("^hello\\d+$");
-        expect!("hello").not().to_match("\\d+");
-        expect!("2024-01-15").to_match("\\d{4}-\\d{2}-\\d{2}");
-        expect!("test@example.com").to_match("[a-zA-Z]+@[a-zA-Z]+\\.[a-zA-Z]+");
-        expect!(String::from("abc123")).to_match("[a-z]+\\d+");
+        expect!(output).to_equal_ignoring_ansi("✓ login passed docs");
+        expect!(output).to_contain_ignoring_ansi("login passed");
+        expect!(output).not().to_contain("login passed");
+        expect!(output.to_string()).to_contain_ignoring_ansi("\x1b[31mlogin\x1b[0m passed");
     }
 
     #[test]
-    #[should_panic(expected = "Invalid regex pattern")]
-    fn test_invalid_regex_panics() {
-        let _assertion = expect!("hello").to_match("[invalid");
-        std::hint::black_box(_assertion);
+    #[should_panic(expected = "equal \"✓ login failed\" ignoring ANSI codes (got \"✓ login passed\")")]
+    fn test_different_text_ignoring_ansi_fails() {
+        let output = "\x1b[32m✓\x1b[0m login passed";
+        expect!(output).to_equal_ignoring_ansi("✓ login failed");
     }
 
     #[test]
-    #[should_panic(expected = "not match")]
-    fn test_not_matches_when_it_does_fails() {
-        let _assertion = expect!("hello world").not().to_match("hello");
-        std::hint::black_box(_assertion);
-    }
+    fn test_strip_ansi_sequences() {
+        assert_eq!(super::strip_ansi("\x1b[1;31mred\x1b[0m \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\"), "red link");
+        assert_eq!(super::strip_ansi("\x1b[?25lhidden cursor\x1b[?25h"), "hidden cursor");
 
-    #[test]
-    #[should_panic(expected = "match")]
-    fn test_matches_when_it_doesnt_fails() {
-        let _assertion = expect!("hello world").to_match("goodbye");
-        std::hint::black_box(_assertion);
+        // Incomplete sequences are not escape codes
+        assert_eq!(super::strip_ansi("cut \x1b[31"), "cut \x1b[31");
+        assert_eq!(super::strip_ansi("\x1b]8;;unterminated"), "\x1b]8;;unterminated");
     }
 }
@@ -0,0 +1,274 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::{self, Debug, Display, Formatter};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Maximum number of graphemes listed in failure details
+const MAX_LISTED_GRAPHEMES: usize = 20;
+
+/// Unicode normalization forms
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Form {
+    /// Canonical composition, as typed on most keyboards and used on the web
+    NFC,
+    /// Canonical decomposition, as stored by some file systems
+    NFD,
+    /// Compatibility composition, also folding ligatures, width and font variants
+    NFKC,
+    /// Compatibility decomposition
+    NFKD,
+}
+
+impl Form {
+    /// Normalize a string to this form
+    fn normalize(self, text: &str) -> String {
+        return match self {
+            Form::NFC => text.nfc().collect(),
+            Form::NFD => text.nfd().collect(),
+            Form::NFKC => text.nfkc().collect(),
+            Form::NFKD => text.nfkd().collect(),
+        };
+    }
+}
+
+impl Display for Form {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        return Debug::fmt(self, f);
+    }
+}
+
+/// Trait for Unicode-aware string assertions.
+///
+/// Available with the `unicode` feature, for strings owned or borrowed. Text that looks the same can hold different
+/// code points, so failures show non-ASCII characters as escapes (`cafe\u{301}`).
+pub trait UnicodeMatchers {
+    /// Check that the string equals the expected one once both are normalized
+    ///
+    /// Canonically equivalent strings are equal, such as `é` typed as one code point or as `e` followed by a
+    /// combining accent. Compatibility variants such as the `ﬁ` ligature are still different.
+    #[track_caller]
+    fn to_equal_normalized(self, expected: &str) -> Self;
+
+    /// Check that the string is already in the given normalization form
+    #[track_caller]
+    fn to_be_normalized(self, form: Form) -> Self;
+
+    /// Check that the string is made of the expected number of user-perceived characters (extended grapheme clusters)
+    ///
+    /// Unlike `to_have_length`, which counts bytes, `e\u{301}` and `👍🏽` each count as one.
+    #[track_caller]
+    fn to_have_grapheme_count(self, expected: usize) -> Self;
+
+    /// Check that the string takes the expected number of columns once rendered in a terminal
+    ///
+    /// Wide characters such as CJK ideographs and most emoji take two columns, combining marks none. For text
+    /// spanning several lines, the width is the one of the widest line.
+    #[track_caller]
+    fn to_have_display_width(self, expected: usize) -> Self;
+}
+
+/// Helper trait for string-like types
+trait AsUnicode {
+    fn text(&self) -> &str;
+}
+
+impl<U: AsUnicode + ?Sized> AsUnicode for &U {
+    fn text(&self) -> &str {
+        return (**self).text();
+    }
+}
+
+impl AsUnicode for String {
+    fn text(&self) -> &str {
+        return self.as_str();
+    }
+}
+
+impl AsUnicode for str {
+    fn text(&self) -> &str {
+        return self;
+    }
+}
+
+/// Quote a string, escaping every non-ASCII character so that look-alike strings can be told apart
+fn escape_non_ascii(text: &str) -> String {
+    let escaped = text.chars().map(|c| if c.is_ascii() { c.escape_debug().to_string() } else { format!("\\u{{{:x}}}", c as u32) });
+
+    return format!("\"{}\"", escaped.collect::<String>());
+}
+
+/// Describe the first character that differs between two strings, as a failure detail
+fn first_difference(expected: &str, actual: &str) -> Option<String> {
+    let describe = |c: Option<char>| match c {
+        Some(c) => format!("U+{:04X} {:?}", c as u32, c),
+        None => "end of string".to_string(),
+    };
+
+    let (mut expected_chars, mut actual_chars) = (expected.chars(), actual.chars());
+    let mut index = 0;
+    loop {
+        match (expected_chars.next(), actual_chars.next()) {
+            (None, None) => return None,
+            (e, a) if e == a => index += 1,
+            (e, a) => return Some(format!("first difference at character {}: expected {}, got {}", index, describe(e), describe(a))),
+        }
+    }
+}
+
+/// Number of terminal columns of each line of a string
+fn line_widths(text: &str) -> Vec<usize> {
+    return text.lines().map(UnicodeWidthStr::width).collect();
+}
+
+// Single implementation for any type that implements AsUnicode
+impl<V> UnicodeMatchers for Assertion<V>
+where
+    V: AsUnicode + Debug + Clone,
+{
+    fn to_equal_normalized(self, expected: &str) -> Self {
+        let actual = self.value.text();
+        let normalized_actual = Form::NFC.normalize(actual);
+        let normalized_expected = Form::NFC.normalize(expected);
+        let result = normalized_actual == normalized_expected;
+
+        let mut sentence = AssertionSentence::new("equal", format!("{} once normalized", escape_non_ascii(expected)))
+            .with_actual(escape_non_ascii(actual));
+        if let Some(difference) = first_difference(&normalized_expected, &normalized_actual) {
+            sentence = sentence.with_detail(format!("{} (after NFC normalization)", difference));
+        }
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_be_normalized(self, form: Form) -> Self {
+        let actual = self.value.text();
+        let normalized = form.normalize(actual);
+        let result = normalized == actual;
+
+        let mut sentence = AssertionSentence::new("be", format!("{} normalized", form)).with_actual(escape_non_ascii(actual));
+        if !result {
+            sentence = sentence.with_detail(format!("{} form: {}", form, escape_non_ascii(&normalized)));
+        }
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_grapheme_count(self, expected: usize) -> Self {
+        let graphemes = self.value.text().graphemes(true).collect::<Vec<_>>();
+        let result = graphemes.len() == expected;
+
+        let mut sentence = AssertionSentence::new("have", format!("{} graphemes", expected)).with_actual(graphemes.len().to_string());
+        if !result && graphemes.len() <= MAX_LISTED_GRAPHEMES {
+            let listed = graphemes.iter().map(|grapheme| escape_non_ascii(grapheme)).collect::<Vec<_>>();
+            sentence = sentence.with_detail(format!("graphemes: {}", listed.join(", ")));
+        }
+
+        return self.add_step(sentence, result);
+    }
+
+    fn to_have_display_width(self, expected: usize) -> Self {
+        let widths = line_widths(self.value.text());
+        let width = widths.iter().copied().max().unwrap_or(0);
+        let result = width == expected;
+
+        let mut sentence =
+            AssertionSentence::new("have", format!("a display width of {} columns", expected)).with_actual(width.to_string());
+        if !result && widths.len() > 1 {
+            for (index, line_width) in widths.iter().enumerate().filter(|(_, line_width)| **line_width != expected) {
+                sentence = sentence.with_detail(format!("line {}: {} columns", index + 1, line_width));
+            }
+        }
+
+        return self.add_step(sentence, result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    const COMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    #[test]
+    fn test_to_equal_normalized() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        expect!(DECOMPOSED).to_equal_normalized(COMPOSED);
+        expect!(COMPOSED.to_string()).to_equal_normalized(DECOMPOSED);
+        expect!("Zo\u{eb}").to_equal_normalized("Zoe\u{308}");
+        expect!(COMPOSED).not().to_equal_normalized("cafe");
+        // Compatibility variants aren't canonically equivalent
+        expect!("\u{fb01}le").not().to_equal_normalized("file");
+    }
+
+    #[test]
+    fn test_to_be_normalized() {
+        crate::Reporter::disable_deduplication();
+
+        expect!(COMPOSED).to_be_normalized(Form::NFC);
+        expect!(COMPOSED).not().to_be_normalized(Form::NFD);
+        expect!(DECOMPOSED).to_be_normalized(Form::NFD);
+        expect!(DECOMPOSED).not().to_be_normalized(Form::NFC);
+        expect!("\u{fb01}le").to_be_normalized(Form::NFC);
+        expect!("\u{fb01}le").not().to_be_normalized(Form::NFKC);
+        expect!("plain ascii").to_be_normalized(Form::NFKD);
+    }
+
+    #[test]
+    fn test_to_have_grapheme_count() {
+        crate::Reporter::disable_deduplication();
+
+        expect!(DECOMPOSED).to_have_grapheme_count(4);
+        expect!("👍🏽 ok").to_have_grapheme_count(4);
+        expect!("🇫🇷".to_string()).to_have_grapheme_count(1);
+        expect!("").to_have_grapheme_count(0);
+        expect!(DECOMPOSED).not().to_have_grapheme_count(5);
+    }
+
+    #[test]
+    fn test_to_have_display_width() {
+        crate::Reporter::disable_deduplication();
+
+        expect!("status").to_have_display_width(6);
+        expect!(DECOMPOSED).to_have_display_width(4);
+        expect!("日本語").to_have_display_width(6);
+        expect!("┌──┐\n│ok│\n└──┘").to_have_display_width(4);
+        expect!("").to_have_display_width(0);
+        expect!("日本語").not().to_have_display_width(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "have 3 graphemes (got 2)\n  graphemes: \"e\\u{301}\", \"\\u{1f44d}\"")]
+    fn test_grapheme_count_lists_graphemes() {
+        let _assertion = expect!("e\u{301}👍").to_have_grapheme_count(3);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "have a display width of 4 columns (got 6)\n  line 2: 6 columns")]
+    fn test_display_width_lists_misaligned_lines() {
+        let _assertion = expect!("│ab│\n│日本│\n│cd│").to_have_display_width(4);
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "equal \"cafe\" once normalized (got \"caf\\u{e9}\")\n  first difference at character 3: expected U+0065 'e', got U+00E9 'é'"
+    )]
+    fn test_normalized_difference_shows_code_points() {
+        let _assertion = expect!(COMPOSED).to_equal_normalized("cafe");
+        std::hint::black_box(_assertion);
+    }
+
+    #[test]
+    #[should_panic(expected = "be NFC normalized (got \"cafe\\u{301}\")\n  NFC form: \"caf\\u{e9}\"")]
+    fn test_unnormalized_string_shows_normalized_form() {
+        let _assertion = expect!(DECOMPOSED).to_be_normalized(Form::NFC);
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,232 @@
+//! Per-test memory usage tracking
+//!
+//! Available with the `memory-tracking` feature. Install the instrumented allocator in the test crate:
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: rest::backend::memory::TrackingAllocator = rest::backend::memory::TrackingAllocator;
+//! ```
+//!
+//! Every fixture-wrapped test then records the peak number of bytes its thread held during the test
+//! body and the bytes still held when it returned. `Reporter::summarize()` prints the most
+//! memory-hungry tests. Allocations made on threads spawned by a test are not attributed to it.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+/// Number of tests listed in the session summary
+pub const TOP_MEMORY_TESTS: usize = 5;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+static RECORDED_USAGE: LazyLock<Mutex<Vec<TestMemoryUsage>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+thread_local! {
+    // Net bytes allocated by this thread; signed since memory may be freed by another thread
+    static THREAD_CURRENT: Cell<isize> = const { Cell::new(0) };
+    // Highest value reached by THREAD_CURRENT since the last reset
+    static THREAD_PEAK: Cell<isize> = const { Cell::new(0) };
+}
+
+/// Global allocator wrapping the system allocator with per-thread accounting
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            track(layout.size() as isize);
+        }
+        return ptr;
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            track(layout.size() as isize);
+        }
+        return ptr;
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        track(-(layout.size() as isize));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            track(new_size as isize - layout.size() as isize);
+        }
+        return new_ptr;
+    }
+}
+
+/// Record an allocation delta for the current thread
+fn track(delta: isize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+
+    // The thread locals have no destructor, but may be unavailable very late in thread teardown
+    let _ = THREAD_CURRENT.try_with(|current| {
+        let value = current.get() + delta;
+        current.set(value);
+        let _ = THREAD_PEAK.try_with(|peak| {
+            if value > peak.get() {
+                peak.set(value);
+            }
+        });
+    });
+}
+
+/// Memory used by a single test body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestMemoryUsage {
+    /// Full path of the test
+    pub test: String,
+    /// Highest number of bytes held above the level at test start
+    pub peak_bytes: usize,
+    /// Bytes still held when the test returned
+    pub retained_bytes: usize,
+}
+
+/// Check if the tracking allocator is installed as the global allocator
+pub fn is_tracking_enabled() -> bool {
+    return INSTALLED.load(Ordering::Relaxed);
+}
+
+/// Run a test body and record its memory usage, even if it panics
+pub(crate) fn measure<R>(module_path: &'static str, test_name: &'static str, test_fn: impl FnOnce() -> R) -> R {
+    // Records the usage when dropped, so panicking tests are measured too
+    struct Measurement {
+        module_path: &'static str,
+        test_name: &'static str,
+        baseline: isize,
+    }
+
+    impl Drop for Measurement {
+        fn drop(&mut self) {
+            if !is_tracking_enabled() {
+                return;
+            }
+
+            let current = THREAD_CURRENT.with(|current| current.get());
+            let peak = THREAD_PEAK.with(|peak| peak.get());
+
+            let usage = TestMemoryUsage {
+                test: format!("{}::{}", self.module_path, self.test_name),
+                peak_bytes: (peak - self.baseline).max(0) as usize,
+                retained_bytes: (current - self.baseline).max(0) as usize,
+            };
+
+            if let Ok(mut recorded) = RECORDED_USAGE.lock() {
+                recorded.push(usage);
+            }
+        }
+    }
+
+    // Start the peak from the current level so earlier tests on this thread don't count
+    let baseline = THREAD_CURRENT.with(|current| current.get());
+    THREAD_PEAK.with(|peak| peak.set(baseline));
+
+    let _measurement = Measurement { module_path, test_name, baseline };
+    return test_fn();
+}
+
+/// All recorded tests, sorted by decreasing peak usage
+pub fn recorded_usage() -> Vec<TestMemoryUsage> {
+    let mut usage = RECORDED_USAGE.lock().unwrap().clone();
+    usage.sort_by(|a, b| b.peak_bytes.cmp(&a.peak_bytes).then_with(|| a.test.cmp(&b.test)));
+    return usage;
+}
+
+/// The `count` tests with the highest peak usage
+pub fn top_memory_tests(count: usize) -> Vec<TestMemoryUsage> {
+    let mut usage = recorded_usage();
+    usage.truncate(count);
+    return usage;
+}
+
+/// Format a byte count with binary units
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    return format!("{:.1} {}", value, UNITS[unit]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_measure_records_peak_and_retained_bytes() {
+        let mut kept: Vec<u8> = Vec::new();
+
+        measure("memory::tests", "allocating_body", || {
+            let scratch = vec![0u8; 64 * 1024];
+            std::hint::black_box(&scratch);
+            kept = vec![1u8; 4 * 1024];
+        });
+
+        let usage = recorded_usage().into_iter().find(|usage| usage.test == "memory::tests::allocating_body").unwrap();
+        assert!(is_tracking_enabled());
+        assert!(usage.peak_bytes >= 64 * 1024, "peak was {}", usage.peak_bytes);
+        assert!(usage.retained_bytes >= 4 * 1024, "retained was {}", usage.retained_bytes);
+        assert!(usage.retained_bytes < 64 * 1024, "retained was {}", usage.retained_bytes);
+        std::hint::black_box(kept);
+    }
+
+    #[test]
+    fn test_measure_records_panicking_tests() {
+        let result = std::panic::catch_unwind(|| {
+            measure("memory::tests", "panicking_body", || {
+                let scratch = vec![0u8; 16 * 1024];
+                std::hint::black_box(&scratch);
+                panic!("test failure");
+            });
+        });
+
+        assert!(result.is_err());
+        assert!(recorded_usage().iter().any(|usage| usage.test == "memory::tests::panicking_body" && usage.peak_bytes >= 16 * 1024));
+    }
+
+    #[test]
+    fn test_top_memory_tests_is_sorted_and_bounded() {
+        measure("memory::tests", "small_body", || {
+            let buffer = vec![0u8; 1024];
+            std::hint::black_box(&buffer);
+        });
+        measure("memory::tests", "large_body", || {
+            let buffer = vec![0u8; 1024 * 1024];
+            std::hint::black_box(&buffer);
+        });
+
+        let top = top_memory_tests(2);
+        assert_eq!(top.len(), 2);
+        assert!(top[0].peak_bytes >= top[1].peak_bytes);
+        assert!(top_memory_tests(0).is_empty());
+    }
+}
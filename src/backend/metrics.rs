@@ -0,0 +1,371 @@
+//! Per-test capture of emitted metrics
+//!
+//! Available with the `metrics-capture` feature. Counters, gauges and histograms emitted through the
+//! `metrics` crate are recorded in memory per test thread while a capture is active, so instrumented
+//! code can be checked without a metrics backend. Every fixture-wrapped test gets its own capture;
+//! other tests start one with [`capture_metrics`]. `expect_metric!` then asserts on a metric:
+//!
+//! ```ignore
+//! let _metrics = capture_metrics();
+//! server.handle(request);
+//! expect_metric!("requests_total").to_have_value(1);
+//! expect_metric!("requests_total", "status" => "200").to_be_recorded();
+//! ```
+//!
+//! The capture is installed as the thread's local recorder, so metrics emitted on threads spawned by
+//! a test are not captured.
+
+use ::metrics::{Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, LocalRecorderGuard, Metadata, Recorder};
+use ::metrics::{SharedString, Unit};
+use std::cell::RefCell;
+use std::fmt::{self, Display};
+use std::sync::Arc;
+
+/// Recorder shared by every capture, forwarding to the capture of the calling thread
+static RECORDER: CaptureRecorder = CaptureRecorder;
+
+thread_local! {
+    // Series captured on this thread, `None` when no capture is active
+    static CAPTURED: RefCell<Option<Vec<MetricSeries>>> = const { RefCell::new(None) };
+}
+
+/// Value of a captured series
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetricValue {
+    Counter(u64),
+    Gauge(f64),
+    /// Every recorded observation, oldest first
+    Histogram(Vec<f64>),
+}
+
+impl Display for MetricValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            MetricValue::Counter(value) => write!(f, "counter {}", value),
+            MetricValue::Gauge(value) => write!(f, "gauge {}", value),
+            MetricValue::Histogram(samples) if samples.len() == 1 => write!(f, "histogram with 1 observation"),
+            MetricValue::Histogram(samples) => write!(f, "histogram with {} observations", samples.len()),
+        };
+    }
+}
+
+/// A metric name with one set of labels, and its value
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSeries {
+    pub name: String,
+    /// Labels sorted by key
+    pub labels: Vec<(String, String)>,
+    pub value: MetricValue,
+}
+
+impl MetricSeries {
+    /// Check if the series carries the given label
+    pub fn has_label(&self, key: &str, value: &str) -> bool {
+        return self.labels.iter().any(|(label_key, label_value)| label_key == key && label_value == value);
+    }
+}
+
+impl Display for MetricSeries {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let labels = self.labels.iter().map(|(key, value)| format!("{}={:?}", key, value)).collect::<Vec<_>>();
+        return write!(f, "{}{{{}}} {}", self.name, labels.join(", "), self.value);
+    }
+}
+
+/// The series of one metric, as asserted by `expect_metric!`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metric {
+    name: String,
+    series: Vec<MetricSeries>,
+}
+
+impl Metric {
+    /// Name of the metric
+    pub fn name(&self) -> &str {
+        return &self.name;
+    }
+
+    /// Captured series of the metric, one per label set
+    pub fn series(&self) -> &[MetricSeries] {
+        return &self.series;
+    }
+
+    /// Check if the metric was emitted at all
+    pub fn is_recorded(&self) -> bool {
+        return !self.series.is_empty();
+    }
+
+    /// Counter or gauge value, summed across label sets; `None` for histograms and unrecorded metrics
+    pub fn value(&self) -> Option<f64> {
+        let mut total = None;
+        for series in &self.series {
+            let value = match series.value {
+                MetricValue::Counter(value) => value as f64,
+                MetricValue::Gauge(value) => value,
+                MetricValue::Histogram(_) => return None,
+            };
+            total = Some(total.unwrap_or(0.0) + value);
+        }
+
+        return total;
+    }
+
+    /// Histogram observations across label sets
+    pub fn observations(&self) -> Vec<f64> {
+        return self
+            .series
+            .iter()
+            .filter_map(|series| match &series.value {
+                MetricValue::Histogram(samples) => Some(samples.clone()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+    }
+}
+
+impl Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self.series.as_slice() {
+            [] => write!(f, "not recorded"),
+            [series] => write!(f, "{}", series.value),
+            [first, ..] => match self.value() {
+                Some(value) if matches!(first.value, MetricValue::Counter(_)) => {
+                    write!(f, "counter {} across {} series", value, self.series.len())
+                }
+                Some(value) => write!(f, "gauge {} across {} series", value, self.series.len()),
+                None => write!(f, "histogram with {} observations across {} series", self.observations().len(), self.series.len()),
+            },
+        };
+    }
+}
+
+/// Series captured on the current thread
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapturedMetrics {
+    series: Vec<MetricSeries>,
+}
+
+impl CapturedMetrics {
+    /// Every captured series, in order of first emission
+    pub fn series(&self) -> &[MetricSeries] {
+        return &self.series;
+    }
+
+    /// The series of a metric, whatever their labels
+    pub fn metric(&self, name: &str) -> Metric {
+        return self.metric_with_labels(name, &[]);
+    }
+
+    /// The series of a metric carrying all the given labels
+    pub fn metric_with_labels(&self, name: &str, labels: &[(&str, &str)]) -> Metric {
+        let series = self
+            .series
+            .iter()
+            .filter(|series| series.name == name && labels.iter().all(|(key, value)| series.has_label(key, value)))
+            .cloned()
+            .collect();
+
+        return Metric { name: name.to_string(), series };
+    }
+}
+
+/// Guard keeping a metrics capture active on the current thread until dropped
+///
+/// Nested captures start empty and restore the enclosing capture when dropped.
+pub struct MetricsCapture {
+    enclosing: Option<Vec<MetricSeries>>,
+    _recorder: LocalRecorderGuard<'static>,
+}
+
+impl Drop for MetricsCapture {
+    fn drop(&mut self) {
+        let enclosing = self.enclosing.take();
+        let _ = CAPTURED.try_with(|captured| *captured.borrow_mut() = enclosing);
+    }
+}
+
+/// Start capturing the metrics emitted on the current thread
+pub fn capture_metrics() -> MetricsCapture {
+    let enclosing = CAPTURED.with(|captured| captured.replace(Some(Vec::new())));
+    let recorder = ::metrics::set_default_local_recorder(&RECORDER);
+
+    return MetricsCapture { enclosing, _recorder: recorder };
+}
+
+/// Series captured so far by the active capture of the current thread
+pub fn captured_metrics() -> CapturedMetrics {
+    let series = CAPTURED.with(|captured| captured.borrow().clone()).unwrap_or_default();
+    return CapturedMetrics { series };
+}
+
+/// Update a series of the active capture, if any, creating it from `initial` on first emission
+fn update_series(handle: &SeriesHandle, initial: MetricValue, update: impl FnOnce(&mut MetricValue)) {
+    let _ = CAPTURED.try_with(|captured| {
+        if let Ok(mut captured) = captured.try_borrow_mut()
+            && let Some(series) = captured.as_mut()
+        {
+            let index = match series.iter().position(|series| series.name == handle.name && series.labels == handle.labels) {
+                Some(index) => index,
+                None => {
+                    series.push(MetricSeries { name: handle.name.clone(), labels: handle.labels.clone(), value: initial });
+                    series.len() - 1
+                }
+            };
+            update(&mut series[index].value);
+        }
+    });
+}
+
+/// Recorder handing out handles that write into the capture of the calling thread
+struct CaptureRecorder;
+
+impl CaptureRecorder {
+    fn handle(key: &Key) -> Arc<SeriesHandle> {
+        let mut labels = key.labels().map(|label| (label.key().to_string(), label.value().to_string())).collect::<Vec<_>>();
+        labels.sort();
+
+        return Arc::new(SeriesHandle { name: key.name().to_string(), labels });
+    }
+}
+
+impl Recorder for CaptureRecorder {
+    fn describe_counter(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_gauge(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn describe_histogram(&self, _key: KeyName, _unit: Option<Unit>, _description: SharedString) {}
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        return Counter::from_arc(Self::handle(key));
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        return Gauge::from_arc(Self::handle(key));
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        return Histogram::from_arc(Self::handle(key));
+    }
+}
+
+/// Handle of one series; the same handle serves counters, gauges and histograms
+struct SeriesHandle {
+    name: String,
+    labels: Vec<(String, String)>,
+}
+
+impl CounterFn for SeriesHandle {
+    fn increment(&self, value: u64) {
+        update_series(self, MetricValue::Counter(0), |current| {
+            if let MetricValue::Counter(count) = current {
+                *count += value;
+            }
+        });
+    }
+
+    fn absolute(&self, value: u64) {
+        update_series(self, MetricValue::Counter(0), |current| {
+            if let MetricValue::Counter(count) = current {
+                *count = (*count).max(value);
+            }
+        });
+    }
+}
+
+impl GaugeFn for SeriesHandle {
+    fn increment(&self, value: f64) {
+        update_series(self, MetricValue::Gauge(0.0), |current| {
+            if let MetricValue::Gauge(gauge) = current {
+                *gauge += value;
+            }
+        });
+    }
+
+    fn decrement(&self, value: f64) {
+        update_series(self, MetricValue::Gauge(0.0), |current| {
+            if let MetricValue::Gauge(gauge) = current {
+                *gauge -= value;
+            }
+        });
+    }
+
+    fn set(&self, value: f64) {
+        update_series(self, MetricValue::Gauge(0.0), |current| {
+            if let MetricValue::Gauge(gauge) = current {
+                *gauge = value;
+            }
+        });
+    }
+}
+
+impl HistogramFn for SeriesHandle {
+    fn record(&self, value: f64) {
+        update_series(self, MetricValue::Histogram(Vec::new()), |current| {
+            if let MetricValue::Histogram(samples) = current {
+                samples.push(value);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counters_gauges_and_histograms_are_captured() {
+        let _metrics = capture_metrics();
+
+        ::metrics::counter!("requests_total").increment(2);
+        ::metrics::counter!("requests_total").increment(1);
+        ::metrics::gauge!("connections").set(5.0);
+        ::metrics::gauge!("connections").decrement(2.0);
+        ::metrics::histogram!("latency_seconds").record(0.25);
+
+        let captured = captured_metrics();
+        assert_eq!(captured.metric("requests_total").value(), Some(3.0));
+        assert_eq!(captured.metric("connections").value(), Some(3.0));
+        assert_eq!(captured.metric("latency_seconds").observations(), vec![0.25]);
+        assert_eq!(captured.metric("latency_seconds").value(), None);
+        assert!(!captured.metric("errors_total").is_recorded());
+    }
+
+    #[test]
+    fn test_series_are_split_by_labels() {
+        let _metrics = capture_metrics();
+
+        ::metrics::counter!("requests_total", "status" => "200", "method" => "GET").increment(2);
+        ::metrics::counter!("requests_total", "method" => "GET", "status" => "500").increment(1);
+
+        let captured = captured_metrics();
+        let requests = captured.metric("requests_total");
+        assert_eq!(requests.series().len(), 2);
+        assert_eq!(requests.value(), Some(3.0));
+        assert_eq!(requests.to_string(), "counter 3 across 2 series");
+        assert_eq!(requests.series()[0].to_string(), "requests_total{method=\"GET\", status=\"200\"} counter 2");
+        assert_eq!(captured.metric_with_labels("requests_total", &[("status", "500")]).value(), Some(1.0));
+    }
+
+    #[test]
+    fn test_nothing_is_captured_without_capture() {
+        ::metrics::counter!("requests_total").increment(1);
+        assert!(captured_metrics().series().is_empty());
+    }
+
+    #[test]
+    fn test_nested_capture_restores_the_enclosing_one() {
+        let _outer = capture_metrics();
+        ::metrics::counter!("outer_total").increment(1);
+
+        {
+            let _inner = capture_metrics();
+            ::metrics::counter!("inner_total").increment(1);
+            assert_eq!(captured_metrics().series().len(), 1);
+        }
+
+        let captured = captured_metrics();
+        assert!(captured.metric("outer_total").is_recorded());
+        assert!(!captured.metric("inner_total").is_recorded());
+    }
+}
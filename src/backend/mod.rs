@@ -1,10 +1,37 @@
 //! Backend module for test evaluation and result generation
 
+pub mod artifacts;
 pub mod assertions;
+pub mod diff;
+pub mod discovery;
+pub mod expected_failure;
 pub mod fixtures;
+#[cfg(feature = "log-capture")]
+pub mod logs;
+#[doc(hidden)]
+pub mod macros_version;
 pub mod matchers;
+#[cfg(feature = "memory-tracking")]
+pub mod memory;
+#[cfg(feature = "metrics-capture")]
+pub mod metrics;
 pub mod modifiers;
+pub(crate) mod number;
+pub mod order;
+pub mod panic_hook;
+pub mod property;
+pub mod run;
+pub mod session;
+pub mod shard;
+#[cfg(feature = "regex")]
+pub mod template;
 
-pub use assertions::sentence::AssertionSentence;
-pub use assertions::{Assertion, AssertionStep, LogicalOp, TestSessionResult};
+pub use artifacts::{Artifact, ArtifactContent, attach_artifact, attached_artifacts};
+pub use assertions::sentence::{AssertionSentence, MatcherDocs};
+pub use assertions::{Assertion, AssertionStep, LogicalOp, Severity, TestSessionResult};
+pub use discovery::{TestDescriptor, discovered_tests};
+pub use fixtures::progress::ModuleStats;
 pub use fixtures::{is_in_fixture_test, register_setup, register_teardown, run_test_with_fixtures};
+pub use order::{OrderReport, check_test_order, run_order_check};
+pub use run::{RunInfo, current_run};
+pub use session::Session;
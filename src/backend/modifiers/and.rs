@@ -27,6 +27,14 @@ impl<T: Clone> AndModifier<T> for Assertion<T> {
             in_chain: true,  // Always mark as part of a chain
             is_final: false, // This is not the final step - there will be more after 'and()'
             evaluated: false,
+            context: result.context.clone(),
+            location: result.location,
+            severity: result.severity,
+            type_name: result.type_name,
+            item_count: result.item_count,
+            created_at: result.created_at,
+            event: None,
+            memo: result.memo.clone(),
         };
     }
 }
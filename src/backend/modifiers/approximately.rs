@@ -0,0 +1,165 @@
+use crate::backend::Assertion;
+use crate::backend::assertions::sentence::AssertionSentence;
+use std::fmt::Debug;
+
+/// Approximately modifier trait for comparing floating-point values within a tolerance
+pub trait ApproximatelyModifier<T> {
+    /// Makes the following `to_equal` compare floats within an absolute tolerance
+    /// This keeps the usual matcher name for float comparisons:
+    /// expect(0.1 + 0.2).approximately(1e-9).to_equal(0.3)
+    fn approximately(self, epsilon: f64) -> Approximately<T>;
+}
+
+impl<T> ApproximatelyModifier<T> for Assertion<T> {
+    fn approximately(self, epsilon: f64) -> Approximately<T> {
+        return Approximately { assertion: self, epsilon };
+    }
+}
+
+/// Assertion whose next `to_equal` compares floats within a tolerance, created by `approximately`
+#[derive(Debug)]
+pub struct Approximately<T> {
+    assertion: Assertion<T>,
+    epsilon: f64,
+}
+
+impl<T: Debug + Clone> Approximately<T> {
+    /// Check if the value is equal to the expected value, with floats differing by at most the tolerance
+    #[track_caller]
+    pub fn to_equal<E: Debug>(self, expected: E) -> Assertion<T>
+    where
+        T: ApproxEq<E>,
+    {
+        let result = self.assertion.value.approx_eq(&expected, self.epsilon);
+
+        return self.assertion.add_step_with(result, |actual| {
+            return AssertionSentence::new("be", format!("equal to {:?}", expected))
+                .with_qualifier(format!("within {}", self.epsilon))
+                .with_actual(format!("{:?}", actual));
+        });
+    }
+}
+
+/// Equality within an absolute tolerance, for floats and the tuples and collections containing them
+///
+/// Implement it for domain types holding floats to compare them with `approximately`.
+pub trait ApproxEq<Rhs: ?Sized = Self> {
+    /// Whether the values are equal, with floats differing by at most `epsilon`
+    fn approx_eq(&self, other: &Rhs, epsilon: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, epsilon: f64) -> bool {
+        return self == other || (self - other).abs() <= epsilon;
+    }
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &f32, epsilon: f64) -> bool {
+        return f64::from(*self).approx_eq(&f64::from(*other), epsilon);
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &[T], epsilon: f64) -> bool {
+        return self.len() == other.len() && self.iter().zip(other).all(|(value, other)| value.approx_eq(other, epsilon));
+    }
+}
+
+impl<T: ApproxEq, const N: usize> ApproxEq for [T; N] {
+    fn approx_eq(&self, other: &[T; N], epsilon: f64) -> bool {
+        return self.as_slice().approx_eq(other.as_slice(), epsilon);
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Vec<T>, epsilon: f64) -> bool {
+        return self.as_slice().approx_eq(other.as_slice(), epsilon);
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Option<T> {
+    fn approx_eq(&self, other: &Option<T>, epsilon: f64) -> bool {
+        return match (self, other) {
+            (Some(value), Some(other)) => value.approx_eq(other, epsilon),
+            (None, None) => true,
+            _ => false,
+        };
+    }
+}
+
+// References compare with references and with the values they point to, as `to_equal` does
+impl<T: ApproxEq + ?Sized> ApproxEq for &T {
+    fn approx_eq(&self, other: &&T, epsilon: f64) -> bool {
+        return (**self).approx_eq(*other, epsilon);
+    }
+}
+
+impl<T: ApproxEq + ?Sized> ApproxEq<T> for &T {
+    fn approx_eq(&self, other: &T, epsilon: f64) -> bool {
+        return (**self).approx_eq(other, epsilon);
+    }
+}
+
+// Tuples compare element-wise
+macro_rules! impl_tuple_approx_eq {
+    ($($name:ident: $index:tt),+) => {
+        impl<$($name: ApproxEq),+> ApproxEq for ($($name,)+) {
+            fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+                return $(self.$index.approx_eq(&other.$index, epsilon))&&+;
+            }
+        }
+    };
+}
+
+impl_tuple_approx_eq!(A: 0);
+impl_tuple_approx_eq!(A: 0, B: 1);
+impl_tuple_approx_eq!(A: 0, B: 1, C: 2);
+impl_tuple_approx_eq!(A: 0, B: 1, C: 2, D: 3);
+impl_tuple_approx_eq!(A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_tuple_approx_eq!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    #[test]
+    fn test_approximately_modifier() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let sum = 0.1 + 0.2;
+        expect!(sum).approximately(1e-9).to_equal(0.3);
+        expect!(sum).not().approximately(1e-20).to_equal(0.3);
+
+        let point = (1.0_f32 / 3.0, 2.0 / 3.0);
+        expect!(point).approximately(0.001).to_equal((0.333, 0.667));
+
+        let values = vec![0.1 + 0.2, 1.0 / 3.0];
+        expect!(&values).approximately(0.001).to_equal(vec![0.3, 0.333]);
+        expect!(&values).not().approximately(0.001).to_equal(vec![0.3]);
+    }
+
+    #[test]
+    fn test_approximately_chains() {
+        let sum = 0.1 + 0.2;
+        expect!(sum).approximately(1e-9).to_equal(0.3).and().to_be_greater_than(0.0);
+    }
+
+    #[test]
+    fn test_tolerance_is_a_qualifier() {
+        let run = crate::testkit::run_matcher(0.5, |value| value.approximately(0.001).to_equal(0.3));
+
+        expect!(run.passed()).to_be_false();
+        expect!(run.sentence()).to_equal("be equal to 0.3 within 0.001".to_string());
+        expect!(run.actual()).to_equal(Some("0.5"));
+    }
+
+    #[test]
+    #[should_panic(expected = "be equal to [0.3, 0.5] within 0.01")]
+    fn test_approximately_fails_outside_tolerance() {
+        let values = [0.3, 0.4];
+        let _assertion = expect!(values).approximately(0.01).to_equal([0.3, 0.5]);
+        std::hint::black_box(_assertion);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::{Assertion, LogicalOp};
+use std::time::Duration;
+
+/// Budget modifier trait for timing the matchers of a chain
+pub trait BudgetModifier<T> {
+    /// Check that the matchers chained so far evaluated within a time budget
+    ///
+    /// The time runs from the creation of the assertion, once its value is evaluated, so it covers the matcher
+    /// computations themselves, such as the predicates of `to_have_all_windows_satisfying` on large data. The check is
+    /// added to the chain as one more step, with the actual evaluation time on failure:
+    /// expect(ids).to_have_all_windows_satisfying(2, is_sorted).within_budget(Duration::from_micros(50))
+    #[track_caller]
+    fn within_budget(self, budget: Duration) -> Self;
+}
+
+impl<T: Clone> BudgetModifier<T> for Assertion<T> {
+    fn within_budget(self, budget: Duration) -> Self {
+        let elapsed = self.created_at.elapsed();
+
+        // The timed matchers and the budget form a single chain
+        let mut result = self;
+        result.mark_as_intermediate();
+        result.set_last_logic(LogicalOp::And);
+
+        return result.add_step_with(elapsed <= budget, |_| {
+            return AssertionSentence::new("evaluate", format!("within {:?}", budget)).with_actual(format!("{:?}", elapsed));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::LogicalOp;
+    use crate::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_chain_within_budget() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let ids = vec![1, 2, 3];
+
+        expect!(ids).to_have_length(3).within_budget(Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_budget_step_joins_the_chain() {
+        let run = crate::testkit::run_matcher(vec![1, 2, 3], |ids| ids.to_contain(4).within_budget(Duration::from_secs(60)));
+
+        expect!(run.steps().len()).to_equal(2);
+        expect!(run.steps()[0].logical_op).to_equal(Some(LogicalOp::And));
+        expect!(run.sentence()).to_equal("evaluate within 60s".to_string());
+        expect!(run.passed()).to_be_false();
+    }
+
+    #[test]
+    #[should_panic(expected = "evaluate within 1ms (got ")]
+    fn test_slow_matchers_exceed_the_budget() {
+        let ids = vec![1, 2, 3];
+
+        expect!(ids)
+            .to_have_all_windows_satisfying(2, |_| {
+                std::thread::sleep(Duration::from_millis(5));
+                return true;
+            })
+            .within_budget(Duration::from_millis(1));
+    }
+}
@@ -1,7 +1,11 @@
 mod and;
+mod approximately;
+mod budget;
 mod not;
 mod or;
 
 pub use and::*;
+pub use approximately::*;
+pub use budget::*;
 pub use not::*;
 pub use or::*;
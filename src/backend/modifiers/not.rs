@@ -19,6 +19,14 @@ impl<T: Clone> NotModifier<T> for Assertion<T> {
             in_chain: self.in_chain, // Preserve chain status
             is_final: self.is_final, // Preserve finality status
             evaluated: false,
+            context: self.context.clone(),
+            location: self.location,
+            severity: self.severity,
+            type_name: self.type_name,
+            item_count: self.item_count,
+            created_at: self.created_at,
+            event: None,
+            memo: self.memo.clone(),
         };
     }
 }
@@ -0,0 +1,150 @@
+//! Rendering of numbers in assertion sentences
+//!
+//! Floats are rendered from the shortest digits that read back as the same float, as `{:e}` gives them, and written
+//! out or in scientific notation depending on their exponent only. Sentences and `#[should_panic(expected = ..)]`
+//! expectations then read the same on every platform and whatever the magnitude, instead of switching notation where
+//! `Display` and `Debug` do. The precision and the threshold of scientific notation are set with
+//! [`Config::float_precision`](crate::Config::float_precision) and
+//! [`Config::scientific_threshold`](crate::Config::scientific_threshold).
+
+use crate::config::FloatFormat;
+use std::fmt::LowerExp;
+
+/// Numbers rendered the same way by every matcher
+pub(crate) trait FormatNumber {
+    /// Render the number with the float format of the current configuration
+    fn format_number(&self) -> String;
+}
+
+impl<N: FormatNumber + ?Sized> FormatNumber for &N {
+    fn format_number(&self) -> String {
+        return (**self).format_number();
+    }
+}
+
+macro_rules! impl_format_display {
+    ($($t:ty),*) => {
+        $(
+            impl FormatNumber for $t {
+                fn format_number(&self) -> String {
+                    return self.to_string();
+                }
+            }
+        )*
+    };
+}
+
+impl_format_display!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "decimal")]
+impl_format_display!(rust_decimal::Decimal);
+
+#[cfg(feature = "bigint")]
+impl_format_display!(num_bigint::BigInt, num_bigint::BigUint);
+
+impl FormatNumber for f32 {
+    fn format_number(&self) -> String {
+        return format_float(*self, crate::config::float_format());
+    }
+}
+
+impl FormatNumber for f64 {
+    fn format_number(&self) -> String {
+        return format_float(*self, crate::config::float_format());
+    }
+}
+
+/// Render a list of numbers like `Debug` renders slices: `[1.0, 2.5]`
+pub(crate) fn format_numbers<N: FormatNumber>(numbers: &[N]) -> String {
+    return format!("[{}]", numbers.iter().map(FormatNumber::format_number).collect::<Vec<_>>().join(", "));
+}
+
+/// Render a float with the given format
+///
+/// Floats whose decimal exponent is at least the threshold, in absolute value, are written like `1.5e-7`, and others
+/// written out like `0.0125` or `3.0`. NaN and infinities are written `NaN`, `inf` and `-inf`.
+pub(crate) fn format_float<F: LowerExp>(value: F, format: FloatFormat) -> String {
+    let scientific = match format.precision {
+        Some(digits) => format!("{:.*e}", digits.max(1) - 1, value),
+        None => format!("{:e}", value),
+    };
+    // NaN and infinities have no exponent
+    let Some((mantissa, exponent)) = scientific.split_once('e') else {
+        return scientific;
+    };
+    let exponent = exponent.parse::<i32>().expect("`{:e}` writes integer exponents");
+
+    let (sign, mantissa) = match mantissa.strip_prefix('-') {
+        Some(mantissa) => ("-", mantissa),
+        None => ("", mantissa),
+    };
+    // Rounding to a precision leaves trailing zeros
+    let digits = mantissa.replace('.', "").trim_end_matches('0').to_string();
+    let digits = if digits.is_empty() { "0".to_string() } else { digits };
+
+    if digits == "0" {
+        return format!("{}0.0", sign);
+    }
+    if exponent.unsigned_abs() >= format.scientific_threshold {
+        let mantissa = if digits.len() > 1 { format!("{}.{}", &digits[..1], &digits[1..]) } else { digits };
+        return format!("{}{}e{}", sign, mantissa, exponent);
+    }
+
+    return if exponent < 0 {
+        format!("{}0.{}{}", sign, "0".repeat((-exponent - 1) as usize), digits)
+    } else {
+        let integer_length = exponent as usize + 1;
+        if digits.len() > integer_length {
+            format!("{}{}.{}", sign, &digits[..integer_length], &digits[integer_length..])
+        } else {
+            format!("{}{}{}.0", sign, digits, "0".repeat(integer_length - digits.len()))
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortest(threshold: u32) -> FloatFormat {
+        return FloatFormat { precision: None, scientific_threshold: threshold };
+    }
+
+    #[test]
+    fn test_floats_are_written_out_below_the_threshold() {
+        assert_eq!(format_float(3.0_f64, shortest(7)), "3.0");
+        assert_eq!(format_float(0.0125_f64, shortest(7)), "0.0125");
+        assert_eq!(format_float(-1234.5_f64, shortest(7)), "-1234.5");
+        assert_eq!(format_float(1000000.0_f64, shortest(7)), "1000000.0");
+        assert_eq!(format_float(0.000001_f64, shortest(7)), "0.000001");
+        assert_eq!(format_float(0.1_f32, shortest(7)), "0.1");
+        assert_eq!(format_float(2.0000005_f32, shortest(7)), "2.0000005");
+        assert_eq!(format_float(-0.0_f64, shortest(7)), "-0.0");
+    }
+
+    #[test]
+    fn test_floats_are_scientific_from_the_threshold() {
+        assert_eq!(format_float(1e-7_f64, shortest(7)), "1e-7");
+        assert_eq!(format_float(-1.5e7_f64, shortest(7)), "-1.5e7");
+        assert_eq!(format_float(1e300_f64, shortest(7)), "1e300");
+        assert_eq!(format_float(0.0125_f64, shortest(2)), "1.25e-2");
+        assert_eq!(format_float(1.0_f64, shortest(0)), "1e0");
+    }
+
+    #[test]
+    fn test_floats_are_rounded_to_the_precision() {
+        let three_digits = FloatFormat { precision: Some(3), scientific_threshold: 7 };
+
+        assert_eq!(format_float(0.1_f64 + 0.2, three_digits), "0.3");
+        assert_eq!(format_float(2.0_f64 / 3.0, three_digits), "0.667");
+        assert_eq!(format_float(123456.0_f64, three_digits), "123000.0");
+        assert_eq!(format_float(9.9999e-9_f64, three_digits), "1e-8");
+    }
+
+    #[test]
+    fn test_special_floats() {
+        assert_eq!(format_float(f64::NAN, shortest(7)), "NaN");
+        assert_eq!(format_float(f64::NEG_INFINITY, shortest(7)), "-inf");
+        assert_eq!(format_numbers(&[1_u8, 2]), "[1, 2]");
+    }
+}
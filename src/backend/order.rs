@@ -0,0 +1,260 @@
+//! Detection of tests depending on the order they run in
+//!
+//! Tests of a module sharing state through fixtures or globals can pass in one order and fail in another, which
+//! only shows up when the harness happens to schedule them differently. The order check runs the tests of every
+//! module twice in one process, in declaration order then in reverse order, and flags the tests whose outcome
+//! differs between the two runs.
+//!
+//! Every synchronous `#[with_fixtures]` test that isn't `#[ignore]`d registers a runner for the check. The libtest
+//! harness can't run tests twice, so the check runs from a custom harness, a test target with `harness = false`
+//! whose `main` calls [`run_order_check`]:
+//!
+//! ```toml
+//! [[test]]
+//! name = "order_check"
+//! path = "tests/order_check.rs"
+//! harness = false
+//! ```
+//!
+//! Assertions run by the check are neither reported nor counted; failures only appear in the [`OrderReport`].
+
+use crate::backend::assertions::capture::capture_failure;
+use crate::backend::fixtures::{TestOutput, run_test_with_fixtures};
+use crate::backend::matchers::failure::panic_message;
+use std::fmt::Write as _;
+use std::panic::AssertUnwindSafe;
+use std::sync::{LazyLock, Mutex};
+
+/// Outcome of a test run by the order check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TestOutcome {
+    Passed,
+    /// Failed with the message of the failed assertion, the panic or the returned error
+    Failed(String),
+}
+
+impl TestOutcome {
+    /// Check if the test passed
+    pub fn passed(&self) -> bool {
+        return matches!(self, TestOutcome::Passed);
+    }
+
+    fn describe(&self) -> String {
+        return match self {
+            TestOutcome::Passed => "passed".to_string(),
+            TestOutcome::Failed(message) => format!("failed ({})", message),
+        };
+    }
+}
+
+/// Test body run with its module fixtures by the order check, registered by `#[with_fixtures]`
+pub type TestRunner = fn() -> TestOutcome;
+
+/// Test registered for the order check
+#[derive(Clone)]
+struct RegisteredRunner {
+    module: &'static str,
+    name: &'static str,
+    line: u32,
+    run: TestRunner,
+}
+
+static RUNNERS: LazyLock<Mutex<Vec<RegisteredRunner>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+/// Register a test for the order check
+///
+/// This is automatically called by the `#[with_fixtures]` attribute macro.
+pub fn register_runner(module: &'static str, name: &'static str, line: u32, run: TestRunner) {
+    RUNNERS.lock().unwrap().push(RegisteredRunner { module, name, line, run });
+}
+
+/// Run a test with its module fixtures, capturing its outcome instead of failing
+///
+/// This is automatically called by the runners registered by `#[with_fixtures]`. Tests marked `#[should_panic]`
+/// pass when they panic.
+pub fn run_test<F, R>(module_path: &'static str, should_panic: bool, test_fn: AssertUnwindSafe<F>) -> TestOutcome
+where
+    F: FnOnce() -> R,
+    R: TestOutput,
+{
+    let (result, failure) = capture_failure(|| run_test_with_fixtures(module_path, test_fn));
+
+    return match (result, should_panic) {
+        (Ok(output), false) if output.passed() => TestOutcome::Passed,
+        (Ok(_), false) => TestOutcome::Failed("returned an error".to_string()),
+        (Ok(_), true) => TestOutcome::Failed("didn't panic".to_string()),
+        (Err(_), true) => TestOutcome::Passed,
+        (Err(payload), false) => match failure {
+            Some(failure) => TestOutcome::Failed(failure.format_stable_message()),
+            None => TestOutcome::Failed(format!("panicked with {:?}", panic_message(&payload))),
+        },
+    };
+}
+
+/// Test whose outcome depends on the order the tests of its module ran in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrderDependentTest {
+    /// Module path the test is defined in
+    pub module: &'static str,
+    /// Function name of the test
+    pub name: &'static str,
+    /// Outcome when the tests of the module ran in declaration order
+    pub in_declaration_order: TestOutcome,
+    /// Outcome when the tests of the module ran in reverse order
+    pub in_reverse_order: TestOutcome,
+}
+
+impl OrderDependentTest {
+    /// Full path of the test, as shown by the test harness
+    pub fn path(&self) -> String {
+        return format!("{}::{}", self.module, self.name);
+    }
+}
+
+/// Result of an order check
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderReport {
+    /// Number of modules whose tests were run
+    pub modules: usize,
+    /// Number of tests run, each of them twice
+    pub tests: usize,
+    /// Tests whose outcome differed between the two orders, by module then declaration order
+    pub dependent: Vec<OrderDependentTest>,
+}
+
+impl OrderReport {
+    /// Check if every test had the same outcome in both orders
+    pub fn is_independent(&self) -> bool {
+        return self.dependent.is_empty();
+    }
+
+    /// Render the report as printed by [`run_order_check`]
+    pub fn render(&self) -> String {
+        let mut output =
+            format!("order check: {} tests in {} modules, run in declaration order then in reverse order\n", self.tests, self.modules);
+        if self.is_independent() {
+            output.push_str("every test had the same outcome in both orders\n");
+            return output;
+        }
+
+        let _ = match self.dependent.len() {
+            1 => writeln!(output, "1 test depends on the order of its module:"),
+            count => writeln!(output, "{} tests depend on the order of their module:", count),
+        };
+        for test in &self.dependent {
+            let _ = writeln!(output, "  {}", test.path());
+            let _ = writeln!(output, "    in declaration order: {}", test.in_declaration_order.describe());
+            let _ = writeln!(output, "    in reverse order: {}", test.in_reverse_order.describe());
+        }
+        output.push_str("they likely share state with other tests through fixtures or globals\n");
+
+        return output;
+    }
+}
+
+/// Run the registered tests of every module twice, in declaration order then in reverse order
+pub fn check_test_order() -> OrderReport {
+    // In discovery mode, list the registered tests instead of running them
+    crate::backend::discovery::dump_in_discovery_mode();
+
+    let mut runners = RUNNERS.lock().unwrap().clone();
+    runners.sort_by(|a, b| a.module.cmp(b.module).then(a.line.cmp(&b.line)).then(a.name.cmp(b.name)));
+    runners.dedup_by(|a, b| a.module == b.module && a.name == b.name);
+
+    let mut report = OrderReport { tests: runners.len(), ..OrderReport::default() };
+    for module in runners.chunk_by(|a, b| a.module == b.module) {
+        report.modules += 1;
+
+        let in_declaration_order = module.iter().map(|runner| (runner.run)()).collect::<Vec<_>>();
+        let mut in_reverse_order = module.iter().rev().map(|runner| (runner.run)()).collect::<Vec<_>>();
+        in_reverse_order.reverse();
+
+        for ((runner, first), second) in module.iter().zip(in_declaration_order).zip(in_reverse_order) {
+            if first.passed() != second.passed() {
+                report.dependent.push(OrderDependentTest {
+                    module: runner.module,
+                    name: runner.name,
+                    in_declaration_order: first,
+                    in_reverse_order: second,
+                });
+            }
+        }
+    }
+
+    return report;
+}
+
+/// Entry point of a custom harness checking the order dependence of the registered tests
+///
+/// Prints the report and exits the process, with a failure status if any test depends on the order.
+pub fn run_order_check() -> ! {
+    let report = check_test_order();
+    print!("{}", report.render());
+    std::process::exit(if report.is_independent() { 0 } else { 1 });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    fn boom() {
+        panic!("boom");
+    }
+
+    #[test]
+    fn test_outcomes_of_the_runs() {
+        assert_eq!(run_test("order::outcomes", false, AssertUnwindSafe(|| {})), TestOutcome::Passed);
+        assert_eq!(run_test("order::outcomes", true, AssertUnwindSafe(boom)), TestOutcome::Passed);
+        assert_eq!(
+            run_test("order::outcomes", false, AssertUnwindSafe(|| Err::<(), _>("closed"))),
+            TestOutcome::Failed("returned an error".to_string())
+        );
+        assert_eq!(run_test("order::outcomes", false, AssertUnwindSafe(boom)), TestOutcome::Failed("panicked with \"boom\"".to_string()));
+
+        let count = 2;
+        assert_eq!(
+            run_test(
+                "order::outcomes",
+                false,
+                AssertUnwindSafe(|| {
+                    expect!(count).to_equal(1);
+                })
+            ),
+            TestOutcome::Failed("expected count to be equal to 1, got 2".to_string())
+        );
+    }
+
+    // Tests of a module coupled through a global: reading passes only after the write
+    static WRITTEN: AtomicBool = AtomicBool::new(false);
+
+    fn write() -> TestOutcome {
+        WRITTEN.store(true, Ordering::SeqCst);
+        return TestOutcome::Passed;
+    }
+
+    fn read() -> TestOutcome {
+        return run_test("order::coupled", false, AssertUnwindSafe(|| assert!(WRITTEN.swap(false, Ordering::SeqCst), "nothing written")));
+    }
+
+    fn independent() -> TestOutcome {
+        return TestOutcome::Passed;
+    }
+
+    #[test]
+    fn test_order_dependent_tests_are_flagged() {
+        register_runner("order::coupled", "test_read", 2, read);
+        register_runner("order::coupled", "test_write", 1, write);
+        register_runner("order::independent", "test_alone", 1, independent);
+
+        let report = check_test_order();
+        let dependent = report.dependent.iter().filter(|test| test.module.starts_with("order::")).collect::<Vec<_>>();
+
+        assert_eq!(dependent.len(), 1);
+        assert_eq!(dependent[0].path(), "order::coupled::test_read");
+        assert_eq!(dependent[0].in_declaration_order, TestOutcome::Passed);
+        assert!(!dependent[0].in_reverse_order.passed());
+        assert!(report.render().contains("  order::coupled::test_read\n    in declaration order: passed\n"));
+    }
+}
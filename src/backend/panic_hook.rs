@@ -0,0 +1,145 @@
+//! Managed panic hook, keeping assertion failures from being reported twice
+//!
+//! Failed assertions panic to fail their test. When the console already rendered the failure, the message, location
+//! and backtrace note printed by the panic hook only repeat it, more so with hooks installed by error-reporting
+//! crates. The managed hook wraps the hook installed before Rest's first assertion: it replaces the report of
+//! assertion panics that were already rendered with the location of the failed `expect!`, and forwards every other
+//! panic to the wrapped hook, or to the hook set for the current test with [`set_test_panic_hook`].
+//!
+//! Setting `RUST_BACKTRACE` forwards assertion panics to the wrapped hook as well, to get their backtrace.
+
+use crate::backend::assertions::capture;
+use std::cell::{Cell, RefCell};
+use std::panic::{self, Location, PanicHookInfo};
+use std::sync::Once;
+
+/// Hook called with the panics of the current test
+pub type TestPanicHook = Box<dyn Fn(&PanicHookInfo<'_>)>;
+
+static MANAGED_HOOK: Once = Once::new();
+
+thread_local! {
+    // Set once the console rendered the failure being handled on this thread
+    static FAILURE_RENDERED: Cell<bool> = const { Cell::new(false) };
+    // Location of the failed assertion whose upcoming panic was already rendered
+    static EXPECTED_PANIC: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
+    // Hook of the test running on this thread, in place of the wrapped hook
+    static TEST_HOOK: RefCell<Option<TestPanicHook>> = const { RefCell::new(None) };
+}
+
+/// Install the managed hook around the current panic hook, once per process
+pub(crate) fn install() {
+    MANAGED_HOOK.call_once(|| {
+        let wrapped = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            // Panics inside a capture only abort the captured closure
+            if capture::is_capturing() {
+                return;
+            }
+
+            if let Some(location) = EXPECTED_PANIC.take()
+                && !backtrace_requested()
+            {
+                let thread = std::thread::current();
+                eprintln!("thread '{}' failed an assertion at {}", thread.name().unwrap_or("<unnamed>"), location);
+                return;
+            }
+
+            let handled = TEST_HOOK.with(|hook| {
+                // A panic in the test hook itself goes to the wrapped hook
+                let hook = hook.try_borrow().ok()?;
+                return hook.as_ref().map(|hook| hook(info));
+            });
+            if handled.is_none() {
+                wrapped(info);
+            }
+        }));
+    });
+}
+
+/// Set a panic hook for the test running on the current thread, called for panics other than rendered failures
+///
+/// It replaces the process-wide hook for this thread only, and is removed when a `#[with_fixtures]` test completes
+/// or by [`clear_test_panic_hook`].
+pub fn set_test_panic_hook(hook: impl Fn(&PanicHookInfo<'_>) + 'static) {
+    install();
+    TEST_HOOK.with(|test_hook| *test_hook.borrow_mut() = Some(Box::new(hook)));
+}
+
+/// Remove the panic hook of the test running on the current thread
+pub fn clear_test_panic_hook() {
+    TEST_HOOK.with(|test_hook| test_hook.borrow_mut().take());
+}
+
+/// Reset the panic handling state of the current thread once its test completed
+pub(crate) fn end_test() {
+    clear_test_panic_hook();
+    FAILURE_RENDERED.set(false);
+    EXPECTED_PANIC.set(None);
+}
+
+/// Forget whether a previous failure was rendered, before reporting a new one
+pub(crate) fn begin_failure_report() {
+    FAILURE_RENDERED.set(false);
+}
+
+/// Note that the console rendered the failure being reported on this thread
+pub(crate) fn failure_rendered() {
+    FAILURE_RENDERED.set(true);
+}
+
+/// Announce the panic of a failed assertion, which the managed hook reports briefly when its failure was rendered
+pub(crate) fn expect_assertion_panic(location: &'static Location<'static>) {
+    if FAILURE_RENDERED.take() {
+        install();
+        EXPECTED_PANIC.set(Some(location));
+    }
+}
+
+/// Whether `RUST_BACKTRACE` asks for backtraces
+fn backtrace_requested() -> bool {
+    return std::env::var_os("RUST_BACKTRACE").is_some_and(|value| !value.is_empty() && value != "0");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_test_hook_sees_unexpected_panics() {
+        install();
+        let messages = Rc::new(RefCell::new(Vec::new()));
+        let recorded = Rc::clone(&messages);
+        set_test_panic_hook(move |info| {
+            recorded.borrow_mut().push(info.payload().downcast_ref::<&str>().map(|message| message.to_string()));
+        });
+
+        let result = panic::catch_unwind(|| panic!("unexpected"));
+        end_test();
+        let _ = panic::catch_unwind(|| panic!("after the test"));
+
+        assert!(result.is_err());
+        assert_eq!(*messages.borrow(), vec![Some("unexpected".to_string())]);
+    }
+
+    #[test]
+    fn test_rendered_failures_skip_the_test_hook() {
+        install();
+        let calls = Rc::new(Cell::new(0));
+        let counted = Rc::clone(&calls);
+        set_test_panic_hook(move |_| counted.set(counted.get() + 1));
+
+        // A failure that wasn't rendered panics like any other
+        begin_failure_report();
+        expect_assertion_panic(Location::caller());
+        let _ = panic::catch_unwind(|| panic!("not rendered"));
+
+        failure_rendered();
+        expect_assertion_panic(Location::caller());
+        let _ = panic::catch_unwind(|| panic!("rendered"));
+        end_test();
+
+        assert_eq!(calls.get(), if backtrace_requested() { 2 } else { 1 });
+    }
+}
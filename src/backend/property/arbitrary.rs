@@ -0,0 +1,361 @@
+use super::generator::Gen;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Maximum number of elements removed one by one when shrinking a collection
+const SHRINK_REMOVALS: usize = 32;
+
+/// Types that can be generated at random for property tests
+///
+/// Implemented for primitives, strings, common collections, `Option`, tuples and arrays.
+/// Derive it for your own structs and enums with `#[derive(RestArbitrary)]`.
+pub trait Arbitrary: Clone {
+    /// Generate a random value
+    fn arbitrary(g: &mut Gen) -> Self;
+
+    /// Simpler candidates for a failing value, simplest first
+    ///
+    /// Used to shrink a counterexample before reporting it. Defaults to no candidates.
+    fn shrink(&self) -> Vec<Self> {
+        return Vec::new();
+    }
+}
+
+/// Shrink candidates for an integer: zero, half way to zero, one step towards zero
+macro_rules! shrink_integer {
+    ($value:expr) => {{
+        let value = $value;
+        let mut candidates = Vec::new();
+        if value != 0 {
+            let step = if value > 0 { value - 1 } else { value + 1 };
+            for candidate in [0, value / 2, step] {
+                if candidate != value && !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+        candidates
+    }};
+}
+
+/// Collections that can be generated with a given length
+///
+/// Used by `#[arbitrary(len = ...)]` on derived fields.
+pub trait ArbitraryLen: Sized {
+    /// Generate a random collection holding exactly `len` elements
+    fn arbitrary_with_len(g: &mut Gen, len: usize) -> Self;
+}
+
+macro_rules! impl_arbitrary_for_integers {
+    ($($t:ty => $small:expr),*) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    // Edge values break far more properties than uniform noise, so favor them
+                    if g.one_in(8) {
+                        const EDGES: [$t; 4] = [0, 1, <$t>::MIN, <$t>::MAX];
+                        return EDGES[g.gen_index(EDGES.len())];
+                    }
+
+                    // Mostly small magnitudes, sometimes the full domain
+                    if g.gen_bool() {
+                        return g.gen_range($small);
+                    }
+
+                    return g.next_u64() as $t;
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    return shrink_integer!(*self);
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_integers!(
+    i8 => -100..=100,
+    i16 => -100..=100,
+    i32 => -100..=100,
+    i64 => -100..=100,
+    isize => -100..=100,
+    u8 => 0..=100,
+    u16 => 0..=100,
+    u32 => 0..=100,
+    u64 => 0..=100,
+    usize => 0..=100
+);
+
+impl Arbitrary for i128 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        return (u128::arbitrary(g)) as i128;
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return shrink_integer!(*self);
+    }
+}
+
+impl Arbitrary for u128 {
+    fn arbitrary(g: &mut Gen) -> Self {
+        return ((g.next_u64() as u128) << 64) | g.next_u64() as u128;
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return shrink_integer!(*self);
+    }
+}
+
+macro_rules! impl_arbitrary_for_floats {
+    ($($t:ty),*) => {
+        $(
+            impl Arbitrary for $t {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    if g.one_in(8) {
+                        const EDGES: [$t; 5] = [0.0, 1.0, -1.0, <$t>::MIN_POSITIVE, <$t>::EPSILON];
+                        return EDGES[g.gen_index(EDGES.len())];
+                    }
+
+                    // Finite values only; NaN and infinities must be asked for explicitly
+                    let magnitude = 10f64.powi(g.gen_range(0..=6)) as $t;
+                    return g.gen_range(-magnitude..=magnitude);
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    let mut candidates: Vec<$t> = Vec::new();
+                    for candidate in [0.0, self.trunc(), self / 2.0] {
+                        if candidate != *self && candidate.is_finite() && !candidates.contains(&candidate) {
+                            candidates.push(candidate);
+                        }
+                    }
+                    return candidates;
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_floats!(f32, f64);
+
+impl Arbitrary for bool {
+    fn arbitrary(g: &mut Gen) -> Self {
+        return g.gen_bool();
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return if *self { vec![false] } else { Vec::new() };
+    }
+}
+
+impl Arbitrary for char {
+    fn arbitrary(g: &mut Gen) -> Self {
+        // Mostly printable ASCII, sometimes any Unicode scalar value
+        if g.one_in(4) {
+            loop {
+                if let Some(c) = char::from_u32(g.gen_range(0..=0x10ffffu32)) {
+                    return c;
+                }
+            }
+        }
+
+        return g.gen_range(0x20u8..=0x7e) as char;
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return if *self != 'a' { vec!['a'] } else { Vec::new() };
+    }
+}
+
+impl Arbitrary for () {
+    fn arbitrary(_g: &mut Gen) -> Self {}
+}
+
+impl Arbitrary for String {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = g.gen_range(0..=g.size());
+        return Self::arbitrary_with_len(g, len);
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return self.chars().collect::<Vec<_>>().shrink().into_iter().map(|chars| chars.into_iter().collect()).collect();
+    }
+}
+
+impl ArbitraryLen for String {
+    fn arbitrary_with_len(g: &mut Gen, len: usize) -> Self {
+        return (0..len).map(|_| char::arbitrary(g)).collect();
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Option<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        if g.one_in(4) {
+            return None;
+        }
+        return Some(T::arbitrary(g));
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return match self {
+            Some(value) => std::iter::once(None).chain(value.shrink().into_iter().map(Some)).collect(),
+            None => Vec::new(),
+        };
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Box<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        return Box::new(T::arbitrary(g));
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return (**self).shrink().into_iter().map(Box::new).collect();
+    }
+}
+
+impl<T: Arbitrary, const N: usize> Arbitrary for [T; N] {
+    fn arbitrary(g: &mut Gen) -> Self {
+        return std::array::from_fn(|_| T::arbitrary(g));
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        return shrink_elements(self).into_iter().map(|elements| elements.try_into().ok().expect("same length")).collect();
+    }
+}
+
+impl<T: Arbitrary> Arbitrary for Vec<T> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = g.gen_range(0..=g.size());
+        return Self::arbitrary_with_len(g, len);
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates = Vec::new();
+
+        // Drop whole chunks first, then single elements, then simplify elements in place
+        if !self.is_empty() {
+            candidates.push(Vec::new());
+        }
+        if self.len() > 1 {
+            let half = self.len() / 2;
+            candidates.push(self[..half].to_vec());
+            candidates.push(self[half..].to_vec());
+        }
+        if self.len() > 1 {
+            for index in 0..self.len().min(SHRINK_REMOVALS) {
+                let mut removed = self.clone();
+                removed.remove(index);
+                candidates.push(removed);
+            }
+        }
+        candidates.extend(shrink_elements(self));
+
+        return candidates;
+    }
+}
+
+impl<T: Arbitrary> ArbitraryLen for Vec<T> {
+    fn arbitrary_with_len(g: &mut Gen, len: usize) -> Self {
+        return (0..len).map(|_| T::arbitrary(g)).collect();
+    }
+}
+
+/// Candidates replacing a single element with one of its own shrink candidates
+fn shrink_elements<T: Arbitrary>(elements: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    for (index, element) in elements.iter().enumerate() {
+        for simpler in element.shrink() {
+            let mut candidate = elements.to_vec();
+            candidate[index] = simpler;
+            candidates.push(candidate);
+        }
+    }
+
+    return candidates;
+}
+
+// Other sequences are generated by length and shrunk through a `Vec` of their elements
+macro_rules! impl_arbitrary_for_sequences {
+    ($($collection:ident<$t:ident> => [$($bound:path),*]),*) => {
+        $(
+            impl<$t: Arbitrary $(+ $bound)*> Arbitrary for $collection<$t> {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    let len = g.gen_range(0..=g.size());
+                    return Self::arbitrary_with_len(g, len);
+                }
+
+                fn shrink(&self) -> Vec<Self> {
+                    let elements = self.iter().cloned().collect::<Vec<_>>();
+                    return elements.shrink().into_iter().map(|candidate| candidate.into_iter().collect()).collect();
+                }
+            }
+
+            impl<$t: Arbitrary $(+ $bound)*> ArbitraryLen for $collection<$t> {
+                fn arbitrary_with_len(g: &mut Gen, len: usize) -> Self {
+                    return (0..len).map(|_| $t::arbitrary(g)).collect();
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_sequences!(VecDeque<T> => [], HashSet<T> => [Eq, Hash], BTreeSet<T> => [Ord]);
+
+impl<K: Arbitrary + Eq + Hash, V: Arbitrary> Arbitrary for HashMap<K, V> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = g.gen_range(0..=g.size());
+        return (0..len).map(|_| (K::arbitrary(g), V::arbitrary(g))).collect();
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let entries = self.iter().map(|(key, value)| (key.clone(), value.clone())).collect::<Vec<_>>();
+        return entries.shrink().into_iter().map(|candidate| candidate.into_iter().collect()).collect();
+    }
+}
+
+impl<K: Arbitrary + Ord, V: Arbitrary> Arbitrary for BTreeMap<K, V> {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let len = g.gen_range(0..=g.size());
+        return (0..len).map(|_| (K::arbitrary(g), V::arbitrary(g))).collect();
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let entries = self.iter().map(|(key, value)| (key.clone(), value.clone())).collect::<Vec<_>>();
+        return entries.shrink().into_iter().map(|candidate| candidate.into_iter().collect()).collect();
+    }
+}
+
+macro_rules! impl_arbitrary_for_tuples {
+    ($(($($name:ident . $index:tt),+)),*) => {
+        $(
+            impl<$($name: Arbitrary),+> Arbitrary for ($($name,)+) {
+                fn arbitrary(g: &mut Gen) -> Self {
+                    return ($($name::arbitrary(g),)+);
+                }
+
+                // Shrink one component at a time, keeping the others
+                fn shrink(&self) -> Vec<Self> {
+                    let mut candidates = Vec::new();
+                    $(
+                        for simpler in self.$index.shrink() {
+                            let mut candidate = self.clone();
+                            candidate.$index = simpler;
+                            candidates.push(candidate);
+                        }
+                    )+
+                    return candidates;
+                }
+            }
+        )*
+    };
+}
+
+impl_arbitrary_for_tuples!(
+    (A.0),
+    (A.0, B.1),
+    (A.0, B.1, C.2),
+    (A.0, B.1, C.2, D.3),
+    (A.0, B.1, C.2, D.3, E.4),
+    (A.0, B.1, C.2, D.3, E.4, F.5)
+);
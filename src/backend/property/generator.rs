@@ -0,0 +1,132 @@
+use std::ops::{Range, RangeInclusive};
+
+/// Default upper bound for generated collection lengths
+pub const DEFAULT_SIZE: usize = 16;
+
+/// Seeded pseudo-random source used to generate property test inputs
+///
+/// Uses SplitMix64, so a seed always reproduces the same sequence of values on every platform.
+#[derive(Debug, Clone)]
+pub struct Gen {
+    state: u64,
+    size: usize,
+}
+
+impl Gen {
+    /// Create a generator from a seed
+    pub fn new(seed: u64) -> Self {
+        return Self { state: seed, size: DEFAULT_SIZE };
+    }
+
+    /// Set the upper bound used for unconstrained collection lengths
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        return self;
+    }
+
+    /// Upper bound used for unconstrained collection lengths
+    pub fn size(&self) -> usize {
+        return self.size;
+    }
+
+    /// Next raw 64-bit value
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        return mix(self.state);
+    }
+
+    /// Uniform float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+
+    /// Fair coin flip
+    pub fn gen_bool(&mut self) -> bool {
+        return self.next_u64() & 1 == 1;
+    }
+
+    /// True with probability `1 / denominator`
+    pub fn one_in(&mut self, denominator: u64) -> bool {
+        return self.next_u64().is_multiple_of(denominator.max(1));
+    }
+
+    /// Uniform index in `0..len`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0.
+    pub fn gen_index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot choose an index in an empty range");
+        return (self.next_u64() % len as u64) as usize;
+    }
+
+    /// Uniform value in a range, e.g. `g.gen_range(1..=6)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn gen_range<T, R: SampleRange<T>>(&mut self, range: R) -> T {
+        return range.sample(self);
+    }
+}
+
+/// SplitMix64 output function
+pub(crate) fn mix(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    return z ^ (z >> 31);
+}
+
+/// Ranges a [`Gen`] can sample values of type `T` from
+pub trait SampleRange<T> {
+    fn sample(self, g: &mut Gen) -> T;
+}
+
+macro_rules! impl_sample_range_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl SampleRange<$t> for RangeInclusive<$t> {
+                fn sample(self, g: &mut Gen) -> $t {
+                    let (low, high) = (*self.start() as i128, *self.end() as i128);
+                    assert!(low <= high, "cannot sample empty range {:?}", self);
+                    let span = (high - low) as u128 + 1;
+                    return (low + (g.next_u64() as u128 % span) as i128) as $t;
+                }
+            }
+
+            impl SampleRange<$t> for Range<$t> {
+                fn sample(self, g: &mut Gen) -> $t {
+                    assert!(self.start < self.end, "cannot sample empty range {:?}", self);
+                    return (self.start..=self.end - 1).sample(g);
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_range_for_integers!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_sample_range_for_floats {
+    ($($t:ty),*) => {
+        $(
+            impl SampleRange<$t> for Range<$t> {
+                fn sample(self, g: &mut Gen) -> $t {
+                    assert!(self.start < self.end, "cannot sample empty range {:?}", self);
+                    let value = self.start + (self.end - self.start) * g.next_f64() as $t;
+                    // Rounding can land exactly on the excluded end
+                    return if value < self.end { value } else { self.start };
+                }
+            }
+
+            impl SampleRange<$t> for RangeInclusive<$t> {
+                fn sample(self, g: &mut Gen) -> $t {
+                    let (low, high) = (*self.start(), *self.end());
+                    assert!(low <= high, "cannot sample empty range {:?}", self);
+                    return (low + (high - low) * g.next_f64() as $t).min(high);
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_range_for_floats!(f32, f64);
@@ -0,0 +1,394 @@
+//! Property-based testing support
+//!
+//! A property is a closure run against many randomly generated inputs; any panic inside it,
+//! including a failed `expect!`, fails the property. The failing input is then shrunk to a simpler
+//! counterexample, reported like any other assertion along with the seed that produced it:
+//!
+//! ```
+//! use rest::prelude::*;
+//!
+//! for_all(|(a, b): (i32, i32)| {
+//!     expect!(a.wrapping_add(b)).to_equal(b.wrapping_add(a));
+//! });
+//! ```
+//!
+//! Inputs implement [`Arbitrary`], which can be derived for user types with `#[derive(RestArbitrary)]`.
+//! The number of cases and the seed can be overridden with the `REST_PROPERTY_CASES` and
+//! `REST_PROPERTY_SEED` environment variables, so a reported seed can be replayed without code changes.
+
+mod arbitrary;
+mod generator;
+
+pub use arbitrary::{Arbitrary, ArbitraryLen};
+pub use generator::{DEFAULT_SIZE, Gen, SampleRange};
+
+use crate::backend::assertions::{capture, report_and_panic};
+use crate::backend::{Assertion, AssertionSentence};
+use std::any::Any;
+use std::env;
+use std::fmt::Debug;
+use std::hash::BuildHasher;
+
+// Environment variables overriding the property runner settings
+const ENV_PROPERTY_CASES: &str = "REST_PROPERTY_CASES";
+pub(crate) const ENV_PROPERTY_SEED: &str = "REST_PROPERTY_SEED";
+
+/// Number of generated inputs checked by default
+pub const DEFAULT_CASES: usize = 100;
+
+/// Maximum number of candidates tried while shrinking a failing input
+const MAX_SHRINK_ATTEMPTS: usize = 1000;
+
+/// Runner checking a property against generated inputs
+#[derive(Debug, Clone)]
+pub struct Property {
+    cases: usize,
+    seed: u64,
+    size: usize,
+}
+
+impl Default for Property {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Property {
+    /// Creates a runner with the default settings, honoring the property environment variables
+    pub fn new() -> Self {
+        return Self::from_env(|key| env::var(key).ok());
+    }
+
+    /// Creates a runner by reading env vars through the provided closure
+    fn from_env(get_var: impl Fn(&str) -> Option<String>) -> Self {
+        let cases = get_var(ENV_PROPERTY_CASES).and_then(|value| value.trim().parse().ok()).unwrap_or(DEFAULT_CASES);
+        let seed = get_var(ENV_PROPERTY_SEED).and_then(|value| parse_seed(&value)).unwrap_or_else(random_seed);
+
+        return Self { cases, seed, size: DEFAULT_SIZE };
+    }
+
+    /// Set the number of generated inputs to check
+    pub fn cases(mut self, cases: usize) -> Self {
+        self.cases = cases;
+        return self;
+    }
+
+    /// Set the seed of the first case, e.g. to replay a reported failure
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        return self;
+    }
+
+    /// Set the upper bound for unconstrained collection lengths
+    pub fn size(mut self, size: usize) -> Self {
+        self.size = size;
+        return self;
+    }
+
+    /// Generated inputs of every case, with the seed reproducing each of them
+    pub(crate) fn inputs<T: Arbitrary>(&self) -> impl Iterator<Item = (u64, T)> + '_ {
+        return (0..self.cases).map(move |case| {
+            let seed = case_seed(self.seed, case);
+            return (seed, T::arbitrary(&mut Gen::new(seed).with_size(self.size)));
+        });
+    }
+
+    /// Check the property against generated inputs, panicking on the first failing one
+    ///
+    /// The failing input is shrunk before being reported, and the report carries the seed that
+    /// reproduces it.
+    pub fn check<T, F>(self, property: F)
+    where
+        T: Arbitrary + Debug,
+        F: Fn(T),
+    {
+        for (case, (seed, input)) in self.inputs::<T>().enumerate() {
+            if let Some(failure) = run_case(&property, input.clone()) {
+                let original = format!("{:?}", input);
+                let (shrunk, shrinks, failure) = shrink(&property, input, failure);
+
+                let mut details = vec![format!("failed after {} passed case(s)", case)];
+                if shrinks > 0 {
+                    details.push(format!("shrunk {} time(s) from {}", shrinks, original));
+                }
+                details.push(format!("reproduce with {}={}", ENV_PROPERTY_SEED, seed));
+
+                report_failure(failure, format!("for input {:?}", shrunk), details);
+            }
+        }
+    }
+}
+
+/// Why a property failed for a given input
+enum Failure {
+    /// A failed assertion inside the property
+    Assertion(Assertion<()>),
+    /// Any other panic, with its message
+    Panic(String),
+}
+
+/// Run the property against one input with assertion failures captured
+fn run_case<T, F: Fn(T)>(property: &F, input: T) -> Option<Failure> {
+    let (result, failure) = capture::capture_failure(|| property(input));
+
+    return match (result, failure) {
+        (Ok(()), _) => None,
+        (Err(_), Some(assertion)) => Some(Failure::Assertion(assertion)),
+        (Err(err), None) => Some(Failure::Panic(panic_message(&err))),
+    };
+}
+
+/// Greedily replace the failing input by its first still failing shrink candidate
+///
+/// Returns the simplest failing input found, the number of successful shrinks and its failure.
+fn shrink<T: Arbitrary, F: Fn(T)>(property: &F, mut input: T, mut failure: Failure) -> (T, usize, Failure) {
+    let mut shrinks = 0;
+    let mut attempts = 0;
+
+    'shrinking: while attempts < MAX_SHRINK_ATTEMPTS {
+        for candidate in input.shrink() {
+            if attempts == MAX_SHRINK_ATTEMPTS {
+                break 'shrinking;
+            }
+            attempts += 1;
+
+            if let Some(candidate_failure) = run_case(property, candidate.clone()) {
+                input = candidate;
+                failure = candidate_failure;
+                shrinks += 1;
+                continue 'shrinking;
+            }
+        }
+
+        // No candidate fails anymore, the input is minimal
+        break;
+    }
+
+    return (input, shrinks, failure);
+}
+
+/// Report the final failure as an assertion, rendered with its input and detail lines
+fn report_failure(failure: Failure, context: String, details: Vec<String>) -> ! {
+    let mut assertion = match failure {
+        Failure::Assertion(assertion) => assertion,
+        Failure::Panic(message) => {
+            let sentence = AssertionSentence::new("complete", "without panicking").with_actual(format!("{:?}", message));
+            Assertion::new((), "property").add_step(sentence, false)
+        }
+    };
+
+    if let Some(step) = assertion.steps.iter_mut().find(|step| !step.passed) {
+        step.sentence.details.extend(details);
+    }
+    assertion.context = Some(context);
+    assertion.is_final = true;
+    assertion.evaluated = false;
+
+    let message = format!("property failed {}", assertion.context.as_deref().unwrap_or_default());
+    report_and_panic(assertion, message);
+}
+
+/// Check a property against generated inputs with the default runner settings
+pub fn for_all<T, F>(property: F)
+where
+    T: Arbitrary + Debug,
+    F: Fn(T),
+{
+    Property::new().check(property);
+}
+
+/// Seed of a given case; the first case uses the base seed so a reported seed replays directly
+fn case_seed(base: u64, case: usize) -> u64 {
+    if case == 0 {
+        return base;
+    }
+    return generator::mix(base.wrapping_add(case as u64));
+}
+
+/// Parse a decimal or `0x`-prefixed hexadecimal seed
+pub(crate) fn parse_seed(value: &str) -> Option<u64> {
+    let value = value.trim();
+    return match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    };
+}
+
+/// Fresh seed for runs that don't ask for a specific one
+pub(crate) fn random_seed() -> u64 {
+    let nanos = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+    return std::collections::hash_map::RandomState::new().hash_one(nanos);
+}
+
+/// Extract the message of a caught panic
+fn panic_message(err: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = err.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = err.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return "non-string panic payload".to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_gen_is_deterministic() {
+        let first = (0..8).map({
+            let mut g = Gen::new(42);
+            move |_| g.next_u64()
+        });
+        let second = (0..8).map({
+            let mut g = Gen::new(42);
+            move |_| g.next_u64()
+        });
+
+        assert!(first.eq(second));
+        assert_ne!(Gen::new(1).next_u64(), Gen::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_gen_range_stays_in_bounds() {
+        let mut g = Gen::new(7);
+
+        for _ in 0..1000 {
+            let small: u8 = g.gen_range(3..7);
+            let signed: i64 = g.gen_range(-5..=5);
+            let float: f64 = g.gen_range(0.5..1.5);
+
+            assert!((3..7).contains(&small));
+            assert!((-5..=5).contains(&signed));
+            assert!((0.5..1.5).contains(&float));
+        }
+
+        assert_eq!(g.gen_range(i8::MIN..=i8::MIN), i8::MIN);
+        let _full: u64 = g.gen_range(0..=u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot sample empty range")]
+    fn test_gen_range_rejects_empty_ranges() {
+        let _value: i32 = Gen::new(0).gen_range(5..5);
+    }
+
+    #[test]
+    fn test_arbitrary_collections_respect_lengths() {
+        let mut g = Gen::new(3).with_size(4);
+
+        for _ in 0..100 {
+            assert!(Vec::<u8>::arbitrary(&mut g).len() <= 4);
+            assert!(String::arbitrary(&mut g).chars().count() <= 4);
+        }
+
+        assert_eq!(Vec::<i32>::arbitrary_with_len(&mut g, 9).len(), 9);
+        assert_eq!(String::arbitrary_with_len(&mut g, 5).chars().count(), 5);
+    }
+
+    #[test]
+    fn test_property_from_env() {
+        let property = Property::from_env(|key| match key {
+            ENV_PROPERTY_CASES => Some("12".into()),
+            ENV_PROPERTY_SEED => Some("0x2a".into()),
+            _ => None,
+        });
+
+        assert_eq!(property.cases, 12);
+        assert_eq!(property.seed, 42);
+        assert_eq!(Property::from_env(|_| None).cases, DEFAULT_CASES);
+        assert_eq!(parse_seed(" 99 "), Some(99));
+        assert_eq!(parse_seed("nope"), None);
+    }
+
+    #[test]
+    fn test_check_runs_every_case() {
+        let seen = RefCell::new(Vec::new());
+
+        Property::new().cases(25).seed(5).check(|value: u16| seen.borrow_mut().push(value));
+
+        assert_eq!(seen.borrow().len(), 25);
+    }
+
+    #[test]
+    fn test_seed_replays_the_same_inputs() {
+        let first = RefCell::new(Vec::new());
+        let second = RefCell::new(Vec::new());
+
+        Property::new().cases(10).seed(1234).check(|value: (i32, String)| first.borrow_mut().push(value));
+        Property::new().cases(10).seed(1234).check(|value: (i32, String)| second.borrow_mut().push(value));
+
+        assert_eq!(first.into_inner(), second.into_inner());
+    }
+
+    #[test]
+    fn test_integers_shrink_towards_zero() {
+        expect!(250u8.shrink()).to_equal(vec![0, 125, 249]);
+        expect!((-7i32).shrink()).to_equal(vec![0, -3, -6]);
+        expect!(0u64.shrink()).to_be_empty();
+        expect!(Some(true).shrink()).to_equal(vec![None, Some(false)]);
+    }
+
+    #[test]
+    fn test_shrink_finds_the_minimal_failing_input() {
+        let property = |value: u8| assert!(value < 200);
+        let failure = run_case(&property, 250).expect("250 should fail");
+
+        let (shrunk, shrinks, _failure) = shrink(&property, 250, failure);
+
+        assert_eq!(shrunk, 200);
+        assert!(shrinks > 0);
+    }
+
+    #[test]
+    fn test_shrink_simplifies_collections() {
+        let property = |values: Vec<u8>| assert!(values.iter().all(|value| *value < 10));
+        let failure = run_case(&property, vec![3, 42, 7, 99]).expect("input should fail");
+
+        let (shrunk, _shrinks, _failure) = shrink(&property, vec![3, 42, 7, 99], failure);
+
+        assert_eq!(shrunk, vec![10]);
+    }
+
+    #[test]
+    fn test_captured_assertions_are_kept_as_failures() {
+        let property = |value: i32| {
+            expect!(value).to_be_greater_than(0);
+        };
+
+        assert!(run_case(&property, 5).is_none());
+        match run_case(&property, -5) {
+            Some(Failure::Assertion(assertion)) => {
+                assert_eq!(assertion.steps[0].sentence.format_with_actual(), "be greater than 0 (got -5)")
+            }
+            _ => panic!("expected a captured assertion"),
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "for input 200, complete without panicking")]
+    fn test_failing_property_reports_the_shrunk_input() {
+        Property::new().cases(200).seed(9).check(|value: u8| {
+            assert!(value < 200, "value too large");
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "for input 0, be positive (got 0)")]
+    fn test_failing_assertion_is_rendered_with_its_input() {
+        Property::new().seed(3).check(|value: i32| {
+            expect!(value).to_be_positive();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "reproduce with REST_PROPERTY_SEED=")]
+    fn test_failing_property_reports_input_and_seed() {
+        Property::new().cases(200).seed(9).check(|value: u8| {
+            assert!(value < 200, "value too large");
+        });
+    }
+}
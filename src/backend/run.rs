@@ -0,0 +1,226 @@
+//! Identity of the current test run
+//!
+//! Every test binary has a [`RunInfo`]: a random run ID, the start timestamp and the git commit under test when it
+//! can be found. The console summary and the discovery JSON embed it, so results written by several binaries,
+//! machines or formats can be correlated. Only the start time is recorded when the binary loads: the rest is
+//! generated on first use, and `git` only runs when a report needs the commit and no variable provides it.
+//!
+//! CI pipelines running the suite in shards can share one ID between all of them with `REST_RUN_ID`, and
+//! provide the commit with `REST_GIT_SHA` when the checkout has no `.git` directory. The run also draws the seed
+//! of its randomized checks, such as shuffled matchers, which `REST_SEED` replays.
+
+use crate::backend::discovery::json_string;
+use crate::backend::property::{parse_seed, random_seed};
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::process::Command;
+use std::sync::{LazyLock, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Environment variable overriding the generated run ID
+const ENV_RUN_ID: &str = "REST_RUN_ID";
+
+// Environment variable replaying the seed of a previous run
+pub(crate) const ENV_SEED: &str = "REST_SEED";
+
+// Environment variables holding the commit under test, by priority
+const GIT_SHA_VARS: [&str; 4] = ["REST_GIT_SHA", "GITHUB_SHA", "CI_COMMIT_SHA", "GIT_COMMIT"];
+
+// Number of characters of the commit shown in the console
+const SHORT_SHA_LENGTH: usize = 12;
+
+static STARTED_AT: OnceLock<SystemTime> = OnceLock::new();
+
+static CURRENT_RUN: LazyLock<RunInfo> = LazyLock::new(|| RunInfo::from_env(|key| std::env::var(key).ok()));
+
+/// Identity of a test run, shared by every report of a test binary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunInfo {
+    /// Random UUID (version 4) of the run, or the value of `REST_RUN_ID`
+    pub id: String,
+    /// When the test binary started
+    pub started_at: SystemTime,
+    /// Seed of the randomized checks of the run, random or the value of `REST_SEED`
+    pub seed: u64,
+    // Commit under test, looked up in the repository on first use when no variable provides it
+    git_sha: OnceLock<Option<String>>,
+}
+
+impl RunInfo {
+    /// Create the run information from the environment, generating a new ID unless one is provided
+    fn from_env(get_var: impl Fn(&str) -> Option<String>) -> Self {
+        let id = get_var(ENV_RUN_ID).map(|id| id.trim().to_string()).filter(|id| !id.is_empty()).unwrap_or_else(random_uuid);
+        let git_sha = GIT_SHA_VARS.iter().find_map(|key| get_var(key).map(|sha| sha.trim().to_string()).filter(|sha| !sha.is_empty()));
+        let seed = get_var(ENV_SEED).and_then(|value| parse_seed(&value)).unwrap_or_else(random_seed);
+
+        return Self { id, started_at: started_at(), seed, git_sha: git_sha.map(|sha| OnceLock::from(Some(sha))).unwrap_or_default() };
+    }
+
+    /// Commit under test, when known
+    pub fn git_sha(&self) -> Option<&str> {
+        return self.git_sha.get_or_init(git_head).as_deref();
+    }
+
+    /// Start timestamp in RFC 3339 format, in UTC with second precision (`2026-10-16T09:41:07Z`)
+    pub fn timestamp(&self) -> String {
+        let seconds = self.started_at.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default();
+        let (year, month, day) = civil_from_days((seconds / 86_400) as i64);
+        let time_of_day = seconds % 86_400;
+
+        return format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            time_of_day / 3600,
+            time_of_day % 3600 / 60,
+            time_of_day % 60
+        );
+    }
+
+    /// One-line description for the console, such as `run 4f0c…, started 2026-10-16T09:41:07Z, git 1a2b3c4d5e6f`
+    pub fn summary(&self) -> String {
+        let mut summary = format!("run {}, started {}", self.id, self.timestamp());
+        if let Some(sha) = self.git_sha() {
+            summary.push_str(&format!(", git {}", sha.chars().take(SHORT_SHA_LENGTH).collect::<String>()));
+        }
+
+        return summary;
+    }
+
+    /// Render the run information as a JSON object
+    pub fn to_json(&self) -> String {
+        let git_sha = self.git_sha().map(json_string).unwrap_or_else(|| "null".to_string());
+
+        return format!("{{\"id\":{},\"started_at\":{},\"git_sha\":{}}}", json_string(&self.id), json_string(&self.timestamp()), git_sha);
+    }
+}
+
+/// Information about the current test run, generated on first use
+pub fn current_run() -> &'static RunInfo {
+    return &CURRENT_RUN;
+}
+
+/// When the test binary started
+fn started_at() -> SystemTime {
+    return *STARTED_AT.get_or_init(SystemTime::now);
+}
+
+/// Record the start time as soon as the test binary loads, without generating anything else before `main`
+#[ctor::ctor]
+fn record_start() {
+    started_at();
+}
+
+/// Generate a random UUID (version 4) from the randomly seeded std hasher
+fn random_uuid() -> String {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos()).unwrap_or_default();
+    let high = RandomState::new().hash_one((nanos, std::process::id()));
+    let low = RandomState::new().hash_one((high, nanos));
+
+    // Set the version (4) and variant (RFC 4122) bits
+    let bits = ((high as u128) << 64 | low as u128) & !(0xF000u128 << 64) & !(0xC000u128 << 48) | (0x4000u128 << 64) | (0x8000u128 << 48);
+    let hex = format!("{:032x}", bits);
+
+    return format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32]);
+}
+
+/// Commit checked out in the current directory, if it is a git repository
+fn git_head() -> Option<String> {
+    let output = Command::new("git").args(["rev-parse", "HEAD"]).output().ok().filter(|output| output.status.success())?;
+    let sha = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    return if sha.is_empty() { None } else { Some(sha) };
+}
+
+/// Convert a number of days since 1970-01-01 to a (year, month, day) date of the proleptic Gregorian calendar
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // Shift the epoch to 0000-03-01 so that leap days end the 400-year eras
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    return (year, month, day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_random_uuid_format() {
+        let uuid = random_uuid();
+
+        assert_eq!(uuid.len(), 36);
+        assert_eq!(uuid.chars().nth(14), Some('4'));
+        assert!(matches!(uuid.chars().nth(19), Some('8' | '9' | 'a' | 'b')));
+        assert_ne!(uuid, random_uuid());
+    }
+
+    #[test]
+    fn test_run_info_from_env() {
+        let run = RunInfo::from_env(|key| match key {
+            "REST_RUN_ID" => Some("nightly-42".to_string()),
+            "GITHUB_SHA" => Some("1a2b3c4d5e6f7a8b9c0d".to_string()),
+            _ => None,
+        });
+
+        assert_eq!(run.id, "nightly-42");
+        assert_eq!(run.git_sha(), Some("1a2b3c4d5e6f7a8b9c0d"));
+
+        // The rest-specific variable wins over the CI ones
+        let run = RunInfo::from_env(|key| match key {
+            "REST_GIT_SHA" => Some("abc".to_string()),
+            "GITHUB_SHA" => Some("def".to_string()),
+            _ => None,
+        });
+        assert_eq!(run.git_sha(), Some("abc"));
+        assert_eq!(run.id.len(), 36);
+
+        let run = RunInfo::from_env(|key| (key == ENV_SEED).then(|| "0x2a".to_string()));
+        assert_eq!(run.seed, 42);
+        // Without a variable, the repository is only looked up when the commit is needed
+        assert_eq!(run.git_sha.get(), None);
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let run = |seconds| RunInfo {
+            id: "id".to_string(),
+            started_at: UNIX_EPOCH + Duration::from_secs(seconds),
+            seed: 0,
+            git_sha: OnceLock::from(None),
+        };
+
+        assert_eq!(run(0).timestamp(), "1970-01-01T00:00:00Z");
+        assert_eq!(run(951_782_400).timestamp(), "2000-02-29T00:00:00Z");
+        assert_eq!(run(1_792_143_667).timestamp(), "2026-10-16T09:41:07Z");
+    }
+
+    #[test]
+    fn test_summary_and_json() {
+        let run = RunInfo {
+            id: "4f0c7d1e-8b2a-4c3d-9e5f-6a7b8c9d0e1f".to_string(),
+            started_at: UNIX_EPOCH + Duration::from_secs(1_792_143_667),
+            seed: 7,
+            git_sha: OnceLock::from(Some("1a2b3c4d5e6f7a8b9c0d".to_string())),
+        };
+
+        assert_eq!(run.summary(), "run 4f0c7d1e-8b2a-4c3d-9e5f-6a7b8c9d0e1f, started 2026-10-16T09:41:07Z, git 1a2b3c4d5e6f");
+        assert_eq!(
+            run.to_json(),
+            "{\"id\":\"4f0c7d1e-8b2a-4c3d-9e5f-6a7b8c9d0e1f\",\"started_at\":\"2026-10-16T09:41:07Z\",\"git_sha\":\"1a2b3c4d5e6f7a8b9c0d\"}"
+        );
+
+        let without_sha = RunInfo { git_sha: OnceLock::from(None), ..run };
+        assert_eq!(without_sha.summary(), "run 4f0c7d1e-8b2a-4c3d-9e5f-6a7b8c9d0e1f, started 2026-10-16T09:41:07Z");
+        assert!(without_sha.to_json().ends_with("\"git_sha\":null}"));
+    }
+}
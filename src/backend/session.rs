@@ -0,0 +1,232 @@
+//! Result aggregation for tools embedding Rest outside of the libtest harness
+//!
+//! A [`Session`] collects the results of assertions handed to it into a [`TestSessionResult`], the same result the
+//! console summary renders, without panicking, emitting events or touching the per-thread session of the reporter.
+//! Fuzzing harnesses, REPL-driven runners or notebooks can then use the matchers and the rendering of Rest with their
+//! own process model:
+//!
+//! ```
+//! use rest::prelude::*;
+//! use rest::Session;
+//! use rest::frontend::ConsoleRenderer;
+//!
+//! let mut session = Session::new();
+//!
+//! session.begin("parser::test_numbers");
+//! session.record(expect!("42".parse::<i32>()).to_be_ok());
+//! session.record(expect!("4x2".parse::<i32>()).to_be_ok());
+//! session.end();
+//!
+//! let result = session.finalize();
+//! assert_eq!(result.failed_count, 1);
+//! print!("{}", ConsoleRenderer::plain().render_session_summary(&result));
+//! ```
+//!
+//! Recorded assertions are values owned by the session, so it can be moved between threads or kept across evaluations.
+//! [`Session::run`] also takes code written with plain `expect!` calls, as in tests, capturing its first failure.
+
+use crate::backend::assertions::capture::capture_failure;
+use crate::backend::assertions::sentence::AssertionSentence;
+use crate::backend::fixtures::TestOutput;
+use crate::backend::matchers::failure::panic_message;
+use crate::backend::{Assertion, Severity, TestSessionResult};
+
+/// Test of a session between [`Session::begin`] and [`Session::end`]
+#[derive(Debug)]
+struct OpenTest {
+    name: String,
+    failed: bool,
+}
+
+/// Results of the assertions of an embedding tool, grouped by test
+///
+/// Tests are delimited with [`Session::begin`] and [`Session::end`], and fail when any of their assertions does. An
+/// assertion recorded outside of a test counts as a test of its own, as an expression evaluated in a REPL would.
+#[derive(Debug, Default)]
+pub struct Session {
+    result: TestSessionResult,
+    current: Option<OpenTest>,
+}
+
+impl Session {
+    /// Creates an empty session
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Start a test, ending the current one if any
+    pub fn begin(&mut self, test: impl Into<String>) {
+        self.end();
+        self.current = Some(OpenTest { name: test.into(), failed: false });
+    }
+
+    /// Record the result of an assertion, returning whether it passed
+    ///
+    /// The assertion is evaluated by the session instead of on drop: a failure doesn't panic, and isn't reported or
+    /// counted anywhere else. Failures are named after the current test, unless they have a context of their own.
+    /// Failed warnings are recorded as warnings and don't fail the test.
+    pub fn record<T>(&mut self, mut assertion: Assertion<T>) -> bool {
+        let mut recorded = assertion.type_erased();
+        // The session evaluates the assertion, which must not report itself when dropped
+        assertion.evaluated = true;
+        drop(assertion);
+
+        let passed = recorded.calculate_chain_result();
+        if passed {
+            if self.current.is_none() {
+                self.result.passed_count += 1;
+            }
+            return true;
+        }
+
+        if let (None, Some(test)) = (&recorded.context, &self.current) {
+            recorded.context = Some(test.name.clone());
+        }
+        if recorded.severity == Severity::Warning {
+            self.result.warning_count += 1;
+            self.result.warnings.push(recorded);
+            return false;
+        }
+
+        self.result.failures.push(recorded);
+        match self.current {
+            Some(ref mut test) => test.failed = true,
+            None => self.result.failed_count += 1,
+        }
+
+        return false;
+    }
+
+    /// End the current test, returning whether it passed, or `true` when no test was started
+    pub fn end(&mut self) -> bool {
+        let Some(test) = self.current.take() else {
+            return true;
+        };
+
+        match test.failed {
+            true => self.result.failed_count += 1,
+            false => self.result.passed_count += 1,
+        }
+        return !test.failed;
+    }
+
+    /// Run code written with `expect!` as a test of its own, returning whether it passed
+    ///
+    /// Its assertions are neither reported nor counted. The first failed one fails the test and is recorded, and so
+    /// are other panics and returned errors.
+    pub fn run<R: TestOutput>(&mut self, test: impl Into<String>, body: impl FnOnce() -> R) -> bool {
+        self.begin(test);
+
+        let (result, failure) = capture_failure(body);
+        let failure = match (result, failure) {
+            (Ok(output), _) if output.passed() => None,
+            (Ok(_), _) => Some(Self::failure_of("return", "without an error", "an error".to_string())),
+            (Err(_), Some(failure)) => Some(failure),
+            (Err(payload), None) => Some(Self::failure_of("complete", "without panicking", format!("{:?}", panic_message(&payload)))),
+        };
+        if let Some(failure) = failure {
+            self.record(failure);
+        }
+
+        return self.end();
+    }
+
+    /// Failed assertion standing for a test that failed outside of its assertions
+    fn failure_of(verb: &str, object: &str, actual: String) -> Assertion<()> {
+        let mut assertion = Assertion::new((), "test").add_step(AssertionSentence::new(verb, object).with_actual(actual), false);
+        // Only recorded by the session, never evaluated on its own
+        assertion.evaluated = true;
+        return assertion;
+    }
+
+    /// Results recorded so far, the current test excluded from the counts
+    pub fn result(&self) -> &TestSessionResult {
+        return &self.result;
+    }
+
+    /// End the current test and get the results of the session
+    pub fn finalize(mut self) -> TestSessionResult {
+        self.end();
+        return std::mem::take(&mut self.result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frontend::ConsoleRenderer;
+    use crate::prelude::*;
+
+    #[test]
+    fn test_tests_fail_with_any_of_their_assertions() {
+        let mut session = Session::new();
+
+        session.begin("cart::test_total");
+        assert!(session.record(expect!(2 + 2).to_equal(4)));
+        assert!(!session.record(expect!(3).to_be_greater_than(5)));
+        session.begin("cart::test_items");
+        assert!(session.record(expect!(vec![1, 2]).to_have_length(2)));
+        assert!(session.end());
+
+        let result = session.finalize();
+        assert_eq!((result.passed_count, result.failed_count), (1, 1));
+        assert_eq!(result.failures.len(), 1);
+        assert_eq!(result.failures[0].context.as_deref(), Some("cart::test_total"));
+        assert_eq!(result.failures[0].format_stable_message(), "expected 3 to be greater than 5, got 3");
+    }
+
+    #[test]
+    fn test_assertions_outside_of_tests_count_on_their_own() {
+        let mut session = Session::new();
+
+        session.record(expect!(1).to_equal(1));
+        session.record(expect!(1).to_equal(2));
+        session.record(expect_warn!(10).to_be_less_than(5));
+
+        let result = session.finalize();
+        assert_eq!((result.passed_count, result.failed_count, result.warning_count), (1, 1, 1));
+        assert_eq!(result.failures[0].context, None);
+    }
+
+    #[test]
+    fn test_run_captures_failures() {
+        let mut session = Session::new();
+        let limit = 3;
+
+        assert!(session.run("suite::test_passes", || {
+            expect!(limit).to_be_less_than(5);
+        }));
+        assert!(!session.run("suite::test_fails", || {
+            expect!(limit).to_be_greater_than(5);
+        }));
+        assert!(!session.run("suite::test_errors", || "x".parse::<u8>().map(|_| ())));
+        assert!(!session.run("suite::test_panics", || {
+            if limit < 5 {
+                panic!("boom");
+            }
+        }));
+
+        let result = session.finalize();
+        let messages = result.failures.iter().map(Assertion::format_stable_message).collect::<Vec<_>>();
+        assert_eq!((result.passed_count, result.failed_count), (1, 3));
+        assert_eq!(
+            messages,
+            vec![
+                "expected limit to be greater than 5, got 3",
+                "expected test to return without an error, got an error",
+                "expected test to complete without panicking, got \"boom\""
+            ]
+        );
+    }
+
+    #[test]
+    fn test_results_render_like_the_summary() {
+        let mut session = Session::new();
+        session.begin("suite::test_name");
+        session.record(expect!("rest").to_equal("test"));
+
+        let summary = ConsoleRenderer::plain().render_session_summary(&session.finalize());
+        assert!(summary.contains("0 passed / 1 failed"), "{}", summary);
+        assert!(summary.contains("suite::test_name"), "{}", summary);
+    }
+}
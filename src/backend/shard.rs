@@ -0,0 +1,124 @@
+//! Test sharding for distributed CI
+//!
+//! A shard is a deterministic slice of the fixture-wrapped tests: each test path is hashed and
+//! assigned to exactly one of `count` shards, so running every shard index on a separate machine
+//! covers the whole suite exactly once. Select a shard with environment variables:
+//!
+//! ```text
+//! REST_SHARD_INDEX=2 REST_SHARD_COUNT=4 cargo test
+//! ```
+//!
+//! with the `--shard-index 2 --shard-count 4` flags of test binaries built without the libtest harness, which take
+//! precedence over the environment, or with `rest::config().shard(2, 4).apply()`. Indexes are 1-based. Tests assigned to another
+//! shard return immediately without running their fixtures or body, and print a `skipped` line: the
+//! test harness can't skip a test once started, so it still lists them as passed. Plain `#[test]`
+//! functions aren't registered and run in every shard.
+
+use std::fmt;
+
+/// A 1-based shard index out of a total shard count
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    index: usize,
+    count: usize,
+}
+
+impl Shard {
+    /// Create a shard, returning `None` unless `1 <= index <= count`
+    pub fn new(index: usize, count: usize) -> Option<Self> {
+        if count == 0 || index == 0 || index > count {
+            return None;
+        }
+
+        return Some(Self { index, count });
+    }
+
+    /// 1-based index of this shard
+    pub fn index(&self) -> usize {
+        return self.index;
+    }
+
+    /// Total number of shards
+    pub fn count(&self) -> usize {
+        return self.count;
+    }
+
+    /// Check if the test identified by its module path and name belongs to this shard
+    pub fn contains(&self, module_path: &str, test_name: &str) -> bool {
+        return shard_of(module_path, test_name, self.count) == self.index;
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(f, "{}/{}", self.index, self.count);
+    }
+}
+
+/// 1-based shard a test is assigned to out of `count` shards
+///
+/// Uses FNV-1a over the full test path, which is stable across platforms, toolchains and runs.
+pub fn shard_of(module_path: &str, test_name: &str, count: usize) -> usize {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = FNV_OFFSET;
+    for byte in module_path.bytes().chain("::".bytes()).chain(test_name.bytes()) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+
+    return (hash % count.max(1) as u64) as usize + 1;
+}
+
+/// Shard selected in the current configuration, if any
+pub fn current_shard() -> Option<Shard> {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.shard;
+}
+
+/// Check if a test should run under the current shard selection
+///
+/// Always true when no shard is configured.
+pub fn is_in_current_shard(module_path: &str, test_name: &str) -> bool {
+    return match current_shard() {
+        Some(shard) => shard.contains(module_path, test_name),
+        None => true,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shard_bounds() {
+        assert_eq!(Shard::new(0, 4), None);
+        assert_eq!(Shard::new(5, 4), None);
+        assert_eq!(Shard::new(1, 0), None);
+        assert_eq!(Shard::new(4, 4).map(|shard| shard.to_string()), Some("4/4".to_string()));
+    }
+
+    #[test]
+    fn test_every_test_lands_in_exactly_one_shard() {
+        let names = (0..200).map(|i| format!("test_{}", i)).collect::<Vec<_>>();
+        let shards = (1..=3).map(|index| Shard::new(index, 3).unwrap()).collect::<Vec<_>>();
+
+        for name in &names {
+            let owners = shards.iter().filter(|shard| shard.contains("suite::module", name)).count();
+            assert_eq!(owners, 1, "{} must belong to exactly one shard", name);
+        }
+
+        // The partition should not leave any shard empty on a reasonably sized suite
+        for shard in &shards {
+            assert!(names.iter().any(|name| shard.contains("suite::module", name)));
+        }
+    }
+
+    #[test]
+    fn test_shard_assignment_is_deterministic() {
+        assert_eq!(shard_of("suite::module", "test_login", 4), shard_of("suite::module", "test_login", 4));
+        assert_eq!(shard_of("suite::module", "test_login", 1), 1);
+        assert_eq!(shard_of("a", "b", 7), shard_of("a", "b", 7));
+    }
+}
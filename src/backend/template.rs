@@ -0,0 +1,206 @@
+//! Message templates with named placeholders
+//!
+//! A [`Template`] such as `Hello {name}, you have {count} messages` matches the strings where each placeholder
+//! stands for any text, and captures that text by name. `{{` and `}}` stand for literal braces, and a placeholder
+//! used several times must capture the same text each time.
+
+use regex::Regex;
+use std::ops::Index;
+
+/// Part of a template, between or at placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(String),
+}
+
+/// Parsed template with named placeholders
+#[derive(Debug, Clone)]
+pub struct Template {
+    segments: Vec<Segment>,
+    regex: Regex,
+}
+
+impl Template {
+    /// Parse a template, failing on unbalanced braces and unnamed placeholders
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        if c == '}' {
+                            closed = true;
+                            break;
+                        }
+                        name.push(c);
+                    }
+                    if !closed || name.contains('{') {
+                        return Err(format!("unclosed placeholder {{{}", name));
+                    }
+                    if name.trim().is_empty() {
+                        return Err("placeholder without a name".to_string());
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Placeholder(name.trim().to_string()));
+                }
+                '}' => return Err("unmatched }, use }} for a literal brace".to_string()),
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+
+        let regex = build_regex(&segments, true);
+        return Ok(Self { segments, regex });
+    }
+
+    /// Values of the placeholders if the text matches the template
+    pub fn captures(&self, text: &str) -> Option<TemplateCaptures> {
+        let matched = self.regex.captures(text)?;
+        let mut captures = TemplateCaptures::default();
+        let placeholders = self.segments.iter().filter_map(|segment| match segment {
+            Segment::Placeholder(name) => Some(name),
+            Segment::Literal(_) => None,
+        });
+
+        for (index, name) in placeholders.enumerate() {
+            let value = matched.get(index + 1).map_or("", |group| group.as_str());
+            match captures.get(name) {
+                Some(previous) if previous != value => return None,
+                Some(_) => {}
+                None => captures.values.push((name.clone(), value.to_string())),
+            }
+        }
+
+        return Some(captures);
+    }
+
+    /// Explain where a text that doesn't match the template departs from it
+    pub fn explain_mismatch(&self, text: &str) -> Vec<String> {
+        // The longest part of the template, up to a literal, found at the start of the text
+        let literals = self.segments.iter().enumerate().filter(|(_, segment)| matches!(segment, Segment::Literal(_)));
+        let matched = literals
+            .map(|(index, _)| index)
+            .rev()
+            .find_map(|index| build_regex(&self.segments[..=index], false).find(text).map(|found| (index + 1, found.end())));
+        let (next_segment, matched_end) = matched.unwrap_or((0, 0));
+
+        let next_literal = self.segments[next_segment..].iter().find_map(|segment| match segment {
+            Segment::Literal(literal) => Some(literal),
+            Segment::Placeholder(_) => None,
+        });
+
+        return match next_literal {
+            Some(literal) => vec![format!("matched up to {:?}", &text[..matched_end]), format!("then expected {:?}", literal)],
+            None if next_segment == self.segments.len() && matched_end < text.len() => {
+                vec![format!("matched up to {:?}", &text[..matched_end]), format!("then unexpected {:?}", &text[matched_end..])]
+            }
+            None => vec!["repeated placeholders captured different values".to_string()],
+        };
+    }
+}
+
+/// Values captured by the placeholders of a template, by name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TemplateCaptures {
+    values: Vec<(String, String)>,
+}
+
+impl TemplateCaptures {
+    /// Value captured by a placeholder
+    pub fn get(&self, name: &str) -> Option<&str> {
+        return self.values.iter().find(|(placeholder, _)| placeholder == name).map(|(_, value)| value.as_str());
+    }
+
+    /// Placeholder names and captured values, in the order of the template
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        return self.values.iter().map(|(name, value)| (name.as_str(), value.as_str()));
+    }
+}
+
+impl Index<&str> for TemplateCaptures {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &str {
+        return self.get(name).unwrap_or_else(|| panic!("the template has no placeholder {{{}}}", name));
+    }
+}
+
+/// Regex matching the segments, each placeholder capturing the shortest text, up to the end of the text if anchored
+fn build_regex(segments: &[Segment], anchored: bool) -> Regex {
+    let mut pattern = "(?s)^".to_string();
+    for segment in segments {
+        match segment {
+            Segment::Literal(literal) => pattern.push_str(&regex::escape(literal)),
+            Segment::Placeholder(_) => pattern.push_str("(.*?)"),
+        }
+    }
+    if anchored {
+        pattern.push('$');
+    }
+
+    return Regex::new(&pattern).expect("escaped template literals form a valid regex");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures_placeholders() {
+        let template = Template::parse("Hello {name}, you have {count} messages").unwrap();
+        let captures = template.captures("Hello Ada, you have 3 messages").unwrap();
+
+        assert_eq!(captures.get("name"), Some("Ada"));
+        assert_eq!(&captures["count"], "3");
+        assert_eq!(captures.iter().collect::<Vec<_>>(), vec![("name", "Ada"), ("count", "3")]);
+        assert!(template.captures("Hello Ada, you have 3 new messages!").is_none());
+    }
+
+    #[test]
+    fn test_escaped_braces_and_repeated_placeholders() {
+        let template = Template::parse("{{{key}}} = {value}, again {key}").unwrap();
+
+        assert_eq!(template.captures("{port} = 80, again port").unwrap().get("key"), Some("port"));
+        assert!(template.captures("{port} = 80, again host").is_none());
+    }
+
+    #[test]
+    fn test_invalid_templates() {
+        assert!(Template::parse("Hello {name").is_err());
+        assert!(Template::parse("Hello {}").is_err());
+        assert!(Template::parse("Hello }").is_err());
+    }
+
+    #[test]
+    fn test_explain_mismatch() {
+        let template = Template::parse("Hello {name}, you have {count} messages").unwrap();
+
+        assert_eq!(
+            template.explain_mismatch("Hello Ada, you had 3 messages"),
+            vec!["matched up to \"Hello \"".to_string(), "then expected \", you have \"".to_string()]
+        );
+        assert_eq!(template.explain_mismatch("Hi Ada"), vec!["matched up to \"\"".to_string(), "then expected \"Hello \"".to_string()]);
+        assert_eq!(
+            template.explain_mismatch("Hello Ada, you have 3 messages today"),
+            vec!["matched up to \"Hello Ada, you have 3 messages\"".to_string(), "then unexpected \" today\"".to_string()]
+        );
+    }
+}
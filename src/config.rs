@@ -1,20 +1,155 @@
+use crate::backend::shard::Shard;
+use crate::frontend::terminal::{self, Symbols};
+use std::collections::HashMap;
 use std::env;
-use std::sync::Once;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Once, RwLock};
 
 // Initialization flag to ensure we only initialize once
 static INIT: Once = Once::new();
 
+// Set once a configuration was applied explicitly, so test auto-initialization keeps it
+static EXPLICITLY_APPLIED: AtomicBool = AtomicBool::new(false);
+
 // Environment variable to control enhanced output
 const ENV_ENHANCED_OUTPUT: &str = "REST_ENHANCED_OUTPUT";
 const DEFAULT_ENHANCED_OUTPUT: bool = true;
 
+// Environment variables selecting the shard to run
+const ENV_SHARD_INDEX: &str = "REST_SHARD_INDEX";
+const ENV_SHARD_COUNT: &str = "REST_SHARD_COUNT";
+
+// Command-line flags selecting the shard to run, taking precedence over the environment variables
+const ARG_SHARD_INDEX: &str = "--shard-index";
+const ARG_SHARD_COUNT: &str = "--shard-count";
+
+// Environment variable selecting Unicode or ASCII symbols, detected from the terminal by default
+const ENV_SYMBOLS: &str = "REST_SYMBOLS";
+
+// Environment variable selecting the panic message format
+const ENV_PANIC_FORMAT: &str = "REST_PANIC_FORMAT";
+
+/// Format of the panic message raised by a failed assertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PanicFormat {
+    /// Message adapted to the test context and the enhanced output setting
+    #[default]
+    Contextual,
+    /// `expected <expr> to <sentence>, got <actual>`, whatever the thread or output settings
+    ///
+    /// The first failed step of a chain is reported, and `, got <actual>` is left out when the
+    /// matcher has no actual value. Assertion context and detail lines are kept.
+    Stable,
+}
+
+// Environment variable selecting how tests without assertions are handled
+const ENV_VACUOUS_TESTS: &str = "REST_VACUOUS_TESTS";
+
+/// Handling of fixture-wrapped tests that complete without running any assertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VacuousTests {
+    /// Tests without assertions pass silently
+    #[default]
+    Allow,
+    /// Tests without assertions pass and are listed in the session summary
+    Warn,
+    /// Tests without assertions fail
+    Fail,
+}
+
+// Environment variable shuffling the probe order of order-insensitive collection matchers
+const ENV_SHUFFLE_MATCHERS: &str = "REST_SHUFFLE_MATCHERS";
+
+// Environment variable making snapshot matchers rewrite the snapshots that don't match
+pub(crate) const ENV_UPDATE_SNAPSHOTS: &str = "REST_UPDATE_SNAPSHOTS";
+
+// Environment variable recording the assertions executed per source file and test, listed in the summary
+const ENV_ASSERTION_COVERAGE: &str = "REST_ASSERTION_COVERAGE";
+
+// Environment variable showing what the matchers of failed steps check, with a link to their documentation
+const ENV_MATCHER_DOCS: &str = "REST_MATCHER_DOCS";
+
+// Environment variable printing a progress line when the last registered test of a module completes
+const ENV_MODULE_PROGRESS: &str = "REST_MODULE_PROGRESS";
+
+// Environment variable making fixture-wrapped tests capture their logs, with the `log-capture` feature
+const ENV_CAPTURE_LOGS: &str = "REST_CAPTURE_LOGS";
+
+// Environment variable selecting how type names are rendered in failure details
+const ENV_TYPE_NAMES: &str = "REST_TYPE_NAMES";
+
+// Environment variables setting the significant digits of floats in sentences, and the exponent from which they are
+// written in scientific notation
+const ENV_FLOAT_PRECISION: &str = "REST_FLOAT_PRECISION";
+const ENV_SCIENTIFIC_THRESHOLD: &str = "REST_SCIENTIFIC_THRESHOLD";
+
+/// Rendering of the asserted value's type in failure details
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TypeNames {
+    /// Module paths removed and deeply nested generic arguments collapsed, such as `Vec<HashMap<String, _>>`
+    #[default]
+    Abbreviated,
+    /// Type name as returned by `std::any::type_name`
+    Full,
+}
+
+/// Rendering of floats in the sentences of numeric matchers, the same on every platform
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloatFormat {
+    /// Significant digits, `None` for the fewest digits that read back as the same float
+    pub precision: Option<usize>,
+    /// Decimal exponent from which floats are written in scientific notation, such as `1.5e-7` and `2e9` for 7
+    pub scientific_threshold: u32,
+}
+
+impl Default for FloatFormat {
+    fn default() -> Self {
+        return Self { precision: None, scientific_threshold: 7 };
+    }
+}
+
+/// Grammatical number of a subject, deciding how the verbs of its sentences are conjugated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Plurality {
+    /// Conjugated like `is` and `contains`
+    Singular,
+    /// Conjugated like `are` and `contain`
+    Plural,
+}
+
+// Plurality of the subject names that English singularization gets wrong, by lowercase name
+static SUBJECT_PLURALITIES: LazyLock<RwLock<HashMap<String, Plurality>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
 /// Configuration for Rest's output and behavior
+#[derive(Clone)]
 pub struct Config {
     pub(crate) use_colors: bool,
     pub(crate) use_unicode_symbols: bool,
     pub(crate) show_success_details: bool,
     /// Enable enhanced test output (fluent assertions instead of standard output)
     pub(crate) enhanced_output: bool,
+    /// Only run the fixture-wrapped tests assigned to this shard
+    pub(crate) shard: Option<Shard>,
+    /// Format of the panic message raised by a failed assertion
+    pub(crate) panic_format: PanicFormat,
+    /// Handling of fixture-wrapped tests that run no assertion
+    pub(crate) vacuous_tests: VacuousTests,
+    /// Rendering of the asserted value's type in failure details
+    pub(crate) type_names: TypeNames,
+    /// Shuffle the probe order of order-insensitive collection matchers
+    pub(crate) shuffle_matchers: bool,
+    /// Rewrite the snapshots that don't match instead of failing
+    pub(crate) update_snapshots: bool,
+    /// Rendering of floats in the sentences of numeric matchers
+    pub(crate) float_format: FloatFormat,
+    /// Record the assertions executed per source file and test for the summary
+    pub(crate) assertion_coverage: bool,
+    /// Show what the matchers of failed steps check, with a link to their documentation
+    pub(crate) matcher_docs: bool,
+    /// Print a progress line when the last registered test of a module completes
+    pub(crate) module_progress: bool,
+    /// Capture the logs of every fixture-wrapped test
+    pub(crate) capture_logs: bool,
 }
 
 impl Default for Config {
@@ -23,42 +158,70 @@ impl Default for Config {
     }
 }
 
-// Implement Clone for Config
-impl Clone for Config {
-    fn clone(&self) -> Self {
-        Self {
-            use_colors: self.use_colors,
-            use_unicode_symbols: self.use_unicode_symbols,
-            show_success_details: self.show_success_details,
-            enhanced_output: self.enhanced_output,
-        }
-    }
-}
-
 impl Config {
     /// Creates a new configuration with default settings
+    ///
+    /// Settings come from the environment variables, and the shard from the `--shard-index N --shard-count M`
+    /// command-line flags when the test binary was started with them.
     pub fn new() -> Self {
-        Self::from_env(|key| env::var(key).ok())
+        let mut config = Self::from_env(|key| env::var(key).ok());
+        if let Some(shard) = shard_from_args(env::args()) {
+            config.shard = shard;
+        }
+        config
     }
 
     /// Creates a new configuration by reading env vars through the provided closure.
     /// This allows tests to inject mock env values without mutating process-global state.
-    fn from_env(get_var: impl Fn(&str) -> Option<String>) -> Self {
+    pub(crate) fn from_env(get_var: impl Fn(&str) -> Option<String>) -> Self {
         let enhanced_output = match get_var(ENV_ENHANCED_OUTPUT) {
-            Some(val) => bool_from_str(&val, DEFAULT_ENHANCED_OUTPUT),
+            Some(val) => bool_from_str(ENV_ENHANCED_OUTPUT, &val, DEFAULT_ENHANCED_OUTPUT),
             None => DEFAULT_ENHANCED_OUTPUT,
         };
 
-        Self { use_colors: true, use_unicode_symbols: true, show_success_details: true, enhanced_output }
+        let shard = shard_from_env(&get_var);
+        let panic_format = panic_format_from_env(&get_var);
+        let vacuous_tests = vacuous_tests_from_env(&get_var);
+        let type_names = type_names_from_env(&get_var);
+        let float_format = float_format_from_env(&get_var);
+        let shuffle_matchers = get_var(ENV_SHUFFLE_MATCHERS).is_some_and(|val| bool_from_str(ENV_SHUFFLE_MATCHERS, &val, false));
+        let update_snapshots = get_var(ENV_UPDATE_SNAPSHOTS).is_some_and(|val| bool_from_str(ENV_UPDATE_SNAPSHOTS, &val, false));
+        let assertion_coverage = get_var(ENV_ASSERTION_COVERAGE).is_some_and(|val| bool_from_str(ENV_ASSERTION_COVERAGE, &val, false));
+        let matcher_docs = get_var(ENV_MATCHER_DOCS).is_some_and(|val| bool_from_str(ENV_MATCHER_DOCS, &val, false));
+        let module_progress = get_var(ENV_MODULE_PROGRESS).is_some_and(|val| bool_from_str(ENV_MODULE_PROGRESS, &val, false));
+        let capture_logs = get_var(ENV_CAPTURE_LOGS).is_some_and(|val| bool_from_str(ENV_CAPTURE_LOGS, &val, false));
+
+        Self {
+            use_colors: terminal::supports_colors(&get_var),
+            use_unicode_symbols: unicode_symbols_from_env(&get_var),
+            show_success_details: true,
+            enhanced_output,
+            shard,
+            panic_format,
+            vacuous_tests,
+            type_names,
+            shuffle_matchers,
+            update_snapshots,
+            float_format,
+            assertion_coverage,
+            matcher_docs,
+            module_progress,
+            capture_logs,
+        }
     }
 
     /// Enable or disable colored output
+    ///
+    /// Enabled by default unless `NO_COLOR` is set, `TERM` is `dumb` or the Windows console can't display ANSI colors.
     pub fn use_colors(mut self, enable: bool) -> Self {
         self.use_colors = enable;
         self
     }
 
-    /// Enable or disable Unicode symbols
+    /// Enable or disable Unicode symbols, replaced by ASCII ones (`+`, `-` and `!`) when disabled
+    ///
+    /// Enabled by default when the terminal displays Unicode: a UTF-8 locale, or a UTF-8 code page or modern terminal
+    /// on Windows. `REST_SYMBOLS=unicode|ascii` overrides the detection.
     pub fn use_unicode_symbols(mut self, enable: bool) -> Self {
         self.use_unicode_symbols = enable;
         self
@@ -76,13 +239,135 @@ impl Config {
         self
     }
 
+    /// Only run the tests assigned to the given 1-based shard out of `count` shards
+    ///
+    /// Invalid selections (index 0 or greater than count) disable sharding with a warning.
+    pub fn shard(mut self, index: usize, count: usize) -> Self {
+        self.shard = Shard::new(index, count);
+        if self.shard.is_none() {
+            eprintln!("WARNING: Invalid shard {}/{}. Sharding disabled. (Expected 1 <= index <= count)", index, count);
+        }
+        self
+    }
+
+    /// Select the format of the panic message raised by a failed assertion
+    ///
+    /// Use `PanicFormat::Stable` to match failures with `#[should_panic(expected = "...")]`.
+    pub fn panic_format(mut self, format: PanicFormat) -> Self {
+        self.panic_format = format;
+        self
+    }
+
+    /// Select how fixture-wrapped tests that complete without running any assertion are handled
+    ///
+    /// Tests marked `#[allow_no_assertions]` are always allowed.
+    pub fn vacuous_tests(mut self, handling: VacuousTests) -> Self {
+        self.vacuous_tests = handling;
+        self
+    }
+
+    /// Select whether failure details show abbreviated or full type names
+    pub fn type_names(mut self, names: TypeNames) -> Self {
+        self.type_names = names;
+        self
+    }
+
+    /// Shuffle the order in which `to_contain_all_of` and `to_have_same_elements_as` probe their elements
+    ///
+    /// Each call site gets its own permutation, drawn from the seed of the run so that `REST_SEED` replays it. Helps
+    /// catching `PartialEq` implementations that depend on the order of their comparisons. Disabled by default.
+    pub fn shuffle_matchers(mut self, enable: bool) -> Self {
+        self.shuffle_matchers = enable;
+        self
+    }
+
+    /// Make `to_render_like_snapshot` and `to_look_like` write the snapshots and reference images that are missing or
+    /// don't match instead of failing
+    ///
+    /// Meant for a local run accepting intended changes, reviewed in the diff of the snapshot files. Disabled by default.
+    pub fn update_snapshots(mut self, enable: bool) -> Self {
+        self.update_snapshots = enable;
+        self
+    }
+
+    /// Round the floats of numeric matcher sentences to a number of significant digits
+    ///
+    /// By default, floats are written with the fewest digits that read back as the same float.
+    pub fn float_precision(mut self, digits: usize) -> Self {
+        self.float_format.precision = Some(digits.max(1));
+        self
+    }
+
+    /// Write the floats of numeric matcher sentences in scientific notation from a decimal exponent, 7 by default
+    ///
+    /// With the default, `0.000001` and `1000000.0` are written out while `1e-7` and `1.5e7` aren't.
+    pub fn scientific_threshold(mut self, exponent: u32) -> Self {
+        self.float_format.scientific_threshold = exponent;
+        self
+    }
+
+    /// Record the assertions executed per source file and test, listed at the end of the session summary
+    ///
+    /// The summary also lists the modules of fixture-wrapped tests where no assertion ran. Disabled by default.
+    pub fn assertion_coverage(mut self, enable: bool) -> Self {
+        self.assertion_coverage = enable;
+        self
+    }
+
+    /// Show what the matcher of a failed step checks under the step, with a link to its documentation
+    ///
+    /// Helps reading unfamiliar failures, such as ULP comparisons or schema validation. Only matchers that document
+    /// themselves are concerned. Disabled by default.
+    pub fn matcher_docs(mut self, enable: bool) -> Self {
+        self.matcher_docs = enable;
+        self
+    }
+
+    /// Print a progress line, such as `backend::matchers::numeric: 34 passed`, when the last registered test of a
+    /// module completes
+    ///
+    /// The line is written as soon as the module completes, so with the libtest harness it lands among the harness's
+    /// own lines, which reads best with `--test-threads=1`. Disabled by default.
+    pub fn module_progress(mut self, enable: bool) -> Self {
+        self.module_progress = enable;
+        self
+    }
+
+    /// Capture the `log` records and `tracing` events of every fixture-wrapped test, with the `log-capture` feature
+    ///
+    /// The first capture installs a global `log` logger, which fails if the binary installed one already (for instance
+    /// with `env_logger::init()`), and each capture sets the `tracing` default subscriber of the test thread, which
+    /// hides the global subscriber of the binary until the test ends. Disabled by default: other tests, and fixture-wrapped
+    /// tests without this setting, start a capture with `capture_logs()`.
+    pub fn capture_logs(mut self, enable: bool) -> Self {
+        self.capture_logs = enable;
+        self
+    }
+
     /// Apply the configuration
     pub fn apply(self) {
+        self.store(true);
+    }
+
+    /// Apply the configuration read from the environment, unless one was applied explicitly
+    pub(crate) fn apply_default(self) {
+        self.store(false);
+    }
+
+    /// Store the configuration globally; a default never replaces an explicit configuration
+    fn store(self, explicit: bool) {
         use crate::reporter::GLOBAL_CONFIG;
 
         // Clone self before moving it into the global config
         let config = self.clone();
-        *GLOBAL_CONFIG.write().unwrap() = self;
+        {
+            let mut global = GLOBAL_CONFIG.write().unwrap();
+            if !explicit && EXPLICITLY_APPLIED.load(Ordering::SeqCst) {
+                return;
+            }
+            EXPLICITLY_APPLIED.fetch_or(explicit, Ordering::SeqCst);
+            *global = self;
+        }
 
         // Initialize the event system if enhanced output is enabled
         if config.enhanced_output {
@@ -114,15 +399,271 @@ pub fn is_enhanced_output_enabled() -> bool {
     return config.enhanced_output;
 }
 
-/// Convert from one of the allowed string values to a boolean.
-fn bool_from_str(val: &str, default: bool) -> bool {
+/// Get the panic message format of the current configuration
+pub fn panic_format() -> PanicFormat {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.panic_format;
+}
+
+/// Get the handling of tests without assertions of the current configuration
+pub fn vacuous_tests() -> VacuousTests {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.vacuous_tests;
+}
+
+/// Get the rendering of type names of the current configuration
+pub fn type_names() -> TypeNames {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.type_names;
+}
+
+/// Check if order-insensitive collection matchers shuffle their probe order in the current configuration
+pub fn is_shuffling_matchers() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.shuffle_matchers;
+}
+
+/// Get the rendering of floats of the current configuration
+pub fn float_format() -> FloatFormat {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.float_format;
+}
+
+/// Check if snapshot matchers rewrite the snapshots that don't match in the current configuration
+pub fn is_updating_snapshots() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.update_snapshots;
+}
+
+/// Check if assertion coverage is recorded in the current configuration
+pub fn is_assertion_coverage_enabled() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.assertion_coverage;
+}
+
+/// Set the plurality of a subject name, overriding its detection from English singularization
+///
+/// Subjects are matched case-insensitively on their base name, such as `kinder` for `kinder.len()`, then on the last
+/// word of a `snake_case` name, such as `kinder` for `user_kinder`. Overrides apply to every test of the crate, so set
+/// them where the configuration is applied.
+///
+/// ```
+/// use rest::config::{Plurality, subject_plurality};
+///
+/// subject_plurality("kinder", Plurality::Plural);
+/// subject_plurality("children_list", Plurality::Singular);
+/// ```
+pub fn subject_plurality(name: &str, plurality: Plurality) {
+    SUBJECT_PLURALITIES.write().unwrap().insert(name.to_lowercase(), plurality);
+}
+
+/// Get the plurality set for a lowercase subject name, if any
+pub(crate) fn subject_plurality_override(name: &str) -> Option<Plurality> {
+    return SUBJECT_PLURALITIES.read().unwrap().get(name).copied();
+}
+
+/// Check if failed steps show the documentation of their matcher in the current configuration
+pub fn is_showing_matcher_docs() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.matcher_docs;
+}
+
+/// Check if completed modules print their progress line in the current configuration
+pub fn is_printing_module_progress() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.module_progress;
+}
+
+/// Check if fixture-wrapped tests capture their logs in the current configuration
+pub fn is_capturing_logs() -> bool {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return config.capture_logs;
+}
+
+/// Get the symbols of the current configuration
+pub fn symbols() -> Symbols {
+    let config = crate::reporter::GLOBAL_CONFIG.read().unwrap();
+    return Symbols::new(config.use_unicode_symbols);
+}
+
+/// Read the choice of Unicode or ASCII symbols from its environment variable, detecting it from the terminal by default
+fn unicode_symbols_from_env(get_var: &impl Fn(&str) -> Option<String>) -> bool {
+    let Some(value) = get_var(ENV_SYMBOLS) else {
+        return terminal::supports_unicode(get_var);
+    };
+
+    return match value.trim().to_lowercase().as_str() {
+        "unicode" => true,
+        "ascii" => false,
+        "auto" => terminal::supports_unicode(get_var),
+        _ => {
+            eprintln!(
+                "WARNING: Unrecognized value for environment variable {}: {:?}. Defaulting to auto. (Allowed values: unicode, ascii, auto)",
+                ENV_SYMBOLS, value,
+            );
+            terminal::supports_unicode(get_var)
+        }
+    };
+}
+
+/// Read the handling of tests without assertions from its environment variable, warning on unknown values
+fn vacuous_tests_from_env(get_var: &impl Fn(&str) -> Option<String>) -> VacuousTests {
+    let Some(value) = get_var(ENV_VACUOUS_TESTS) else {
+        return VacuousTests::default();
+    };
+
+    return match value.trim().to_lowercase().as_str() {
+        "allow" => VacuousTests::Allow,
+        "warn" => VacuousTests::Warn,
+        "fail" => VacuousTests::Fail,
+        _ => {
+            eprintln!(
+                "WARNING: Unrecognized value for environment variable {}: {:?}. Defaulting to allow. (Allowed values: allow, warn, fail)",
+                ENV_VACUOUS_TESTS, value,
+            );
+            VacuousTests::default()
+        }
+    };
+}
+
+/// Read the panic message format from its environment variable, warning on unknown values
+fn panic_format_from_env(get_var: &impl Fn(&str) -> Option<String>) -> PanicFormat {
+    let Some(value) = get_var(ENV_PANIC_FORMAT) else {
+        return PanicFormat::default();
+    };
+
+    return match value.trim().to_lowercase().as_str() {
+        "stable" => PanicFormat::Stable,
+        "contextual" => PanicFormat::Contextual,
+        _ => {
+            eprintln!(
+                "WARNING: Unrecognized value for environment variable {}: {:?}. Defaulting to contextual. (Allowed values: stable, contextual)",
+                ENV_PANIC_FORMAT, value,
+            );
+            PanicFormat::default()
+        }
+    };
+}
+
+/// Read the rendering of floats from its environment variables, warning on values that aren't numbers
+fn float_format_from_env(get_var: &impl Fn(&str) -> Option<String>) -> FloatFormat {
+    let read = |key: &str| {
+        let value = get_var(key)?;
+        return match value.trim().parse::<u32>() {
+            Ok(number) => Some(number),
+            Err(_) => {
+                eprintln!("WARNING: Invalid value for environment variable {}: {:?}. Expected a non-negative integer.", key, value);
+                None
+            }
+        };
+    };
+
+    let default = FloatFormat::default();
+    return FloatFormat {
+        precision: read(ENV_FLOAT_PRECISION).filter(|digits| *digits > 0).map(|digits| digits as usize),
+        scientific_threshold: read(ENV_SCIENTIFIC_THRESHOLD).unwrap_or(default.scientific_threshold),
+    };
+}
+
+/// Read the rendering of type names from its environment variable, warning on unknown values
+fn type_names_from_env(get_var: &impl Fn(&str) -> Option<String>) -> TypeNames {
+    let Some(value) = get_var(ENV_TYPE_NAMES) else {
+        return TypeNames::default();
+    };
+
+    return match value.trim().to_lowercase().as_str() {
+        "abbreviated" => TypeNames::Abbreviated,
+        "full" => TypeNames::Full,
+        _ => {
+            eprintln!(
+                "WARNING: Unrecognized value for environment variable {}: {:?}. Defaulting to abbreviated. (Allowed values: abbreviated, full)",
+                ENV_TYPE_NAMES, value,
+            );
+            TypeNames::default()
+        }
+    };
+}
+
+/// Read the shard selection from the shard environment variables.
+///
+/// Both variables must be set to valid numbers; anything else disables sharding.
+fn shard_from_env(get_var: &impl Fn(&str) -> Option<String>) -> Option<Shard> {
+    let (index, count) = match (get_var(ENV_SHARD_INDEX), get_var(ENV_SHARD_COUNT)) {
+        (None, None) => return None,
+        (Some(index), Some(count)) => (index, count),
+        (index, count) => {
+            eprintln!(
+                "WARNING: {} and {} must be set together (got {:?} and {:?}). Sharding disabled.",
+                ENV_SHARD_INDEX, ENV_SHARD_COUNT, index, count,
+            );
+            return None;
+        }
+    };
+
+    let shard = match (index.trim().parse::<usize>(), count.trim().parse::<usize>()) {
+        (Ok(index), Ok(count)) => Shard::new(index, count),
+        _ => None,
+    };
+
+    if shard.is_none() {
+        eprintln!(
+            "WARNING: Invalid shard selection {}={:?}, {}={:?}. Sharding disabled. (Expected 1 <= index <= count)",
+            ENV_SHARD_INDEX, index, ENV_SHARD_COUNT, count,
+        );
+    }
+
+    return shard;
+}
+
+/// Read the shard selection from the `--shard-index N --shard-count M` command-line flags.
+///
+/// Each flag takes its value as the next argument or after `=`. `None` when neither flag is given, so that the
+/// environment variables decide; otherwise both must be valid numbers, and anything else disables sharding.
+fn shard_from_args(args: impl IntoIterator<Item = String>) -> Option<Option<Shard>> {
+    let (mut index, mut count) = (None, None);
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        let slot = match flag.as_str() {
+            ARG_SHARD_INDEX => &mut index,
+            ARG_SHARD_COUNT => &mut count,
+            _ => continue,
+        };
+        *slot = value.or_else(|| args.next());
+    }
+
+    if index.is_none() && count.is_none() {
+        return None;
+    }
+
+    let parse = |value: &Option<String>| value.as_deref().and_then(|value| value.trim().parse::<usize>().ok());
+    let shard = match (parse(&index), parse(&count)) {
+        (Some(index), Some(count)) => Shard::new(index, count),
+        _ => None,
+    };
+
+    if shard.is_none() {
+        eprintln!(
+            "WARNING: Invalid shard selection {} {:?} {} {:?}. Sharding disabled. (Expected 1 <= index <= count)",
+            ARG_SHARD_INDEX, index, ARG_SHARD_COUNT, count,
+        );
+    }
+
+    return Some(shard);
+}
+
+/// Convert from one of the allowed string values of an environment variable to a boolean.
+fn bool_from_str(key: &str, val: &str, default: bool) -> bool {
     match val.to_lowercase().as_str() {
         "true" | "1" | "yes" | "on" => true,
         "false" | "0" | "no" | "off" => false,
         _ => {
             eprintln!(
                 "WARNING: Unrecognized value for environment variable {}: {:?}. Defaulting to {}. (Allowed values: true, false, 1, 0, yes, no, on, off)",
-                ENV_ENHANCED_OUTPUT, val, default,
+                key, val, default,
             );
             default
         }
@@ -137,39 +678,39 @@ mod tests {
     fn test_config_default() {
         let config = Config::from_env(|_| None);
 
-        assert_eq!(config.use_colors, true);
-        assert_eq!(config.use_unicode_symbols, true);
-        assert_eq!(config.show_success_details, true);
-        assert_eq!(config.enhanced_output, true); // Default is true without env var
+        assert!(config.use_colors);
+        assert!(config.use_unicode_symbols);
+        assert!(config.show_success_details);
+        assert!(config.enhanced_output); // Default is true without env var
     }
 
     #[test]
     fn test_config_env_var_true() {
         let config = Config::from_env(|_| Some("true".into()));
-        assert_eq!(config.enhanced_output, true);
+        assert!(config.enhanced_output);
     }
 
     #[test]
     fn test_config_env_var_false() {
         let config = Config::from_env(|_| Some("false".into()));
-        assert_eq!(config.enhanced_output, false);
+        assert!(!config.enhanced_output);
     }
 
     #[test]
     fn test_config_env_var_alternative_values() {
         // True values
-        assert_eq!(Config::from_env(|_| Some("1".into())).enhanced_output, true);
-        assert_eq!(Config::from_env(|_| Some("yes".into())).enhanced_output, true);
-        assert_eq!(Config::from_env(|_| Some("on".into())).enhanced_output, true);
+        assert!(Config::from_env(|_| Some("1".into())).enhanced_output);
+        assert!(Config::from_env(|_| Some("yes".into())).enhanced_output);
+        assert!(Config::from_env(|_| Some("on".into())).enhanced_output);
 
         // False values
-        assert_eq!(Config::from_env(|_| Some("0".into())).enhanced_output, false);
-        assert_eq!(Config::from_env(|_| Some("no".into())).enhanced_output, false);
-        assert_eq!(Config::from_env(|_| Some("off".into())).enhanced_output, false);
+        assert!(!Config::from_env(|_| Some("0".into())).enhanced_output);
+        assert!(!Config::from_env(|_| Some("no".into())).enhanced_output);
+        assert!(!Config::from_env(|_| Some("off".into())).enhanced_output);
 
         // Case-insensitivity
-        assert_eq!(Config::from_env(|_| Some("TRUE".into())).enhanced_output, true);
-        assert_eq!(Config::from_env(|_| Some("False".into())).enhanced_output, false);
+        assert!(Config::from_env(|_| Some("TRUE".into())).enhanced_output);
+        assert!(!Config::from_env(|_| Some("False".into())).enhanced_output);
 
         // Garbage input falls back to default
         assert_eq!(Config::from_env(|_| Some("garbage".into())).enhanced_output, DEFAULT_ENHANCED_OUTPUT);
@@ -179,10 +720,10 @@ mod tests {
     fn test_config_builder_methods() {
         let config = Config::new().use_colors(false).use_unicode_symbols(false).show_success_details(false).enhanced_output(true);
 
-        assert_eq!(config.use_colors, false);
-        assert_eq!(config.use_unicode_symbols, false);
-        assert_eq!(config.show_success_details, false);
-        assert_eq!(config.enhanced_output, true);
+        assert!(!config.use_colors);
+        assert!(!config.use_unicode_symbols);
+        assert!(!config.show_success_details);
+        assert!(config.enhanced_output);
     }
 
     #[test]
@@ -197,17 +738,192 @@ mod tests {
         assert_eq!(config1.enhanced_output, config2.enhanced_output);
     }
 
+    #[test]
+    fn test_config_shard_from_env() {
+        let env = |key: &str| match key {
+            ENV_SHARD_INDEX => Some("2".to_string()),
+            ENV_SHARD_COUNT => Some("4".to_string()),
+            _ => None,
+        };
+        let config = Config::from_env(env);
+        assert_eq!(config.shard, Shard::new(2, 4));
+
+        // Missing, partial or out of range selections disable sharding
+        assert_eq!(Config::from_env(|_| None).shard, None);
+        assert_eq!(Config::from_env(|key| if key == ENV_SHARD_INDEX { Some("1".into()) } else { None }).shard, None);
+        assert_eq!(Config::from_env(|key| if key == ENV_SHARD_INDEX { Some("5".into()) } else { Some("4".into()) }).shard, None);
+        assert_eq!(Config::from_env(|_| Some("abc".into())).shard, None);
+    }
+
+    #[test]
+    fn test_config_shard_from_args() {
+        let args = |line: &str| line.split_whitespace().map(String::from).collect::<Vec<_>>();
+
+        assert_eq!(shard_from_args(args("tests --shard-index 2 --shard-count 4")), Some(Shard::new(2, 4)));
+        assert_eq!(shard_from_args(args("tests --shard-count=3 --exact --shard-index=3")), Some(Shard::new(3, 3)));
+
+        // Without the flags the environment variables decide
+        assert_eq!(shard_from_args(args("tests --exact api::test_login")), None);
+
+        // An index past the count, a zero index, a missing flag or value, or a non-number disable sharding
+        assert_eq!(shard_from_args(args("tests --shard-index 5 --shard-count 4")), Some(None));
+        assert_eq!(shard_from_args(args("tests --shard-index 0 --shard-count 4")), Some(None));
+        assert_eq!(shard_from_args(args("tests --shard-index 1")), Some(None));
+        assert_eq!(shard_from_args(args("tests --shard-count 4 --shard-index")), Some(None));
+        assert_eq!(shard_from_args(args("tests --shard-index one --shard-count 4")), Some(None));
+    }
+
+    #[test]
+    fn test_config_shard_builder() {
+        assert_eq!(Config::from_env(|_| None).shard(3, 3).shard, Shard::new(3, 3));
+        assert_eq!(Config::from_env(|_| None).shard(0, 3).shard, None);
+    }
+
+    #[test]
+    fn test_config_panic_format_from_env() {
+        let with_format = |value: &'static str| Config::from_env(move |key| (key == ENV_PANIC_FORMAT).then(|| value.to_string()));
+
+        assert_eq!(Config::from_env(|_| None).panic_format, PanicFormat::Contextual);
+        assert_eq!(with_format("stable").panic_format, PanicFormat::Stable);
+        assert_eq!(with_format("Stable").panic_format, PanicFormat::Stable);
+        assert_eq!(with_format("contextual").panic_format, PanicFormat::Contextual);
+        assert_eq!(with_format("garbage").panic_format, PanicFormat::Contextual);
+    }
+
+    #[test]
+    fn test_config_symbols_from_env() {
+        let with_symbols = |value: &'static str| Config::from_env(move |key| (key == ENV_SYMBOLS).then(|| value.to_string()));
+        let with_locale = |locale: &'static str| Config::from_env(move |key| (key == "LANG").then(|| locale.to_string()));
+
+        assert!(!with_symbols("ascii").use_unicode_symbols);
+        assert!(with_symbols("Unicode").use_unicode_symbols);
+        assert!(with_symbols("auto").use_unicode_symbols);
+        assert!(with_symbols("garbage").use_unicode_symbols);
+        if cfg!(not(windows)) {
+            assert!(!with_locale("C").use_unicode_symbols);
+            assert!(with_locale("en_US.UTF-8").use_unicode_symbols);
+        }
+    }
+
+    #[test]
+    fn test_config_colors_from_env() {
+        assert!(!Config::from_env(|key| (key == "NO_COLOR").then(|| "1".to_string())).use_colors);
+        assert!(!Config::from_env(|key| (key == "TERM").then(|| "dumb".to_string())).use_colors);
+    }
+
+    #[test]
+    fn test_config_vacuous_tests_from_env() {
+        let with_handling = |value: &'static str| Config::from_env(move |key| (key == ENV_VACUOUS_TESTS).then(|| value.to_string()));
+
+        assert_eq!(Config::from_env(|_| None).vacuous_tests, VacuousTests::Allow);
+        assert_eq!(with_handling("warn").vacuous_tests, VacuousTests::Warn);
+        assert_eq!(with_handling("FAIL").vacuous_tests, VacuousTests::Fail);
+        assert_eq!(with_handling("allow").vacuous_tests, VacuousTests::Allow);
+        assert_eq!(with_handling("garbage").vacuous_tests, VacuousTests::Allow);
+        assert_eq!(Config::from_env(|_| None).vacuous_tests(VacuousTests::Fail).vacuous_tests, VacuousTests::Fail);
+    }
+
+    #[test]
+    fn test_config_type_names_from_env() {
+        let with_names = |value: &'static str| Config::from_env(move |key| (key == ENV_TYPE_NAMES).then(|| value.to_string()));
+
+        assert_eq!(Config::from_env(|_| None).type_names, TypeNames::Abbreviated);
+        assert_eq!(with_names("full").type_names, TypeNames::Full);
+        assert_eq!(with_names("Abbreviated").type_names, TypeNames::Abbreviated);
+        assert_eq!(with_names("garbage").type_names, TypeNames::Abbreviated);
+        assert_eq!(Config::from_env(|_| None).type_names(TypeNames::Full).type_names, TypeNames::Full);
+    }
+
+    #[test]
+    fn test_config_shuffle_matchers_from_env() {
+        let with_shuffle = |value: &'static str| Config::from_env(move |key| (key == ENV_SHUFFLE_MATCHERS).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).shuffle_matchers);
+        assert!(with_shuffle("1").shuffle_matchers);
+        assert!(!with_shuffle("off").shuffle_matchers);
+        assert!(Config::from_env(|_| None).shuffle_matchers(true).clone().shuffle_matchers);
+    }
+
+    #[test]
+    fn test_config_float_format_from_env() {
+        let config = Config::from_env(|key| match key {
+            ENV_FLOAT_PRECISION => Some("3".to_string()),
+            ENV_SCIENTIFIC_THRESHOLD => Some("4".to_string()),
+            _ => None,
+        });
+        assert_eq!(config.float_format, FloatFormat { precision: Some(3), scientific_threshold: 4 });
+
+        assert_eq!(Config::from_env(|_| None).float_format, FloatFormat::default());
+        assert_eq!(Config::from_env(|key| (key == ENV_FLOAT_PRECISION).then(|| "many".to_string())).float_format.precision, None);
+        assert_eq!(
+            Config::from_env(|_| None).float_precision(0).scientific_threshold(3).float_format,
+            FloatFormat { precision: Some(1), scientific_threshold: 3 }
+        );
+    }
+
+    #[test]
+    fn test_config_update_snapshots_from_env() {
+        let with_update = |value: &'static str| Config::from_env(move |key| (key == ENV_UPDATE_SNAPSHOTS).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).update_snapshots);
+        assert!(with_update("yes").update_snapshots);
+        assert!(Config::from_env(|_| None).update_snapshots(true).clone().update_snapshots);
+    }
+
+    #[test]
+    fn test_config_assertion_coverage_from_env() {
+        let with_coverage = |value: &'static str| Config::from_env(move |key| (key == ENV_ASSERTION_COVERAGE).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).assertion_coverage);
+        assert!(with_coverage("1").assertion_coverage);
+        assert!(Config::from_env(|_| None).assertion_coverage(true).clone().assertion_coverage);
+    }
+
+    #[test]
+    fn test_config_matcher_docs_from_env() {
+        let with_docs = |value: &'static str| Config::from_env(move |key| (key == ENV_MATCHER_DOCS).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).matcher_docs);
+        assert!(with_docs("true").matcher_docs);
+        assert!(Config::from_env(|_| None).matcher_docs(true).clone().matcher_docs);
+    }
+
+    #[test]
+    fn test_config_module_progress_from_env() {
+        let with_progress = |value: &'static str| Config::from_env(move |key| (key == ENV_MODULE_PROGRESS).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).module_progress);
+        assert!(with_progress("1").module_progress);
+        assert!(Config::from_env(|_| None).module_progress(true).clone().module_progress);
+    }
+
+    #[test]
+    fn test_config_capture_logs_from_env() {
+        let with_capture = |value: &'static str| Config::from_env(move |key| (key == ENV_CAPTURE_LOGS).then(|| value.to_string()));
+
+        assert!(!Config::from_env(|_| None).capture_logs);
+        assert!(with_capture("on").capture_logs);
+        assert!(Config::from_env(|_| None).capture_logs(true).clone().capture_logs);
+    }
+
+    #[test]
+    fn test_config_panic_format_builder() {
+        let config = Config::from_env(|_| None).panic_format(PanicFormat::Stable);
+        assert_eq!(config.panic_format, PanicFormat::Stable);
+        assert_eq!(config.clone().panic_format, PanicFormat::Stable);
+    }
+
     #[test]
     fn test_bool_from_str() {
-        assert_eq!(bool_from_str("true", false), true);
-        assert_eq!(bool_from_str("false", true), false);
-        assert_eq!(bool_from_str("1", false), true);
-        assert_eq!(bool_from_str("0", true), false);
-        assert_eq!(bool_from_str("yes", false), true);
-        assert_eq!(bool_from_str("no", true), false);
-        assert_eq!(bool_from_str("on", false), true);
-        assert_eq!(bool_from_str("off", true), false);
-        assert_eq!(bool_from_str("invalid", true), true);
-        assert_eq!(bool_from_str("invalid", false), false);
+        assert!(bool_from_str(ENV_ENHANCED_OUTPUT, "true", false));
+        assert!(!bool_from_str(ENV_ENHANCED_OUTPUT, "false", true));
+        assert!(bool_from_str(ENV_ENHANCED_OUTPUT, "1", false));
+        assert!(!bool_from_str(ENV_ENHANCED_OUTPUT, "0", true));
+        assert!(bool_from_str(ENV_ENHANCED_OUTPUT, "yes", false));
+        assert!(!bool_from_str(ENV_ENHANCED_OUTPUT, "no", true));
+        assert!(bool_from_str(ENV_ENHANCED_OUTPUT, "on", false));
+        assert!(!bool_from_str(ENV_ENHANCED_OUTPUT, "off", true));
+        assert!(bool_from_str(ENV_ENHANCED_OUTPUT, "invalid", true));
+        assert!(!bool_from_str(ENV_ENHANCED_OUTPUT, "invalid", false));
     }
 }
@@ -1,24 +1,71 @@
 use crate::backend::Assertion;
+use crate::backend::ModuleStats;
+use crate::backend::artifacts::Artifact;
 use std::cell::RefCell;
+use std::sync::LazyLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::LocalKey;
+use std::time::{Duration, Instant, SystemTime};
 
 /// Event types that can be emitted within the testing system
+///
+/// New kinds of events may be added in minor releases, so matches need a wildcard arm.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub enum AssertionEvent {
     /// A successful assertion
     Success(Assertion<()>),
     /// A failed assertion
     Failure(Assertion<()>),
+    /// A failed assertion reported as a warning
+    Warning(Assertion<()>),
+    /// A file attached to the current test
+    ArtifactAttached(Artifact),
+    /// The last registered test of a module completed, with the outcomes of the module's tests
+    ModuleCompleted(&'static str, ModuleStats),
     /// Test session completed
     SessionCompleted,
 }
 
+/// When and in which order an event was emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventMetadata {
+    /// Position of the event among the events of the test binary, increasing across threads
+    pub sequence: u64,
+    /// Wall-clock time of the emission
+    pub timestamp: SystemTime,
+    /// Time since the first event of the test binary, from a monotonic clock, for latencies between events
+    pub elapsed: Duration,
+}
+
+impl EventMetadata {
+    /// Stamp an event being emitted with the next sequence number
+    fn next() -> Self {
+        let sequence = NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        return Self { sequence, timestamp: SystemTime::now(), elapsed: EVENTS_START.elapsed() };
+    }
+}
+
+// Sequence number of the next event emitted by any thread
+static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+// Reference point of the monotonic clock measuring event times
+static EVENTS_START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
 // Thread-local registry of success handlers
 // Define type aliases to reduce complexity
 type AssertionHandler = Box<dyn Fn(Assertion<()>)>;
+type ArtifactHandler = Box<dyn Fn(Artifact)>;
+type ModuleHandler = Box<dyn Fn(&'static str, ModuleStats)>;
+type EventHandler = Box<dyn Fn(&AssertionEvent, &EventMetadata)>;
 
 thread_local! {
+    static EVENT_HANDLERS: RefCell<Vec<EventHandler>> = RefCell::new(Vec::new());
     static SUCCESS_HANDLERS: RefCell<Vec<AssertionHandler>> = RefCell::new(Vec::new());
     static FAILURE_HANDLERS: RefCell<Vec<AssertionHandler>> = RefCell::new(Vec::new());
+    static WARNING_HANDLERS: RefCell<Vec<AssertionHandler>> = RefCell::new(Vec::new());
+    static ARTIFACT_HANDLERS: RefCell<Vec<ArtifactHandler>> = RefCell::new(Vec::new());
+    static MODULE_COMPLETED_HANDLERS: RefCell<Vec<ModuleHandler>> = RefCell::new(Vec::new());
     static SESSION_COMPLETED_HANDLERS: RefCell<Vec<Box<dyn Fn()>>> = RefCell::new(Vec::new());
     static INITIALIZED: RefCell<bool> = const { RefCell::new(false) };
 }
@@ -39,48 +86,63 @@ impl EventEmitter {
 
     /// Emit an event to all registered handlers
     ///
-    /// Handlers are temporarily taken out of the registry before being called,
-    /// so the RefCell borrow is not held during handler execution. This allows
-    /// handlers to safely trigger code that registers new handlers (e.g.
-    /// Assertion::drop → initialize() → Reporter::init() → on_success()).
-    pub fn emit(event: AssertionEvent) {
+    /// The event is stamped with its metadata first: handlers registered with `on_event` get it alongside the event,
+    /// and the assertions handed to success, failure and warning handlers carry it as well.
+    pub fn emit(mut event: AssertionEvent) {
+        let metadata = EventMetadata::next();
+
+        // Copies handed to handlers must not evaluate the assertion again when dropped
+        if let AssertionEvent::Success(assertion) | AssertionEvent::Failure(assertion) | AssertionEvent::Warning(assertion) = &mut event {
+            assertion.evaluated = true;
+            assertion.event = Some(metadata);
+        }
+
+        dispatch(&EVENT_HANDLERS, |handler| handler(&event, &metadata));
+
         match event {
-            AssertionEvent::Success(mut assertion) => {
-                assertion.evaluated = true;
-                SUCCESS_HANDLERS.with(|cell| {
-                    let taken = cell.replace(Vec::new());
-                    for handler in taken.iter() {
-                        handler(assertion.clone());
-                    }
-                    let mut new_during_emit = cell.replace(taken);
-                    cell.borrow_mut().append(&mut new_during_emit);
-                });
-            }
-            AssertionEvent::Failure(mut assertion) => {
-                assertion.evaluated = true;
-                FAILURE_HANDLERS.with(|cell| {
-                    let taken = cell.replace(Vec::new());
-                    for handler in taken.iter() {
-                        handler(assertion.clone());
-                    }
-                    let mut new_during_emit = cell.replace(taken);
-                    cell.borrow_mut().append(&mut new_during_emit);
-                });
-            }
-            AssertionEvent::SessionCompleted => {
-                SESSION_COMPLETED_HANDLERS.with(|cell| {
-                    let taken = cell.replace(Vec::new());
-                    for handler in taken.iter() {
-                        handler();
-                    }
-                    let mut new_during_emit = cell.replace(taken);
-                    cell.borrow_mut().append(&mut new_during_emit);
-                });
+            AssertionEvent::Success(assertion) => dispatch(&SUCCESS_HANDLERS, |handler| handler(assertion.clone())),
+            AssertionEvent::Failure(assertion) => dispatch(&FAILURE_HANDLERS, |handler| handler(assertion.clone())),
+            AssertionEvent::Warning(assertion) => dispatch(&WARNING_HANDLERS, |handler| handler(assertion.clone())),
+            AssertionEvent::ArtifactAttached(artifact) => dispatch(&ARTIFACT_HANDLERS, |handler| handler(artifact.clone())),
+            AssertionEvent::ModuleCompleted(module_path, stats) => {
+                dispatch(&MODULE_COMPLETED_HANDLERS, |handler| handler(module_path, stats))
             }
+            AssertionEvent::SessionCompleted => dispatch(&SESSION_COMPLETED_HANDLERS, |handler| handler()),
         }
     }
 }
 
+/// Call every handler of a registry
+///
+/// Handlers are temporarily taken out of the registry before being called,
+/// so the RefCell borrow is not held during handler execution. This allows
+/// handlers to safely trigger code that registers new handlers (e.g.
+/// Assertion::drop → initialize() → Reporter::init() → on_success()).
+fn dispatch<H>(registry: &'static LocalKey<RefCell<Vec<H>>>, call: impl Fn(&H)) {
+    registry.with(|cell| {
+        let taken = cell.replace(Vec::new());
+        for handler in taken.iter() {
+            call(handler);
+        }
+        let mut new_during_emit = cell.replace(taken);
+        cell.borrow_mut().append(&mut new_during_emit);
+    });
+}
+
+/// Register a handler for every event, with its sequence number and emission times
+///
+/// Subscribers can order the events of all threads by sequence number, tell events apart without comparing them, and
+/// compute the latency between assertions from the monotonic `elapsed` time. The assertions of success, failure and
+/// warning events carry the same metadata, from `Assertion::event_metadata`.
+pub fn on_event<F>(handler: F)
+where
+    F: Fn(&AssertionEvent, &EventMetadata) + 'static,
+{
+    EVENT_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(Box::new(handler));
+    });
+}
+
 /// Register a handler for success events
 pub fn on_success<F>(handler: F)
 where
@@ -101,6 +163,36 @@ where
     });
 }
 
+/// Register a handler for warning events
+pub fn on_warning<F>(handler: F)
+where
+    F: Fn(Assertion<()>) + 'static,
+{
+    WARNING_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(Box::new(handler));
+    });
+}
+
+/// Register a handler for artifacts attached to the current test
+pub fn on_artifact<F>(handler: F)
+where
+    F: Fn(Artifact) + 'static,
+{
+    ARTIFACT_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(Box::new(handler));
+    });
+}
+
+/// Register a handler for module completion events, called with the module path and the outcomes of its tests
+pub fn on_module_completed<F>(handler: F)
+where
+    F: Fn(&'static str, ModuleStats) + 'static,
+{
+    MODULE_COMPLETED_HANDLERS.with(|handlers| {
+        handlers.borrow_mut().push(Box::new(handler));
+    });
+}
+
 /// Register a handler for session completion events
 pub fn on_session_completed<F>(handler: F)
 where
@@ -116,8 +208,12 @@ where
 /// the same thread.
 #[cfg(test)]
 pub fn reset_handlers() {
+    EVENT_HANDLERS.with(|h| h.borrow_mut().clear());
     SUCCESS_HANDLERS.with(|h| h.borrow_mut().clear());
     FAILURE_HANDLERS.with(|h| h.borrow_mut().clear());
+    WARNING_HANDLERS.with(|h| h.borrow_mut().clear());
+    ARTIFACT_HANDLERS.with(|h| h.borrow_mut().clear());
+    MODULE_COMPLETED_HANDLERS.with(|h| h.borrow_mut().clear());
     SESSION_COMPLETED_HANDLERS.with(|h| h.borrow_mut().clear());
 }
 
@@ -142,7 +238,12 @@ mod tests {
     // Create a test assertion
     fn create_test_assertion() -> Assertion<()> {
         let mut assertion = Assertion::new((), "test_value");
-        assertion.steps.push(AssertionStep { sentence: AssertionSentence::new("be", "test assertion"), passed: true, logical_op: None });
+        assertion.steps.push(AssertionStep {
+            sentence: AssertionSentence::new("be", "test assertion"),
+            passed: true,
+            logical_op: None,
+            location: None,
+        });
         assertion
     }
 
@@ -217,6 +318,36 @@ mod tests {
         assert_eq!(*called_clone.borrow(), true);
     }
 
+    #[test]
+    fn test_on_artifact_handler() {
+        reset_handlers();
+        let names = Rc::new(RefCell::new(Vec::new()));
+        let names_clone = names.clone();
+
+        on_artifact(move |artifact| {
+            names.borrow_mut().push(artifact.name);
+        });
+
+        crate::backend::artifacts::attach_artifact("trace", "/tmp/trace.json");
+
+        assert_eq!(*names_clone.borrow(), vec!["trace".to_string()]);
+    }
+
+    #[test]
+    fn test_on_module_completed_handler() {
+        reset_handlers();
+        let completed = Rc::new(RefCell::new(Vec::new()));
+        let completed_clone = completed.clone();
+
+        on_module_completed(move |module_path, stats| {
+            completed.borrow_mut().push((module_path, stats.passed));
+        });
+
+        EventEmitter::emit(AssertionEvent::ModuleCompleted("matchers::numeric", ModuleStats { passed: 34, failed: 0 }));
+
+        assert_eq!(*completed_clone.borrow(), vec![("matchers::numeric", 34)]);
+    }
+
     #[test]
     fn test_multiple_handlers() {
         reset_handlers();
@@ -260,6 +391,48 @@ mod tests {
         assert_eq!(*session_count_clone.borrow(), 3);
     }
 
+    #[test]
+    fn test_on_event_handler_gets_metadata() {
+        reset_handlers();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+
+        on_event(move |event, metadata| {
+            received_clone.borrow_mut().push((matches!(event, AssertionEvent::Failure(_)), *metadata));
+        });
+
+        let assertion = create_test_assertion();
+        EventEmitter::emit(AssertionEvent::Success(assertion.clone()));
+        EventEmitter::emit(AssertionEvent::Failure(assertion));
+        EventEmitter::emit(AssertionEvent::SessionCompleted);
+
+        let received = received.borrow();
+        assert_eq!(received.iter().map(|(is_failure, _)| *is_failure).collect::<Vec<_>>(), vec![false, true, false]);
+
+        // Other threads may emit in between, so sequence numbers only increase
+        let metadata = received.iter().map(|(_, metadata)| *metadata).collect::<Vec<_>>();
+        assert!(metadata.windows(2).all(|pair| pair[0].sequence < pair[1].sequence));
+        assert!(metadata.windows(2).all(|pair| pair[0].elapsed <= pair[1].elapsed));
+    }
+
+    #[test]
+    fn test_assertion_handlers_get_metadata() {
+        reset_handlers();
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let from_events = Rc::new(RefCell::new(Vec::new()));
+
+        let received_clone = received.clone();
+        on_failure(move |assertion| received_clone.borrow_mut().push(assertion.event_metadata()));
+        let from_events_clone = from_events.clone();
+        on_event(move |_, metadata| from_events_clone.borrow_mut().push(Some(*metadata)));
+
+        EventEmitter::emit(AssertionEvent::Failure(create_test_assertion()));
+
+        assert!(received.borrow()[0].is_some());
+        assert_eq!(*received.borrow(), *from_events.borrow());
+        assert_eq!(create_test_assertion().event_metadata(), None);
+    }
+
     #[test]
     fn test_assertion_event_debug() {
         reset_handlers();
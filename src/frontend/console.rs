@@ -1,17 +1,41 @@
 use crate::backend::LogicalOp;
-use crate::backend::{Assertion, TestSessionResult};
+use crate::backend::artifacts::{Artifact, attached_artifacts};
+use crate::backend::assertions::coverage::CoverageReport;
+use crate::backend::expected_failure::ExpectedFailureRecord;
+use crate::backend::run::{ENV_SEED, current_run};
+use crate::backend::shard::Shard;
+use crate::backend::{Assertion, ModuleStats, TestSessionResult};
 use crate::config::Config;
+use crate::frontend::Symbols;
 use colored::*;
 
 /// Handles rendering of test results to the console
+///
+/// The `render_*` methods return the text printed by the `print_*` ones, so that custom themes and renderers built
+/// on them can be golden-tested. [`ConsoleRenderer::plain`] renders the same text on every machine and every run.
 pub struct ConsoleRenderer {
     config: Config,
+    show_run_info: bool,
 }
 
 impl ConsoleRenderer {
     /// Create a new renderer with the provided configuration
     pub fn new(config: Config) -> Self {
-        Self { config }
+        Self { config, show_run_info: true }
+    }
+
+    /// Create a renderer with deterministic output, for golden tests
+    ///
+    /// Colors are disabled, steps are marked with Unicode symbols, the settings read from environment variables keep
+    /// their defaults, and the session summary leaves out the run ID, start time and commit.
+    pub fn plain() -> Self {
+        Self { config: Config::from_env(|_| None).use_colors(false).use_unicode_symbols(true), show_run_info: false }
+    }
+
+    /// Show or hide the run ID, start time and commit at the top of the session summary
+    pub fn with_run_info(mut self, show: bool) -> Self {
+        self.show_run_info = show;
+        self
     }
 
     /// Render a successful assertion result
@@ -19,11 +43,11 @@ impl ConsoleRenderer {
         let message = self.build_assertion_message(result);
 
         if self.config.show_success_details {
-            let prefix = if self.config.use_unicode_symbols { "✓ " } else { "+ " };
+            let prefix = self.symbols().passed;
             if self.config.use_colors {
-                return format!("{}{}", prefix.green(), message.green());
+                return format!("{} {}", prefix.green(), message.green());
             } else {
-                return format!("{}{}", prefix, message);
+                return format!("{} {}", prefix, message);
             }
         } else {
             return String::new(); // Empty string when not showing success details
@@ -33,39 +57,58 @@ impl ConsoleRenderer {
     /// Render a failed assertion result
     pub fn render_failure(&self, result: &Assertion<()>) -> (String, String) {
         let message = self.build_assertion_message(result);
-        let details = self.build_failure_details(result);
+        let details = self.build_details(result);
 
-        let prefix = if self.config.use_unicode_symbols { "✗ " } else { "- " };
-        let header = if self.config.use_colors { format!("{}{}", prefix, message.red().bold()) } else { format!("{}{}", prefix, message) };
+        let prefix = self.symbols().failed;
+        let header =
+            if self.config.use_colors { format!("{} {}", prefix, message.red().bold()) } else { format!("{} {}", prefix, message) };
 
         return (header, details);
     }
 
-    /// Build a failure details string
-    fn build_failure_details(&self, result: &Assertion<()>) -> String {
-        let mut details = String::new();
+    /// Render a failed warning assertion
+    pub fn render_warning(&self, result: &Assertion<()>) -> (String, String) {
+        let message = self.build_assertion_message(result);
+        let details = self.build_details(result);
 
-        // Add individual step results with proper formatting
-        for step in &result.steps {
-            let result_symbol = if step.passed { "✓" } else { "✗" };
-            // For individual steps, conjugate based on the subject name
-            let formatted_sentence = if step.passed {
-                step.sentence.format_with_conjugation(result.expr_str)
-            } else {
-                // On failure, append the actual value for better diagnostics
-                let base = step.sentence.format_with_conjugation(result.expr_str);
-                if let Some(ref actual) = step.sentence.actual_value { format!("{} (got {})", base, actual) } else { base }
-            };
+        let prefix = self.symbols().warning;
+        let header =
+            if self.config.use_colors { format!("{} {}", prefix, message.yellow().bold()) } else { format!("{} {}", prefix, message) };
 
-            // Always indent and add pass/fail prefix
-            details.push_str(&format!("  {} {}\n", result_symbol, formatted_sentence));
-        }
+        return (header, details);
+    }
 
+    /// Symbols marking passed and failed steps and warnings, Unicode or ASCII as configured
+    fn symbols(&self) -> Symbols {
+        return Symbols::new(self.config.use_unicode_symbols);
+    }
+
+    /// Replay the steps of an assertion, followed by the type of its value
+    fn build_details(&self, result: &Assertion<()>) -> String {
+        let mut details = result.format_step_details_with(self.symbols());
+        if let Some(value_type) = result.describe_type(self.config.type_names) {
+            details.push_str(&format!("  type: {}\n", value_type));
+        }
         return details;
     }
 
-    /// Build the main assertion message
+    /// Render the artifacts attached to a test, one indented line each
+    pub fn render_artifacts(&self, artifacts: &[Artifact]) -> String {
+        return artifacts.iter().map(|artifact| format!("  {}", artifact.describe())).collect::<Vec<_>>().join("\n");
+    }
+
+    /// Build the main assertion message, prefixed with its context if any
     fn build_assertion_message(&self, result: &Assertion<()>) -> String {
+        let message = self.build_chain_message(result);
+
+        return match result.context {
+            Some(ref context) => format!("{}, {}", context, message),
+            None => message,
+        };
+    }
+
+    /// Build the message describing the assertion chain
+    fn build_chain_message(&self, result: &Assertion<()>) -> String {
         if result.steps.is_empty() {
             return "No assertions made".to_string();
         }
@@ -73,13 +116,13 @@ impl ConsoleRenderer {
         // Clean expression string (remove reference symbols)
         let clean_expr = result.expr_str.trim_start_matches('&');
 
-        // For single assertions, conjugate based on the subject name
+        // For single assertions, conjugate based on the subject name, keeping multi-line objects on the headline
         if result.steps.len() == 1 {
-            return format!("{} {}", clean_expr, result.steps[0].sentence.format_with_conjugation(result.expr_str));
+            return format!("{} {}", clean_expr, result.steps[0].sentence.format_with_conjugation_inline(result.expr_str));
         }
 
         // Start with the first step and conjugate based on the subject
-        let mut message = format!("{} {}", clean_expr, result.steps[0].sentence.format_with_conjugation(result.expr_str));
+        let mut message = format!("{} {}", clean_expr, result.steps[0].sentence.format_with_conjugation_inline(result.expr_str));
 
         // Add remaining steps with logical operators
         for i in 1..result.steps.len() {
@@ -94,7 +137,7 @@ impl ConsoleRenderer {
 
             // For all subsequent parts in a chain, use conjugated verbs with grammatical format for consistency
             // This makes phrases like "is greater than X AND is less than Y" instead of "is greater than X AND be less than Y"
-            message.push_str(&format!("{}{}", op_str, curr.sentence.format_with_conjugation(result.expr_str)));
+            message.push_str(&format!("{}{}", op_str, curr.sentence.format_with_conjugation_inline(result.expr_str)));
         }
 
         return message;
@@ -102,19 +145,59 @@ impl ConsoleRenderer {
 
     /// Render a full test session result
     pub fn render_session_summary(&self, result: &TestSessionResult) -> String {
-        let mut output = String::from("\nTest Results:\n");
+        let mut output = match self.config.shard {
+            Some(shard) => format!("\nTest Results (shard {}):\n", shard),
+            None => String::from("\nTest Results:\n"),
+        };
+        if self.show_run_info {
+            output.push_str(&format!("  {}\n", current_run().summary()));
+            if self.config.shuffle_matchers {
+                output.push_str(&format!("  matchers shuffled with {}={}\n", ENV_SEED, current_run().seed));
+            }
+        }
 
         let passed_msg = format!("{} passed", result.passed_count);
         let failed_msg = format!("{} failed", result.failed_count);
+        let skipped_msg = format!("{} skipped", result.skipped_count);
+        let ignored_msg = format!("{} ignored", result.ignored_count);
+        let warning_msg = format!("{} {}", result.warning_count, if result.warning_count == 1 { "warning" } else { "warnings" });
 
         if self.config.use_colors {
             output.push_str(&format!(
-                "  {} / {}\n",
+                "  {} / {}",
                 if result.passed_count > 0 { passed_msg.green() } else { passed_msg.normal() },
                 if result.failed_count > 0 { failed_msg.red().bold() } else { failed_msg.normal() }
             ));
+            if result.skipped_count > 0 {
+                output.push_str(&format!(" / {}", skipped_msg.yellow()));
+            }
+            if result.ignored_count > 0 {
+                output.push_str(&format!(" / {}", ignored_msg.dimmed()));
+            }
+            if result.warning_count > 0 {
+                output.push_str(&format!(" / {}", warning_msg.yellow().bold()));
+            }
         } else {
-            output.push_str(&format!("  {} / {}\n", passed_msg, failed_msg));
+            output.push_str(&format!("  {} / {}", passed_msg, failed_msg));
+            if result.skipped_count > 0 {
+                output.push_str(&format!(" / {}", skipped_msg));
+            }
+            if result.ignored_count > 0 {
+                output.push_str(&format!(" / {}", ignored_msg));
+            }
+            if result.warning_count > 0 {
+                output.push_str(&format!(" / {}", warning_msg));
+            }
+        }
+        output.push('\n');
+
+        // libtest can't skip a test once started, so its own pass count includes the tests of other shards
+        if result.skipped_count > 0 {
+            let tests = if result.skipped_count == 1 { "test" } else { "tests" };
+            output.push_str(&format!(
+                "  {} skipped {} didn't run, although the test harness lists them as ok\n",
+                result.skipped_count, tests
+            ));
         }
 
         if result.failed_count > 0 {
@@ -130,6 +213,135 @@ impl ConsoleRenderer {
             }
         }
 
+        if result.warning_count > 0 {
+            output.push_str("\nWarning Details:\n");
+            for (i, warning) in result.warnings.iter().enumerate() {
+                let (header, details) = self.render_warning(warning);
+                output.push_str(&format!("  {}. {}\n", i + 1, header));
+
+                for line in details.lines() {
+                    output.push_str(&format!("     {}\n", line));
+                }
+            }
+        }
+
+        return output;
+    }
+
+    /// Render the progress line of a completed module: `backend::matchers::numeric: 34 passed / 1 failed`
+    ///
+    /// The module path is shown without the name of the crate, which is the test binary the line is printed by.
+    pub fn render_module_progress(&self, module_path: &str, stats: &ModuleStats) -> String {
+        let module = module_path.split_once("::").map_or(module_path, |(_, module)| module);
+        let passed_msg = format!("{} passed", stats.passed);
+        let failed_msg = format!("{} failed", stats.failed);
+
+        return match (self.config.use_colors, stats.failed > 0) {
+            (true, true) => format!("{}: {} / {}", module.bold(), passed_msg.green(), failed_msg.red().bold()),
+            (true, false) => format!("{}: {}", module.bold(), passed_msg.green()),
+            (false, true) => format!("{}: {} / {}", module, passed_msg, failed_msg),
+            (false, false) => format!("{}: {}", module, passed_msg),
+        };
+    }
+
+    /// Render the line of a test assigned to another shard: `api::test_login: skipped (not in shard 2/4)`
+    ///
+    /// The test harness can't skip a test once it started, so it lists the test as passed; this line tells them apart.
+    pub fn render_skipped_test(&self, module_path: &str, test_name: &str, shard: Shard) -> String {
+        let module = module_path.split_once("::").map_or(module_path, |(_, module)| module);
+        let skipped_msg = format!("skipped (not in shard {})", shard);
+
+        return match self.config.use_colors {
+            true => format!("{}::{}: {}", module.bold(), test_name.bold(), skipped_msg.yellow()),
+            false => format!("{}::{}: {}", module, test_name, skipped_msg),
+        };
+    }
+
+    /// Render the tests with the highest peak memory usage
+    #[cfg(feature = "memory-tracking")]
+    pub fn render_memory_report(&self, usage: &[crate::backend::memory::TestMemoryUsage]) -> String {
+        use crate::backend::memory::format_bytes;
+
+        if usage.is_empty() {
+            return String::new();
+        }
+
+        let title = "Top memory usage:";
+        let mut output = if self.config.use_colors { format!("{}\n", title.bold()) } else { format!("{}\n", title) };
+
+        for (i, entry) in usage.iter().enumerate() {
+            output.push_str(&format!(
+                "  {}. {} - peak {}, retained {}\n",
+                i + 1,
+                entry.test,
+                format_bytes(entry.peak_bytes),
+                format_bytes(entry.retained_bytes)
+            ));
+        }
+
+        return output;
+    }
+
+    /// Render the fixture-wrapped tests that completed without running any assertion
+    pub fn render_vacuous_report(&self, tests: &[String]) -> String {
+        if tests.is_empty() {
+            return String::new();
+        }
+
+        let title = "Tests without assertions:";
+        let mut output = if self.config.use_colors { format!("{}\n", title.yellow().bold()) } else { format!("{}\n", title) };
+
+        for (i, test) in tests.iter().enumerate() {
+            output.push_str(&format!("  {}. {}\n", i + 1, test));
+        }
+
+        return output;
+    }
+
+    /// Render the tests marked `#[expected_failure]` that failed as expected, with their reason and failure
+    pub fn render_expected_failure_report(&self, failures: &[ExpectedFailureRecord]) -> String {
+        if failures.is_empty() {
+            return String::new();
+        }
+
+        let title = "Expected failures:";
+        let mut output = if self.config.use_colors { format!("{}\n", title.bold()) } else { format!("{}\n", title) };
+
+        for (i, failure) in failures.iter().enumerate() {
+            output.push_str(&format!("  {}. {} ({})\n", i + 1, failure.test, failure.reason));
+            for line in failure.failure.lines() {
+                output.push_str(&format!("     {}\n", line));
+            }
+        }
+
+        return output;
+    }
+
+    /// Render the assertions executed per source file and test, then the modules where none ran
+    pub fn render_coverage_report(&self, report: &CoverageReport) -> String {
+        if report.files.is_empty() && report.uncovered_modules.is_empty() {
+            return String::new();
+        }
+
+        let title = "Assertion coverage:";
+        let mut output = if self.config.use_colors { format!("{}\n", title.bold()) } else { format!("{}\n", title) };
+
+        for file in &report.files {
+            let assertions = if file.assertions == 1 { "1 assertion".to_string() } else { format!("{} assertions", file.assertions) };
+            output.push_str(&format!("  {}: {}\n", file.file, assertions));
+            for test in &file.tests {
+                output.push_str(&format!("    {}: {}\n", test.test, test.assertions));
+            }
+        }
+
+        if !report.uncovered_modules.is_empty() {
+            let title = "Modules without assertions:";
+            output.push_str(&if self.config.use_colors { format!("{}\n", title.yellow().bold()) } else { format!("{}\n", title) });
+            for module in &report.uncovered_modules {
+                output.push_str(&format!("  {}\n", module));
+            }
+        }
+
         return output;
     }
 
@@ -141,9 +353,29 @@ impl ConsoleRenderer {
         }
     }
 
+    /// Print the progress line of a completed module to the console
+    ///
+    /// The line is written to the raw standard output handle, so that the test harness doesn't capture it with the
+    /// output of the module's last test.
+    pub fn print_module_progress(&self, module_path: &str, stats: &ModuleStats) {
+        use std::io::Write;
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", self.render_module_progress(module_path, stats)).and_then(|_| stdout.flush());
+    }
+
+    /// Print the line of a test assigned to another shard, bypassing the output capture of the test harness
+    pub fn print_skipped_test(&self, module_path: &str, test_name: &str, shard: Shard) {
+        use std::io::Write;
+
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}", self.render_skipped_test(module_path, test_name, shard)).and_then(|_| stdout.flush());
+    }
+
     /// Format and print a failed test result to the console
     pub fn print_failure(&self, result: &Assertion<()>) {
         let (header, details) = self.render_failure(result);
+        let symbols = self.symbols();
 
         // Print the main error message
         println!("{}", header);
@@ -151,9 +383,9 @@ impl ConsoleRenderer {
         // Print the details with appropriate colors
         if self.config.use_colors {
             for line in details.lines() {
-                if line.contains("✓") {
+                if line.contains(symbols.passed) {
                     println!("{}", line.green());
-                } else if line.contains("✗") {
+                } else if line.contains(symbols.failed) {
                     println!("{}", line.red());
                 } else {
                     println!("{}", line);
@@ -163,10 +395,249 @@ impl ConsoleRenderer {
             // Print without colors
             println!("{}", details);
         }
+
+        // List the files attached by the failing test
+        let artifacts = self.render_artifacts(&attached_artifacts());
+        if !artifacts.is_empty() {
+            println!("{}", if self.config.use_colors { artifacts.cyan().to_string() } else { artifacts });
+        }
+    }
+
+    /// Format and print a failed warning assertion to the console
+    pub fn print_warning(&self, result: &Assertion<()>) {
+        let (header, details) = self.render_warning(result);
+        let symbols = self.symbols();
+
+        println!("{}", header);
+        if self.config.use_colors {
+            for line in details.lines() {
+                if line.contains(symbols.passed) {
+                    println!("{}", line.green());
+                } else if line.contains(symbols.failed) {
+                    println!("{}", line.yellow());
+                } else {
+                    println!("{}", line);
+                }
+            }
+        } else {
+            println!("{}", details);
+        }
     }
 
     /// Print the complete test session summary
     pub fn print_session_summary(&self, result: &TestSessionResult) {
         println!("{}", self.render_session_summary(result));
+
+        let report = self.render_vacuous_report(&crate::backend::assertions::count::vacuous_tests());
+        if !report.is_empty() {
+            println!("{}", report);
+        }
+
+        let report = self.render_expected_failure_report(&crate::backend::expected_failure::expected_failures());
+        if !report.is_empty() {
+            println!("{}", report);
+        }
+
+        if self.config.assertion_coverage {
+            let report = self.render_coverage_report(&crate::backend::assertions::coverage::coverage_report());
+            if !report.is_empty() {
+                println!("{}", report);
+            }
+        }
+
+        #[cfg(feature = "memory-tracking")]
+        {
+            let report = self.render_memory_report(&crate::backend::memory::top_memory_tests(crate::backend::memory::TOP_MEMORY_TESTS));
+            if !report.is_empty() {
+                println!("{}", report);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::AssertionSentence;
+    use crate::config::TypeNames;
+
+    fn plain_renderer() -> ConsoleRenderer {
+        return ConsoleRenderer::plain();
+    }
+
+    // Create a failed assertion that won't evaluate on drop
+    fn failed_assertion(expr_str: &'static str, sentence: AssertionSentence) -> Assertion<()> {
+        let mut assertion = Assertion::new((), expr_str).add_step(sentence, false);
+        assertion.evaluated = true;
+        return assertion;
+    }
+
+    #[test]
+    fn test_multiline_values_are_indented_under_the_step() {
+        let sentence = AssertionSentence::new("equal", "Config {\n    retries: 3,\n}")
+            .with_actual("Config {\n    retries: 5,\n}")
+            .with_detail("[retries] expected 3, got 5");
+        let assertion = failed_assertion("config", sentence);
+
+        let (header, details) = plain_renderer().render_failure(&assertion);
+
+        assert_eq!(header, "✗ config equals Config { retries: 3, }");
+        assert_eq!(
+            details,
+            concat!(
+                "  ✗ equals:\n",
+                "        Config {\n",
+                "            retries: 3,\n",
+                "        }\n",
+                "      got:\n",
+                "        Config {\n",
+                "            retries: 5,\n",
+                "        }\n",
+                "      [retries] expected 3, got 5\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_single_line_values_stay_inline() {
+        let sentence = AssertionSentence::new("be", "greater than 42").with_actual("10");
+        let assertion = failed_assertion("value", sentence);
+
+        let (header, details) = plain_renderer().render_failure(&assertion);
+
+        assert_eq!(header, "✗ value is greater than 42");
+        assert_eq!(details, "  ✗ is greater than 42 (got 10)\n");
+    }
+
+    #[test]
+    fn test_colored_output_reads_the_same_without_ansi_codes() {
+        use crate::matchers::StringMatchers;
+
+        let assertion = failed_assertion("value", AssertionSentence::new("be", "greater than 42").with_actual("10"));
+        let (header, details) = ConsoleRenderer::new(Config::new().use_colors(true).use_unicode_symbols(true)).render_failure(&assertion);
+
+        crate::expect!(header).to_equal_ignoring_ansi("✗ value is greater than 42");
+        crate::expect!(details).to_contain_ignoring_ansi("is greater than 42 (got 10)");
+    }
+
+    #[test]
+    fn test_ascii_symbols_replace_unicode_ones() {
+        let assertion = failed_assertion("value", AssertionSentence::new("be", "greater than 42").with_actual("10"));
+        let renderer = ConsoleRenderer::new(Config::new().use_colors(false).use_unicode_symbols(false));
+
+        let (header, details) = renderer.render_failure(&assertion);
+        assert_eq!(header, "- value is greater than 42");
+        assert_eq!(details, "  - is greater than 42 (got 10)\n");
+
+        let (header, _) = renderer.render_warning(&assertion);
+        assert_eq!(header, "! value is greater than 42");
+    }
+
+    #[test]
+    fn test_module_progress_line() {
+        let renderer = plain_renderer();
+
+        let line = renderer.render_module_progress("rest::backend::matchers::numeric", &ModuleStats { passed: 34, failed: 0 });
+        assert_eq!(line, "backend::matchers::numeric: 34 passed");
+
+        let line = renderer.render_module_progress("fixtures_test", &ModuleStats { passed: 3, failed: 1 });
+        assert_eq!(line, "fixtures_test: 3 passed / 1 failed");
+    }
+
+    #[test]
+    fn test_skipped_test_line() {
+        let line = plain_renderer().render_skipped_test("suite::api", "test_login", Shard::new(2, 4).unwrap());
+        assert_eq!(line, "api::test_login: skipped (not in shard 2/4)");
+    }
+
+    #[test]
+    fn test_value_type_follows_the_steps() {
+        let mut assertion = failed_assertion("servers", AssertionSentence::new("have", "length 2").with_actual("3"));
+        assertion.type_name = std::any::type_name::<Vec<std::collections::HashMap<String, Vec<u8>>>>();
+        assertion.item_count = Some(3);
+
+        let (_, details) = plain_renderer().render_failure(&assertion);
+        assert_eq!(details, "  ✗ have length 2 (got 3)\n  type: Vec<HashMap<String, _>> (3 items)\n");
+
+        let (_, details) = ConsoleRenderer::new(Config::new().use_colors(false).type_names(TypeNames::Full)).render_failure(&assertion);
+        assert!(details.ends_with(
+            "type: alloc::vec::Vec<std::collections::hash::map::HashMap<alloc::string::String, alloc::vec::Vec<u8>>> (3 items)\n"
+        ));
+    }
+
+    #[test]
+    fn test_summary_shows_the_seed_of_shuffled_matchers() {
+        let renderer = ConsoleRenderer::new(Config::from_env(|_| None).use_colors(false).shuffle_matchers(true));
+
+        let summary = renderer.render_session_summary(&TestSessionResult::default());
+
+        assert!(summary.contains(&format!("  matchers shuffled with REST_SEED={}\n", current_run().seed)));
+        assert!(!plain_renderer().with_run_info(true).render_session_summary(&TestSessionResult::default()).contains("shuffled"));
+    }
+
+    #[test]
+    fn test_summary_counts_warnings_apart() {
+        let mut warning = failed_assertion("latency", AssertionSentence::new("be", "less than 200").with_actual("250"));
+        warning.severity = crate::backend::Severity::Warning;
+        let session = TestSessionResult { passed_count: 3, warning_count: 1, warnings: vec![warning], ..Default::default() };
+
+        let summary = plain_renderer().with_run_info(true).render_session_summary(&session);
+
+        assert!(summary.starts_with(&format!("\nTest Results:\n  {}\n  3 passed / 0 failed / 1 warning\n", current_run().summary())));
+        assert!(summary.contains("Warning Details:\n  1. ⚠ latency is less than 200\n       ✗ is less than 200 (got 250)\n"));
+        assert!(!summary.contains("Failure Details"));
+    }
+
+    #[test]
+    fn test_coverage_report_lists_files_then_uncovered_modules() {
+        use crate::backend::assertions::coverage::{FileCoverage, TestCoverage};
+
+        let report = CoverageReport {
+            files: vec![FileCoverage {
+                file: "tests/cart_test.rs",
+                assertions: 3,
+                tests: vec![
+                    TestCoverage { test: "cart_test::test_add".to_string(), assertions: 2 },
+                    TestCoverage { test: "cart_test::test_remove".to_string(), assertions: 1 },
+                ],
+            }],
+            uncovered_modules: vec!["cart_test::legacy"],
+        };
+
+        assert_eq!(
+            plain_renderer().render_coverage_report(&report),
+            "Assertion coverage:\n  tests/cart_test.rs: 3 assertions\n    cart_test::test_add: 2\n    cart_test::test_remove: 1\n\
+             Modules without assertions:\n  cart_test::legacy\n"
+        );
+        assert_eq!(plain_renderer().render_coverage_report(&CoverageReport::default()), "");
+    }
+
+    #[test]
+    fn test_expected_failures_are_listed_with_their_reason() {
+        let failures = vec![ExpectedFailureRecord {
+            test: "suite::test_total".to_string(),
+            reason: "issue #42".to_string(),
+            failure: "expected total to be equal to 0.3, got 0.30000000000000004".to_string(),
+        }];
+
+        assert_eq!(
+            plain_renderer().render_expected_failure_report(&failures),
+            "Expected failures:\n  1. suite::test_total (issue #42)\n     expected total to be equal to 0.3, got 0.30000000000000004\n"
+        );
+        assert_eq!(plain_renderer().render_expected_failure_report(&[]), "");
+    }
+
+    #[test]
+    fn test_artifacts_are_listed_one_per_line() {
+        let artifacts = vec![
+            Artifact { name: "screenshot".to_string(), test: None, path: "/tmp/login.png".into() },
+            Artifact { name: "heap dump".to_string(), test: None, path: "/tmp/heap.bin".into() },
+        ];
+
+        assert_eq!(
+            plain_renderer().render_artifacts(&artifacts),
+            "  artifact screenshot: /tmp/login.png\n  artifact heap dump: /tmp/heap.bin"
+        );
+        assert_eq!(plain_renderer().render_artifacts(&[]), "");
     }
 }
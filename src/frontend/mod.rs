@@ -1,6 +1,8 @@
 //! Frontend module for rendering test results
 
 mod console;
+pub(crate) mod terminal;
 
 pub use crate::backend::{Assertion, AssertionStep, TestSessionResult};
 pub use console::ConsoleRenderer;
+pub use terminal::Symbols;
@@ -0,0 +1,176 @@
+//! Detection of what the terminal can display
+//!
+//! Legacy Windows consoles print the ✓, ✗ and ⚠ symbols as garbage unless their code page is UTF-8, and ignore ANSI
+//! colors unless virtual terminal processing can be enabled. Unix terminals display what their locale encodes.
+//! [`Config`](crate::Config) uses these checks for its defaults, which `REST_SYMBOLS`, `NO_COLOR`, `CLICOLOR_FORCE`
+//! and the `use_unicode_symbols` / `use_colors` builder methods override.
+
+/// Symbols marking passed and failed steps and warnings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbols {
+    /// Marks passed assertions and steps
+    pub passed: &'static str,
+    /// Marks failed assertions and steps
+    pub failed: &'static str,
+    /// Marks failed warning assertions
+    pub warning: &'static str,
+}
+
+impl Symbols {
+    /// Unicode symbols: ✓, ✗ and ⚠
+    pub const UNICODE: Symbols = Symbols { passed: "✓", failed: "✗", warning: "⚠" };
+
+    /// ASCII fallbacks for terminals that can't display the Unicode symbols: +, - and !
+    pub const ASCII: Symbols = Symbols { passed: "+", failed: "-", warning: "!" };
+
+    /// Symbols for a configuration with or without Unicode symbols
+    pub fn new(unicode: bool) -> Self {
+        return if unicode { Self::UNICODE } else { Self::ASCII };
+    }
+}
+
+/// Check if the terminal displays Unicode symbols, from the environment and the console of the process
+pub(crate) fn supports_unicode(get_var: &impl Fn(&str) -> Option<String>) -> bool {
+    #[cfg(windows)]
+    return is_modern_windows_terminal(get_var) || windows::output_is_utf8();
+
+    #[cfg(not(windows))]
+    return locale_is_utf8(get_var);
+}
+
+/// Check if the terminal displays ANSI colors, from the environment and the console of the process
+///
+/// Whether the output is a terminal at all, `CLICOLOR` and `CLICOLOR_FORCE` are left to the `colored` crate.
+pub(crate) fn supports_colors(get_var: &impl Fn(&str) -> Option<String>) -> bool {
+    if get_var("NO_COLOR").is_some_and(|value| !value.is_empty()) || get_var("TERM").is_some_and(|term| term == "dumb") {
+        return false;
+    }
+
+    #[cfg(windows)]
+    return is_modern_windows_terminal(get_var) || windows::enable_ansi_escapes();
+
+    #[cfg(not(windows))]
+    return true;
+}
+
+/// Check if the locale encodes characters in UTF-8, as set by the first of `LC_ALL`, `LC_CTYPE` and `LANG`
+///
+/// Without locale, as in most containers, the output is assumed to be read by a UTF-8 terminal or log viewer.
+#[cfg_attr(windows, allow(dead_code))]
+fn locale_is_utf8(get_var: &impl Fn(&str) -> Option<String>) -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].iter().filter_map(|key| get_var(key)).find(|value| !value.is_empty());
+
+    return match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => true,
+    };
+}
+
+/// Check if the process runs in a Windows terminal emulator known to display Unicode and ANSI colors
+///
+/// Windows Terminal, the VS Code terminal, ConEmu and the MSYS2 / Cygwin terminals, which set `TERM`.
+#[cfg_attr(not(windows), allow(dead_code))]
+fn is_modern_windows_terminal(get_var: &impl Fn(&str) -> Option<String>) -> bool {
+    return get_var("WT_SESSION").is_some()
+        || get_var("TERM_PROGRAM").is_some()
+        || get_var("ConEmuANSI").is_some_and(|value| value == "ON")
+        || get_var("TERM").is_some();
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::io::IsTerminal;
+    use std::os::windows::io::AsRawHandle;
+    use std::sync::OnceLock;
+
+    // Code page identifier of UTF-8
+    const CP_UTF8: u32 = 65001;
+
+    // Console mode flag making the console interpret ANSI escape sequences
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetConsoleOutputCP() -> u32;
+        fn GetConsoleMode(console: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: *mut c_void, mode: u32) -> i32;
+    }
+
+    /// Check if the console displays UTF-8 output; output redirected to a file or a pipe is written in UTF-8
+    pub(super) fn output_is_utf8() -> bool {
+        static UTF8: OnceLock<bool> = OnceLock::new();
+
+        return *UTF8.get_or_init(|| {
+            // SAFETY: GetConsoleOutputCP has no arguments and only reads the console of the process
+            return !std::io::stdout().is_terminal() || unsafe { GetConsoleOutputCP() } == CP_UTF8;
+        });
+    }
+
+    /// Enable the ANSI escape sequences of the console, and check if the console interprets them
+    ///
+    /// Legacy consoles, before Windows 10, refuse the mode. Output that isn't a console is left to `colored`.
+    pub(super) fn enable_ansi_escapes() -> bool {
+        static ENABLED: OnceLock<bool> = OnceLock::new();
+
+        return *ENABLED.get_or_init(|| {
+            let console = std::io::stdout().as_raw_handle();
+            let mut mode = 0;
+
+            // SAFETY: the handle is the standard output of the process, and `mode` outlives the call
+            if unsafe { GetConsoleMode(console, &mut mode) } == 0 {
+                return true;
+            }
+            if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+                return true;
+            }
+
+            // SAFETY: same handle, only adding a flag to the mode read above
+            return unsafe { SetConsoleMode(console, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) } != 0;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        return move |key| vars.iter().find(|(name, _)| *name == key).map(|(_, value)| value.to_string());
+    }
+
+    #[test]
+    fn test_utf8_locales() {
+        assert!(locale_is_utf8(&env(&[("LANG", "en_US.UTF-8")])));
+        assert!(locale_is_utf8(&env(&[("LC_ALL", "C.utf8"), ("LANG", "C")])));
+        assert!(locale_is_utf8(&env(&[])));
+    }
+
+    #[test]
+    fn test_other_locales() {
+        assert!(!locale_is_utf8(&env(&[("LANG", "C")])));
+        assert!(!locale_is_utf8(&env(&[("LC_CTYPE", "POSIX"), ("LANG", "en_US.UTF-8")])));
+        assert!(!locale_is_utf8(&env(&[("LC_ALL", "de_DE.ISO-8859-1")])));
+
+        // Empty variables don't select a locale
+        assert!(!locale_is_utf8(&env(&[("LC_ALL", ""), ("LANG", "fr_FR")])));
+    }
+
+    #[test]
+    fn test_colors_are_disabled_by_the_environment() {
+        assert!(!supports_colors(&env(&[("NO_COLOR", "1")])));
+        assert!(!supports_colors(&env(&[("TERM", "dumb")])));
+        assert!(supports_colors(&env(&[("NO_COLOR", "")])));
+    }
+
+    #[test]
+    fn test_modern_windows_terminals() {
+        assert!(is_modern_windows_terminal(&env(&[("WT_SESSION", "8c8a4f31")])));
+        assert!(is_modern_windows_terminal(&env(&[("ConEmuANSI", "ON")])));
+        assert!(!is_modern_windows_terminal(&env(&[("ConEmuANSI", "OFF")])));
+        assert!(!is_modern_windows_terminal(&env(&[])));
+    }
+}
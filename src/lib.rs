@@ -36,15 +36,22 @@ pub mod config;
 pub mod events;
 pub mod frontend;
 mod reporter;
+pub mod testkit;
 
 // Auto-initialize for tests if enhanced output is enabled
 pub fn auto_initialize_for_tests() {
     TEST_INIT.call_once(|| {
+        // In discovery mode, list the registered tests instead of running the first assertion
+        backend::discovery::dump_in_discovery_mode();
+
+        // Keep rendered assertion failures from being reported again by the panic hook
+        backend::panic_hook::install();
+
         // Check environment variable to enable enhanced output
         let config = config::Config::new();
         if config.enhanced_output {
-            // Apply the config which will initialize the event system
-            config.apply();
+            // Apply the config which will initialize the event system, keeping any explicit configuration
+            config.apply_default();
         }
     });
 }
@@ -53,7 +60,18 @@ pub fn auto_initialize_for_tests() {
 pub use config::initialize;
 
 // Export attribute macros for fixtures
-pub use rest_macros::{after_all, before_all, setup, tear_down, with_fixtures, with_fixtures_module};
+#[cfg(feature = "macros")]
+pub use rest_macros::{
+    after_all, allow_no_assertions, before_all, expected_failure, no_conjugation, setup, tear_down, with_fixtures, with_fixtures_module,
+};
+
+// Export the derive macro for property test inputs
+#[cfg(feature = "macros")]
+pub use rest_macros::RestArbitrary;
+
+// Load-time registration used by the code of the macros, so tests don't depend on ctor themselves
+#[doc(hidden)]
+pub use ctor as __ctor;
 
 // Global exit handler for after_all fixtures
 #[ctor::dtor]
@@ -63,27 +81,70 @@ fn run_after_all_fixtures() {
 
 /// Matcher traits module for bringing the traits into scope
 pub mod matchers {
+    #[cfg(feature = "archive")]
+    pub use crate::backend::matchers::archive::{ArchiveMatchers, EntryContent, containing};
     pub use crate::backend::matchers::boolean::BooleanMatchers;
+    pub use crate::backend::matchers::capacity::CapacityMatchers;
+    #[cfg(feature = "syn")]
+    pub use crate::backend::matchers::code::CodeMatchers;
     pub use crate::backend::matchers::collection::{CollectionExtensions, CollectionMatchers};
+    pub use crate::backend::matchers::condition::ConditionMatchers;
+    pub use crate::backend::matchers::control_flow::ControlFlowMatchers;
     pub use crate::backend::matchers::equality::EqualityMatchers;
+    pub use crate::backend::matchers::error::ErrorMatchers;
+    pub use crate::backend::matchers::failure::FailureMatchers;
+    pub use crate::backend::matchers::float::FloatSliceMatchers;
+    pub use crate::backend::matchers::frame::FrameMatchers;
+    pub use crate::backend::matchers::function::FunctionMatchers;
     pub use crate::backend::matchers::hashmap::HashMapMatchers;
-    pub use crate::backend::matchers::numeric::NumericMatchers;
+    #[cfg(feature = "image")]
+    pub use crate::backend::matchers::image::ImageMatchers;
+    #[cfg(feature = "json-schema")]
+    pub use crate::backend::matchers::json_schema::JsonSchemaMatchers;
+    #[cfg(feature = "log-capture")]
+    pub use crate::backend::matchers::logs::LogMatchers;
+    #[cfg(feature = "metrics-capture")]
+    pub use crate::backend::matchers::metrics::MetricMatchers;
+    pub use crate::backend::matchers::numeric::{CheckedArithmeticMatchers, NumericMatchers};
     pub use crate::backend::matchers::option::OptionMatchers;
+    pub use crate::backend::matchers::path::PathMatchers;
+    #[cfg(feature = "regex")]
+    pub use crate::backend::matchers::pattern::PatternMatchers;
+    pub use crate::backend::matchers::poll::PollMatchers;
+    #[cfg(feature = "prost")]
+    pub use crate::backend::matchers::protobuf::ProtobufMatchers;
     pub use crate::backend::matchers::result::ResultMatchers;
+    pub use crate::backend::matchers::round_trip::RoundTripMatchers;
+    pub use crate::backend::matchers::size::SizeMatchers;
+    #[cfg(feature = "sqlx")]
+    pub use crate::backend::matchers::sql::SqlMatchers;
     pub use crate::backend::matchers::string::StringMatchers;
+    #[cfg(feature = "unicode")]
+    pub use crate::backend::matchers::unicode::{Form, UnicodeMatchers};
 }
 
-/// Main prelude module containing everything needed for fluent testing
-pub mod prelude {
+/// Minimal prelude with the assertion macros, modifiers and fixtures, but no matcher trait
+///
+/// Large test suites can import it along with the matcher traits they use, to avoid method name collisions
+/// between matcher traits and the cost of resolving every matcher in every test. `custom_prelude!` builds a
+/// crate-wide prelude from it.
+pub mod prelude_minimal {
     pub use crate::backend::Assertion;
     pub use crate::expect;
+    pub use crate::expect_assertions;
+    pub use crate::expect_build;
+    pub use crate::expect_condition;
+    pub use crate::expect_err_matches;
+    pub use crate::expect_failure;
+    pub use crate::expect_fn;
     pub use crate::expect_not;
+    pub use crate::expect_warn;
 
     // Fixture attribute macros
-    pub use crate::{after_all, before_all, setup, tear_down, with_fixtures, with_fixtures_module};
-
-    // Import all matcher traits
-    pub use crate::matchers::*;
+    #[cfg(feature = "macros")]
+    pub use crate::{
+        after_all, allow_no_assertions, before_all, expected_failure, no_conjugation, setup, tear_down, with_fixtures, with_fixtures_module,
+    };
 
     // Import modifiers
     pub use crate::backend::modifiers::*;
@@ -91,13 +152,46 @@ pub mod prelude {
     // Import configuration and initialization
     pub use crate::config;
     pub use crate::initialize;
+}
+
+/// Main prelude module containing everything needed for fluent testing
+pub mod prelude {
+    pub use crate::prelude_minimal::*;
+
+    // Import all matcher traits
+    pub use crate::matchers::*;
+
+    // Polling futures by hand
+    pub use crate::backend::matchers::poll::{noop_context, poll_once};
+
+    // Property-based testing
+    #[cfg(feature = "macros")]
+    pub use crate::RestArbitrary;
+    pub use crate::backend::property::{Arbitrary, Gen, Property, for_all};
+
+    // Log capture
+    #[cfg(feature = "log-capture")]
+    pub use crate::backend::logs::{Level, capture_logs};
+    #[cfg(feature = "log-capture")]
+    pub use crate::expect_logs;
+
+    // Metrics capture
+    #[cfg(feature = "metrics-capture")]
+    pub use crate::backend::metrics::capture_metrics;
+    #[cfg(feature = "metrics-capture")]
+    pub use crate::expect_metric;
 
     #[cfg(test)]
     pub use crate::auto_initialize_for_tests;
 }
 
 // Re-exports
-pub use crate::config::Config;
+pub use crate::backend::artifacts::{Artifact, ArtifactContent, attach_artifact, attached_artifacts};
+pub use crate::backend::assertions::hook::{CallSite, FailureAction, clear_assertion_failure_hook, on_assertion_failure};
+pub use crate::backend::panic_hook::{TestPanicHook, clear_test_panic_hook, set_test_panic_hook};
+pub use crate::backend::run::{RunInfo, current_run};
+pub use crate::backend::session::Session;
+pub use crate::config::{Config, FloatFormat, PanicFormat, Plurality, TypeNames, VacuousTests};
 pub use crate::reporter::Reporter;
 
 /// Creates a new test configuration
@@ -112,7 +206,180 @@ macro_rules! expect {
         // Always auto-initialize
         $crate::auto_initialize_for_tests();
 
-        $crate::backend::Assertion::new($expr, stringify!($expr))
+        $crate::__new_assertion!($expr)
+    }};
+}
+
+/// Create the assertion of `expect!` and its variants, recording the number of items of collections
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __new_assertion {
+    ($expr:expr) => {
+        // Matching keeps the temporaries of the expression alive until the end of the enclosing statement
+        match $crate::backend::Assertion::new($expr, stringify!($expr)) {
+            assertion => {
+                #[allow(unused_imports)]
+                use $crate::backend::assertions::type_name::{BorrowedItems as _, NoItems as _, OwnedItems as _};
+
+                let item_count = (&&&$crate::backend::assertions::type_name::ItemProbe(&assertion.value)).item_count();
+                assertion.with_item_count(item_count)
+            }
+        }
+    };
+}
+
+/// Expectation whose failure is reported and counted as a warning, without failing the test
+///
+/// Useful for soft guardrails, such as performance budgets during a migration, that should be visible but not
+/// block the test suite. Same as `expect!(expr).as_warning()`.
+#[macro_export]
+macro_rules! expect_warn {
+    ($expr:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::__new_assertion!($expr).as_warning()
+    }};
+}
+
+/// Start an assertion on a condition polled until it holds or a timeout expires
+///
+/// The closure is called on every poll, so it can watch a flag set by another thread or a channel:
+/// `expect_condition!(|| flag.load(Ordering::SeqCst)).to_become_true_within(Duration::from_secs(1))`.
+/// Failures report the last polled value and the number of attempts.
+#[macro_export]
+macro_rules! expect_condition {
+    (|| $body:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::matchers::condition::Condition::new(|| $body), stringify!($body))
+    }};
+    (move || $body:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::matchers::condition::Condition::new(move || $body), stringify!($body))
+    }};
+    ($probe:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::matchers::condition::Condition::new($probe), stringify!($probe))
+    }};
+}
+
+/// Start an assertion on a pure function from a type to itself, checking algebraic properties over inputs
+///
+/// `expect_fn!(normalize).to_be_idempotent_for(inputs)` checks `normalize(normalize(x)) == normalize(x)` for every
+/// input and reports the first violating one. Matchers without the `_for` suffix generate their inputs like
+/// `for_all`, such as `expect_fn!(encode).to_commute_with(compress)`.
+#[macro_export]
+macro_rules! expect_fn {
+    ($function:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::matchers::function::PureFunction::new($function), stringify!($function))
+    }};
+}
+
+/// Assert that a result is an error matching a pattern, such as a variant of a `thiserror` enum
+///
+/// The match is reported like any other assertion: `result matches Err(MyError::NotFound { id: 42 })`, with the
+/// actual result on failure. Guards are supported, and `=> expr` evaluates an expression over the bindings of the
+/// pattern for follow-up assertions:
+///
+/// ```rust,ignore
+/// let id = expect_err_matches!(result, MyError::NotFound { id } => *id);
+/// expect!(id).to_be_greater_than(40);
+/// ```
+///
+/// The result is matched by reference, so bindings are references. When a failure doesn't panic, such as with a
+/// failure hook that continues, the form with bindings panics since it has no value to return.
+#[macro_export]
+macro_rules! expect_err_matches {
+    ($result:expr, $pattern:pat $(if $guard:expr)? => $bindings:expr $(,)?) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        let result = &$result;
+        let bindings = match result {
+            Err($pattern) $(if $guard)? => Some($bindings),
+            _ => None,
+        };
+        $crate::__report_err_match!(result, bindings.is_some(), $result, $pattern $(if $guard)?);
+
+        match bindings {
+            Some(bindings) => bindings,
+            None => panic!("expect_err_matches!: {} didn't match the pattern, no bindings to return", stringify!($result)),
+        }
+    }};
+    ($result:expr, $pattern:pat $(if $guard:expr)? $(,)?) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        let result = &$result;
+        let matched = matches!(result, Err($pattern) $(if $guard)?);
+        $crate::__report_err_match!(result, matched, $result, $pattern $(if $guard)?);
+    }};
+}
+
+/// Report the step of `expect_err_matches!`, evaluated when the assertion is dropped
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __report_err_match {
+    ($result:expr, $matched:expr, $expr:expr, $pattern:pat $(if $guard:expr)?) => {
+        drop($crate::backend::Assertion::new($result, stringify!($expr)).add_step_with($matched, |actual| {
+            let pattern = concat!("Err(", stringify!($pattern), ")" $(, " if ", stringify!($guard))?);
+            let actual = $crate::backend::matchers::result::describe_variant(actual.as_ref());
+            return $crate::backend::AssertionSentence::new("match", pattern).with_actual(actual);
+        }));
+    };
+}
+
+/// Run assertions that are expected to fail, to test matchers on their failing side
+///
+/// Assertions in the closure are neither reported nor counted, and the first one to fail is captured instead of
+/// failing the test. Unlike `#[should_panic]`, which only tells that something in the whole test panicked, the
+/// expectation covers the closure alone and can check the failure message:
+///
+/// ```rust,ignore
+/// expect_failure!(|| {
+///     expect!(x).to_equal(5);
+/// })
+/// .with_message_containing("equal to 5");
+/// ```
+///
+/// The closure passing, or panicking without a failed assertion, fails the expectation.
+#[macro_export]
+macro_rules! expect_failure {
+    (|| $body:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::matchers::FailureMatchers::to_fail($crate::backend::Assertion::new(
+            $crate::backend::matchers::failure::ExpectedFailure::capture(|| $body),
+            stringify!($body),
+        ))
+    }};
+    (move || $body:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::matchers::FailureMatchers::to_fail($crate::backend::Assertion::new(
+            $crate::backend::matchers::failure::ExpectedFailure::capture(move || $body),
+            stringify!($body),
+        ))
+    }};
+    ($closure:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::matchers::FailureMatchers::to_fail($crate::backend::Assertion::new(
+            $crate::backend::matchers::failure::ExpectedFailure::capture($closure),
+            stringify!($closure),
+        ))
     }};
 }
 
@@ -125,10 +392,117 @@ macro_rules! expect_not {
         $crate::auto_initialize_for_tests();
 
         use $crate::backend::modifiers::NotModifier;
-        $crate::backend::Assertion::new($expr, stringify!($expr)).not()
+        $crate::__new_assertion!($expr).not()
+    }};
+}
+
+/// Assert several properties of a built value as a single assertion chain
+///
+/// The value is built once, and every statement of the body gets its own assertion on a clone of it, bound to the
+/// name of the closure parameter:
+///
+/// ```rust,ignore
+/// expect_build!(UserBuilder::new().name("a").build(), |user| {
+///     user.to_have_name("a");
+///     user.to_be_active();
+/// });
+/// ```
+///
+/// The steps of the statements are folded into one chain, reported once with every step and its outcome:
+/// `UserBuilder::new().name("a").build() has name "a" AND is active`. Statements with an `or()` become a single
+/// `satisfy (... OR ...)` step. The macro returns the folded assertion, so the chain can go on with `and()`.
+#[macro_export]
+macro_rules! expect_build {
+    ($value:expr, |$subject:ident| { $($assertion:expr);* $(;)? } $(,)?) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        let value = $value;
+        let group = $crate::backend::assertions::group::AssertionGroup::begin();
+        $(
+            {
+                #[allow(unused_variables)]
+                let $subject = $crate::backend::Assertion::new(::core::clone::Clone::clone(&value), stringify!($value));
+                $assertion;
+            }
+        )*
+        group.finish($crate::backend::Assertion::new(value, stringify!($value)))
+    }};
+}
+
+/// Declare how many assertions the rest of the current scope must execute
+///
+/// The test fails with the actual count when the scope ends after fewer or more assertion chains
+/// ran on this thread, protecting against early returns and skipped loop bodies.
+#[macro_export]
+macro_rules! expect_assertions {
+    ($expected:expr) => {
+        let _rest_assertion_count = $crate::backend::assertions::count::AssertionCount::new($expected);
+    };
+}
+
+/// Start an assertion on the log records captured so far in the current test
+///
+/// Tests wrapped by `#[with_fixtures]` capture their logs with `config().capture_logs(true)`; other
+/// tests start a capture with `capture_logs()`.
+#[cfg(feature = "log-capture")]
+#[macro_export]
+macro_rules! expect_logs {
+    () => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::logs::captured_logs(), "logs")
+    }};
+}
+
+/// Start an assertion on a metric captured so far in the current test
+///
+/// Labels narrow the assertion to the matching series: `expect_metric!("requests_total", "status" => "200")`.
+/// Tests wrapped by `#[with_fixtures]` capture their metrics automatically; other tests start a
+/// capture with `capture_metrics()`.
+#[cfg(feature = "metrics-capture")]
+#[macro_export]
+macro_rules! expect_metric {
+    ($name:expr) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new($crate::backend::metrics::captured_metrics().metric($name), stringify!($name))
+    }};
+    ($name:expr, $($key:expr => $value:expr),+ $(,)?) => {{
+        // Always auto-initialize
+        $crate::auto_initialize_for_tests();
+
+        $crate::backend::Assertion::new(
+            $crate::backend::metrics::captured_metrics().metric_with_labels($name, &[$(($key, $value)),+]),
+            stringify!($name),
+        )
     }};
 }
 
+/// Build a prelude from the minimal prelude and a chosen set of matcher traits
+///
+/// Expands to `pub use` items, so it is meant to be the body of a module shared by the tests of a crate:
+///
+/// ```
+/// mod prelude {
+///     rest::custom_prelude!(EqualityMatchers, NumericMatchers, StringMatchers);
+/// }
+///
+/// use prelude::*;
+///
+/// expect!(2 + 2).to_equal(4);
+/// expect!("hello").to_start_with("he");
+/// ```
+#[macro_export]
+macro_rules! custom_prelude {
+    ($($matchers:ident),* $(,)?) => {
+        pub use $crate::prelude_minimal::*;
+        $(pub use $crate::matchers::$matchers;)*
+    };
+}
+
 /// Run all Rest tests in a module
 ///
 /// This can be used as a test harness to handle initialization
@@ -166,14 +540,46 @@ macro_rules! rest_test {
 #[cfg(test)]
 pub mod test_utils {
     // Just re-export all the traits for easy importing in tests
+    #[cfg(feature = "archive")]
+    pub use crate::backend::matchers::archive::{ArchiveMatchers, EntryContent, containing};
     pub use crate::backend::matchers::boolean::BooleanMatchers;
+    pub use crate::backend::matchers::capacity::CapacityMatchers;
+    #[cfg(feature = "syn")]
+    pub use crate::backend::matchers::code::CodeMatchers;
     pub use crate::backend::matchers::collection::{CollectionExtensions, CollectionMatchers};
+    pub use crate::backend::matchers::condition::ConditionMatchers;
+    pub use crate::backend::matchers::control_flow::ControlFlowMatchers;
     pub use crate::backend::matchers::equality::EqualityMatchers;
+    pub use crate::backend::matchers::error::ErrorMatchers;
+    pub use crate::backend::matchers::failure::FailureMatchers;
+    pub use crate::backend::matchers::float::FloatSliceMatchers;
+    pub use crate::backend::matchers::frame::FrameMatchers;
+    pub use crate::backend::matchers::function::FunctionMatchers;
     pub use crate::backend::matchers::hashmap::HashMapMatchers;
-    pub use crate::backend::matchers::numeric::NumericMatchers;
+    #[cfg(feature = "image")]
+    pub use crate::backend::matchers::image::ImageMatchers;
+    #[cfg(feature = "json-schema")]
+    pub use crate::backend::matchers::json_schema::JsonSchemaMatchers;
+    #[cfg(feature = "log-capture")]
+    pub use crate::backend::matchers::logs::LogMatchers;
+    #[cfg(feature = "metrics-capture")]
+    pub use crate::backend::matchers::metrics::MetricMatchers;
+    pub use crate::backend::matchers::numeric::{CheckedArithmeticMatchers, NumericMatchers};
     pub use crate::backend::matchers::option::OptionMatchers;
+    pub use crate::backend::matchers::path::PathMatchers;
+    #[cfg(feature = "regex")]
+    pub use crate::backend::matchers::pattern::PatternMatchers;
+    pub use crate::backend::matchers::poll::PollMatchers;
+    #[cfg(feature = "prost")]
+    pub use crate::backend::matchers::protobuf::ProtobufMatchers;
     pub use crate::backend::matchers::result::ResultMatchers;
+    pub use crate::backend::matchers::round_trip::RoundTripMatchers;
+    pub use crate::backend::matchers::size::SizeMatchers;
+    #[cfg(feature = "sqlx")]
+    pub use crate::backend::matchers::sql::SqlMatchers;
     pub use crate::backend::matchers::string::StringMatchers;
+    #[cfg(feature = "unicode")]
+    pub use crate::backend::matchers::unicode::{Form, UnicodeMatchers};
 
     // Helper function to set up testing
     pub fn setup_tests() {
@@ -1,23 +1,48 @@
-use crate::backend::{Assertion, TestSessionResult};
+use crate::backend::shard::Shard;
+use crate::backend::{Assertion, AssertionStep, ModuleStats, Severity, TestSessionResult};
 use crate::config::Config;
-use crate::events::{AssertionEvent, EventEmitter, on_failure, on_success};
+use crate::events::{AssertionEvent, EventEmitter, on_failure, on_success, on_warning};
 use crate::frontend::ConsoleRenderer;
 use std::cell::RefCell;
 use std::collections::HashSet;
+use std::panic::Location;
 use std::sync::{LazyLock, RwLock};
 
 pub(crate) static GLOBAL_CONFIG: LazyLock<RwLock<Config>> = LazyLock::new(|| RwLock::new(Config::new()));
 
 thread_local! {
     static TEST_SESSION: RefCell<TestSessionResult> = RefCell::new(TestSessionResult::default());
-    // Track already reported messages to avoid duplicates
-    static REPORTED_MESSAGES: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+    // Track already reported assertions to avoid duplicates
+    static REPORTED_MESSAGES: RefCell<HashSet<ReportKey>> = RefCell::new(HashSet::new());
     // Flag to enable/disable deduplication
     static DEDUPLICATE_ENABLED: RefCell<bool> = const { RefCell::new(true) };
     // Flag to enable silent mode for intermediate steps in a chain
     static SILENT_MODE: RefCell<bool> = const { RefCell::new(false) };
 }
 
+/// What makes two reports of an assertion the same: the assertion, where it was made, and how each step went
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ReportKey {
+    expr_str: &'static str,
+    context: Option<String>,
+    location: &'static Location<'static>,
+    severity: Severity,
+    steps: Vec<AssertionStep>,
+}
+
+impl ReportKey {
+    /// Key of the report of an assertion
+    fn of(result: &Assertion<()>) -> Self {
+        return Self {
+            expr_str: result.expr_str,
+            context: result.context.clone(),
+            location: result.location,
+            severity: result.severity,
+            steps: result.steps.clone(),
+        };
+    }
+}
+
 pub struct Reporter;
 
 impl Reporter {
@@ -32,6 +57,11 @@ impl Reporter {
         on_failure(|result| {
             Self::handle_failure_event(result);
         });
+
+        // Register warning event handler
+        on_warning(|result| {
+            Self::handle_warning_event(result);
+        });
     }
 
     /// Handle success events
@@ -47,34 +77,10 @@ impl Reporter {
             return;
         }
 
-        // Check if we should deduplicate
-        let should_report = DEDUPLICATE_ENABLED.with(|enabled| {
-            if !*enabled.borrow() {
-                // Deduplication disabled, always report
-                return true;
-            }
-
-            // Only report each unique success message once
-            REPORTED_MESSAGES.with(|msgs| {
-                let key = format!("{:?}", result);
-                let mut messages = msgs.borrow_mut();
-                if !messages.contains(&key) {
-                    messages.insert(key);
-                    true
-                } else {
-                    false
-                }
-            })
-        });
+        let should_report = Self::is_first_report(&result);
 
         if should_report {
-            let config = GLOBAL_CONFIG.read().unwrap();
-            let renderer = ConsoleRenderer::new(Config {
-                use_colors: config.use_colors,
-                use_unicode_symbols: config.use_unicode_symbols,
-                show_success_details: config.show_success_details,
-                enhanced_output: config.enhanced_output,
-            });
+            let renderer = Self::renderer();
             renderer.print_success(&result);
         }
     }
@@ -93,38 +99,51 @@ impl Reporter {
             return;
         }
 
-        // Check if we should deduplicate
-        let should_report = DEDUPLICATE_ENABLED.with(|enabled| {
-            if !*enabled.borrow() {
-                // Deduplication disabled, always report
-                return true;
-            }
+        let should_report = Self::is_first_report(&result);
 
-            // Only report each unique failure message once
-            let key = format!("{:?}", result);
-            REPORTED_MESSAGES.with(|msgs| {
-                let mut messages = msgs.borrow_mut();
-                if !messages.contains(&key) {
-                    messages.insert(key);
-                    true
-                } else {
-                    false
-                }
-            })
+        if should_report {
+            let renderer = Self::renderer();
+            renderer.print_failure(&result);
+            crate::backend::panic_hook::failure_rendered();
+        }
+    }
+
+    /// Handle warning events
+    fn handle_warning_event(result: Assertion<()>) {
+        TEST_SESSION.with(|session| {
+            let mut session = session.borrow_mut();
+            session.warning_count += 1;
+            session.warnings.push(result.clone());
         });
 
+        // Check if silent mode is enabled
+        let silent = SILENT_MODE.with(|silent| *silent.borrow());
+        if silent {
+            return;
+        }
+
+        let should_report = Self::is_first_report(&result);
+
         if should_report {
-            let config = GLOBAL_CONFIG.read().unwrap();
-            let renderer = ConsoleRenderer::new(Config {
-                use_colors: config.use_colors,
-                use_unicode_symbols: config.use_unicode_symbols,
-                show_success_details: config.show_success_details,
-                enhanced_output: config.enhanced_output,
-            });
-            renderer.print_failure(&result);
+            let renderer = Self::renderer();
+            renderer.print_warning(&result);
         }
     }
 
+    /// Renderer for the current configuration
+    fn renderer() -> ConsoleRenderer {
+        return ConsoleRenderer::new(GLOBAL_CONFIG.read().unwrap().clone());
+    }
+
+    /// Check if an assertion should be reported, which is only the first time when deduplication is enabled
+    fn is_first_report(result: &Assertion<()>) -> bool {
+        if !DEDUPLICATE_ENABLED.with(|enabled| *enabled.borrow()) {
+            return true;
+        }
+
+        return REPORTED_MESSAGES.with(|messages| messages.borrow_mut().insert(ReportKey::of(result)));
+    }
+
     /// Clear the message cache to allow duplicated messages in different test scopes
     pub fn reset_message_cache() {
         REPORTED_MESSAGES.with(|msgs| {
@@ -160,16 +179,30 @@ impl Reporter {
         });
     }
 
+    /// Print the progress line of a module whose registered tests all completed, when enabled, and emit its completion
+    /// event
+    pub fn module_completed(module_path: &'static str, stats: ModuleStats) {
+        if crate::config::is_enhanced_output_enabled() && crate::config::is_printing_module_progress() {
+            let renderer = Self::renderer();
+            renderer.print_module_progress(module_path, &stats);
+        }
+
+        EventEmitter::emit(AssertionEvent::ModuleCompleted(module_path, stats));
+    }
+
+    /// Print the line of a fixture-wrapped test skipped because it belongs to another shard than the given one
+    pub fn test_skipped(module_path: &str, test_name: &str, shard: Shard) {
+        if crate::config::is_enhanced_output_enabled() {
+            Self::renderer().print_skipped_test(module_path, test_name, shard);
+        }
+    }
+
     pub fn summarize() {
         TEST_SESSION.with(|session| {
-            let session = session.borrow();
-            let config = GLOBAL_CONFIG.read().unwrap();
-            let renderer = ConsoleRenderer::new(Config {
-                use_colors: config.use_colors,
-                use_unicode_symbols: config.use_unicode_symbols,
-                show_success_details: config.show_success_details,
-                enhanced_output: config.enhanced_output,
-            });
+            let mut session = session.borrow_mut();
+            (session.skipped_count, session.ignored_count) = crate::backend::discovery::tests_not_run();
+
+            let renderer = Self::renderer();
             renderer.print_session_summary(&session);
         });
 
@@ -200,6 +233,7 @@ mod tests {
             sentence: AssertionSentence::new("be", if passed { "correct" } else { "incorrect" }),
             passed,
             logical_op: None,
+            location: None,
         });
 
         // Set it as non-final to prevent Drop evaluation
@@ -251,14 +285,16 @@ mod tests {
 
     #[test]
     fn test_reporter_message_cache() {
-        // Add a message to the cache
+        let key = ReportKey::of(&create_test_assertion(true));
+
+        // Add a report to the cache
         REPORTED_MESSAGES.with(|msgs| {
-            msgs.borrow_mut().insert("test_message".to_string());
+            msgs.borrow_mut().insert(key.clone());
         });
 
         // Verify it's in the cache
         REPORTED_MESSAGES.with(|msgs| {
-            assert!(msgs.borrow().contains("test_message"));
+            assert!(msgs.borrow().contains(&key));
         });
 
         // Reset the cache
@@ -266,10 +302,29 @@ mod tests {
 
         // Verify it's been cleared
         REPORTED_MESSAGES.with(|msgs| {
-            assert!(!msgs.borrow().contains("test_message"));
+            assert!(!msgs.borrow().contains(&key));
         });
     }
 
+    #[test]
+    fn test_report_keys_tell_assertions_apart() {
+        let assertion = create_test_assertion(true);
+        assert_eq!(ReportKey::of(&assertion), ReportKey::of(&assertion.clone()));
+
+        // Same expression and sentence, different actual value
+        let mut other_actual = assertion.clone();
+        other_actual.steps[0].sentence = other_actual.steps[0].sentence.clone().with_actual("42");
+        assert_ne!(ReportKey::of(&assertion), ReportKey::of(&other_actual));
+
+        // Same assertion, made at another line
+        let mut other_location = assertion.clone();
+        other_location.location = Location::caller();
+        assert_ne!(ReportKey::of(&assertion), ReportKey::of(&other_location));
+
+        // Same assertion, reported as a warning
+        assert_ne!(ReportKey::of(&assertion), ReportKey::of(&assertion.clone().as_warning()));
+    }
+
     #[test]
     fn test_handle_success_event() {
         // Start with a clean session
@@ -297,6 +352,31 @@ mod tests {
         Reporter::reset_message_cache();
     }
 
+    #[test]
+    fn test_handle_warning_event() {
+        // Start with a clean session
+        TEST_SESSION.with(|session| {
+            *session.borrow_mut() = TestSessionResult::default();
+        });
+
+        Reporter::enable_silent_mode();
+        Reporter::handle_warning_event(create_test_assertion(false).as_warning());
+
+        // Warnings are counted apart from failures
+        TEST_SESSION.with(|session| {
+            let session = session.borrow();
+            assert_eq!(session.warning_count, 1);
+            assert_eq!(session.warnings.len(), 1);
+            assert_eq!(session.failed_count, 0);
+        });
+
+        // Clean up
+        Reporter::disable_silent_mode();
+        TEST_SESSION.with(|session| {
+            *session.borrow_mut() = TestSessionResult::default();
+        });
+    }
+
     #[test]
     fn test_session_tracking() {
         // Start with a clean session
@@ -0,0 +1,182 @@
+//! Utilities to unit-test custom matchers
+//!
+//! [`run_matcher`] runs a matcher against a value and records the steps it produced, without panicking on failure,
+//! emitting events or counting the assertion, so tests can check both outcomes of a matcher and the sentences it
+//! builds:
+//!
+//! ```rust,ignore
+//! use rest::prelude::*;
+//! use rest::testkit::run_matcher;
+//!
+//! let run = run_matcher(regular_user(), |user| user.to_be_admin());
+//! expect!(run.passed()).to_be_false();
+//! expect!(run.sentence()).to_equal("be an admin".to_string());
+//! expect!(run.message()).to_equal("expected value to be an admin, got Role::User".to_string());
+//! ```
+//!
+//! Sentences are always built in full, including with the `fast-messages` feature.
+
+use crate::backend::{Assertion, AssertionStep};
+use std::cell::Cell;
+
+/// Subject of the assertions run by [`run_matcher`], unless the matcher names it with `as_`
+const SUBJECT: &str = "value";
+
+thread_local! {
+    // Number of matcher runs active on this thread
+    static RECORDING_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Check if assertions on this thread are recorded by a matcher run instead of evaluated
+pub(crate) fn is_recording() -> bool {
+    return RECORDING_DEPTH.with(|depth| depth.get() > 0);
+}
+
+/// Steps recorded by running a matcher with [`run_matcher`]
+#[derive(Debug, Clone)]
+pub struct MatcherRun {
+    assertion: Assertion<()>,
+}
+
+impl MatcherRun {
+    /// The recorded assertion, without its value, such as to render it with a `ConsoleRenderer`
+    pub fn assertion(&self) -> &Assertion<()> {
+        return &self.assertion;
+    }
+
+    /// Steps produced by the matcher, in order
+    pub fn steps(&self) -> &[AssertionStep] {
+        return &self.assertion.steps;
+    }
+
+    /// Whether the assertion passed, combining the steps as the assertion chain would
+    pub fn passed(&self) -> bool {
+        return self.assertion.calculate_chain_result();
+    }
+
+    /// Sentence of the last step, without subject, such as `be greater than 5` or `not contain 3`
+    pub fn sentence(&self) -> String {
+        return self.steps().last().map(|step| step.sentence.format()).unwrap_or_default();
+    }
+
+    /// Sentences of all the steps, without subject
+    pub fn sentences(&self) -> Vec<String> {
+        return self.steps().iter().map(|step| step.sentence.format()).collect();
+    }
+
+    /// Actual value captured by the last step, shown on failure
+    pub fn actual(&self) -> Option<&str> {
+        return self.steps().last().and_then(|step| step.sentence.actual_value.as_deref());
+    }
+
+    /// Message of the first failed step, or of the first step when all passed: `expected <expr> to <sentence>, got <actual>`
+    pub fn message(&self) -> String {
+        return self.assertion.format_stable_message();
+    }
+}
+
+/// Run a matcher against a value and record the steps it produced
+///
+/// The matcher gets an assertion on the value with `value` as subject. The assertions built during the run are not
+/// evaluated: a failure doesn't panic, no event is emitted and nothing is counted.
+///
+/// # Panics
+///
+/// Panics if the matcher didn't add any step to the assertion.
+#[track_caller]
+pub fn run_matcher<T, U>(value: T, matcher: impl FnOnce(Assertion<T>) -> Assertion<U>) -> MatcherRun {
+    // Stop recording even if the matcher panics
+    struct RecordingGuard;
+
+    impl Drop for RecordingGuard {
+        fn drop(&mut self) {
+            RECORDING_DEPTH.with(|depth| depth.set(depth.get() - 1));
+        }
+    }
+
+    RECORDING_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let guard = RecordingGuard;
+
+    let assertion = matcher(Assertion::new(value, SUBJECT));
+    let recorded = assertion.type_erased();
+    drop(assertion);
+    drop(guard);
+
+    assert!(!recorded.steps.is_empty(), "run_matcher: the matcher didn't add any step");
+    return MatcherRun { assertion: recorded };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::assertions::sentence::AssertionSentence;
+    use crate::prelude::*;
+
+    trait LuckMatchers {
+        fn to_be_lucky(self) -> Self;
+    }
+
+    impl LuckMatchers for Assertion<i32> {
+        fn to_be_lucky(self) -> Self {
+            let result = self.value == 7;
+            return self.add_step_with(result, |actual| {
+                return AssertionSentence::new("be", "lucky").with_actual(actual.to_string());
+            });
+        }
+    }
+
+    #[test]
+    fn test_failing_matcher_is_recorded_without_panicking() {
+        // Disable deduplication for tests
+        crate::Reporter::disable_deduplication();
+
+        let run = run_matcher(3, |value| value.to_be_lucky());
+
+        expect!(run.passed()).to_be_false();
+        expect!(run.sentence()).to_equal("be lucky".to_string());
+        expect!(run.actual()).to_equal(Some("3"));
+        expect!(run.message()).to_equal("expected value to be lucky, got 3".to_string());
+        expect!(is_recording()).to_be_false();
+    }
+
+    #[test]
+    fn test_passing_matcher_keeps_its_sentence() {
+        let run = run_matcher(7, |value| value.to_be_lucky());
+
+        expect!(run.passed()).to_be_true();
+        expect!(run.sentence()).to_equal("be lucky".to_string());
+    }
+
+    #[test]
+    fn test_negation_and_chains_are_recorded() {
+        let run = run_matcher(7, |value| value.not().to_be_lucky());
+        expect!(run.passed()).to_be_false();
+        expect!(run.sentence()).to_equal("not be lucky".to_string());
+
+        let run = run_matcher(7, |value| value.to_be_lucky().and().to_be_greater_than(10));
+        expect!(run.passed()).to_be_false();
+        expect!(run.sentences()).to_equal(vec!["be lucky".to_string(), "be greater than 10".to_string()]);
+        expect!(run.message()).to_equal("expected value to be greater than 10, got 7".to_string());
+    }
+
+    #[test]
+    fn test_named_subject() {
+        let run = run_matcher(3, |value| value.as_("count").to_be_lucky());
+
+        expect!(run.message()).to_equal("expected count to be lucky, got 3".to_string());
+    }
+
+    #[test]
+    fn test_runs_are_not_counted() {
+        let before = crate::backend::assertions::count::executed_assertions();
+        let _ = run_matcher(3, |value| value.to_be_lucky());
+
+        expect!(crate::backend::assertions::count::executed_assertions()).to_equal(before);
+    }
+
+    #[test]
+    #[should_panic(expected = "the matcher didn't add any step")]
+    fn test_matcher_without_step() {
+        let _ = run_matcher(3, |value| value);
+    }
+}
@@ -0,0 +1,28 @@
+use rest::prelude::*;
+use std::path::PathBuf;
+
+#[test]
+fn test_artifacts_belong_to_the_current_test() {
+    rest::attach_artifact("service log", "/var/log/service.log");
+    rest::attach_artifact("response", b"{\"status\":503}".to_vec());
+
+    let artifacts = rest::attached_artifacts();
+    assert_eq!(artifacts.len(), 2);
+    assert_eq!(artifacts[0].test.as_deref(), Some("test_artifacts_belong_to_the_current_test"));
+    assert_eq!(artifacts[0].path, PathBuf::from("/var/log/service.log"));
+    assert_eq!(std::fs::read_to_string(&artifacts[1].path).unwrap(), "{\"status\":503}");
+
+    // Another test thread sees its own artifacts only
+    let other = std::thread::spawn(rest::attached_artifacts).join().unwrap();
+    assert!(other.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "artifact screenshot: /tmp/checkout.png")]
+fn test_failure_message_lists_artifacts() {
+    rest::attach_artifact("screenshot", "/tmp/checkout.png");
+
+    let total = 41;
+    let _assertion = expect!(total).to_equal(42);
+    std::hint::black_box(_assertion);
+}
@@ -0,0 +1,41 @@
+#![allow(clippy::needless_return)]
+
+use rest::prelude::*;
+
+fn parse_ports(input: &str) -> Vec<u16> {
+    return input.split(',').filter_map(|port| port.trim().parse().ok()).collect();
+}
+
+#[test]
+fn test_every_port_is_checked() {
+    expect_assertions!(3);
+
+    for port in parse_ports("80, 443, 8080") {
+        expect!(port).to_be_greater_than(0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "run 3 assertions (got 1)")]
+fn test_unparsed_ports_are_caught() {
+    expect_assertions!(3);
+
+    // Only "80" parses as a port, so the loop body runs once instead of three times
+    for port in parse_ports("80, https, http-alt") {
+        expect!(port).to_be_greater_than(0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "run 2 assertions (got 1)")]
+fn test_early_return_is_caught() {
+    expect_assertions!(2);
+
+    let ports = parse_ports("");
+    expect!(ports.is_empty()).to_be_true();
+    if ports.is_empty() {
+        return;
+    }
+
+    expect!(ports[0]).to_equal(80);
+}
@@ -0,0 +1,57 @@
+use rest::frontend::{ConsoleRenderer, TestSessionResult};
+use rest::prelude::*;
+use rest::testkit::run_matcher;
+
+#[test]
+fn test_plain_failure_rendering() {
+    let run = run_matcher(10, |value| value.as_("retries").to_be_greater_than(42));
+
+    let (header, details) = ConsoleRenderer::plain().render_failure(run.assertion());
+
+    expect!(header).to_equal("✗ retries are greater than 42".to_string());
+    expect!(details).to_equal("  ✗ are greater than 42 (got 10) [to_be_greater_than(42)]\n  type: i32\n".to_string());
+}
+
+#[test]
+fn test_plain_chain_rendering() {
+    let run = run_matcher(vec![1, 2, 3], |value| value.as_("ids").to_have_length(3).and().to_contain(4));
+
+    let (header, details) = ConsoleRenderer::plain().render_failure(run.assertion());
+
+    expect!(header).to_equal("✗ ids have length 3 AND contain 4".to_string());
+    expect!(details)
+        .to_equal("  ✓ have length 3 [to_have_length(3)]\n  ✗ contain 4 (got [1, 2, 3]) [to_contain(4)]\n  type: Vec<i32>\n".to_string());
+    expect!(ConsoleRenderer::plain().render_success(run.assertion())).to_equal("✓ ids have length 3 AND contain 4".to_string());
+}
+
+#[test]
+fn test_plain_session_summary() {
+    let failure = run_matcher("beta", |value| value.as_("channel").to_equal("stable"));
+    let summary = TestSessionResult { passed_count: 2, failed_count: 1, failures: vec![failure.assertion().clone()], ..Default::default() };
+
+    expect!(ConsoleRenderer::plain().render_session_summary(&summary)).to_equal(
+        concat!(
+            "\nTest Results:\n",
+            "  2 passed / 1 failed\n",
+            "\nFailure Details:\n",
+            "  1. ✗ channel is equal to \"stable\"\n",
+            "       ✗ is equal to \"stable\" (got \"beta\") [to_equal(\"stable\")]\n",
+            "       type: &str\n",
+        )
+        .to_string(),
+    );
+}
+
+#[test]
+fn test_plain_summary_with_tests_not_run() {
+    let summary = TestSessionResult { passed_count: 12, failed_count: 0, skipped_count: 3, ignored_count: 2, ..Default::default() };
+
+    expect!(ConsoleRenderer::plain().render_session_summary(&summary)).to_equal(
+        concat!(
+            "\nTest Results:\n",
+            "  12 passed / 0 failed / 3 skipped / 2 ignored\n",
+            "  3 skipped tests didn't run, although the test harness lists them as ok\n",
+        )
+        .to_string(),
+    );
+}
@@ -0,0 +1,38 @@
+// Only the chosen matcher traits are in scope in this file
+mod prelude {
+    rest::custom_prelude!(CollectionMatchers, NumericMatchers);
+}
+
+use prelude::*;
+
+#[test]
+fn test_custom_prelude_brings_chosen_matchers() {
+    let scores = vec![3, 5, 8];
+
+    expect!(&scores).to_have_length(3);
+    expect!(&scores).to_contain(5);
+    expect!(scores[2]).to_be_greater_than(4).and().to_be_even();
+}
+
+#[test]
+fn test_custom_prelude_keeps_macros_and_modifiers() {
+    expect_assertions!(2);
+
+    expect_not!(7).to_be_even();
+    expect!(vec![1, 2]).not().to_contain(3);
+}
+
+#[test]
+#[cfg(feature = "macros")]
+#[with_fixtures]
+fn test_custom_prelude_keeps_fixtures() {
+    expect!(&[1, 2]).not().to_have_length(3);
+}
+
+#[test]
+fn test_minimal_prelude_with_explicit_imports() {
+    use rest::matchers::StringMatchers;
+    use rest::prelude_minimal::*;
+
+    expect!("minimal").to_start_with("min").or().to_end_with("max");
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::discovery::{FixtureKind, discovered_tests, discovery_json, fixtures_for};
+use rest::prelude::*;
+use std::process::Command;
+
+// Module whose tests are listed through the discovery registry
+#[with_fixtures_module]
+mod discovered_module {
+    use super::*;
+
+    #[setup]
+    fn prepare() {}
+
+    #[tear_down]
+    fn cleanup() {}
+
+    #[test]
+    fn test_plain() {
+        expect!(1 + 1).to_equal(2);
+    }
+
+    #[test]
+    #[ignore = "listed but never run"]
+    fn test_ignored() {
+        expect!(true).to_be_false();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_panicking() {
+        panic!("expected panic");
+    }
+
+    #[test]
+    #[with_fixtures(tags = ["slow", "db"])]
+    fn test_tagged() {
+        expect!(2 + 2).to_equal(4);
+    }
+
+    // Neither compiled nor registered
+    #[test]
+    #[cfg(any())]
+    fn test_compiled_out() {
+        unreachable!();
+    }
+}
+
+#[test]
+fn test_discovery_lists_fixture_wrapped_tests() {
+    let tests = discovered_tests();
+    let module = tests.iter().filter(|test| test.module.ends_with("discovered_module")).collect::<Vec<_>>();
+    let names = module.iter().map(|test| test.name).collect::<Vec<_>>();
+
+    expect!(names).to_equal(vec!["test_ignored", "test_panicking", "test_plain", "test_tagged"]);
+    expect!(module[0].ignored).to_be_true();
+    expect!(module[1].should_panic).to_be_true();
+    expect!(module[2].ignored || module[2].should_panic).to_be_false();
+    expect!(module[3].tags).to_equal(&["slow", "db"][..]);
+    expect!(module[0].file).to_end_with("discovery_test.rs");
+}
+
+#[test]
+fn test_discovery_lists_module_fixtures() {
+    let module_path = concat!(module_path!(), "::discovered_module");
+
+    expect!(fixtures_for(module_path, FixtureKind::Setup)).to_equal(vec!["prepare"]);
+    expect!(fixtures_for(module_path, FixtureKind::TearDown)).to_equal(vec!["cleanup"]);
+    expect!(fixtures_for(module_path, FixtureKind::BeforeAll)).to_be_empty();
+}
+
+#[test]
+fn test_discovery_json_describes_tests() {
+    let json = discovery_json();
+
+    expect!(json.as_str()).to_start_with(&format!("{{\"run\":{{\"id\":\"{}\",", rest::current_run().id));
+    expect!(json.as_str()).to_contain("},\"tests\":[");
+    expect!(json.as_str()).to_contain("\"name\":\"test_tagged\"");
+    expect!(json.as_str()).to_contain("\"tags\":[\"slow\",\"db\"]");
+    expect!(json.as_str()).to_contain("\"setup\":[\"prepare\"],\"tear_down\":[\"cleanup\"]");
+}
+
+// Plain test whose assertion fails if it runs in discovery mode
+#[test]
+fn test_plain_probe() {
+    expect!(std::env::var_os("REST_DISCOVERY_OUTPUT")).to_be_none();
+}
+
+#[test]
+fn test_discovery_mode_runs_no_test() {
+    let output_path = std::env::temp_dir().join(format!("rest_discovery_{}.json", std::process::id()));
+    let binary = std::env::current_exe().unwrap();
+
+    // Only a plain test is selected, which stops at its first assertion
+    let run = Command::new(binary).args(["--exact", "test_plain_probe"]).env("REST_DISCOVERY_OUTPUT", &output_path).output().unwrap();
+    let stdout = String::from_utf8_lossy(&run.stdout).to_string();
+    let json = std::fs::read_to_string(&output_path).unwrap_or_default();
+    let _ = std::fs::remove_file(&output_path);
+
+    expect!(run.status.success()).to_be_true();
+    expect!(stdout.as_str()).not().to_contain("test result");
+    expect!(json.as_str()).to_contain("\"name\":\"test_tagged\"");
+}
@@ -0,0 +1,118 @@
+#![allow(clippy::needless_return)]
+
+use rest::backend::AssertionSentence;
+use rest::backend::matchers::failure::ExpectedFailure;
+use rest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct User {
+    name: String,
+    active: bool,
+    roles: Vec<&'static str>,
+}
+
+#[derive(Default)]
+struct UserBuilder {
+    name: String,
+    active: bool,
+    roles: Vec<&'static str>,
+}
+
+impl UserBuilder {
+    fn new() -> Self {
+        return Self::default();
+    }
+
+    fn name(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        return self;
+    }
+
+    fn active(mut self) -> Self {
+        self.active = true;
+        return self;
+    }
+
+    fn role(mut self, role: &'static str) -> Self {
+        self.roles.push(role);
+        return self;
+    }
+
+    fn build(self) -> User {
+        return User { name: self.name, active: self.active, roles: self.roles };
+    }
+}
+
+trait UserMatchers {
+    fn to_have_name(self, name: &str) -> Self;
+    fn to_be_active(self) -> Self;
+}
+
+impl UserMatchers for Assertion<User> {
+    #[track_caller]
+    fn to_have_name(self, name: &str) -> Self {
+        let result = self.value.name == name;
+        return self.add_step_with(result, |user| {
+            return AssertionSentence::new("have", format!("name {:?}", name)).with_actual(format!("{:?}", user.name));
+        });
+    }
+
+    #[track_caller]
+    fn to_be_active(self) -> Self {
+        let result = self.value.active;
+        return self.add_step_with(result, |_| AssertionSentence::new("be", "active").with_actual("inactive"));
+    }
+}
+
+#[test]
+fn test_built_value_satisfies_every_assertion() {
+    expect_build!(UserBuilder::new().name("ada").active().build(), |user| {
+        user.to_have_name("ada");
+        user.to_be_active();
+        expect!(user.value.roles.clone()).to_be_empty();
+    });
+}
+
+#[test]
+fn test_assertions_are_reported_as_one_chain() {
+    let failure = ExpectedFailure::capture(|| {
+        expect_build!(UserBuilder::new().name("ada").role("admin").build(), |user| {
+            user.to_have_name("ada");
+            user.to_be_active();
+        });
+    });
+
+    let steps = failure.failure().map(|assertion| assertion.steps.clone()).unwrap_or_default();
+    expect!(steps.iter().map(|step| step.passed).collect::<Vec<_>>()).to_equal(vec![true, false]);
+    expect!(steps[1].sentence.format()).to_equal("be active".to_string());
+    expect!(failure.failure().map(|assertion| assertion.expr_str))
+        .to_equal(Some("UserBuilder::new().name(\"ada\").role(\"admin\").build()"));
+}
+
+#[test]
+fn test_folded_chain_counts_as_one_assertion() {
+    expect_assertions!(1);
+
+    expect_build!(UserBuilder::new().name("ada").active().build(), |user| {
+        user.to_have_name("ada");
+        user.to_be_active();
+    });
+}
+
+#[test]
+fn test_folded_chain_goes_on() {
+    expect_build!(UserBuilder::new().name("ada").active().build(), |user| {
+        user.to_have_name("ada");
+    })
+    .and()
+    .to_be_active();
+}
+
+#[test]
+#[should_panic(expected = "is active (got inactive) [to_be_active()]")]
+fn test_failed_step_fails_the_test() {
+    expect_build!(UserBuilder::new().name("ada").build(), |user| {
+        user.to_have_name("ada");
+        user.to_be_active();
+    });
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::expected_failure::expected_failures;
+use rest::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Number of times the fixtures of this file ran
+static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+#[tear_down]
+fn count_teardown() {
+    TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+}
+
+fn total(prices: &[f64]) -> f64 {
+    prices.iter().sum()
+}
+
+#[test]
+#[expected_failure(reason = "totals aren't rounded to cents, issue #42")]
+fn test_known_rounding_bug() {
+    let total = total(&[0.1, 0.2]);
+    expect!(total).to_equal(0.3);
+}
+
+#[test]
+#[with_fixtures]
+#[expected_failure(reason = "fixtures run around the expected failure")]
+fn test_expected_failure_with_fixtures() {
+    let teardowns = TEARDOWNS.load(Ordering::SeqCst);
+    expect!(teardowns).to_equal(usize::MAX);
+}
+
+#[test]
+#[expected_failure(reason = "parsing of signs, issue #43")]
+fn test_known_error() -> Result<(), std::num::ParseIntError> {
+    let value: u8 = "+-1".parse()?;
+    expect!(value).to_equal(1);
+    Ok(())
+}
+
+#[test]
+#[expected_failure(reason = "this bug is fixed")]
+#[should_panic(expected = "test_unexpected_pass_fails, fail as expected (this bug is fixed) (got passed)")]
+fn test_unexpected_pass_fails() {
+    expect!(total(&[0.5, 0.25])).to_equal(0.75);
+}
+
+#[test]
+fn test_expected_failures_are_recorded() {
+    test_known_rounding_bug();
+
+    let failures = expected_failures();
+    let rounding = failures.iter().find(|failure| failure.test == "expected_failure_test::test_known_rounding_bug").expect("recorded");
+    expect!(rounding.reason.as_str()).to_equal("totals aren't rounded to cents, issue #42");
+    expect!(rounding.failure.as_str()).to_equal("expected total to be equal to 0.3, got 0.30000000000000004");
+}
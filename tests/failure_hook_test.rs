@@ -0,0 +1,80 @@
+#![allow(clippy::needless_return)]
+
+use rest::prelude::*;
+use rest::{CallSite, FailureAction};
+use std::sync::{Mutex, Once};
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Failures seen by the hook: thread name, failed sentence and call site
+static FAILURES: Mutex<Vec<(String, String, CallSite)>> = Mutex::new(Vec::new());
+
+/// Every test of this binary shares the hook, which only lets tests named `..._continues` carry on
+fn install_hook() {
+    INSTALL_HOOK.call_once(|| {
+        rest::on_assertion_failure(|assertion, call_site| {
+            let thread = call_site.thread.clone().unwrap_or_default();
+            let sentence = assertion.steps.iter().find(|step| !step.passed).unwrap().sentence.format_with_actual();
+            FAILURES.lock().unwrap().push((thread.clone(), sentence, call_site.clone()));
+
+            return if thread.ends_with("_continues") { FailureAction::Continue } else { FailureAction::Panic };
+        });
+    });
+}
+
+/// Failures seen by the hook on the current thread
+fn failures_of_this_test() -> Vec<(String, CallSite)> {
+    let thread = std::thread::current().name().unwrap().to_string();
+    let failures = FAILURES.lock().unwrap();
+
+    return failures.iter().filter(|(name, _, _)| *name == thread).map(|(_, sentence, site)| (sentence.clone(), site.clone())).collect();
+}
+
+#[test]
+fn test_failed_assertion_continues() {
+    install_hook();
+
+    let value = 10;
+    let line = line!() + 1;
+    expect!(value).to_be_greater_than(42);
+    expect!(value).to_be_even();
+
+    let failures = failures_of_this_test();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].0, "be greater than 42 (got 10)");
+    assert_eq!(failures[0].1.file, file!());
+    assert_eq!(failures[0].1.line, line);
+    assert_eq!(failures[0].1.column, 5);
+}
+
+#[test]
+fn test_every_failed_step_of_a_chain_continues() {
+    install_hook();
+
+    let name = "trusty";
+    expect!(name).to_start_with("t").and().not().to_contain("rust");
+    expect!(name).to_have_length(3);
+
+    let sentences = failures_of_this_test().into_iter().map(|(sentence, _)| sentence).collect::<Vec<_>>();
+    assert_eq!(sentences, vec!["not contain \"rust\" (got \"trusty\")", "have length 3 (got 6)"]);
+}
+
+#[test]
+#[should_panic(expected = "be odd (got 4)")]
+fn test_failed_assertion_panics_after_the_hook() {
+    install_hook();
+
+    let number = 4;
+    let _assertion = expect!(number).to_be_odd();
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+fn test_passing_assertions_do_not_call_the_hook() {
+    install_hook();
+
+    expect!(2 + 2).to_equal(4);
+    expect!(vec![1, 2]).to_contain(2);
+
+    assert!(failures_of_this_test().is_empty());
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::discovery::{FixtureKind, discovered_tests, fixtures_for};
+use rest::prelude::*;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Number of times the fixtures of the nested modules ran
+static INNER_SETUPS: AtomicUsize = AtomicUsize::new(0);
+static DEEPEST_SETUPS: AtomicUsize = AtomicUsize::new(0);
+
+// Default values recorded by the instances of a generic fixture
+static GENERIC_DEFAULTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// Fixtures removed by `cfg` are neither compiled nor registered
+mod gated {
+    use super::*;
+
+    #[setup]
+    #[cfg(any())]
+    fn never_compiled() {
+        unreachable!();
+    }
+
+    #[setup]
+    #[cfg(test)]
+    fn always_compiled() {}
+}
+
+// Generic fixtures run once per instance given to the attribute
+mod generic {
+    use super::*;
+
+    #[setup(::<u8>, ::<String>)]
+    fn record_default<T: Default + std::fmt::Debug>() {
+        GENERIC_DEFAULTS.lock().unwrap().push(format!("{:?}", T::default()));
+    }
+
+    #[test]
+    #[with_fixtures]
+    fn test_generic_fixture_runs_for_each_instance() {
+        let defaults = GENERIC_DEFAULTS.lock().unwrap().clone();
+
+        expect!(&defaults).to_contain("0".to_string()).and().to_contain("\"\"".to_string());
+    }
+}
+
+#[with_fixtures_module]
+mod outer {
+    pub mod inner {
+        use super::super::*;
+
+        #[setup]
+        fn count_inner() {
+            INNER_SETUPS.fetch_add(1, Ordering::SeqCst);
+        }
+
+        #[test]
+        fn test_inner_module_runs_its_fixtures() {
+            expect!(INNER_SETUPS.load(Ordering::SeqCst)).to_be_greater_than(0);
+        }
+
+        pub mod deepest {
+            use super::super::super::*;
+
+            #[setup]
+            fn count_deepest() {
+                DEEPEST_SETUPS.fetch_add(1, Ordering::SeqCst);
+            }
+
+            #[test]
+            fn test_deepest_module_runs_its_fixtures() {
+                expect!(DEEPEST_SETUPS.load(Ordering::SeqCst)).to_be_greater_than(0);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_cfg_gated_fixtures_are_registered_when_compiled() {
+    let module_path = concat!(module_path!(), "::gated");
+
+    expect!(fixtures_for(module_path, FixtureKind::Setup)).to_equal(vec!["always_compiled"]);
+}
+
+#[test]
+fn test_generic_fixtures_are_registered_once() {
+    expect!(fixtures_for(concat!(module_path!(), "::generic"), FixtureKind::Setup)).to_equal(vec!["record_default"]);
+}
+
+#[test]
+fn test_tests_of_nested_modules_get_fixtures() {
+    let names = discovered_tests().into_iter().filter(|test| test.module.contains("::outer::")).map(|test| test.name).collect::<Vec<_>>();
+
+    expect!(names).to_equal(vec!["test_inner_module_runs_its_fixtures", "test_deepest_module_runs_its_fixtures"]);
+}
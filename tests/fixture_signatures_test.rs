@@ -0,0 +1,74 @@
+#![cfg(feature = "macros")]
+
+use rest::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Number of times the fixtures of this file ran
+static SETUPS: AtomicUsize = AtomicUsize::new(0);
+static TEARDOWNS: AtomicUsize = AtomicUsize::new(0);
+
+#[setup]
+fn count_setup() {
+    SETUPS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[tear_down]
+fn count_teardown() {
+    TEARDOWNS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+#[with_fixtures]
+fn test_result_is_returned_to_the_harness() -> Result<(), std::num::ParseIntError> {
+    let port: u16 = "8080".parse()?;
+
+    expect!(port).to_equal(8080);
+    expect!(SETUPS.load(Ordering::SeqCst)).to_be_greater_than(0);
+    Ok(())
+}
+
+#[with_fixtures]
+fn failing_check() -> Result<(), String> {
+    Err("no free port".to_string())
+}
+
+#[test]
+fn test_errors_are_returned_after_the_teardown() {
+    let teardowns = TEARDOWNS.load(Ordering::SeqCst);
+
+    expect!(failing_check()).to_equal(Err("no free port".to_string()));
+    expect!(TEARDOWNS.load(Ordering::SeqCst)).to_be_greater_than(teardowns);
+}
+
+#[with_fixtures]
+unsafe fn read_through_pointer() {
+    let value = 42_u32;
+
+    expect!(unsafe { std::ptr::from_ref(&value).read() }).to_equal(42);
+}
+
+#[test]
+fn test_unsafe_functions_stay_unsafe() {
+    let setups = SETUPS.load(Ordering::SeqCst);
+    unsafe { read_through_pointer() };
+
+    expect!(SETUPS.load(Ordering::SeqCst)).to_be_greater_than(setups);
+}
+
+#[tokio::test]
+#[with_fixtures]
+async fn test_async_body_runs_between_fixtures() {
+    let setups = SETUPS.load(Ordering::SeqCst);
+    tokio::task::yield_now().await;
+
+    expect!(setups).to_be_greater_than(0);
+}
+
+#[with_fixtures]
+#[tokio::test]
+async fn test_async_result_is_returned_to_the_harness() -> Result<(), String> {
+    tokio::task::yield_now().await;
+
+    expect!(SETUPS.load(Ordering::SeqCst)).to_be_greater_than(0);
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#![allow(clippy::needless_return)]
+
+use rest::backend::matchers::failure::ExpectedFailure;
+use rest::prelude::*;
+
+/// Draw a cart the way a TUI would, with colors and padding to the terminal width
+fn render_cart(items: &[(&str, u32)]) -> String {
+    let mut frame = String::from("\x1b[1m┌ Cart ──────┐\x1b[0m          \n");
+    for (name, count) in items {
+        frame.push_str(&format!("│ {:<7}{:>3} │          \n", name, count));
+    }
+    frame.push_str("└────────────┘\n\n");
+    return frame;
+}
+
+#[test]
+fn test_frame_renders_like_its_snapshot() {
+    expect!(render_cart(&[("apples", 3), ("pears", 12)])).to_render_like_snapshot();
+}
+
+#[test]
+fn test_changed_frame_fails() {
+    let failure = ExpectedFailure::capture(|| {
+        expect!(render_cart(&[("apples", 3), ("pears", 13)])).to_render_like_snapshot();
+    });
+    let details = failure.failure().map(|assertion| assertion.steps[0].sentence.details.clone()).unwrap_or_default();
+
+    // The new frame is written next to the snapshot for review, and the failure says how to accept it
+    let new_frame = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/test_changed_frame_fails.frame.new");
+    expect!(details).to_equal(
+        [
+            "row 3, column 12: expected '2', got '3'",
+            "stored: tests/snapshots/test_changed_frame_fails.frame",
+            "new: tests/snapshots/test_changed_frame_fails.frame.new",
+            "accept with: REST_UPDATE_SNAPSHOTS=1 cargo test -- --exact test_changed_frame_fails",
+        ]
+        .map(String::from)
+        .to_vec(),
+    );
+    expect!(std::fs::read_to_string(new_frame).unwrap_or_default()).to_contain("│ pears   13 │");
+
+    std::fs::remove_file(new_frame).unwrap();
+}
+
+mod checkout {
+    use super::render_cart;
+    use rest::prelude::*;
+
+    // Same name as the test above, with a snapshot of its own
+    #[test]
+    fn test_frame_renders_like_its_snapshot() {
+        expect!(render_cart(&[("plums", 6)])).to_render_like_snapshot();
+    }
+}
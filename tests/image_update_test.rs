@@ -0,0 +1,33 @@
+#![cfg(feature = "image")]
+
+use image::{Rgba, RgbaImage};
+use rest::prelude::*;
+
+// Snapshots are updated for every test of this binary
+fn update_snapshots() {
+    rest::config().update_snapshots(true).apply();
+}
+
+#[test]
+fn test_update_rewrites_the_reference() {
+    update_snapshots();
+    let dir = std::env::temp_dir().join(format!("rest-image-update-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let reference = dir.join("badge.png");
+    let stale = dir.join("badge.new.png");
+    RgbaImage::from_pixel(4, 4, Rgba([255, 255, 255, 255])).save(&reference).unwrap();
+    std::fs::write(&stale, b"left by a previous failure").unwrap();
+
+    let red = RgbaImage::from_pixel(4, 4, Rgba([255, 0, 0, 255]));
+    expect!(&red).to_look_like(&reference, 0.0);
+    expect!(image::open(&reference).unwrap().to_rgba8() == red).to_be_true();
+    expect!(stale.exists()).to_be_false();
+
+    // A missing reference is recorded
+    let missing = dir.join("icon.png");
+    expect!(&red).to_look_like(&missing, 0.0);
+    expect!(missing.is_file()).to_be_true();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
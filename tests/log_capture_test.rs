@@ -0,0 +1,62 @@
+#![cfg(all(feature = "log-capture", feature = "macros"))]
+
+use rest::prelude::*;
+
+// Fixture-wrapped tests capture their logs for every test of this binary
+fn capture_fixture_logs() {
+    rest::config().capture_logs(true).apply();
+}
+
+mod client {
+    use super::*;
+
+    #[setup]
+    fn connect() {
+        log::info!("connecting to the test server");
+    }
+
+    #[with_fixtures]
+    pub fn fetch_with_retry() {
+        log::warn!("retrying request in 5s");
+        tracing::info!(status = 200, "request succeeded");
+
+        expect_logs!().to_contain_entry(Level::Info, "connecting");
+        expect_logs!().to_contain_entry(Level::Warn, "retrying");
+        expect_logs!().to_contain_message("succeeded status=200");
+    }
+
+    #[with_fixtures]
+    pub fn fetch_quietly() {
+        // Entries of the previous test don't leak into this one
+        expect_logs!().not().to_contain_message("retrying");
+        expect_logs!().to_contain_message("connecting");
+    }
+}
+
+#[test]
+fn test_each_fixture_test_has_its_own_capture() {
+    capture_fixture_logs();
+
+    client::fetch_with_retry();
+    client::fetch_quietly();
+}
+
+#[test]
+fn test_capture_ends_with_the_test() {
+    capture_fixture_logs();
+
+    client::fetch_with_retry();
+
+    log::warn!("retrying outside of any test");
+    expect!(rest::backend::logs::captured_logs().entries().len()).to_equal(0);
+}
+
+#[test]
+#[should_panic(expected = "contain WARN entry \"giving up\"")]
+fn test_missing_entry_fails() {
+    let _logs = capture_logs();
+    log::warn!("retrying request in 5s");
+
+    let _assertion = expect_logs!().to_contain_entry(Level::Warn, "giving up");
+    std::hint::black_box(_assertion);
+}
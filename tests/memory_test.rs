@@ -0,0 +1,34 @@
+#![cfg(all(feature = "memory-tracking", feature = "macros"))]
+
+use rest::backend::memory::{TrackingAllocator, recorded_usage};
+use rest::prelude::*;
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+mod hungry {
+    use super::*;
+
+    #[with_fixtures]
+    pub fn allocate_a_megabyte() {
+        let buffer = vec![0u8; 1024 * 1024];
+        std::hint::black_box(&buffer);
+    }
+
+    #[with_fixtures]
+    pub fn allocate_nothing() {}
+}
+
+#[test]
+fn test_fixture_wrapped_tests_record_their_peak_memory() {
+    hungry::allocate_a_megabyte();
+    hungry::allocate_nothing();
+
+    let usage = recorded_usage();
+    let hungry = usage.iter().find(|usage| usage.test.ends_with("hungry::allocate_a_megabyte")).unwrap();
+    let idle = usage.iter().find(|usage| usage.test.ends_with("hungry::allocate_nothing")).unwrap();
+
+    expect!(hungry.peak_bytes).to_be_greater_than_or_equal(1024 * 1024);
+    expect!(hungry.retained_bytes).to_be_less_than(1024 * 1024);
+    expect!(idle.peak_bytes).to_be_less_than(hungry.peak_bytes);
+}
@@ -0,0 +1,55 @@
+#![cfg(all(feature = "metrics-capture", feature = "macros"))]
+
+use rest::prelude::*;
+
+mod server {
+    use super::*;
+
+    #[setup]
+    fn warm_up() {
+        metrics::gauge!("pool_connections").set(4.0);
+    }
+
+    #[with_fixtures]
+    pub fn handle_requests() {
+        metrics::counter!("requests_total", "status" => "200").increment(2);
+        metrics::counter!("requests_total", "status" => "503").increment(1);
+        metrics::histogram!("request_seconds").record(0.12);
+
+        expect_metric!("pool_connections").to_have_value(4);
+        expect_metric!("requests_total").to_have_value(3);
+        expect_metric!("requests_total", "status" => "503").to_have_value(1);
+        expect_metric!("request_seconds").to_have_observation_count(1);
+    }
+
+    #[with_fixtures]
+    pub fn handle_nothing() {
+        // Metrics of the previous test don't leak into this one
+        expect_metric!("requests_total").not().to_be_recorded();
+        expect_metric!("pool_connections").to_have_value(4);
+    }
+}
+
+#[test]
+fn test_each_fixture_test_has_its_own_capture() {
+    server::handle_requests();
+    server::handle_nothing();
+}
+
+#[test]
+fn test_capture_ends_with_the_test() {
+    server::handle_requests();
+
+    metrics::counter!("requests_total").increment(1);
+    expect!(rest::backend::metrics::captured_metrics().series().len()).to_equal(0);
+}
+
+#[test]
+#[should_panic(expected = "have value 2 (got counter 1)")]
+fn test_wrong_value_fails() {
+    let _metrics = capture_metrics();
+    metrics::counter!("requests_total").increment(1);
+
+    let _assertion = expect_metric!("requests_total").to_have_value(2);
+    std::hint::black_box(_assertion);
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "macros")]
+
 use rest::prelude::*;
 use std::cell::RefCell;
 use std::sync::{
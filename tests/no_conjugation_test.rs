@@ -0,0 +1,70 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::matchers::failure::ExpectedFailure;
+use rest::prelude::*;
+
+/// Failure details of the first failed assertion of the closure, formatted where the closure runs
+fn failure_details(body: impl FnOnce()) -> String {
+    let captured = ExpectedFailure::capture(body);
+    captured.failure().map(|failure| failure.format_step_details()).unwrap_or_default()
+}
+
+#[no_conjugation]
+mod spanish_names {
+    use super::failure_details;
+    use rest::prelude::*;
+
+    #[test]
+    fn test_sentences_keep_the_base_verb() {
+        let datos = vec![1, 2, 3];
+        let details = failure_details(|| {
+            expect!(datos).not().to_contain(3);
+        });
+
+        assert!(details.contains("not contain 3"), "{}", details);
+    }
+
+    #[test]
+    #[with_fixtures]
+    fn test_with_fixtures() {
+        let valor = 2;
+        let details = failure_details(|| {
+            expect!(valor).to_be_greater_than(5);
+        });
+
+        assert!(details.contains("be greater than 5"), "{}", details);
+    }
+}
+
+#[test]
+#[no_conjugation]
+fn test_single_function() {
+    let lista = [1, 2];
+    let details = failure_details(|| {
+        expect!(lista).to_be_empty();
+    });
+
+    assert!(details.contains("be empty"), "{}", details);
+}
+
+#[test]
+fn test_conjugation_outside_of_the_marked_items() {
+    let value = 2;
+    let details = failure_details(|| {
+        expect!(value).to_be_greater_than(5);
+    });
+
+    assert!(details.contains("is greater than 5"), "{}", details);
+}
+
+#[test]
+fn test_subject_plurality() {
+    rest::config::subject_plurality("daten", rest::Plurality::Plural);
+
+    let daten = [1, 2];
+    let details = failure_details(|| {
+        expect!(daten).to_be_empty();
+    });
+
+    assert!(details.contains("are empty"), "{}", details);
+}
@@ -0,0 +1,65 @@
+//! Custom harness running the order check over modules with and without hidden coupling
+#![cfg(feature = "macros")]
+
+use rest::backend::order::{TestOutcome, check_test_order};
+use rest::prelude::*;
+use std::sync::Mutex;
+
+// Cart shared by the tests of `coupled`, emptied by none of its fixtures
+static CART: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+// `#[with_fixtures]` goes above `#[test]`, which removes the function from builds without the libtest harness
+mod coupled {
+    use super::*;
+
+    #[with_fixtures]
+    #[test]
+    fn test_add_item() {
+        CART.lock().unwrap().push("apple");
+        expect!(CART.lock().unwrap().len()).to_equal(1);
+    }
+
+    #[with_fixtures]
+    #[test]
+    fn test_cart_has_the_item() {
+        expect!(CART.lock().unwrap().contains(&"apple")).to_be_true();
+    }
+}
+
+// Same tests, isolated by a setup emptying the cart
+static ISOLATED_CART: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+#[with_fixtures_module]
+mod isolated {
+    use super::*;
+
+    #[setup]
+    fn empty_cart() {
+        ISOLATED_CART.lock().unwrap().clear();
+    }
+
+    #[test]
+    fn test_add_item() {
+        ISOLATED_CART.lock().unwrap().push("apple");
+        expect!(ISOLATED_CART.lock().unwrap().len()).to_equal(1);
+    }
+
+    #[test]
+    fn test_cart_starts_empty() {
+        expect!(ISOLATED_CART.lock().unwrap().is_empty()).to_be_true();
+    }
+}
+
+fn main() {
+    let report = check_test_order();
+
+    assert_eq!((report.modules, report.tests), (2, 4));
+    let dependent = report.dependent.iter().map(|test| test.path()).collect::<Vec<_>>();
+    // The second run adds a second apple, after the cart was read with the first one in it
+    assert_eq!(dependent, vec!["order_check_test::coupled::test_add_item"]);
+
+    let add_item = &report.dependent[0];
+    assert_eq!(add_item.in_declaration_order, TestOutcome::Passed);
+    assert_eq!(add_item.in_reverse_order, TestOutcome::Failed("expected CART.lock().unwrap().len() to be equal to 1, got 2".to_string()));
+    print!("{}", report.render());
+}
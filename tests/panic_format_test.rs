@@ -0,0 +1,81 @@
+use rest::PanicFormat;
+use rest::prelude::*;
+
+/// Every test of this binary uses the stable format and Unicode symbols, so applying them concurrently is harmless
+fn use_stable_format() {
+    config().panic_format(PanicFormat::Stable).use_unicode_symbols(true).apply();
+}
+
+#[test]
+#[should_panic(expected = "expected value to be greater than 42, got 10")]
+fn test_stable_message() {
+    use_stable_format();
+
+    let value = 10;
+    let _assertion = expect!(value).to_be_greater_than(42);
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+#[should_panic(expected = "expected name to not contain \"rust\", got \"trusty\"")]
+fn test_stable_message_of_negated_assertion() {
+    use_stable_format();
+
+    let name = "trusty";
+    let _assertion = expect!(name).not().to_contain("rust");
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+#[should_panic(expected = "expected number to be odd, got 4")]
+fn test_stable_message_reports_the_first_failed_step() {
+    use_stable_format();
+
+    let number = 4;
+    let _assertion = expect!(number).to_be_positive().and().to_be_odd();
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+fn test_stable_message_of_a_chain_is_only_its_failed_step() {
+    use_stable_format();
+
+    let chain = std::thread::spawn(|| {
+        let number = 4;
+        let _assertion = expect!(number).to_be_positive().and().to_be_odd().and().to_be_less_than(10);
+    });
+
+    let payload = chain.join().unwrap_err();
+    let message = payload.downcast_ref::<String>().unwrap();
+    expect!(message.as_str()).to_equal("expected number to be odd, got 4");
+}
+
+#[test]
+#[should_panic(expected = "expected primary server port to be greater than 1024, got 80")]
+fn test_stable_message_uses_the_subject_alias() {
+    use_stable_format();
+
+    let ports = [80, 8080];
+    let _assertion = expect!(ports[0]).as_("primary server port").to_be_greater_than(1024);
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+fn test_stable_message_does_not_depend_on_the_thread_name() {
+    use_stable_format();
+
+    let unnamed = std::thread::spawn(|| {
+        let value = 10;
+        let _assertion = expect!(value).to_be_greater_than(42);
+    });
+    let named = std::thread::Builder::new().name("worker".to_string()).spawn(|| {
+        let value = 10;
+        let _assertion = expect!(value).to_be_greater_than(42);
+    });
+
+    for result in [unnamed.join(), named.unwrap().join()] {
+        let payload = result.unwrap_err();
+        let message = payload.downcast_ref::<String>().unwrap();
+        expect!(message.as_str()).to_equal("expected value to be greater than 42, got 10");
+    }
+}
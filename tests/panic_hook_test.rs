@@ -0,0 +1,36 @@
+use rest::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Panics seen by the hook installed before the first assertion
+static USER_HOOK_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+/// The only test of this binary, since the process-wide hook is installed once
+#[test]
+fn test_user_hook_only_sees_unexpected_panics() {
+    std::panic::set_hook(Box::new(|_| {
+        USER_HOOK_CALLS.fetch_add(1, Ordering::SeqCst);
+    }));
+    config().enhanced_output(true).apply();
+    let backtrace = std::env::var_os("RUST_BACKTRACE").is_some_and(|value| !value.is_empty() && value != "0");
+
+    // The failure is rendered on the console, so its panic doesn't reach the user hook
+    let failed = std::panic::catch_unwind(|| {
+        let value = 10;
+        expect!(value).to_be_greater_than(42);
+    });
+    assert!(failed.is_err());
+    assert_eq!(USER_HOOK_CALLS.load(Ordering::SeqCst), if backtrace { 1 } else { 0 });
+
+    let _ = std::panic::catch_unwind(|| panic!("unexpected"));
+    assert_eq!(USER_HOOK_CALLS.load(Ordering::SeqCst), if backtrace { 2 } else { 1 });
+
+    // A test hook takes over for the current thread
+    let test_hook_calls = std::rc::Rc::new(std::cell::Cell::new(0));
+    let counted = std::rc::Rc::clone(&test_hook_calls);
+    rest::set_test_panic_hook(move |_| counted.set(counted.get() + 1));
+    let _ = std::panic::catch_unwind(|| panic!("unexpected again"));
+    rest::clear_test_panic_hook();
+
+    assert_eq!(test_hook_calls.get(), 1);
+    assert_eq!(USER_HOOK_CALLS.load(Ordering::SeqCst), if backtrace { 2 } else { 1 });
+}
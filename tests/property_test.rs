@@ -0,0 +1,116 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::property::Gen;
+use rest::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, RestArbitrary)]
+struct Point {
+    #[arbitrary(range = -10..=10)]
+    x: i32,
+    #[arbitrary(range = 0..5)]
+    y: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, RestArbitrary)]
+struct Order {
+    #[arbitrary(range = 1..=100)]
+    quantity: u32,
+    #[arbitrary(len = 1..8)]
+    sku: String,
+    #[arbitrary(len = 0..=3)]
+    tags: Vec<u8>,
+    #[arbitrary(range = 0.0..1.0)]
+    discount: f64,
+    express: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, RestArbitrary)]
+struct Wrapper<T>(T, #[arbitrary(range = 1..=2)] u8);
+
+#[derive(Debug, Clone, PartialEq, RestArbitrary)]
+enum Shape {
+    Empty,
+    Circle(#[arbitrary(range = 1..=10)] u16),
+    Rect { origin: Point, width: u8 },
+}
+
+#[test]
+fn test_derived_structs_respect_field_ranges() {
+    Property::new().cases(300).check(|point: Point| {
+        expect!(point.x).to_be_in_range(-10..11);
+        expect!(point.y).to_be_in_range(0..5);
+    });
+}
+
+#[test]
+fn test_derived_structs_respect_field_lengths() {
+    Property::new().cases(300).check(|order: Order| {
+        expect!(order.quantity).to_be_in_range(1..101);
+        expect!(order.sku.chars().count()).to_be_in_range(1..8);
+        expect!(order.tags.len()).to_be_less_than_or_equal(3);
+        expect!(order.discount).to_be_in_range(0.0..1.0);
+    });
+}
+
+#[test]
+fn test_derived_generic_tuple_structs() {
+    for_all(|wrapper: Wrapper<Option<bool>>| {
+        expect!(wrapper.1).to_be_in_range(1..3);
+    });
+}
+
+#[test]
+fn test_derived_enums_generate_every_variant() {
+    let mut g = Gen::new(11);
+    let shapes = (0..200).map(|_| Shape::arbitrary(&mut g)).collect::<Vec<_>>();
+
+    expect!(shapes.iter().any(|shape| matches!(shape, Shape::Empty))).to_be_true();
+    expect!(shapes.iter().any(|shape| matches!(shape, Shape::Circle(radius) if (1..=10).contains(radius)))).to_be_true();
+    expect!(shapes.iter().any(|shape| matches!(shape, Shape::Rect { .. }))).to_be_true();
+}
+
+#[test]
+fn test_derived_values_are_reproducible_from_a_seed() {
+    let first = Order::arbitrary(&mut Gen::new(2024));
+    let second = Order::arbitrary(&mut Gen::new(2024));
+
+    expect!(first).to_equal(second);
+}
+
+#[test]
+#[should_panic(expected = "for input Point { x: 0, y: 0 }, be greater than 0 (got 0)")]
+fn test_failing_properties_report_the_shrunk_input() {
+    Property::new().seed(7).check(|point: Point| {
+        expect!(point.y).to_be_greater_than(0);
+    });
+}
+
+#[test]
+fn test_derived_shrinking_respects_constraints() {
+    let order = Order { quantity: 50, sku: "abcdef".into(), tags: vec![4, 9], discount: 0.5, express: true };
+
+    for candidate in order.shrink() {
+        expect!(candidate.quantity).to_be_in_range(1..101);
+        expect!(candidate.sku.chars().count()).to_be_in_range(1..8);
+        expect!(candidate.tags.len()).to_be_less_than_or_equal(3);
+    }
+}
+
+#[test]
+#[should_panic(expected = r#"for input Order { quantity: 3, sku: "a", tags: [], discount: 0.0, express: false }"#)]
+fn test_derived_inputs_shrink_every_field() {
+    Property::new().seed(21).check(|order: Order| {
+        expect!(order.quantity).to_be_less_than(3);
+    });
+}
+
+#[test]
+fn test_derived_enums_shrink_within_their_variant() {
+    let shape = Shape::Rect { origin: Point { x: 4, y: 3 }, width: 9 };
+
+    let candidates = shape.shrink();
+
+    expect!(candidates.is_empty()).to_be_false();
+    expect!(candidates.iter().all(|candidate| matches!(candidate, Shape::Rect { .. }))).to_be_true();
+    expect!(Shape::Empty.shrink().is_empty()).to_be_true();
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "macros")]
+
+use rest::backend::shard::shard_of;
+use rest::prelude::*;
+use std::sync::Mutex;
+
+// Names of the probes whose body actually ran
+static RAN: Mutex<Vec<&'static str>> = Mutex::new(Vec::new());
+
+mod probes {
+    use super::*;
+
+    #[with_fixtures]
+    pub fn probe_alpha() {
+        RAN.lock().unwrap().push("probe_alpha");
+    }
+
+    #[with_fixtures]
+    pub fn probe_beta() {
+        RAN.lock().unwrap().push("probe_beta");
+    }
+
+    #[with_fixtures]
+    pub fn probe_gamma() {
+        RAN.lock().unwrap().push("probe_gamma");
+    }
+
+    #[with_fixtures]
+    pub fn probe_delta() {
+        RAN.lock().unwrap().push("probe_delta");
+    }
+}
+
+#[test]
+fn test_only_tests_of_the_selected_shard_run() {
+    rest::config().shard(1, 2).apply();
+
+    probes::probe_alpha();
+    probes::probe_beta();
+    probes::probe_gamma();
+    probes::probe_delta();
+
+    let module_path = concat!(module_path!(), "::probes");
+    let expected = ["probe_alpha", "probe_beta", "probe_gamma", "probe_delta"]
+        .into_iter()
+        .filter(|name| shard_of(module_path, name, 2) == 1)
+        .collect::<Vec<_>>();
+
+    expect!(RAN.lock().unwrap().clone()).to_equal(expected);
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "macros")]
+
+use rest::VacuousTests;
+use rest::prelude::*;
+
+/// Every test of this binary fails tests without assertions, so applying it concurrently is harmless
+fn fail_on_vacuous_tests() {
+    config().vacuous_tests(VacuousTests::Fail).apply();
+}
+
+mod checks {
+    use super::*;
+
+    #[with_fixtures]
+    pub fn without_assertion() {}
+
+    #[with_fixtures]
+    #[allow_no_assertions]
+    pub fn allowed() {}
+}
+
+#[test]
+#[should_panic(expected = "vacuous_fail_test::checks::without_assertion, run at least 1 assertion (got 0)")]
+fn test_test_without_assertions_fails() {
+    fail_on_vacuous_tests();
+    checks::without_assertion();
+}
+
+#[test]
+fn test_allowed_test_passes() {
+    fail_on_vacuous_tests();
+    checks::allowed();
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "macros")]
+
+use rest::VacuousTests;
+use rest::backend::assertions::count::vacuous_tests;
+use rest::prelude::*;
+
+/// Every test of this binary warns about tests without assertions, so applying it concurrently is harmless
+fn warn_on_vacuous_tests() {
+    config().vacuous_tests(VacuousTests::Warn).apply();
+}
+
+mod checks {
+    use super::*;
+
+    #[with_fixtures]
+    pub fn with_assertion() {
+        expect!(2 + 2).to_equal(4);
+    }
+
+    #[with_fixtures]
+    pub fn without_assertion() {
+        let _ = 2 + 2;
+    }
+
+    #[with_fixtures]
+    #[allow_no_assertions]
+    pub fn allowed_after() {}
+
+    #[allow_no_assertions]
+    #[with_fixtures]
+    pub fn allowed_before() {}
+}
+
+#[test]
+fn test_tests_without_assertions_are_listed() {
+    warn_on_vacuous_tests();
+
+    checks::with_assertion();
+    checks::without_assertion();
+
+    let vacuous = vacuous_tests();
+    expect!(&vacuous).to_contain("vacuous_test::checks::without_assertion".to_string());
+    expect!(&vacuous).not().to_contain("vacuous_test::checks::with_assertion".to_string());
+}
+
+#[test]
+fn test_allowed_tests_are_not_listed() {
+    warn_on_vacuous_tests();
+
+    checks::allowed_after();
+    checks::allowed_before();
+
+    let vacuous = vacuous_tests();
+    expect!(&vacuous).not().to_contain("vacuous_test::checks::allowed_after".to_string());
+    expect!(&vacuous).not().to_contain("vacuous_test::checks::allowed_before".to_string());
+}
@@ -0,0 +1,36 @@
+use rest::prelude::*;
+
+#[test]
+fn test_failed_warning_does_not_fail_the_test() {
+    let duration_ms = 250;
+
+    expect_warn!(duration_ms).to_be_less_than(200);
+    expect!(duration_ms).to_be_greater_than(0).as_warning();
+}
+
+#[test]
+fn test_warning_chains() {
+    let name = "trusty";
+
+    expect_warn!(name).not().to_contain("rust").and().to_have_length(3);
+    expect_warn!(name).to_start_with("a").or().to_end_with("z");
+    expect_warn!(name).to_start_with("t").and().to_end_with("y");
+}
+
+#[test]
+#[should_panic(expected = "be less than 200 (got 250)")]
+fn test_failures_still_fail_next_to_warnings() {
+    let duration_ms = 250;
+
+    expect_warn!(duration_ms).to_be_less_than(100);
+    let _assertion = expect!(duration_ms).to_be_less_than(200);
+    std::hint::black_box(_assertion);
+}
+
+#[test]
+fn test_warnings_inside_property_tests_do_not_fail_the_property() {
+    for_all(|value: u8| {
+        expect_warn!(value).to_be_less_than(10);
+        expect!(value as u16).to_be_less_than(256);
+    });
+}